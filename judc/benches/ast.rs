@@ -0,0 +1,130 @@
+//! Measures the time and memory it takes to parse programs of growing sizes into syntax trees.
+//!
+//! Results with the previous representation, where nodes were boxed and kept copies of their
+//! tokens and names, for reference:
+//!
+//! ```text
+//!    units   source bytes      allocated       retained       time
+//!      100          33360       14354412        5520770     6.9 ms
+//!     1000         347460      129282488       51990622    70.4 ms
+//!    10000        3614460     1179005736      494354766     707 ms
+//! ```
+//!
+//! With nodes in an arena, interned names, tokens referenced by index and lexemes borrowed from
+//! the source:
+//!
+//! ```text
+//!    units   source bytes      allocated       retained       time
+//!      100          33360        5558647        2474507     0.8 ms
+//!     1000         347460       61069176       28263064    11.3 ms
+//!    10000        3614460      507041184      226628816     124 ms
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use judc::judith::lexical::lexer::tokenize;
+use judc::judith::syntax::arena::Arena;
+use judc::judith::syntax::parser::parse;
+
+/// Counts the bytes allocated, so the memory used by syntax trees can be measured.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        LIVE.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// The sizes of the programs used, in units (see `program`).
+const UNITS: [usize; 3] = [100, 1_000, 10_000];
+
+/// Builds a program made of `units` copies of a small piece of code (about 12 lines each) that
+/// uses most kinds of nodes.
+fn program(units: usize) -> String {
+    let mut src = String::new();
+    for i in 0..units {
+        src.push_str(&format!("\
+-- Unit number {i}.
+func area_{i} (width: Num, height: Num = 2) -> Num => width * height
+
+let mut total_{i}: Num = area_{i}(3, 4) + {i}
+if total_{i} > 10 and not done then
+    total_{i} += 1
+else
+    total_{i} = -total_{i}
+end
+let point_{i} = Point {{ x = [1, 2][0], y = (total_{i} - 1) }}
+let name_{i}: String? = point_{i}?.name
+__p_print \"unit {i}\"
+"));
+    }
+    src
+}
+
+/// Prints the memory each program takes, from its source to its syntax tree.
+fn report_memory() {
+    println!(
+        "{:>8} {:>14} {:>14} {:>14} {:>14}",
+        "units", "source bytes", "allocated", "retained", "arena"
+    );
+    for units in UNITS {
+        let src = program(units);
+
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        let live = LIVE.load(Ordering::Relaxed);
+        let arena = Arena::new();
+        let parser_res = parse(tokenize(&src).tokens, &arena);
+        let allocated = ALLOCATED.load(Ordering::Relaxed) - allocated;
+        let retained = LIVE.load(Ordering::Relaxed) - live;
+
+        assert_eq!(parser_res.messages.count(), 0);
+        println!(
+            "{:>8} {:>14} {:>14} {:>14} {:>14}",
+            units, src.len(), allocated, retained, arena.allocated_bytes()
+        );
+        drop(parser_res);
+        drop(arena);
+    }
+}
+
+fn bench_programs(c: &mut Criterion) {
+    report_memory();
+
+    for units in UNITS {
+        let src = program(units);
+        let tokens = tokenize(&src).tokens;
+        c.bench_function(&format!("parse_program/{}", units), |b| {
+            // The arena is returned so the tree is freed outside of the measurement.
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| {
+                    let arena = Arena::new();
+                    black_box(parse(black_box(tokens), &arena).node_count);
+                    arena
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_programs
+}
+criterion_main!(benches);
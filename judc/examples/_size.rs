@@ -1,7 +1,14 @@
-use judc::judith::lexical::token::Token;
-use judc::judith::syntax::nodes::Identifier;
+use judc::judith::lexical::symbol::Symbol;
+use judc::judith::lexical::token::{Token, TokenId};
+use judc::judith::syntax::nodes::{Expr, Identifier, SyntaxNode};
 use judc::SourceSpan;
 
 fn main() {
-    println!("size: {} bytes", size_of::<SourceSpan>());
-}
\ No newline at end of file
+    println!("SourceSpan: {} bytes", size_of::<SourceSpan>());
+    println!("Token: {} bytes", size_of::<Token>());
+    println!("TokenId: {} bytes", size_of::<TokenId>());
+    println!("Symbol: {} bytes", size_of::<Symbol>());
+    println!("Identifier: {} bytes", size_of::<Identifier>());
+    println!("Expr: {} bytes", size_of::<Expr>());
+    println!("SyntaxNode: {} bytes", size_of::<SyntaxNode>());
+}
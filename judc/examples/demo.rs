@@ -5,6 +5,7 @@ use std::path::Path;
 use serde_json::ser;
 use judc::judith::compiler_messages::MessageContainer;
 use judc::judith::lexical::lexer::{tokenize, Lexer};
+use judc::judith::syntax::arena::Arena;
 use judc::judith::syntax::parser::{parse, ParseAttempt, Parser};
 
 fn __main2__() {
@@ -15,7 +16,8 @@ fn __main2__() {
 
     //let lexer_res = tokenize("mut sS (Num) -> Num?");
     let lexer_res = tokenize("Num?[5] | String & ISend");
-    let mut parser = Parser::new(&lexer_res.tokens);
+    let arena = Arena::new();
+    let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
     let ParseAttempt::Ok(ty) = parser.parse_type() else { panic!("Invalid type???") };
 
     let ty_json = serde_json::to_string_pretty(&ty).unwrap();
@@ -41,7 +43,8 @@ fn main() {
     if lexer_res.messages.count() != 0 { return; }
 
     // 1.2 Parse
-    let parser_res = parse(lexer_res.tokens);
+    let arena = Arena::new();
+    let parser_res = parse(lexer_res.tokens, &arena);
     let ast_json = serde_json::to_string_pretty(&parser_res.nodes).unwrap();
 
     messages.add_all(lexer_res.messages);
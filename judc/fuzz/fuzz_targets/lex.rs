@@ -0,0 +1,16 @@
+//! Lexes arbitrary UTF-8 text, which must never panic.
+//!
+//! Run with `cargo fuzz run lex` from the `judc` directory.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use judc::judith::lexical::lexer::tokenize;
+
+fuzz_target!(|src: &str| {
+    let lexer_res = tokenize(src);
+    for tok in &lexer_res.tokens {
+        let _ = lexer_res.leading_trivia(tok);
+        let _ = lexer_res.trailing_trivia(tok);
+    }
+});
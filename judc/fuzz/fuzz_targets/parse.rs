@@ -0,0 +1,17 @@
+//! Lexes and parses arbitrary UTF-8 text, both from a list of tokens and by pulling tokens from the
+//! lexer as the parser goes. Neither must ever panic.
+//!
+//! Run with `cargo fuzz run parse` from the `judc` directory.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use judc::judith::lexical::lexer::{tokenize, Lexer};
+use judc::judith::syntax::arena::Arena;
+use judc::judith::syntax::parser::{parse, parse_source};
+
+fuzz_target!(|src: &str| {
+    let arena = Arena::new();
+    let _ = parse(tokenize(src).tokens, &arena);
+    let _ = parse_source(Lexer::new(src), &arena);
+});
@@ -0,0 +1,469 @@
+use std::fmt::Write;
+use crate::SourceSpan;
+use crate::judith::syntax::nodes::*;
+
+/// The index of a block inside its graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockId(pub usize);
+
+/// Something executed inside a basic block. Control structures are split across blocks, so these
+/// never contain control flow, except for control structures nested in other expressions (e.g.
+/// an `if` used as the value of a local), which are kept whole.
+#[derive(Debug, Clone, Copy)]
+pub enum CfgNode<'a> {
+    Node(&'a SyntaxNode<'a>),
+    /// An expression that isn't a node by itself, such as the expression of an arrow body.
+    Expr(&'a Expr<'a>),
+}
+
+impl<'a> CfgNode<'a> {
+    pub fn span(&self) -> &'a Option<SourceSpan> {
+        match self {
+            CfgNode::Node(node) => node.span(),
+            CfgNode::Expr(expr) => expr.span(),
+        }
+    }
+}
+
+/// How control leaves a basic block.
+#[derive(Debug)]
+pub enum Terminator<'a> {
+    /// Continues to the block given.
+    Goto(BlockId),
+    /// Continues to one block or another depending on the value of `test`.
+    Branch { test: &'a Expr<'a>, when_true: BlockId, when_false: BlockId },
+    /// Returns from the function, continuing to the exit block.
+    Return { span: Option<SourceSpan> },
+    /// The end of the graph. Only the exit block ends like this.
+    Exit,
+}
+
+#[derive(Debug)]
+pub struct BasicBlock<'a> {
+    pub nodes: Vec<CfgNode<'a>>,
+    pub terminator: Terminator<'a>,
+}
+
+impl<'a> BasicBlock<'a> {
+    pub fn successors(&self, exit: BlockId) -> Vec<BlockId> {
+        match &self.terminator {
+            Terminator::Goto(target) => vec![*target],
+            Terminator::Branch { when_true, when_false, .. } => vec![*when_true, *when_false],
+            Terminator::Return { .. } => vec![exit],
+            Terminator::Exit => vec![],
+        }
+    }
+
+    /// The span of the first thing this block executes, if it executes anything.
+    pub fn first_span(&self) -> Option<SourceSpan> {
+        if let Some(node) = self.nodes.first() {
+            return *node.span();
+        }
+
+        match &self.terminator {
+            Terminator::Branch { test, .. } => *test.span(),
+            Terminator::Return { span } => *span,
+            _ => None,
+        }
+    }
+}
+
+/// A `loop` expression and the blocks it spans. Blocks are numbered in the order they appear in
+/// the source, so every block between `header` and `after` (exclusive) is part of the loop.
+#[derive(Debug)]
+pub struct LoopInfo<'a> {
+    pub expr: &'a LoopExpr<'a>,
+    pub header: BlockId,
+    /// The block that follows the loop, which is only entered by breaking out of it.
+    pub after: BlockId,
+}
+
+/// The control-flow graph of a function body or a program. Blocks are numbered in the order they
+/// appear in the source.
+#[derive(Debug)]
+pub struct ControlFlowGraph<'a> {
+    pub blocks: Vec<BasicBlock<'a>>,
+    pub entry: BlockId,
+    pub exit: BlockId,
+    pub loops: Vec<LoopInfo<'a>>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    /// Builds the graph of the body of a function.
+    pub fn from_body(body: &'a Body<'a>) -> ControlFlowGraph<'a> {
+        let mut builder = CfgBuilder::new();
+        builder.lower_body(body);
+        builder.finish()
+    }
+
+    /// Builds the graph of a sequence of nodes, such as a whole program.
+    pub fn from_nodes(nodes: &'a [SyntaxNode]) -> ControlFlowGraph<'a> {
+        let mut builder = CfgBuilder::new();
+        builder.lower_nodes(nodes);
+        builder.finish()
+    }
+
+    pub fn block(&self, id: BlockId) -> &BasicBlock<'a> {
+        &self.blocks[id.0]
+    }
+
+    /// Returns the predecessors of every block, indexed by block.
+    pub fn predecessors(&self) -> Vec<Vec<BlockId>> {
+        let mut preds = vec![Vec::new(); self.blocks.len()];
+        for (i, block) in self.blocks.iter().enumerate() {
+            for succ in block.successors(self.exit) {
+                preds[succ.0].push(BlockId(i));
+            }
+        }
+
+        preds
+    }
+
+    /// Returns which blocks can be reached from the entry block, indexed by block.
+    pub fn reachable_blocks(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut pending = vec![self.entry];
+
+        while let Some(id) = pending.pop() {
+            if reachable[id.0] {
+                continue;
+            }
+            reachable[id.0] = true;
+            pending.extend(self.block(id).successors(self.exit));
+        }
+
+        reachable
+    }
+
+    /// Returns true if the end of the body can be reached without returning.
+    pub fn falls_through(&self) -> bool {
+        let reachable = self.reachable_blocks();
+
+        self.blocks.iter().enumerate().any(|(i, block)| {
+            reachable[i] && matches!(block.terminator, Terminator::Goto(target) if target == self.exit)
+        })
+    }
+
+    /// Returns true if control can leave the loop given, either by breaking out of it or by
+    /// returning from inside it.
+    pub fn loop_has_exit(&self, info: &LoopInfo) -> bool {
+        let preds = self.predecessors();
+        if !preds[info.after.0].is_empty() {
+            return true;
+        }
+
+        self.blocks[info.header.0..info.after.0].iter().any(|block| {
+            matches!(block.terminator, Terminator::Return { .. })
+        })
+    }
+
+    /// Writes the graph in Graphviz's DOT format, to inspect it while debugging.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cfg {\n    node [shape=box];\n");
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let mut label = match BlockId(i) {
+                id if id == self.entry => String::from("entry"),
+                id if id == self.exit => String::from("exit"),
+                _ => format!("b{}", i),
+            };
+            for node in &block.nodes {
+                label.push_str(&format!("\\l{}", describe_node(node)));
+            }
+            if let Terminator::Branch { test, .. } = &block.terminator {
+                label.push_str(&format!("\\lbranch on {}", describe_expr(test)));
+            }
+            writeln!(dot, "    b{} [label=\"{}\\l\"];", i, label).unwrap();
+        }
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let edges = match &block.terminator {
+                Terminator::Goto(target) => vec![(*target, None)],
+                Terminator::Branch { when_true, when_false, .. } => {
+                    vec![(*when_true, Some("true")), (*when_false, Some("false"))]
+                }
+                Terminator::Return { .. } => vec![(self.exit, Some("return"))],
+                Terminator::Exit => vec![],
+            };
+
+            for (target, edge_label) in edges {
+                match edge_label {
+                    Some(edge_label) => writeln!(
+                        dot, "    b{} -> b{} [label=\"{}\"];", i, target.0, edge_label
+                    ),
+                    None => writeln!(dot, "    b{} -> b{};", i, target.0),
+                }.unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// A jump out of the normal flow of a body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jump {
+    Return,
+    Break,
+    Continue,
+}
+
+/// Where `break` and `continue` go inside the loop being lowered.
+struct LoopTargets {
+    header: BlockId,
+    /// The blocks that break out of the loop, whose target isn't known until the loop is lowered.
+    breaks: Vec<BlockId>,
+}
+
+/// A block whose terminator may not be known yet.
+struct PartialBlock<'a> {
+    nodes: Vec<CfgNode<'a>>,
+    terminator: Option<Terminator<'a>>,
+}
+
+struct CfgBuilder<'a> {
+    blocks: Vec<PartialBlock<'a>>,
+    /// The block new nodes are added to.
+    current: BlockId,
+    loop_targets: Vec<LoopTargets>,
+    loops: Vec<LoopInfo<'a>>,
+}
+
+impl<'a> CfgBuilder<'a> {
+    fn new() -> CfgBuilder<'a> {
+        let mut builder = CfgBuilder {
+            blocks: Vec::new(),
+            current: BlockId(0),
+            loop_targets: Vec::new(),
+            loops: Vec::new(),
+        };
+        builder.current = builder.new_block();
+
+        builder
+    }
+
+    fn finish(mut self) -> ControlFlowGraph<'a> {
+        let exit = self.new_block();
+        self.terminate(exit, Terminator::Exit);
+
+        // Every block that's still open falls through to the end of the body.
+        let blocks = self.blocks.into_iter().map(|block| BasicBlock {
+            nodes: block.nodes,
+            terminator: block.terminator.unwrap_or(Terminator::Goto(exit)),
+        }).collect();
+
+        ControlFlowGraph {
+            blocks,
+            entry: BlockId(0),
+            exit,
+            loops: self.loops,
+        }
+    }
+
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(PartialBlock {
+            nodes: Vec::new(),
+            terminator: None,
+        });
+
+        BlockId(self.blocks.len() - 1)
+    }
+
+    fn terminate(&mut self, block: BlockId, terminator: Terminator<'a>) {
+        self.blocks[block.0].terminator = Some(terminator);
+    }
+
+    /// Ends the current block with a jump to a new block, which becomes the current one.
+    fn goto_new_block(&mut self) -> BlockId {
+        let block = self.new_block();
+        self.terminate(self.current, Terminator::Goto(block));
+        self.current = block;
+
+        block
+    }
+
+    fn lower_body(&mut self, body: &'a Body<'a>) {
+        match body {
+            Body::Block(body) => self.lower_nodes(body.nodes),
+            Body::Arrow(body) => self.lower_expr(&body.expr, CfgNode::Expr(&body.expr)),
+            Body::Expr(body) => self.lower_expr(&body.expr, CfgNode::Expr(&body.expr)),
+        }
+    }
+
+    fn lower_nodes(&mut self, nodes: &'a [SyntaxNode]) {
+        for node in nodes {
+            match node {
+                SyntaxNode::Expr(expr) => self.lower_expr(expr, CfgNode::Node(node)),
+                SyntaxNode::Stmt(Stmt::Expr(stmt)) => self.lower_expr(&stmt.expr, CfgNode::Node(node)),
+                _ => match Self::as_jump(node) {
+                    Some(jump) => self.lower_jump(jump, *node.span()),
+                    None => self.blocks[self.current.0].nodes.push(CfgNode::Node(node)),
+                },
+            }
+        }
+    }
+
+    /// Lowers the expression given, which is found in the graph as `node` if it doesn't contain
+    /// control flow.
+    fn lower_expr(&mut self, expr: &'a Expr<'a>, node: CfgNode<'a>) {
+        match expr {
+            Expr::If(expr) => self.lower_if_expr(expr),
+            Expr::Loop(expr) => self.lower_loop_expr(expr),
+            Expr::While(expr) => self.lower_while_expr(expr),
+            _ => self.blocks[self.current.0].nodes.push(node),
+        }
+    }
+
+    fn lower_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        let test_block = self.current;
+
+        let consequent = self.new_block();
+        self.current = consequent;
+        self.lower_body(&expr.consequent);
+        let consequent_end = self.current;
+
+        let alternate = self.new_block();
+        self.current = alternate;
+        if let Some(alternate) = &expr.alternate {
+            self.lower_body(alternate);
+        }
+        let alternate_end = self.current;
+
+        self.terminate(test_block, Terminator::Branch {
+            test: &expr.test,
+            when_true: consequent,
+            when_false: alternate,
+        });
+
+        let join = self.new_block();
+        for end in [consequent_end, alternate_end] {
+            if self.blocks[end.0].terminator.is_none() {
+                self.terminate(end, Terminator::Goto(join));
+            }
+        }
+        self.current = join;
+    }
+
+    fn lower_loop_expr(&mut self, expr: &'a LoopExpr<'a>) {
+        let header = self.enter_loop();
+        self.lower_body(&expr.body);
+        let after = self.exit_loop(header);
+
+        self.loops.push(LoopInfo { expr, header, after });
+    }
+
+    fn lower_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        let header = self.enter_loop();
+
+        let body = self.new_block();
+        self.current = body;
+        self.lower_body(&expr.body);
+
+        let after = self.exit_loop(header);
+        self.terminate(header, Terminator::Branch {
+            test: &expr.test,
+            when_true: body,
+            when_false: after,
+        });
+    }
+
+    /// Starts a new block as the header of a loop, which `continue` jumps back to.
+    fn enter_loop(&mut self) -> BlockId {
+        // A loop can't share its header with whatever runs before it, as the header is entered
+        // again on each iteration.
+        let header = match self.blocks[self.current.0].nodes.is_empty() {
+            true => self.current,
+            false => self.goto_new_block(),
+        };
+        self.loop_targets.push(LoopTargets { header, breaks: Vec::new() });
+
+        header
+    }
+
+    /// Closes the body of the loop with the header given, and continues to the block after it.
+    fn exit_loop(&mut self, header: BlockId) -> BlockId {
+        let targets = self.loop_targets.pop().expect("A loop was entered.");
+        if self.blocks[self.current.0].terminator.is_none() {
+            self.terminate(self.current, Terminator::Goto(header));
+        }
+
+        let after = self.new_block();
+        for block in targets.breaks {
+            self.terminate(block, Terminator::Goto(after));
+        }
+        self.current = after;
+
+        after
+    }
+
+    fn lower_jump(&mut self, jump: Jump, span: Option<SourceSpan>) {
+        match (jump, self.loop_targets.last_mut()) {
+            (Jump::Return, _) => self.terminate(self.current, Terminator::Return { span }),
+            (Jump::Break, Some(targets)) => targets.breaks.push(self.current),
+            (Jump::Continue, Some(targets)) => {
+                let header = targets.header;
+                self.terminate(self.current, Terminator::Goto(header));
+            }
+            // Jumps outside of loops are invalid, and are kept as regular nodes.
+            (_, None) => return,
+        }
+
+        // Whatever comes after a jump is only reached if something else jumps to it.
+        self.current = self.new_block();
+    }
+
+    /// Returns the jump made by the node given, if it's a `return`, `break` or `continue`.
+    fn as_jump(_node: &SyntaxNode) -> Option<Jump> {
+        // TODO: These statements aren't parsed yet.
+        None
+    }
+}
+
+/// Describes a node in a single line, to label it in the DOT output.
+fn describe_node(node: &CfgNode) -> String {
+    let kind = match node {
+        CfgNode::Node(SyntaxNode::Item(Item::FuncDef(def))) => format!("func {}", def.name.name),
+        CfgNode::Node(SyntaxNode::Stmt(stmt)) => String::from(match stmt {
+            Stmt::Expr(_) => "expression statement",
+            Stmt::LocalDecl(_) => "local declaration",
+            Stmt::PrivPrint(_) => "__p",
+            Stmt::Error(_) => "error",
+        }),
+        CfgNode::Node(SyntaxNode::Expr(expr)) => describe_expr(expr),
+        CfgNode::Node(SyntaxNode::Error(_)) => String::from("error"),
+        CfgNode::Expr(expr) => describe_expr(expr),
+    };
+
+    match node.span() {
+        Some(span) => format!("{} (line {})", kind, span.line),
+        None => kind,
+    }
+}
+
+fn describe_expr(expr: &Expr) -> String {
+    let kind = match expr {
+        Expr::If(_) => "if expression",
+        Expr::Loop(_) => "loop expression",
+        Expr::While(_) => "while expression",
+        Expr::Assignment(_) => "assignment",
+        Expr::Binary(_) => "binary expression",
+        Expr::LeftUnary(_) | Expr::RightUnary(_) => "unary expression",
+        Expr::Group(_) => "group",
+        Expr::Tuple(_) => "tuple",
+        Expr::Array(_) | Expr::EmptyCollection(_) => "array",
+        Expr::ObjectInit(_) => "object initialization",
+        Expr::Access(_) => "member access",
+        Expr::Call(_) => "call",
+        Expr::Index(_) => "index",
+        Expr::Lambda(_) => "lambda",
+        Expr::Literal(lit) => return lit.literal.source.replace('"', "\\\""),
+        Expr::Identifier(id) => return match &id.identifier {
+            Identifier::Simple(id) => id.name.to_string(),
+            Identifier::Qualified(id) => id.name.name.to_string(),
+        },
+        Expr::Error(_) => "error",
+    };
+
+    String::from(kind)
+}
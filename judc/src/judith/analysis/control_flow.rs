@@ -0,0 +1,259 @@
+use crate::judith::analysis::cfg::ControlFlowGraph;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// Return types that don't require the function to return a value.
+const NO_VALUE_TYPES: [&str; 2] = ["Void", "Never"];
+/// The return type of functions that never return, which are expected to loop forever.
+const NEVER_TYPE: &str = "Never";
+
+/// Warns about code that can't be reached, functions that may end without returning their value
+/// and loops that never end, in the program given and every function inside it.
+pub fn check_control_flow<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = ControlFlowChecker {
+        messages: MessageContainer::new(),
+    };
+
+    checker.check_graph(&ControlFlowGraph::from_nodes(nodes), false);
+    for node in nodes {
+        checker.visit_node(node);
+    }
+
+    checker.messages
+}
+
+struct ControlFlowChecker {
+    messages: MessageContainer,
+}
+
+/// Functions and lambdas have their own graphs, so those are the only nodes checked here.
+impl<'a> Visitor<'a> for ControlFlowChecker {
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        let graph = ControlFlowGraph::from_body(&def.body);
+        self.check_graph(&graph, Self::type_name(&def.return_type) == Some(NEVER_TYPE));
+
+        // Arrow and expression bodies return the value of their expression, and block bodies the
+        // value of their last node.
+        if let Body::Block(body) = def.body
+            && Self::returns_value(&def.return_type)
+            && graph.falls_through()
+            && !Self::yields_value(body.nodes)
+        {
+            self.messages.add(compiler_messages::Analyzer::missing_return(
+                def.name.span, &def.name.name
+            ));
+        }
+
+        self.visit_parameter_list(&def.params);
+        self.visit_body(&def.body);
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        let is_endless = Self::type_name(&expr.return_type) == Some(NEVER_TYPE);
+        self.check_graph(&ControlFlowGraph::from_body(&expr.body), is_endless);
+        visitor::walk_lambda_expr(self, expr);
+    }
+}
+
+impl ControlFlowChecker {
+    /// Reports the unreachable code in the graph given and, unless `is_endless` is set, the loops
+    /// without exits.
+    fn check_graph(&mut self, graph: &ControlFlowGraph, is_endless: bool) {
+        let reachable = graph.reachable_blocks();
+
+        // Blocks are in source order, so only the first block of each run of unreachable code is
+        // reported.
+        let mut is_reported = false;
+        for (i, block) in graph.blocks.iter().enumerate() {
+            let Some(span) = block.first_span() else {
+                continue;
+            };
+
+            if reachable[i] {
+                is_reported = false;
+            }
+            else if !is_reported {
+                self.messages.add(compiler_messages::Analyzer::unreachable_code(Some(span)));
+                is_reported = true;
+            }
+        }
+
+        if is_endless {
+            return;
+        }
+        for info in &graph.loops {
+            if reachable[info.header.0] && !graph.loop_has_exit(info) {
+                self.messages.add(compiler_messages::Analyzer::loop_without_exit(info.expr.span));
+            }
+        }
+    }
+
+    /// Returns `true` if the last of the nodes given always produces a value, which is the value
+    /// of a block that ends without returning.
+    fn yields_value(nodes: &[SyntaxNode]) -> bool {
+        match nodes.last() {
+            Some(SyntaxNode::Expr(expr)) => Self::expr_yields_value(expr),
+            Some(SyntaxNode::Stmt(Stmt::Expr(stmt))) => Self::expr_yields_value(&stmt.expr),
+            _ => false,
+        }
+    }
+
+    fn expr_yields_value(expr: &Expr) -> bool {
+        match expr {
+            Expr::If(expr) => match &expr.alternate {
+                Some(alternate) => {
+                    Self::body_yields_value(&expr.consequent) && Self::body_yields_value(alternate)
+                }
+                None => false,
+            },
+            Expr::While(_) | Expr::Assignment(_) => false,
+            _ => true,
+        }
+    }
+
+    fn body_yields_value(body: &Body) -> bool {
+        match body {
+            Body::Block(body) => Self::yields_value(body.nodes),
+            Body::Arrow(body) => Self::expr_yields_value(&body.expr),
+            Body::Expr(body) => Self::expr_yields_value(&body.expr),
+        }
+    }
+
+    fn returns_value(return_type: &Option<TypeNode>) -> bool {
+        match Self::type_name(return_type) {
+            Some(name) => !NO_VALUE_TYPES.contains(&name),
+            None => return_type.is_some(),
+        }
+    }
+
+    /// Returns the name of the type given, if it's a plain, non-nullable type name.
+    fn type_name<'a>(ty: &Option<TypeNode<'a>>) -> Option<&'a str> {
+        match ty {
+            Some(TypeNode {
+                is_nullable: false,
+                ty: PartialType::Identifier(IdentifierType { name: Identifier::Simple(name), .. }),
+                ..
+            }) => Some(name.name.as_str()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_control_flow)
+    }
+
+    #[test]
+    fn valid_control_flow() {
+        println!("== Testing code where every path continues ==");
+
+        let cases = vec![
+            "let a = 1\n__p(a)",
+            "if a then b else c end\nd",
+            "if a then b elsif c then d end\ne",
+            "while a do b end\nc",
+            "func f () -> Num => 5",
+            "func f () -> Void\n__p(5)\nend",
+            "func f ()\n__p(5)\nend",
+            "func f () -> Never\nloop\n__p(5)\nend\nend",
+            "func f () -> Num\n__p(5)\nend",
+            "func f () -> Num\nlet a = 1\na + 1\nend",
+            "func f () -> Num\nif a then 1 elsif b then 2 else 3 end\nend",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_control_flow() {
+        println!("== Testing code with unreachable code, missing returns and endless loops ==");
+
+        let missing_return = || MessageCode::MissingReturn { name: String::from("f") };
+        let cases = vec![
+            ("loop a end", vec![MessageCode::LoopWithoutExit]),
+            (
+                "loop a end\nb\nif c then d end\ne",
+                vec![MessageCode::UnreachableCode, MessageCode::LoopWithoutExit],
+            ),
+            (
+                "if a then loop b end else loop c end end\nd",
+                vec![
+                    MessageCode::UnreachableCode,
+                    MessageCode::LoopWithoutExit,
+                    MessageCode::LoopWithoutExit,
+                ],
+            ),
+            (
+                "while a do loop b end\nc end\nd",
+                vec![MessageCode::UnreachableCode, MessageCode::LoopWithoutExit],
+            ),
+            ("func f () -> Num\nlet a = 5\nend", vec![missing_return()]),
+            ("func f () -> Num\n__p_print 5\nend", vec![missing_return()]),
+            ("func f () -> Num\nwhile a do 1 end\nend", vec![missing_return()]),
+            ("func f () -> Num\nif a then 1 end\nend", vec![missing_return()]),
+            (
+                "func f () -> Num\nif a then loop b end end\nlet c = 1\nend",
+                vec![missing_return(), MessageCode::LoopWithoutExit],
+            ),
+            (
+                "let g = func () => loop a end",
+                vec![MessageCode::LoopWithoutExit],
+            ),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            let mut codes: Vec<_> = messages.warnings.into_iter().map(|msg| msg.code).collect();
+            codes.sort_by_key(|code| code.i32());
+            assert_eq!(codes, expected);
+        }
+    }
+
+    #[test]
+    fn graph_shape() {
+        println!("== Testing the shape of control-flow graphs ==");
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("let a = 1\nif a then b elsif c then d end\ne").tokens, &arena);
+        let graph = ControlFlowGraph::from_nodes(&parser_res.nodes);
+        println!("{}", graph.to_dot());
+
+        // entry, then, elsif test, elsif then, elsif else, elsif join, join, exit.
+        assert_eq!(graph.blocks.len(), 8);
+        assert_eq!(graph.block(graph.entry).nodes.len(), 1);
+        assert!(graph.reachable_blocks().iter().all(|is_reachable| *is_reachable));
+        assert!(graph.falls_through());
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("while a do b end\nloop c end\nd").tokens, &arena);
+        let graph = ControlFlowGraph::from_nodes(&parser_res.nodes);
+        println!("{}", graph.to_dot());
+
+        assert_eq!(graph.loops.len(), 1);
+        assert!(!graph.loop_has_exit(&graph.loops[0]));
+        assert!(!graph.falls_through());
+        assert!(graph.to_dot().contains("[label=\"false\"]"));
+    }
+}
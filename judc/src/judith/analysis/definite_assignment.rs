@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+use crate::SourceSpan;
+
+/// The locals that are definitely assigned at some point of the program.
+#[derive(Debug, Clone)]
+struct FlowState {
+    /// The ids of the locals assigned on every path that leads to this point.
+    assigned: HashSet<usize>,
+    /// Whether any path leads to this point at all. An unreachable point is considered to have
+    /// every local assigned, so it doesn't restrict the paths it's joined with.
+    reachable: bool,
+}
+
+impl FlowState {
+    fn new() -> FlowState {
+        FlowState {
+            assigned: HashSet::new(),
+            reachable: true,
+        }
+    }
+
+    fn unreachable() -> FlowState {
+        FlowState {
+            assigned: HashSet::new(),
+            reachable: false,
+        }
+    }
+
+    fn is_assigned(&self, local: usize) -> bool {
+        !self.reachable || self.assigned.contains(&local)
+    }
+
+    /// Returns the state of the point where the paths represented by `self` and `other` meet.
+    fn join(self, other: FlowState) -> FlowState {
+        if !self.reachable {
+            return other;
+        }
+        if !other.reachable {
+            return self;
+        }
+
+        FlowState {
+            assigned: self.assigned.intersection(&other.assigned).copied().collect(),
+            reachable: true,
+        }
+    }
+}
+
+struct LocalInfo<'a> {
+    name: Symbol<'a>,
+    span: Option<SourceSpan>,
+}
+
+struct Scope<'a> {
+    names: HashMap<Symbol<'a>, usize>,
+    /// Whether this scope is the outermost scope of a function definition. Functions are hoisted,
+    /// so they may be called at any point of their enclosing scope, and locals declared outside of
+    /// them aren't tracked.
+    is_func_def: bool,
+}
+
+/// Checks that every local in the nodes given is assigned a value on every path that leads to a
+/// place where it's read.
+pub fn check_definite_assignment<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = DefiniteAssignmentChecker {
+        locals: Vec::new(),
+        scopes: Vec::new(),
+        state: FlowState::new(),
+        messages: MessageContainer::new(),
+    };
+
+    checker.push_scope(false);
+    for node in nodes {
+        checker.visit_node(node);
+    }
+    checker.scopes.pop();
+
+    checker.messages
+}
+
+struct DefiniteAssignmentChecker<'a> {
+    locals: Vec<LocalInfo<'a>>,
+    scopes: Vec<Scope<'a>>,
+    /// The state at the point of the program being checked.
+    state: FlowState,
+    messages: MessageContainer,
+}
+
+impl<'a> Visitor<'a> for DefiniteAssignmentChecker<'a> {
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        let state = std::mem::replace(&mut self.state, FlowState::new());
+        self.check_function(&def.params, &def.body, true);
+        self.state = state;
+    }
+
+    fn visit_block_body(&mut self, body: &'a BlockBody<'a>) {
+        self.push_scope(false);
+        visitor::walk_block_body(self, body);
+        self.scopes.pop();
+    }
+
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        // The values are evaluated before the new locals exist, so `let x = x` reads the previous
+        // `x`, if any.
+        let is_initialized = stmt.initializer.is_some();
+        if let Some(init) = &stmt.initializer {
+            self.visit_equals_value_clause(init);
+        }
+
+        for decl in stmt.declarators {
+            match decl {
+                PartialLocalDecl::Regular(decl) => {
+                    self.declare(&decl.declarator.name, is_initialized);
+                }
+                PartialLocalDecl::Destructured(decl) => {
+                    for declarator in decl.declarators {
+                        self.declare(&declarator.name, is_initialized);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        self.visit_expr(&expr.test);
+
+        let state = self.state.clone();
+        self.visit_body(&expr.consequent);
+        let consequent = std::mem::replace(&mut self.state, state);
+        if let Some(alternate) = &expr.alternate {
+            self.visit_body(alternate);
+        }
+
+        let alternate = std::mem::replace(&mut self.state, FlowState::new());
+        self.state = consequent.join(alternate);
+    }
+
+    fn visit_loop_expr(&mut self, expr: &'a LoopExpr<'a>) {
+        self.visit_body(&expr.body);
+
+        // Loops can only be left through an exit, and there are no exits yet, so nothing after a
+        // loop can be reached.
+        self.state = FlowState::unreachable();
+    }
+
+    fn visit_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        self.visit_expr(&expr.test);
+
+        // The body may not run at all, so only the test affects what comes after it.
+        let state = self.state.clone();
+        self.visit_body(&expr.body);
+        self.state = state;
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        self.visit_expr(&expr.right);
+
+        let Expr::Identifier(id) = &expr.left else {
+            // Assigning to a member or an element reads the receiver.
+            self.visit_expr(&expr.left);
+            return;
+        };
+        let Identifier::Simple(name) = &id.identifier else {
+            return;
+        };
+
+        // Compound assignments read the current value before replacing it.
+        if expr.operator.kind != OperatorKind::Assignment {
+            self.check_read(name);
+        }
+        if let Some(local) = self.resolve(name.name) {
+            self.state.assigned.insert(local);
+        }
+    }
+
+    fn visit_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) {
+        self.visit_expr(&expr.left);
+
+        // The right side of these operators may not be evaluated.
+        match expr.operator.kind {
+            OperatorKind::LogicalAnd
+            | OperatorKind::LogicalOr
+            | OperatorKind::NullCoalescing => {
+                let state = self.state.clone();
+                self.visit_expr(&expr.right);
+                self.state = state;
+            }
+            _ => self.visit_expr(&expr.right),
+        }
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        // A lambda may be called as soon as it exists, so the locals it captures must already be
+        // assigned.
+        let state = self.state.clone();
+        self.check_function(&expr.params, &expr.body, false);
+        self.state = state;
+    }
+
+    fn visit_identifier_expr(&mut self, expr: &'a IdentifierExpr<'a>) {
+        if let Identifier::Simple(name) = &expr.identifier {
+            self.check_read(name);
+        }
+    }
+}
+
+impl<'a> DefiniteAssignmentChecker<'a> {
+    fn push_scope(&mut self, is_func_def: bool) {
+        self.scopes.push(Scope {
+            names: HashMap::new(),
+            is_func_def,
+        });
+    }
+
+    /// Checks the body of a function or lambda, starting at the current state. Nothing assigned
+    /// inside a function is definitely assigned outside of it, so the caller restores the state
+    /// afterward.
+    fn check_function(
+        &mut self, params: &'a ParameterList<'a>, body: &'a Body<'a>, is_func_def: bool
+    ) {
+        self.push_scope(is_func_def);
+
+        for param in params.params {
+            if let Some(default_val) = &param.default_val {
+                self.visit_equals_value_clause(default_val);
+            }
+            self.declare(&param.declarator.declarator.name, true);
+        }
+        self.visit_body(body);
+
+        self.scopes.pop();
+    }
+
+    /// Reports the local read by the identifier given if it may not be assigned yet.
+    fn check_read(&mut self, name: &SimpleIdentifier<'a>) {
+        let Some(local) = self.resolve(name.name) else {
+            return;
+        };
+
+        if !self.state.is_assigned(local) {
+            let decl = &self.locals[local];
+            self.messages.add(compiler_messages::Analyzer::use_of_unassigned_local(
+                name.span, &decl.name, decl.span
+            ));
+
+            // The local is reported only once per path.
+            self.state.assigned.insert(local);
+        }
+    }
+
+    fn declare(&mut self, name: &SimpleIdentifier<'a>, is_assigned: bool) {
+        let local = self.locals.len();
+        self.locals.push(LocalInfo {
+            name: name.name,
+            span: name.span,
+        });
+        self.scopes.last_mut().unwrap().names.insert(name.name, local);
+
+        if is_assigned {
+            self.state.assigned.insert(local);
+        }
+    }
+
+    /// Returns the id of the local the name given refers to, or `None` if it doesn't refer to a
+    /// tracked local.
+    fn resolve(&self, name: Symbol) -> Option<usize> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(local) = scope.names.get(&name) {
+                return Some(*local);
+            }
+            if scope.is_func_def {
+                return None;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::{MessageCode, MessageSource};
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_definite_assignment)
+    }
+
+    fn unassigned(name: &str) -> MessageCode {
+        MessageCode::UseOfUnassignedLocal { name: String::from(name) }
+    }
+
+    #[test]
+    fn assigned_locals() {
+        println!("== Testing reads of assigned locals ==");
+
+        let cases = vec![
+            "let x = 1\nx",
+            "let x: Num\nx = 1\nx",
+            "let x\nif c then x = 1 else x = 2 end\nx",
+            "let x\nif a then x = 1 elsif b then x = 2 else x = 3 end\nx",
+            "let x\nlet y = (x = 1)\nx + y",
+            "let x, y = 1, 2\nx + y",
+            "let [a, b] = arr\na + b",
+            "let x\nloop\n    x = 1\nend\nx",
+            "func f (a: Num, b = 2) => a + b",
+            "let x\nfunc f () => x",
+            "let x = 1\nif c then let x\n x = 2 end\nx",
+            "undeclared_name",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 0);
+        }
+    }
+
+    #[test]
+    fn unassigned_locals() {
+        println!("== Testing reads of unassigned locals ==");
+
+        let cases = vec![
+            ("let x: Num\nx", "x"),
+            ("let x\nif c then x = 1 end\nx", "x"),
+            ("let x\nif a then x = 1 elsif b then x = 2 end\nx", "x"),
+            ("let x\nwhile c do x = 1 end\nx", "x"),
+            ("let x\nx += 1", "x"),
+            ("let x\nlet x = x", "x"),
+            ("let a, b = 1\nlet c, d\nc + a", "c"),
+            ("let x\nc and (x = true)\nx", "x"),
+            ("let x\nlet f = () => x", "x"),
+            ("func f ()\n    let y\n    y\nend", "y"),
+            ("let x\nif c then\n    x\nelse\n    x = 1\nend", "x"),
+        ];
+
+        for (input, name) in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            assert_eq!(messages.errors.len(), 1);
+            assert_eq!(messages.errors[0].code, unassigned(name));
+        }
+
+        println!("Testing that the message points at the declaration.");
+        let messages = check("let x: Num\n\n__p_print x");
+        let msg = &messages.errors[0];
+        println!("{}", msg.message);
+        assert!(msg.message.contains("line 1"));
+        assert!(matches!(&msg.source, MessageSource::Span(span) if span.line == 3));
+
+        println!("Testing that each local is reported once.");
+        let messages = check("let x\nx\nx + x");
+        assert_eq!(messages.errors.len(), 1);
+    }
+}
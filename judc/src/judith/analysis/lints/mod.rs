@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::{MessageCode, MessageCodeDiscriminants, MessageContainer, MessageKind};
+use crate::judith::lexical::lexer::LexerResult;
+use crate::judith::lexical::token::{Trivia, TriviaKind};
+use crate::judith::syntax::nodes::SyntaxNode;
+
+pub mod names;
+pub mod style;
+
+/// What happens when a lint finds something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// Nothing is reported.
+    Allow,
+    /// A warning is reported.
+    Warn,
+    /// An error is reported.
+    Deny,
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => Err(format!("Unknown lint level: '{}'. Expected 'allow', 'warn' or 'deny'.", s)),
+        }
+    }
+}
+
+/// A check for code that is valid, but is likely a mistake or goes against the style of the
+/// language.
+#[derive(Debug)]
+pub struct Lint {
+    /// The name used to refer to this lint in the command line, config files and comments.
+    pub name: &'static str,
+    pub default_level: LintLevel,
+    /// The code of the messages produced by this lint.
+    pub code: MessageCodeDiscriminants,
+}
+
+pub const UNUSED_LOCAL: Lint = Lint {
+    name: "unused_local",
+    default_level: LintLevel::Warn,
+    code: MessageCodeDiscriminants::UnusedLocal,
+};
+
+pub const UNUSED_PARAMETER: Lint = Lint {
+    name: "unused_parameter",
+    default_level: LintLevel::Warn,
+    code: MessageCodeDiscriminants::UnusedParameter,
+};
+
+pub const SHADOWING: Lint = Lint {
+    name: "shadowing",
+    default_level: LintLevel::Allow,
+    code: MessageCodeDiscriminants::ShadowedName,
+};
+
+pub const NAMING_CONVENTION: Lint = Lint {
+    name: "naming_convention",
+    default_level: LintLevel::Warn,
+    code: MessageCodeDiscriminants::NamingConvention,
+};
+
+pub const REDUNDANT_PARENTHESES: Lint = Lint {
+    name: "redundant_parentheses",
+    default_level: LintLevel::Warn,
+    code: MessageCodeDiscriminants::RedundantParentheses,
+};
+
+pub const BOOL_COMPARISON: Lint = Lint {
+    name: "bool_comparison",
+    default_level: LintLevel::Warn,
+    code: MessageCodeDiscriminants::ComparisonWithBool,
+};
+
+/// Every lint there is.
+pub const LINTS: [&Lint; 6] = [
+    &UNUSED_LOCAL,
+    &UNUSED_PARAMETER,
+    &SHADOWING,
+    &NAMING_CONVENTION,
+    &REDUNDANT_PARENTHESES,
+    &BOOL_COMPARISON,
+];
+
+impl Lint {
+    pub fn find(name: &str) -> Option<&'static Lint> {
+        LINTS.into_iter().find(|lint| lint.name == name)
+    }
+
+    fn for_code(code: &MessageCode) -> Option<&'static Lint> {
+        let code = MessageCodeDiscriminants::from(code);
+        LINTS.into_iter().find(|lint| lint.code == code)
+    }
+}
+
+/// A level set by a comment, which only applies to some lines.
+struct LineLevel {
+    lint: &'static str,
+    level: LintLevel,
+    first_line: i64,
+    /// The last line affected, or `None` if it affects every line until the end of the file.
+    last_line: Option<i64>,
+}
+
+/// The level of each lint. Levels can be set, in order of precedence, by comments in the code,
+/// by the command line and by config files.
+pub struct LintConfig {
+    levels: HashMap<&'static str, LintLevel>,
+    line_levels: Vec<LineLevel>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig::new()
+    }
+}
+
+impl LintConfig {
+    /// Creates a config where every lint is at its default level.
+    pub fn new() -> LintConfig {
+        LintConfig {
+            levels: LINTS.into_iter().map(|lint| (lint.name, lint.default_level)).collect(),
+            line_levels: Vec::new(),
+        }
+    }
+
+    pub fn set_level(&mut self, name: &str, level: LintLevel) -> Result<(), String> {
+        let lint = Lint::find(name).ok_or_else(|| format!("Unknown lint: '{}'.", name))?;
+        self.levels.insert(lint.name, level);
+
+        Ok(())
+    }
+
+    /// Reads the levels set by a config file. Each line of the file sets the level of a lint
+    /// (e.g. "shadowing = deny"). Empty lines and lines starting with "--" are ignored.
+    pub fn read_config(&mut self, src: &str) -> Result<(), String> {
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("--") {
+                continue;
+            }
+
+            let Some((name, level)) = line.split_once('=') else {
+                return Err(format!("Line {}: expected '<lint> = <level>'.", i + 1));
+            };
+            let level = level.trim().parse().map_err(|err| format!("Line {}: {}", i + 1, err))?;
+            self.set_level(name.trim(), level).map_err(|err| format!("Line {}: {}", i + 1, err))?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the levels set by comments in the trivia of the tokens given, such as
+    /// "-- lint: allow(unused_local, shadowing)". A comment in its own line applies to every line
+    /// after it, while a comment after some code only applies to that line. Returns warnings for
+    /// the lints that don't exist.
+    pub fn read_comments(&mut self, lexer_res: &LexerResult) -> MessageContainer {
+        let mut messages = MessageContainer::new();
+
+        for tok in &lexer_res.tokens {
+            for trivia in lexer_res.leading_trivia(tok) {
+                self.read_comment(trivia, None, &mut messages);
+            }
+            for trivia in lexer_res.trailing_trivia(tok) {
+                self.read_comment(trivia, Some(tok.base().line), &mut messages);
+            }
+        }
+
+        messages
+    }
+
+    fn read_comment(&mut self, trivia: &Trivia, last_line: Option<i64>, messages: &mut MessageContainer) {
+        if trivia.kind != TriviaKind::SingleLineComment {
+            return;
+        }
+        let Some(directive) = trivia.lexeme.trim_start_matches('-').trim().strip_prefix("lint:") else {
+            return;
+        };
+
+        // Each directive looks like "level(lint, lint...)".
+        for directive in directive.split(')') {
+            let Some((level, names)) = directive.split_once('(') else {
+                continue;
+            };
+            let Ok(level) = level.trim().trim_start_matches(',').trim().parse::<LintLevel>() else {
+                continue;
+            };
+
+            for name in names.split(',').map(str::trim) {
+                match Lint::find(name) {
+                    Some(lint) => self.line_levels.push(LineLevel {
+                        lint: lint.name,
+                        level,
+                        first_line: trivia.span.line,
+                        last_line,
+                    }),
+                    None => messages.add(compiler_messages::Linter::unknown_lint(trivia.span, name)),
+                }
+            }
+        }
+    }
+
+    /// Returns the level of the lint given at the line given.
+    pub fn level_at(&self, lint: &Lint, line: i64) -> LintLevel {
+        let line_level = self.line_levels.iter().rev().find(|line_level| {
+            line_level.lint == lint.name
+                && line_level.first_line <= line
+                && line_level.last_line.is_none_or(|last_line| line <= last_line)
+        });
+
+        match line_level {
+            Some(line_level) => line_level.level,
+            None => self.levels.get(lint.name).copied().unwrap_or(lint.default_level),
+        }
+    }
+}
+
+/// Runs every lint over the nodes given, reporting what they find at the level given by `config`.
+pub fn lint(nodes: &[SyntaxNode], config: &LintConfig) -> MessageContainer {
+    let mut found = MessageContainer::new();
+    found.add_all(names::check_names(nodes));
+    found.add_all(style::check_style(nodes));
+
+    let mut messages = MessageContainer::new();
+    let found = found.infos.into_iter().chain(found.warnings).chain(found.errors);
+    for mut msg in found {
+        let lint = Lint::for_code(&msg.code).expect("Every lint message belongs to a lint.");
+
+        msg.kind = match config.level_at(lint, msg.source.get_line()) {
+            LintLevel::Allow => continue,
+            LintLevel::Warn => MessageKind::Warning,
+            LintLevel::Deny => MessageKind::Error,
+        };
+        messages.add(msg);
+    }
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    fn lint_src(src: &str, config: &mut LintConfig) -> MessageContainer {
+        let lexer_res = tokenize(src);
+        let mut messages = config.read_comments(&lexer_res);
+        let arena = Arena::new();
+        let parser_res = parse(lexer_res.tokens, &arena);
+        assert_eq!(parser_res.messages.count(), 0, "'{}' should parse.", src);
+
+        messages.add_all(lint(&parser_res.nodes, config));
+        messages
+    }
+
+    #[test]
+    fn lint_levels() {
+        println!("== Testing lint levels ==");
+
+        let src = "let x = 1";
+        let messages = lint_src(src, &mut LintConfig::new());
+        assert_eq!(messages.warnings.len(), 1);
+        assert_eq!(messages.warnings[0].code, MessageCode::UnusedLocal { name: String::from("x") });
+
+        let mut config = LintConfig::new();
+        config.set_level("unused_local", LintLevel::Deny).unwrap();
+        assert_eq!(lint_src(src, &mut config).errors.len(), 1);
+
+        let mut config = LintConfig::new();
+        config.read_config("-- House style.\n\nunused_local = allow\nshadowing=deny\n").unwrap();
+        assert_eq!(config.level_at(&SHADOWING, 1), LintLevel::Deny);
+        assert_eq!(lint_src(src, &mut config).count(), 0);
+
+        let mut config = LintConfig::new();
+        assert!(config.set_level("unused_locals", LintLevel::Deny).is_err());
+        assert!(config.read_config("unused_local: allow").is_err());
+        assert!(config.read_config("unused_local = ignore").is_err());
+    }
+
+    #[test]
+    fn lint_comments() {
+        println!("== Testing lint levels set by comments ==");
+
+        let src = "let x = 1 -- lint: allow(unused_local)\nlet y = 2";
+        let messages = lint_src(src, &mut LintConfig::new());
+        assert_eq!(messages.warnings.len(), 1);
+        assert_eq!(messages.warnings[0].code, MessageCode::UnusedLocal { name: String::from("y") });
+
+        let src = "let a = 1\n-- lint: deny(unused_local), allow(redundant_parentheses)\nlet b = (2)";
+        let messages = lint_src(src, &mut LintConfig::new());
+        assert_eq!(messages.warnings.len(), 1);
+        assert_eq!(messages.errors.len(), 1);
+        assert_eq!(messages.errors[0].code, MessageCode::UnusedLocal { name: String::from("b") });
+
+        let src = "-- lint: allow(unused_locals)\nlet _a = 1";
+        let messages = lint_src(src, &mut LintConfig::new());
+        assert_eq!(messages.warnings.len(), 1);
+        assert_eq!(messages.warnings[0].code, MessageCode::UnknownLint { name: String::from("unused_locals") });
+    }
+}
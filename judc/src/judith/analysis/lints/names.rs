@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor::Visitor;
+
+struct LocalInfo<'a> {
+    name: &'a SimpleIdentifier<'a>,
+    is_param: bool,
+    is_used: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Binding {
+    Local(usize),
+    Func,
+}
+
+/// Checks the names declared in the nodes given: locals and parameters that are never used,
+/// locals that shadow other variables, and names that don't follow the naming conventions of the
+/// language (snake_case for variables and functions, PascalCase for types).
+pub fn check_names<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = NameChecker {
+        locals: Vec::new(),
+        scopes: Vec::new(),
+        messages: MessageContainer::new(),
+    };
+
+    checker.check_scope(nodes);
+
+    for local in &checker.locals {
+        let name = &local.name.name;
+        if name.starts_with('_') {
+            continue;
+        }
+
+        if !local.is_used {
+            checker.messages.add(match local.is_param {
+                true => compiler_messages::Linter::unused_parameter(local.name.span, name),
+                false => compiler_messages::Linter::unused_local(local.name.span, name),
+            });
+        }
+    }
+
+    checker.messages
+}
+
+struct NameChecker<'a> {
+    locals: Vec<LocalInfo<'a>>,
+    scopes: Vec<HashMap<Symbol<'a>, Binding>>,
+    messages: MessageContainer,
+}
+
+impl<'a> Visitor<'a> for NameChecker<'a> {
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        self.check_snake_case(&def.name);
+        if let Some(return_type) = &def.return_type {
+            self.visit_type_node(return_type);
+        }
+        self.check_function(&def.params, &def.body);
+    }
+
+    fn visit_block_body(&mut self, body: &'a BlockBody<'a>) {
+        self.check_scope(body.nodes);
+    }
+
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        // Initializers are checked first, as they can't use the locals they initialize.
+        if let Some(init) = &stmt.initializer {
+            self.visit_equals_value_clause(init);
+        }
+
+        for decl in stmt.declarators {
+            match decl {
+                PartialLocalDecl::Regular(decl) => self.declare(&decl.declarator, false),
+                PartialLocalDecl::Destructured(decl) => {
+                    for declarator in decl.declarators {
+                        self.declare(declarator, false);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        self.visit_expr(&expr.right);
+
+        // Assigning a new value to a local doesn't use it, but compound assignments do.
+        match (&expr.left, &expr.operator.kind) {
+            (Expr::Identifier(_), OperatorKind::Assignment) => {}
+            _ => self.visit_expr(&expr.left),
+        }
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        if let Some(return_type) = &expr.return_type {
+            self.visit_type_node(return_type);
+        }
+        self.check_function(&expr.params, &expr.body);
+    }
+
+    fn visit_identifier_expr(&mut self, expr: &'a IdentifierExpr<'a>) {
+        if let Identifier::Simple(name) = &expr.identifier {
+            let symbol = self.scopes.iter().rev().find_map(|scope| scope.get(&name.name));
+            if let Some(Binding::Local(local)) = symbol {
+                self.locals[*local].is_used = true;
+            }
+        }
+    }
+
+    /// Checks that the names of the types used are written in PascalCase.
+    fn visit_identifier_type(&mut self, ty: &'a IdentifierType<'a>) {
+        let name = match &ty.name {
+            Identifier::Simple(name) => name,
+            Identifier::Qualified(name) => &name.name,
+        };
+        if !name.is_meta_name && !is_pascal_case(&name.name) {
+            self.messages.add(compiler_messages::Linter::naming_convention(
+                name.span, &name.name, "PascalCase", &to_pascal_case(&name.name)
+            ));
+        }
+    }
+}
+
+impl<'a> NameChecker<'a> {
+    fn check_scope(&mut self, nodes: &'a [SyntaxNode<'a>]) {
+        // Functions can be used before they are declared.
+        let mut scope = HashMap::new();
+        for node in nodes {
+            if let SyntaxNode::Item(Item::FuncDef(def)) = node {
+                scope.insert(def.name.name, Binding::Func);
+            }
+        }
+        self.scopes.push(scope);
+
+        for node in nodes {
+            self.visit_node(node);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn check_function(&mut self, params: &'a ParameterList<'a>, body: &'a Body<'a>) {
+        self.scopes.push(HashMap::new());
+
+        for param in params.params {
+            if let Some(default_val) = &param.default_val {
+                self.visit_equals_value_clause(default_val);
+            }
+            self.declare(&param.declarator.declarator, true);
+        }
+        self.visit_body(body);
+
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, declarator: &'a LocalDeclarator<'a>, is_param: bool) {
+        let name = &declarator.name;
+
+        self.check_snake_case(name);
+        if let Some(annotation) = &declarator.type_annotation {
+            self.visit_type_annotation(annotation);
+        }
+
+        let is_shadowing = self.scopes.iter().any(|scope| scope.contains_key(&name.name));
+        if is_shadowing && name.name != "_" {
+            self.messages.add(compiler_messages::Linter::shadowed_name(name.span, &name.name));
+        }
+
+        self.locals.push(LocalInfo {
+            name,
+            is_param,
+            is_used: false,
+        });
+        self.scopes.last_mut().unwrap().insert(name.name, Binding::Local(self.locals.len() - 1));
+    }
+
+    fn check_snake_case(&mut self, name: &SimpleIdentifier) {
+        if !name.is_meta_name && !is_snake_case(&name.name) {
+            self.messages.add(compiler_messages::Linter::naming_convention(
+                name.span, &name.name, "snake_case", &to_snake_case(&name.name)
+            ));
+        }
+    }
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.chars().any(char::is_uppercase)
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_none_or(char::is_uppercase) && !name.contains('_')
+}
+
+/// Converts a name written in any case (e.g. "camelCase" or "PascalCase") to snake_case.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev: Option<char> = None;
+
+    for c in name.chars() {
+        // A new word starts at each uppercase letter that follows a lowercase letter or a digit.
+        if c.is_uppercase() && prev.is_some_and(|prev| prev.is_lowercase() || prev.is_ascii_digit()) {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+        prev = Some(c);
+    }
+
+    snake
+}
+
+/// Converts a name written in any case (e.g. "snake_case" or "camelCase") to PascalCase.
+fn to_pascal_case(name: &str) -> String {
+    name.split('_').filter(|word| !word.is_empty()).map(|word| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_names)
+    }
+
+    #[test]
+    fn valid_names() {
+        println!("== Testing well named and used variables ==");
+
+        let cases = vec![
+            "let a = 1\n__p(a)",
+            "let mut a = 1\na += 1",
+            "let _a = 1",
+            "func add (a: Num, b: Num) -> Num => a + b",
+            "func f (_unused: Num) => 1",
+            "let [a, b] = arr\n__p(a + b)",
+            "let a = 1\nlet f = func () => a\nf()",
+            "func f () => g()\nfunc g () => f()",
+            "let list: Num? = get()\n__p(list)",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_names() {
+        println!("== Testing badly named and unused variables ==");
+
+        let name = |name: &str| String::from(name);
+        let cases = vec![
+            ("let a = 1", MessageCode::UnusedLocal { name: name("a") }),
+            ("let mut a = 1\na = 2", MessageCode::UnusedLocal { name: name("a") }),
+            ("let [a, _b] = arr", MessageCode::UnusedLocal { name: name("a") }),
+            ("func f (a: Num) => 1", MessageCode::UnusedParameter { name: name("a") }),
+            ("let f = func (a) => 1\nf()", MessageCode::UnusedParameter { name: name("a") }),
+            ("func doThing () => 1", MessageCode::NamingConvention { name: name("doThing") }),
+            ("let myNum = 1\n__p(myNum)", MessageCode::NamingConvention { name: name("myNum") }),
+            ("let _a: num = 1", MessageCode::NamingConvention { name: name("num") }),
+            ("func f () -> my_type => 1", MessageCode::NamingConvention { name: name("my_type") }),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 1);
+            assert_eq!(messages.warnings[0].code, expected);
+        }
+    }
+
+    #[test]
+    fn shadowing() {
+        println!("== Testing shadowed variables ==");
+
+        let messages = check("let a = 1\n__p(a)\nfunc f (a) => a");
+        assert_eq!(messages.count(), 1);
+        assert_eq!(messages.warnings[0].code, MessageCode::ShadowedName { name: String::from("a") });
+    }
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(to_snake_case("doThing"), "do_thing");
+        assert_eq!(to_snake_case("HTTPServer2Go"), "httpserver2_go");
+        assert_eq!(to_pascal_case("my_type"), "MyType");
+        assert_eq!(to_pascal_case("num"), "Num");
+    }
+}
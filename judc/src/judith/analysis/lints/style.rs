@@ -0,0 +1,213 @@
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::lexical::token::TokenKind;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// Checks the nodes given for code that could be written in a simpler way: parentheses that
+/// don't change anything, and comparisons with `true` or `false`.
+pub fn check_style<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = StyleChecker {
+        messages: MessageContainer::new(),
+    };
+
+    for node in nodes {
+        checker.visit_node(node);
+    }
+
+    checker.messages
+}
+
+struct StyleChecker {
+    messages: MessageContainer,
+}
+
+/// Overrides the nodes that hold values standing on their own, such as the value of a local or
+/// an argument, where parentheses around it never change its meaning.
+impl<'a> Visitor<'a> for StyleChecker {
+    fn visit_node(&mut self, node: &'a SyntaxNode<'a>) {
+        match node {
+            SyntaxNode::Expr(expr) => self.check_value(expr),
+            _ => visitor::walk_node(self, node),
+        }
+    }
+
+    fn visit_arrow_body(&mut self, body: &'a ArrowBody<'a>) {
+        self.check_value(&body.expr);
+    }
+
+    fn visit_expr_body(&mut self, body: &'a ExprBody<'a>) {
+        self.check_value(&body.expr);
+    }
+
+    fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt<'a>) {
+        self.check_value(&stmt.expr);
+    }
+
+    fn visit_priv_print_stmt(&mut self, stmt: &'a PrivPrintStmt<'a>) {
+        self.check_value(&stmt.expr);
+    }
+
+    fn visit_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        self.check_value(&expr.test);
+        self.visit_body(&expr.consequent);
+        if let Some(alternate) = &expr.alternate {
+            self.visit_body(alternate);
+        }
+    }
+
+    fn visit_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        self.check_value(&expr.test);
+        self.visit_body(&expr.body);
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        self.visit_expr(&expr.left);
+        self.check_value(&expr.right);
+    }
+
+    fn visit_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) {
+        self.check_bool_comparison(expr);
+        visitor::walk_binary_expr(self, expr);
+    }
+
+    fn visit_group_expr(&mut self, group: &'a GroupExpr<'a>) {
+        // Parentheses around something that can't be split never change its meaning.
+        if let Expr::Identifier(_)
+            | Expr::Literal(_)
+            | Expr::Group(_)
+            | Expr::Array(_)
+            | Expr::EmptyCollection(_) = &group.expr
+        {
+            self.messages.add(compiler_messages::Linter::redundant_parentheses(group.span));
+        }
+        visitor::walk_group_expr(self, group);
+    }
+
+    fn visit_tuple_expr(&mut self, expr: &'a TupleExpr<'a>) {
+        self.check_values(expr.elements);
+    }
+
+    fn visit_array_expr(&mut self, expr: &'a ArrayExpr<'a>) {
+        self.check_values(expr.elements);
+    }
+
+    fn visit_index_expr(&mut self, expr: &'a IndexExpr<'a>) {
+        self.visit_expr(&expr.receiver);
+        self.check_value(&expr.index);
+    }
+
+    fn visit_argument(&mut self, arg: &'a Argument<'a>) {
+        self.check_value(&arg.expr);
+    }
+
+    /// Initializers of locals and fields, and default values of parameters.
+    fn visit_equals_value_clause(&mut self, clause: &'a EqualsValueClause<'a>) {
+        self.check_values(clause.values);
+    }
+}
+
+impl StyleChecker {
+    fn check_values<'a>(&mut self, exprs: &'a [Expr<'a>]) {
+        for expr in exprs {
+            self.check_value(expr);
+        }
+    }
+
+    /// Checks an expression that stands on its own, where any parentheses around it are redundant.
+    fn check_value<'a>(&mut self, expr: &'a Expr<'a>) {
+        match expr {
+            Expr::Group(group) => {
+                self.messages.add(compiler_messages::Linter::redundant_parentheses(group.span));
+                visitor::walk_group_expr(self, group);
+            }
+            _ => self.visit_expr(expr),
+        }
+    }
+
+    /// Reports comparisons such as `a == true`, which are the same as `a` itself.
+    fn check_bool_comparison(&mut self, expr: &BinaryExpr) {
+        let is_equals = match expr.operator.kind {
+            OperatorKind::Equals | OperatorKind::ReferenceEquals => true,
+            OperatorKind::NotEquals | OperatorKind::ReferenceNotEquals => false,
+            _ => return,
+        };
+
+        let Some(value) = Self::bool_literal(&expr.left).or(Self::bool_literal(&expr.right)) else {
+            return;
+        };
+        self.messages.add(compiler_messages::Linter::comparison_with_bool(
+            expr.span, is_equals != value
+        ));
+    }
+
+    fn bool_literal(expr: &Expr) -> Option<bool> {
+        let Expr::Literal(literal) = expr else {
+            return None;
+        };
+
+        match literal.literal.kind {
+            TokenKind::KwTrue => Some(true),
+            TokenKind::KwFalse => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_style)
+    }
+
+    #[test]
+    fn valid_style() {
+        println!("== Testing code without style issues ==");
+
+        let cases = vec![
+            "let a = (b + c) * d",
+            "let a = -(b + c)",
+            "(a?.b).c",
+            "if a and (b or c) then d end",
+            "a == b",
+            "let a, b = (1, 2)",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input);
+            assert_eq!(check(input).count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_style() {
+        println!("== Testing code with style issues ==");
+
+        let cases = vec![
+            ("let a = (b + c)", MessageCode::RedundantParentheses),
+            ("if (a) then b end", MessageCode::RedundantParentheses),
+            ("while (a < b) do b end", MessageCode::RedundantParentheses),
+            ("f((a + b))", MessageCode::RedundantParentheses),
+            ("let a = (b) + c", MessageCode::RedundantParentheses),
+            ("let a = ([1, 2])[0]", MessageCode::RedundantParentheses),
+            ("if a == true then b end", MessageCode::ComparisonWithBool),
+            ("if false != a then b end", MessageCode::ComparisonWithBool),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 1);
+            assert_eq!(messages.warnings[0].code, expected);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// What can be known about the value of an expression before running it, as far as spreading it
+/// across several declarators is concerned.
+enum ValueShape {
+    /// A tuple with the amount of elements given.
+    Tuple(usize),
+    /// A value that is certainly not a tuple, described for diagnostics (e.g. "an array").
+    NotTuple(&'static str),
+    /// Anything else, such as identifiers or calls, whose value isn't known until it's resolved.
+    Unknown,
+}
+
+/// Checks that every local declaration in the nodes given has as many values as declarators, or a
+/// single tuple value to spread across them.
+pub fn check_local_decls<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = LocalDeclChecker {
+        messages: MessageContainer::new(),
+    };
+
+    for node in nodes {
+        checker.visit_node(node);
+    }
+
+    checker.messages
+}
+
+struct LocalDeclChecker {
+    messages: MessageContainer,
+}
+
+impl<'a> Visitor<'a> for LocalDeclChecker {
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        visitor::walk_local_decl_stmt(self, stmt);
+
+        let Some(init) = &stmt.initializer else {
+            return;
+        };
+
+        let declarators = stmt.declarators.len();
+        let values = init.values.len();
+        if declarators == values {
+            return;
+        }
+
+        if values != 1 {
+            self.messages.add(compiler_messages::Analyzer::declarator_count_mismatch(
+                stmt.span, declarators, values
+            ));
+            return;
+        }
+
+        match Self::value_shape(&init.values[0]) {
+            ValueShape::Tuple(elements) if elements != declarators => {
+                self.messages.add(compiler_messages::Analyzer::tuple_spread_length_mismatch(
+                    stmt.span, declarators, elements
+                ));
+            }
+            ValueShape::NotTuple(found) => {
+                self.messages.add(compiler_messages::Analyzer::value_not_spreadable(
+                    stmt.span, declarators, found
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl LocalDeclChecker {
+    fn value_shape(expr: &Expr) -> ValueShape {
+        match expr {
+            Expr::Tuple(expr) => ValueShape::Tuple(expr.elements.len()),
+            Expr::Group(expr) => Self::value_shape(&expr.expr),
+            Expr::Literal(_) => ValueShape::NotTuple("a literal"),
+            Expr::Array(_) | Expr::EmptyCollection(_) => ValueShape::NotTuple("an array"),
+            Expr::ObjectInit(_) => ValueShape::NotTuple("an object"),
+            Expr::Lambda(_) => ValueShape::NotTuple("a function"),
+            _ => ValueShape::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_local_decls)
+    }
+
+    #[test]
+    fn valid_local_decls() {
+        println!("== Testing local declarations with matching values ==");
+
+        let cases = vec![
+            "let a",
+            "let a, b",
+            "let a = 1",
+            "let a, mut b: Num = 1, 2",
+            "let a, b = (1, 2)",
+            "let a, b = ((1, 2))",
+            "let a, b = pair",
+            "let a, b = make_pair()",
+            "let a, [b, c] = 1, arr",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input);
+            assert_eq!(check(input).count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_local_decls() {
+        println!("== Testing local declarations with mismatched values ==");
+
+        let cases = vec![
+            (
+                "let a, b = 1, 2, 3",
+                MessageCode::DeclaratorCountMismatch { declarators: 2, values: 3 },
+            ),
+            (
+                "let a = 1, 2",
+                MessageCode::DeclaratorCountMismatch { declarators: 1, values: 2 },
+            ),
+            (
+                "let a, b, c = (1, 2)",
+                MessageCode::TupleSpreadLengthMismatch { declarators: 3, elements: 2 },
+            ),
+            ("let a, b = 1", MessageCode::ValueNotSpreadable),
+            ("let a, b = [1, 2]", MessageCode::ValueNotSpreadable),
+            (
+                "func f () if true then let a, b = 1, 2, 3 end end",
+                MessageCode::DeclaratorCountMismatch { declarators: 2, values: 3 },
+            ),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let messages = check(input);
+            assert_eq!(messages.errors.len(), 1);
+            assert_eq!(messages.errors[0].code, expected);
+        }
+    }
+}
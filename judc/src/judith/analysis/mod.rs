@@ -1,2 +1,48 @@
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::syntax::nodes::SyntaxNode;
+
 pub mod binder;
-pub mod nodes;
\ No newline at end of file
+pub mod nodes;
+pub mod local_decls;
+pub mod definite_assignment;
+pub mod ownership;
+pub mod nullability;
+pub mod cfg;
+pub mod control_flow;
+pub mod lints;
+
+pub struct AnalyzerResult {
+    pub messages: MessageContainer,
+}
+
+/// Runs every semantic check over the nodes given and returns the messages they produced.
+pub fn analyze(nodes: &[SyntaxNode]) -> AnalyzerResult {
+    let mut messages = MessageContainer::new();
+
+    messages.add_all(local_decls::check_local_decls(nodes));
+    messages.add_all(definite_assignment::check_definite_assignment(nodes));
+    messages.add_all(ownership::check_ownership(nodes));
+    messages.add_all(nullability::check_nullability(nodes));
+    messages.add_all(control_flow::check_control_flow(nodes));
+
+    AnalyzerResult { messages }
+}
+
+/// Parses the source given, which must be valid, and runs the check given over its nodes.
+#[cfg(test)]
+pub(crate) fn check_source(
+    src: &str, check: fn(&[SyntaxNode]) -> MessageContainer
+) -> MessageContainer {
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+
+    let arena = Arena::new();
+    let parser_res = parse(tokenize(src).tokens, &arena);
+    for msg in parser_res.messages.all_messages() {
+        println!("{}", msg.message);
+    }
+    assert_eq!(parser_res.messages.count(), 0, "'{}' should parse.", src);
+
+    check(&parser_res.nodes)
+}
@@ -0,0 +1,798 @@
+use std::collections::HashMap;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::{CompilerMessage, MessageContainer};
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::lexical::token::TokenKind;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// Whether a value may be `null` or `undefined`. These are separate states: `undefined` marks
+/// something that doesn't exist, while `null` is the explicit lack of a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Nullness {
+    null: bool,
+    undefined: bool,
+    /// Whether the value may also be something this analysis can't follow, such as the result of
+    /// an unresolved call. Such values are assumed to be `null` if the variable storing them accepts
+    /// it, as only `undefined` written out is followed.
+    is_unknown: bool,
+}
+
+impl Nullness {
+    const NON_NULL: Nullness = Nullness { null: false, undefined: false, is_unknown: false };
+    const NULL: Nullness = Nullness { null: true, undefined: false, is_unknown: false };
+    const UNDEFINED: Nullness = Nullness { null: false, undefined: true, is_unknown: false };
+    const ANY: Nullness = Nullness { null: true, undefined: true, is_unknown: false };
+    const UNKNOWN: Nullness = Nullness { null: false, undefined: false, is_unknown: true };
+
+    fn is_absent(&self) -> bool {
+        self.null || self.undefined
+    }
+
+    fn join(self, other: Nullness) -> Nullness {
+        Nullness {
+            null: self.null || other.null,
+            undefined: self.undefined || other.undefined,
+            is_unknown: self.is_unknown || other.is_unknown,
+        }
+    }
+
+    /// Returns the nullness of this value once it's stored in a variable that accepts the values
+    /// given. Values the variable doesn't accept are reported when they're assigned, so they
+    /// aren't reported again when it's used.
+    fn stored_in(self, accepts: Nullness) -> Nullness {
+        if !accepts.is_absent() {
+            return Nullness::NON_NULL;
+        }
+
+        let known = Nullness { is_unknown: false, ..self };
+        match self.is_unknown && accepts.null {
+            true => known.join(Nullness::NULL),
+            false => known,
+        }
+    }
+}
+
+/// The nullness of each local at some point of the program. Locals that aren't in the map are
+/// known not to be null, so an empty state can be joined with anything without changing it.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FlowState {
+    locals: HashMap<usize, Nullness>,
+}
+
+impl FlowState {
+    fn get(&self, local: usize) -> Nullness {
+        self.locals.get(&local).copied().unwrap_or_default()
+    }
+
+    fn set(&mut self, local: usize, nullness: Nullness) {
+        if nullness.is_absent() {
+            self.locals.insert(local, Nullness { is_unknown: false, ..nullness });
+        }
+        else {
+            self.locals.remove(&local);
+        }
+    }
+
+    fn join(mut self, other: FlowState) -> FlowState {
+        for (local, nullness) in other.locals {
+            let joined = self.get(local).join(nullness);
+            self.locals.insert(local, joined);
+        }
+
+        self
+    }
+}
+
+struct LocalInfo {
+    /// The values the local accepts: everything, if it's nullable.
+    accepts: Nullness,
+}
+
+#[derive(Clone, Copy)]
+enum Binding<'a> {
+    Local(usize),
+    Func(&'a FuncDef<'a>),
+}
+
+/// Checks that values that may be `null` or `undefined` are never stored in non-nullable
+/// variables, nor accessed without checking them first.
+pub fn check_nullability<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = NullabilityChecker {
+        locals: Vec::new(),
+        scopes: Vec::new(),
+        state: FlowState::default(),
+        value: Nullness::NON_NULL,
+        is_silent: false,
+        messages: MessageContainer::new(),
+    };
+
+    checker.check_scope(nodes);
+
+    checker.messages
+}
+
+struct NullabilityChecker<'a> {
+    locals: Vec<LocalInfo>,
+    scopes: Vec<HashMap<Symbol<'a>, Binding<'a>>>,
+    /// The state at the point of the program being checked.
+    state: FlowState,
+    /// The nullness of the value of the last node checked.
+    value: Nullness,
+    /// When set, diagnostics are discarded. Used while looking for the state at the start of a
+    /// loop's iterations.
+    is_silent: bool,
+    messages: MessageContainer,
+}
+
+impl<'a> Visitor<'a> for NullabilityChecker<'a> {
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        let state = std::mem::take(&mut self.state);
+        self.check_function(&def.params, &def.body);
+        self.state = state;
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_block_body(&mut self, body: &'a BlockBody<'a>) {
+        self.check_scope(body.nodes);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a Stmt<'a>) {
+        visitor::walk_stmt(self, stmt);
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        let values = match &stmt.initializer {
+            Some(init) => init.values,
+            None => &[],
+        };
+
+        let nullnesses: Vec<Nullness> = values.iter().map(|value| self.check_expr(value)).collect();
+        // Values are only paired with declarators when each declarator has its own.
+        let has_own_values = values.len() == stmt.declarators.len();
+
+        for (i, decl) in stmt.declarators.iter().enumerate() {
+            match decl {
+                PartialLocalDecl::Regular(decl) => {
+                    let declarator = &decl.declarator;
+                    let (accepts, nullness) = match has_own_values {
+                        // Without a type, the local takes the nullability of its value.
+                        true => {
+                            let accepts = Self::accepted_nullness(
+                                &declarator.type_annotation, nullnesses[i]
+                            );
+                            self.check_accepts(&declarator.name, accepts, &values[i], nullnesses[i]);
+                            (accepts, nullnesses[i].stored_in(accepts))
+                        }
+                        false => (
+                            Self::accepted_nullness(&declarator.type_annotation, Nullness::ANY),
+                            Nullness::NON_NULL,
+                        ),
+                    };
+
+                    let local = self.declare(declarator.name.name, accepts);
+                    self.state.set(local, nullness);
+                }
+                PartialLocalDecl::Destructured(decl) => {
+                    for declarator in decl.declarators {
+                        let accepts = Self::accepted_nullness(
+                            &declarator.type_annotation, Nullness::ANY
+                        );
+                        self.declare(declarator.name.name, accepts);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        match expr {
+            Expr::Access(_) | Expr::Call(_) | Expr::Index(_) => {
+                let (value, skipped) = self.check_chain(expr);
+                self.value = value.join(skipped);
+            }
+            _ => visitor::walk_expr(self, expr),
+        }
+    }
+
+    fn visit_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        let (when_true, when_false) = self.check_condition(&expr.test);
+
+        self.state = when_true;
+        let consequent_val = self.check_body(&expr.consequent);
+        let consequent = std::mem::replace(&mut self.state, when_false);
+        let alternate_val = match &expr.alternate {
+            Some(alternate) => self.check_body(alternate),
+            None => Nullness::NON_NULL,
+        };
+
+        let alternate = std::mem::take(&mut self.state);
+        self.state = consequent.join(alternate);
+        self.value = consequent_val.join(alternate_val);
+    }
+
+    fn visit_loop_expr(&mut self, expr: &'a LoopExpr<'a>) {
+        self.state = self.loop_entry_state(|this| {
+            this.check_body(&expr.body);
+        });
+        self.check_body(&expr.body);
+
+        // There are no exits yet, so nothing after a loop can be reached.
+        self.state = FlowState::default();
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        self.state = self.loop_entry_state(|this| {
+            (this.state, _) = this.check_condition(&expr.test);
+            this.check_body(&expr.body);
+        });
+
+        let when_false;
+        (self.state, when_false) = self.check_condition(&expr.test);
+        self.check_body(&expr.body);
+        self.state = when_false;
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        let value = self.check_expr(&expr.right);
+
+        let Some(local) = self.resolve_local(&expr.left) else {
+            // Assigning to a member or an element accesses its receiver.
+            self.check_expr(&expr.left);
+            self.value = value;
+            return;
+        };
+        self.check_expr(&expr.left);
+
+        let value = match expr.operator.kind {
+            OperatorKind::Assignment => value,
+            _ => Nullness::NON_NULL,
+        };
+        if let Expr::Identifier(id) = &expr.left
+            && let Identifier::Simple(name) = &id.identifier
+        {
+            self.check_accepts(name, self.locals[local].accepts, &expr.right, value);
+        }
+        self.state.set(local, value.stored_in(self.locals[local].accepts));
+        self.value = value;
+    }
+
+    fn visit_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) {
+        self.value = match expr.operator.kind {
+            OperatorKind::NullCoalescing => {
+                let left = self.check_expr(&expr.left);
+                let state = self.state.clone();
+                let right = self.check_expr(&expr.right);
+
+                let right_state = std::mem::take(&mut self.state);
+                self.state = state.join(right_state);
+                match left.is_absent() || left.is_unknown {
+                    true => right,
+                    false => Nullness::NON_NULL,
+                }
+            }
+            OperatorKind::LogicalAnd | OperatorKind::LogicalOr => {
+                let (when_true, when_false) = self.check_logical_expr(expr);
+                self.state = when_true.join(when_false);
+                Nullness::NON_NULL
+            }
+            _ => {
+                visitor::walk_binary_expr(self, expr);
+                Nullness::NON_NULL
+            }
+        };
+    }
+
+    fn visit_left_unary_expr(&mut self, expr: &'a LeftUnaryExpr<'a>) {
+        visitor::walk_left_unary_expr(self, expr);
+        if expr.operator.kind != OperatorKind::OwnershipTransfer {
+            self.value = Nullness::NON_NULL;
+        }
+    }
+
+    fn visit_right_unary_expr(&mut self, expr: &'a RightUnaryExpr<'a>) {
+        visitor::walk_right_unary_expr(self, expr);
+        if expr.operator.kind != OperatorKind::NonNullAssertion {
+            return;
+        }
+
+        // The assertion fails at runtime if the value is null, so it isn't afterward.
+        if let Some(local) = self.resolve_local(&expr.expr) {
+            self.state.set(local, Nullness::NON_NULL);
+        }
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_tuple_expr(&mut self, expr: &'a TupleExpr<'a>) {
+        visitor::walk_tuple_expr(self, expr);
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_array_expr(&mut self, expr: &'a ArrayExpr<'a>) {
+        visitor::walk_array_expr(self, expr);
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_empty_collection_expr(&mut self, _expr: &'a EmptyCollectionExpr) {
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_object_init_expr(&mut self, expr: &'a ObjectInitExpr<'a>) {
+        visitor::walk_object_init_expr(self, expr);
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        let state = std::mem::take(&mut self.state);
+        self.check_function(&expr.params, &expr.body);
+        self.state = state;
+        self.value = Nullness::NON_NULL;
+    }
+
+    fn visit_identifier_expr(&mut self, expr: &'a IdentifierExpr<'a>) {
+        self.value = match self.resolve_identifier(&expr.identifier) {
+            Some(Binding::Local(local)) => self.state.get(local),
+            _ => Nullness::UNKNOWN,
+        };
+    }
+
+    fn visit_literal_expr(&mut self, expr: &'a LiteralExpr<'a>) {
+        self.value = match expr.literal.kind {
+            TokenKind::KwNull => Nullness::NULL,
+            TokenKind::KwUndefined => Nullness::UNDEFINED,
+            _ => Nullness::NON_NULL,
+        };
+    }
+
+    fn visit_error_node(&mut self, _node: &'a ErrorNode) {
+        self.value = Nullness::NON_NULL;
+    }
+}
+
+impl<'a> NullabilityChecker<'a> {
+    fn report(&mut self, msg: CompilerMessage) {
+        if !self.is_silent {
+            self.messages.add(msg);
+        }
+    }
+
+    /// Checks the nodes given inside a new scope. Leaves the nullness of the value of the last one.
+    fn check_scope(&mut self, nodes: &'a [SyntaxNode<'a>]) {
+        let mut scope = HashMap::new();
+        for node in nodes {
+            if let SyntaxNode::Item(Item::FuncDef(def)) = node {
+                scope.insert(def.name.name, Binding::Func(def));
+            }
+        }
+        self.scopes.push(scope);
+
+        self.value = Nullness::NON_NULL;
+        for node in nodes {
+            self.visit_node(node);
+        }
+
+        self.scopes.pop();
+    }
+
+    /// Checks the body of a function or lambda. Functions may be called at any point, so the
+    /// locals declared outside of them, as well as the parameters, may hold any value they accept.
+    fn check_function(&mut self, params: &'a ParameterList<'a>, body: &'a Body<'a>) {
+        self.state = FlowState::default();
+        for (local, info) in self.locals.iter().enumerate() {
+            self.state.set(local, Nullness::UNKNOWN.stored_in(info.accepts));
+        }
+        self.scopes.push(HashMap::new());
+
+        for param in params.params {
+            let declarator = &param.declarator.declarator;
+            let accepts = Self::accepted_nullness(&declarator.type_annotation, Nullness::ANY);
+
+            if let Some(default_val) = &param.default_val {
+                for value in default_val.values {
+                    let nullness = self.check_expr(value);
+                    self.check_accepts(&declarator.name, accepts, value, nullness);
+                }
+            }
+
+            let local = self.declare(declarator.name.name, accepts);
+            self.state.set(local, Nullness::UNKNOWN.stored_in(accepts));
+        }
+        self.check_body(body);
+
+        self.scopes.pop();
+    }
+
+    /// Checks the body given. Returns the nullness of its value.
+    fn check_body(&mut self, body: &'a Body<'a>) -> Nullness {
+        self.value = Nullness::NON_NULL;
+        self.visit_body(body);
+        self.value
+    }
+
+    /// Checks the expression given. Returns the nullness of its value.
+    fn check_expr(&mut self, expr: &'a Expr<'a>) -> Nullness {
+        self.visit_expr(expr);
+        self.value
+    }
+
+    /// Checks a chain of member accesses, calls and indexing. Returns the nullness of its value and
+    /// the nullness of the values that made a safe access ("?.") skip the rest of the chain.
+    fn check_chain(&mut self, expr: &'a Expr<'a>) -> (Nullness, Nullness) {
+        let (receiver, is_safe) = match expr {
+            Expr::Access(access) => match &access.receiver {
+                Some(receiver) => (receiver, access.operator.kind == OperatorKind::SafeMemberAccess),
+                None => return (Nullness::UNKNOWN, Nullness::NON_NULL),
+            },
+            Expr::Call(call) => (&call.callee, false),
+            Expr::Index(index) => (&index.receiver, false),
+            _ => return (self.check_expr(expr), Nullness::NON_NULL),
+        };
+
+        let (receiver_val, mut skipped) = self.check_chain(receiver);
+        if is_safe {
+            skipped = skipped.join(receiver_val);
+        }
+        else if receiver_val.is_absent() {
+            let desc = describe(receiver);
+            let msg = match receiver_val.null {
+                true => compiler_messages::Analyzer::possibly_null_access(*receiver.span(), &desc),
+                false => compiler_messages::Analyzer::possibly_undefined_access(
+                    *receiver.span(), &desc
+                ),
+            };
+            self.report(msg);
+
+            // Reported once, rather than on every access that follows.
+            if let Some(local) = self.resolve_local(receiver) {
+                self.state.set(local, Nullness::NON_NULL);
+            }
+        }
+
+        let value = match expr {
+            Expr::Call(call) => {
+                let func = match self.resolve(receiver) {
+                    Some(Binding::Func(def)) => Some(def),
+                    _ => None,
+                };
+
+                for (i, arg) in call.arguments.arguments.iter().enumerate() {
+                    let nullness = self.check_expr(&arg.expr);
+
+                    if let Some(param) = func.and_then(|def| def.params.params.get(i)) {
+                        let declarator = &param.declarator.declarator;
+                        let accepts = Self::accepted_nullness(
+                            &declarator.type_annotation, Nullness::ANY
+                        );
+                        self.check_accepts(&declarator.name, accepts, &arg.expr, nullness);
+                    }
+                }
+
+                match func.map(|def| def.return_type.as_ref()) {
+                    Some(Some(ty)) if ty.is_nullable => Nullness::NULL,
+                    Some(Some(_)) => Nullness::NON_NULL,
+                    _ => Nullness::UNKNOWN,
+                }
+            }
+            Expr::Index(index) => {
+                self.check_expr(&index.index);
+                Nullness::UNKNOWN
+            }
+            _ => Nullness::UNKNOWN,
+        };
+
+        (value, skipped)
+    }
+
+    /// Checks the condition given, starting at the current state. Returns the state when it's
+    /// true and the state when it's false.
+    fn check_condition(&mut self, expr: &'a Expr<'a>) -> (FlowState, FlowState) {
+        match expr {
+            Expr::Group(expr) => self.check_condition(&expr.expr),
+            Expr::LeftUnary(unary) if unary.operator.kind == OperatorKind::LogicalNot => {
+                let (when_true, when_false) = self.check_condition(&unary.expr);
+                (when_false, when_true)
+            }
+            Expr::Binary(binary) => match binary.operator.kind {
+                OperatorKind::LogicalAnd | OperatorKind::LogicalOr => {
+                    self.check_logical_expr(binary)
+                }
+                OperatorKind::Equals | OperatorKind::ReferenceEquals => {
+                    let (when_equal, when_different) = self.check_comparison(binary);
+                    (when_equal, when_different)
+                }
+                OperatorKind::NotEquals | OperatorKind::ReferenceNotEquals => {
+                    let (when_equal, when_different) = self.check_comparison(binary);
+                    (when_different, when_equal)
+                }
+                _ => {
+                    self.check_expr(expr);
+                    (self.state.clone(), self.state.clone())
+                }
+            },
+            _ => {
+                self.check_expr(expr);
+                (self.state.clone(), self.state.clone())
+            }
+        }
+    }
+
+    /// Checks an `and` or `or` expression. The right side only runs depending on the left one, so
+    /// it narrows the state it runs with.
+    fn check_logical_expr(&mut self, expr: &'a BinaryExpr<'a>) -> (FlowState, FlowState) {
+        let (left_true, left_false) = self.check_condition(&expr.left);
+
+        match expr.operator.kind {
+            OperatorKind::LogicalAnd => {
+                self.state = left_true;
+                let (right_true, right_false) = self.check_condition(&expr.right);
+                (right_true, left_false.join(right_false))
+            }
+            _ => {
+                self.state = left_false;
+                let (right_true, right_false) = self.check_condition(&expr.right);
+                (left_true.join(right_true), right_false)
+            }
+        }
+    }
+
+    /// Checks an equality comparison. If it compares a local with `null` or `undefined`, returns
+    /// the state when they're equal and when they're different, narrowed accordingly.
+    fn check_comparison(&mut self, expr: &'a BinaryExpr<'a>) -> (FlowState, FlowState) {
+        let left = self.check_expr(&expr.left);
+        let right = self.check_expr(&expr.right);
+        let state = self.state.clone();
+
+        let (local, sentinel) = match (self.resolve_local(&expr.left), self.resolve_local(&expr.right)) {
+            (Some(local), _) if Self::is_sentinel(&expr.right) => (local, right),
+            (_, Some(local)) if Self::is_sentinel(&expr.left) => (local, left),
+            _ => return (state.clone(), state),
+        };
+
+        let current = state.get(local);
+        let mut when_equal = state.clone();
+        when_equal.set(local, sentinel);
+
+        let mut when_different = state;
+        when_different.set(local, Nullness {
+            null: current.null && !sentinel.null,
+            undefined: current.undefined && !sentinel.undefined,
+            is_unknown: false,
+        });
+
+        (when_equal, when_different)
+    }
+
+    /// Returns the state at the start of any iteration of a loop that starts at the current state.
+    /// `iterate` checks one iteration, from the state it's given in `self.state` to the state at
+    /// its end.
+    fn loop_entry_state(&mut self, mut iterate: impl FnMut(&mut Self)) -> FlowState {
+        let was_silent = std::mem::replace(&mut self.is_silent, true);
+
+        // Each iteration can only make locals nullable, so this ends in a few iterations.
+        let state = std::mem::take(&mut self.state);
+        let mut entry = state.clone();
+        loop {
+            self.state = entry.clone();
+            iterate(self);
+            let next = state.clone().join(std::mem::take(&mut self.state));
+            if next == entry {
+                break;
+            }
+            entry = next;
+        }
+
+        self.is_silent = was_silent;
+        entry
+    }
+
+    /// Reports `value` if it's assigned to a variable that doesn't accept its nullness.
+    fn check_accepts(
+        &mut self, name: &SimpleIdentifier, accepts: Nullness, value: &Expr, nullness: Nullness
+    ) {
+        if nullness.null && !accepts.null {
+            self.report(compiler_messages::Analyzer::null_assigned_to_non_nullable(
+                *value.span(), &name.name, &describe(value)
+            ));
+        }
+        else if nullness.undefined && !accepts.undefined {
+            self.report(compiler_messages::Analyzer::undefined_assigned_to_non_nullable(
+                *value.span(), &name.name, &describe(value)
+            ));
+        }
+    }
+
+    fn declare(&mut self, name: Symbol<'a>, accepts: Nullness) -> usize {
+        let local = self.locals.len();
+        self.locals.push(LocalInfo { accepts });
+        self.scopes.last_mut().unwrap().insert(name, Binding::Local(local));
+
+        local
+    }
+
+    fn resolve(&self, expr: &Expr) -> Option<Binding<'a>> {
+        match expr {
+            Expr::Identifier(id) => self.resolve_identifier(&id.identifier),
+            _ => None,
+        }
+    }
+
+    fn resolve_identifier(&self, identifier: &Identifier) -> Option<Binding<'a>> {
+        let Identifier::Simple(name) = identifier else {
+            return None;
+        };
+
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name.name).copied())
+    }
+
+    fn resolve_local(&self, expr: &Expr) -> Option<usize> {
+        match self.resolve(expr) {
+            Some(Binding::Local(local)) => Some(local),
+            _ => None,
+        }
+    }
+
+    /// Returns the values accepted by a variable with the type given. Variables without a type
+    /// are nullable if their value given by `inferred` may be null.
+    fn accepted_nullness(annotation: &Option<TypeAnnotation>, inferred: Nullness) -> Nullness {
+        let is_nullable = match annotation {
+            Some(annotation) => annotation.ty.is_nullable,
+            None => inferred.is_absent(),
+        };
+
+        match is_nullable {
+            true => Nullness::ANY,
+            false => Nullness::NON_NULL,
+        }
+    }
+
+    fn is_sentinel(expr: &Expr) -> bool {
+        let Expr::Literal(literal) = expr else {
+            return false;
+        };
+
+        matches!(
+            literal.literal.kind,
+            TokenKind::KwNull | TokenKind::KwUndefined
+        )
+    }
+}
+
+/// Returns a short description of the expression given, as it'd be written in code, to name it in
+/// diagnostics.
+fn describe(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(expr) => describe_identifier(&expr.identifier),
+        Expr::Literal(expr) => expr.literal.source.to_string(),
+        Expr::Group(expr) => format!("({})", describe(&expr.expr)),
+        Expr::Access(expr) => {
+            let op = match expr.operator.kind {
+                OperatorKind::SafeMemberAccess => "?.",
+                OperatorKind::ScopeResolution => "::",
+                _ => ".",
+            };
+            match &expr.receiver {
+                Some(receiver) => format!("{}{}{}", describe(receiver), op, expr.member.name),
+                None => format!("{}{}", op, expr.member.name),
+            }
+        }
+        Expr::Call(expr) => match expr.arguments.arguments.is_empty() {
+            true => format!("{}()", describe(&expr.callee)),
+            false => format!("{}(...)", describe(&expr.callee)),
+        },
+        Expr::Index(expr) => format!("{}[{}]", describe(&expr.receiver), describe(&expr.index)),
+        Expr::RightUnary(expr) if expr.operator.kind == OperatorKind::NonNullAssertion => {
+            format!("{}!", describe(&expr.expr))
+        }
+        _ => String::from("expression"),
+    }
+}
+
+fn describe_identifier(identifier: &Identifier) -> String {
+    match identifier {
+        Identifier::Simple(id) => id.name.to_string(),
+        Identifier::Qualified(id) => {
+            format!("{}::{}", describe_identifier(&id.qualifier), id.name.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_nullability)
+    }
+
+    #[test]
+    fn valid_nullability() {
+        println!("== Testing null-safe code ==");
+
+        let cases = vec![
+            "let p: Person? = null\np?.name",
+            "let p: Person? = get()\nif p != null then p.name end",
+            "let p: Person? = get()\nif p !== null then p.name end",
+            "let p: Person? = get()\nif p === null then 0 else p.name end",
+            "let p: Person? = get()\nif null == p then 0 elsif c then p.name else p.age end",
+            "let p: Person? = get()\nif not (p == null) then p.name end",
+            "let p: Person? = get()\np != null and p.name",
+            "let p: Person? = get()\np == null or p.name",
+            "let p: Person? = get()\n(p ?? fallback).name",
+            "let p: Person? = get()\nlet q: Person = p ?? fallback",
+            "let p: Person? = get()\np!.name\np.age",
+            "let mut p: Person? = null\np = \"Kevin\"\np.name",
+            "let p: Person? = null\np?.a.b.c()",
+            "let p: Person? = undefined\nif p != undefined then p.name end",
+            "let p: Person? = undefined\nif p != null and p != undefined then p.name end",
+            "let mut p: Person? = undefined\np = null\nif p != null then p.name end",
+            "let mut x: Num? = null\nwhile x == null do x = 1 end\nx.abs()",
+            "func f (p: Person?) => p?.name",
+            "func f (p: Person?) => if p != null then p.name end",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_nullability() {
+        println!("== Testing code that may access null ==");
+
+        let access = |expr: &str| MessageCode::PossiblyNullAccess { expr: String::from(expr) };
+        let undefined_access = |expr: &str| MessageCode::PossiblyUndefinedAccess {
+            expr: String::from(expr)
+        };
+        let assign = |name: &str| MessageCode::NullAssignedToNonNullable { name: String::from(name) };
+        let cases = vec![
+            ("let x: Num = null", assign("x")),
+            ("let mut x: Num = 1\nx = null", assign("x")),
+            ("let mut x = get()\nx = null", assign("x")),
+            ("func f (p: Person) => p\nf(null)", assign("p")),
+            ("func f () -> Person? => get()\nlet p: Person = f()", assign("p")),
+            ("let p: Person? = get()\np.name", access("p")),
+            ("let p: Person? = get()\np()", access("p")),
+            ("let p: Person? = get()\np[0]", access("p")),
+            ("let p: Person? = get()\np?.friend.name\np.name", access("p")),
+            ("let p: Person? = get()\nif p == null then p.name end", access("p")),
+            ("let p: Person? = get()\nif p != null then 0 else p.name end", access("p")),
+            ("let p: Person? = get()\np != null or p.name", access("p")),
+            ("let p: Person? = get()\nif p != undefined then p.name end", access("p")),
+            ("let mut p: Person? = get()\nif c then p = null end\np.name", access("p")),
+            ("func f () -> Person? => get()\nf().name", access("f()")),
+            ("let a: Person? = get()\nlet b = (a?.b).c", access("(a?.b)")),
+            ("let mut p = get()\nwhile c do p.name\np = null end", assign("p")),
+            ("func f (p: Person?) => p.name", access("p")),
+            ("(undefined).name", undefined_access("(undefined)")),
+            ("let p: Person? = undefined\np.name", undefined_access("p")),
+            ("let p: Person? = undefined\nif p != null then p.name end", undefined_access("p")),
+            ("let p: Person? = undefined\nif p == undefined then p.name end", undefined_access("p")),
+            (
+                "let x: Num = undefined",
+                MessageCode::UndefinedAssignedToNonNullable { name: String::from("x") },
+            ),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.errors.len(), 1);
+            assert_eq!(messages.errors[0].code, expected);
+        }
+    }
+}
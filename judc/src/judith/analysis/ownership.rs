@@ -0,0 +1,680 @@
+use std::collections::{HashMap, HashSet};
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::{CompilerMessage, MessageContainer};
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// The names of the primitive types. Values of these types are copied when assigned, so ownership
+/// doesn't restrict how they're aliased.
+const INLINE_TYPES: &[&str] = &[
+    "Bool", "Num", "String", "Char", "I8", "I16", "I32", "I64", "Ui8", "Ui16", "Ui32", "Ui64",
+    "F16", "F32", "F64", "Decimal", "BigInt", "Byte", "Int", "Uint", "Float",
+];
+
+/// The locals whose state may have changed at some point of the program. Unlike definite
+/// assignment, these sets contain the locals affected on *any* path that leads to this point.
+#[derive(Debug, Clone, Default)]
+struct FlowState {
+    /// Locals that may have been assigned a value.
+    maybe_assigned: HashSet<usize>,
+    /// Locals that may have transferred the ownership of their value with `in`.
+    maybe_transferred: HashSet<usize>,
+}
+
+impl FlowState {
+    fn join(mut self, other: FlowState) -> FlowState {
+        self.maybe_assigned.extend(other.maybe_assigned);
+        self.maybe_transferred.extend(other.maybe_transferred);
+        self
+    }
+}
+
+struct LocalInfo {
+    /// The ownership of the local, with the default filled in: `let` for locals and `ref` for
+    /// parameters.
+    ownership: OwnershipKind,
+    is_param: bool,
+    /// Whether the local was given a value where it was declared.
+    is_initialized: bool,
+    /// Whether the local is known to hold a value of an inline type.
+    is_inline: bool,
+}
+
+impl LocalInfo {
+    /// Whether the value of this local (and the local itself) can be mutated.
+    fn is_mutable(&self) -> bool {
+        matches!(
+            self.ownership,
+            OwnershipKind::Mutable | OwnershipKind::Shared | OwnershipKind::In
+        )
+    }
+
+    /// Whether this local owns its value, and thus can transfer it. Mutable parameters only
+    /// borrow their value.
+    fn owns_value(&self) -> bool {
+        match self.ownership {
+            OwnershipKind::Mutable => !self.is_param,
+            OwnershipKind::In => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Binding<'a> {
+    Local(usize),
+    Func(&'a FuncDef<'a>),
+}
+
+/// Checks that locals and parameters are used as their ownership (`final`, `mut`, `sh`, `ref`
+/// and `in`) allows.
+pub fn check_ownership<'a>(nodes: &'a [SyntaxNode<'a>]) -> MessageContainer {
+    let mut checker = OwnershipChecker {
+        locals: Vec::new(),
+        scopes: Vec::new(),
+        state: FlowState::default(),
+        is_silent: false,
+        messages: MessageContainer::new(),
+    };
+
+    checker.check_scope(nodes);
+
+    checker.messages
+}
+
+struct OwnershipChecker<'a> {
+    locals: Vec<LocalInfo>,
+    scopes: Vec<HashMap<Symbol<'a>, Binding<'a>>>,
+    /// The state at the point of the program being checked.
+    state: FlowState,
+    /// When set, diagnostics are discarded. Used to find out the state at the end of a loop body
+    /// before checking it for real.
+    is_silent: bool,
+    messages: MessageContainer,
+}
+
+impl<'a> Visitor<'a> for OwnershipChecker<'a> {
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        // A function may be called at any point, so nothing it uses can be assumed to be
+        // unassigned or to still own its value.
+        let state = std::mem::take(&mut self.state);
+        self.check_function(&def.params, &def.body);
+        self.state = state;
+    }
+
+    fn visit_block_body(&mut self, body: &'a BlockBody<'a>) {
+        self.check_scope(body.nodes);
+    }
+
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        let values = match &stmt.initializer {
+            Some(init) => init.values,
+            None => &[],
+        };
+        // Values are only paired with declarators when each declarator has its own.
+        let has_own_values = values.len() == stmt.declarators.len();
+
+        for (i, decl) in stmt.declarators.iter().enumerate() {
+            let PartialLocalDecl::Regular(decl) = decl else {
+                continue;
+            };
+            if has_own_values {
+                let ownership = Self::local_ownership(&decl.declarator.ownership_kind, false);
+                self.check_alias(&ownership, &values[i]);
+            }
+        }
+        if let Some(init) = &stmt.initializer {
+            self.visit_equals_value_clause(init);
+        }
+
+        for (i, decl) in stmt.declarators.iter().enumerate() {
+            match decl {
+                PartialLocalDecl::Regular(decl) => {
+                    let declarator = &decl.declarator;
+                    let is_inline = Self::is_inline_type(&declarator.type_annotation)
+                        || (has_own_values && Self::is_inline_value(&values[i]));
+                    self.declare(declarator, !values.is_empty(), false, is_inline);
+                }
+                PartialLocalDecl::Destructured(decl) => {
+                    for declarator in decl.declarators {
+                        let is_inline = Self::is_inline_type(&declarator.type_annotation);
+                        self.declare(declarator, true, false, is_inline);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        self.visit_expr(&expr.test);
+
+        let state = self.state.clone();
+        self.visit_body(&expr.consequent);
+        let consequent = std::mem::replace(&mut self.state, state);
+        if let Some(alternate) = &expr.alternate {
+            self.visit_body(alternate);
+        }
+
+        let alternate = std::mem::take(&mut self.state);
+        self.state = consequent.join(alternate);
+    }
+
+    fn visit_loop_expr(&mut self, expr: &'a LoopExpr<'a>) {
+        self.check_loop_body(&expr.body);
+
+        // There are no exits yet, so nothing after a loop can be reached.
+        self.state = FlowState::default();
+    }
+
+    fn visit_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        self.visit_expr(&expr.test);
+
+        let state = self.state.clone();
+        self.check_loop_body(&expr.body);
+        let body = std::mem::take(&mut self.state);
+        self.state = state.join(body);
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        self.visit_expr(&expr.right);
+
+        let Some((local, name)) = self.resolve_identifier(&expr.left) else {
+            // Assigning to a member or an element mutates the value that contains it.
+            if let Some((local, name)) = self.root_local(&expr.left)
+                && !self.locals[local].is_mutable()
+            {
+                self.report(compiler_messages::Analyzer::immutable_value_mutated(
+                    name.span, &name.name
+                ));
+            }
+            self.visit_expr(&expr.left);
+            return;
+        };
+
+        // Immutable locals can be assigned once, if they weren't given a value when declared.
+        let info = &self.locals[local];
+        if !info.is_mutable()
+            && (info.is_initialized || self.state.maybe_assigned.contains(&local))
+        {
+            self.report(compiler_messages::Analyzer::immutable_local_reassigned(
+                name.span, &name.name
+            ));
+        }
+
+        match expr.operator.kind {
+            OperatorKind::Assignment => {
+                let ownership = self.locals[local].ownership;
+                self.check_alias(&ownership, &expr.right);
+            }
+            // Compound assignments read the current value first.
+            _ => self.check_read(&Self::expr_identifier(&expr.left).unwrap().identifier),
+        };
+
+        // A new value replaces the one that was transferred.
+        self.state.maybe_assigned.insert(local);
+        self.state.maybe_transferred.remove(&local);
+    }
+
+    fn visit_left_unary_expr(&mut self, expr: &'a LeftUnaryExpr<'a>) {
+        match expr.operator.kind {
+            OperatorKind::OwnershipTransfer => self.check_transfer(&expr.expr),
+            _ => visitor::walk_left_unary_expr(self, expr),
+        }
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        self.visit_expr(&expr.callee);
+
+        let func = match Self::expr_identifier(&expr.callee).map(|id| &id.identifier) {
+            Some(Identifier::Simple(name)) => match self.resolve(name.name) {
+                Some(Binding::Func(def)) => Some(def),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        for (i, arg) in expr.arguments.arguments.iter().enumerate() {
+            if let Some(param) = func.and_then(|def| def.params.params.get(i)) {
+                self.check_argument(&param.declarator.declarator, &arg.expr);
+            }
+            self.visit_argument(arg);
+        }
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        let state = self.state.clone();
+        self.check_function(&expr.params, &expr.body);
+        self.state = state;
+    }
+
+    fn visit_identifier_expr(&mut self, expr: &'a IdentifierExpr<'a>) {
+        self.check_read(&expr.identifier);
+    }
+}
+
+impl<'a> OwnershipChecker<'a> {
+    fn report(&mut self, msg: CompilerMessage) {
+        if !self.is_silent {
+            self.messages.add(msg);
+        }
+    }
+
+    /// Checks the nodes given inside a new scope.
+    fn check_scope(&mut self, nodes: &'a [SyntaxNode<'a>]) {
+        let mut scope = HashMap::new();
+        // Functions are hoisted, so they can be called before they're defined.
+        for node in nodes {
+            if let SyntaxNode::Item(Item::FuncDef(def)) = node {
+                scope.insert(def.name.name, Binding::Func(def));
+            }
+        }
+        self.scopes.push(scope);
+
+        for node in nodes {
+            self.visit_node(node);
+        }
+
+        self.scopes.pop();
+    }
+
+    /// Checks the body of a function or lambda, starting at the current state. Functions may be
+    /// called more than once, so every local declared outside of them may already be assigned.
+    fn check_function(&mut self, params: &'a ParameterList<'a>, body: &'a Body<'a>) {
+        self.state.maybe_assigned.extend(0..self.locals.len());
+        self.scopes.push(HashMap::new());
+
+        for param in params.params {
+            if let Some(default_val) = &param.default_val {
+                self.visit_equals_value_clause(default_val);
+            }
+
+            let declarator = &param.declarator.declarator;
+            let is_inline = Self::is_inline_type(&declarator.type_annotation);
+            self.declare(declarator, true, true, is_inline);
+        }
+        self.visit_body(body);
+
+        self.scopes.pop();
+    }
+
+    /// Checks the body of a loop, which may run any amount of times, and leaves the state after
+    /// its first iteration.
+    fn check_loop_body(&mut self, body: &'a Body<'a>) {
+        // Anything that happens in an iteration has happened before the next one starts.
+        let state = self.state.clone();
+        let was_silent = std::mem::replace(&mut self.is_silent, true);
+        self.visit_body(body);
+        self.is_silent = was_silent;
+
+        let first = std::mem::take(&mut self.state);
+        self.state = state.join(first);
+        self.visit_body(body);
+    }
+
+    /// Checks that `value` can be given to a variable with the ownership given.
+    fn check_alias(&mut self, target: &OwnershipKind, value: &Expr) {
+        let Some((local, name)) = self.resolve_identifier(value) else {
+            return;
+        };
+        let info = &self.locals[local];
+        if info.is_inline || *target == OwnershipKind::Reference {
+            return;
+        }
+        // Reading a transferred local is reported on its own.
+        if self.state.maybe_transferred.contains(&local) {
+            return;
+        }
+
+        if info.ownership == OwnershipKind::Reference {
+            self.report(compiler_messages::Analyzer::reference_cannot_be_shared(
+                name.span, &name.name
+            ));
+            return;
+        }
+
+        // `let` can be aliased by `let`, and `sh` by `sh`. Values owned by `mut` have a single
+        // owner, so they can only be transferred.
+        let source = Self::normalize_borrowed(&info.ownership);
+        let is_valid = match source {
+            OwnershipKind::Final => *target == OwnershipKind::Final,
+            OwnershipKind::Shared => *target == OwnershipKind::Shared,
+            _ => false,
+        };
+        if !is_valid {
+            self.report(compiler_messages::Analyzer::ownership_alias_mismatch(
+                name.span,
+                &name.name,
+                Self::ownership_name(&source),
+                Self::ownership_name(target),
+            ));
+        }
+    }
+
+    /// Checks that `arg` can be passed to the parameter given.
+    fn check_argument(&mut self, param: &LocalDeclarator, arg: &Expr) {
+        let ownership = Self::local_ownership(&param.ownership_kind, true);
+        let param_name = &param.name.name;
+
+        let Some((local, name)) = self.resolve_identifier(arg) else {
+            return;
+        };
+        let info = &self.locals[local];
+        if info.is_inline || ownership == OwnershipKind::Reference {
+            return;
+        }
+        if self.state.maybe_transferred.contains(&local) {
+            return;
+        }
+
+        if info.ownership == OwnershipKind::Reference {
+            self.report(compiler_messages::Analyzer::reference_cannot_be_shared(
+                name.span, &name.name
+            ));
+            return;
+        }
+
+        let source = Self::normalize_borrowed(&info.ownership);
+        let msg = match ownership {
+            OwnershipKind::Final if source != OwnershipKind::Final => Some(
+                compiler_messages::Analyzer::immutable_argument_expected(
+                    name.span, &name.name, param_name
+                )
+            ),
+            OwnershipKind::Mutable if !info.is_mutable() => Some(
+                compiler_messages::Analyzer::mutable_argument_expected(
+                    name.span, &name.name, param_name
+                )
+            ),
+            OwnershipKind::Shared if source != OwnershipKind::Shared => Some(
+                compiler_messages::Analyzer::shared_argument_expected(
+                    name.span, &name.name, param_name
+                )
+            ),
+            OwnershipKind::In if info.owns_value() => Some(
+                compiler_messages::Analyzer::explicit_transfer_expected(
+                    name.span, &name.name, param_name
+                )
+            ),
+            OwnershipKind::In => Some(
+                compiler_messages::Analyzer::ownership_cannot_be_transferred(
+                    name.span, &name.name
+                )
+            ),
+            _ => None,
+        };
+
+        if let Some(msg) = msg {
+            self.report(msg);
+        }
+    }
+
+    /// Checks `in expr`, which transfers the ownership of the value of `expr`.
+    fn check_transfer(&mut self, expr: &'a Expr<'a>) {
+        self.visit_expr(expr);
+
+        if let Some((local, name)) = self.resolve_identifier(expr) {
+            if !self.locals[local].owns_value() {
+                self.report(compiler_messages::Analyzer::ownership_cannot_be_transferred(
+                    name.span, &name.name
+                ));
+            }
+            self.state.maybe_transferred.insert(local);
+        }
+    }
+
+    fn check_read(&mut self, identifier: &Identifier) {
+        let Identifier::Simple(name) = identifier else {
+            return;
+        };
+        let Some(Binding::Local(local)) = self.resolve(name.name) else {
+            return;
+        };
+
+        if self.state.maybe_transferred.remove(&local) {
+            self.report(compiler_messages::Analyzer::use_of_transferred_local(
+                name.span, &name.name
+            ));
+        }
+    }
+
+    fn declare(
+        &mut self,
+        declarator: &LocalDeclarator<'a>,
+        is_initialized: bool,
+        is_param: bool,
+        is_inline: bool,
+    ) {
+        let local = self.locals.len();
+        self.locals.push(LocalInfo {
+            ownership: Self::local_ownership(&declarator.ownership_kind, is_param),
+            is_param,
+            is_initialized,
+            is_inline,
+        });
+        self.scopes.last_mut().unwrap().insert(declarator.name.name, Binding::Local(local));
+    }
+
+    fn resolve(&self, name: Symbol) -> Option<Binding<'a>> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name).copied())
+    }
+
+    /// Returns the local the expression given names, if it's just a name.
+    fn resolve_identifier<'e>(
+        &self, expr: &'e Expr<'e>
+    ) -> Option<(usize, &'e SimpleIdentifier<'e>)> {
+        let Identifier::Simple(name) = &Self::expr_identifier(expr)?.identifier else {
+            return None;
+        };
+
+        match self.resolve(name.name) {
+            Some(Binding::Local(local)) => Some((local, name)),
+            _ => None,
+        }
+    }
+
+    /// Returns the local that contains the member or element accessed by the expression given,
+    /// e.g. `a` in `a.b[0].c`.
+    fn root_local<'e>(&self, expr: &'e Expr<'e>) -> Option<(usize, &'e SimpleIdentifier<'e>)> {
+        match expr {
+            Expr::Access(expr) => self.root_local(expr.receiver.as_ref()?),
+            Expr::Index(expr) => self.root_local(&expr.receiver),
+            Expr::Group(expr) => self.root_local(&expr.expr),
+            _ => self.resolve_identifier(expr),
+        }
+    }
+
+    fn expr_identifier<'e>(expr: &'e Expr<'e>) -> Option<&'e IdentifierExpr<'e>> {
+        match expr {
+            Expr::Identifier(id) => Some(id),
+            Expr::Group(group) => Self::expr_identifier(&group.expr),
+            _ => None,
+        }
+    }
+
+    /// Returns the ownership of a local or parameter declared with the kind given.
+    fn local_ownership(kind: &OwnershipKind, is_param: bool) -> OwnershipKind {
+        match kind {
+            OwnershipKind::None if is_param => OwnershipKind::Reference,
+            OwnershipKind::None => OwnershipKind::Final,
+            kind => *kind,
+        }
+    }
+
+    /// Returns the kind of value a local with the ownership given holds: `in` parameters hold
+    /// values owned by them, just like `mut` locals.
+    fn normalize_borrowed(kind: &OwnershipKind) -> OwnershipKind {
+        match kind {
+            OwnershipKind::In => OwnershipKind::Mutable,
+            kind => *kind,
+        }
+    }
+
+    fn ownership_name(kind: &OwnershipKind) -> &'static str {
+        match kind {
+            OwnershipKind::None | OwnershipKind::Final => "let",
+            OwnershipKind::Mutable => "mut",
+            OwnershipKind::Shared => "sh",
+            OwnershipKind::Reference => "ref",
+            OwnershipKind::In => "in",
+        }
+    }
+
+    fn is_inline_type(annotation: &Option<TypeAnnotation>) -> bool {
+        let Some(annotation) = annotation else {
+            return false;
+        };
+
+        match &annotation.ty.ty {
+            PartialType::Identifier(ty) => match &ty.name {
+                Identifier::Simple(name) => INLINE_TYPES.contains(&name.name.as_str()),
+                Identifier::Qualified(_) => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the expression given is known to produce a value of an inline type.
+    fn is_inline_value(expr: &Expr) -> bool {
+        match expr {
+            Expr::Literal(_) => true,
+            Expr::Group(expr) => Self::is_inline_value(&expr.expr),
+            Expr::LeftUnary(expr) => expr.operator.kind != OperatorKind::OwnershipTransfer,
+            Expr::Binary(expr) => expr.operator.kind != OperatorKind::NullCoalescing,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::judith::analysis::check_source;
+    use super::*;
+
+    fn check(src: &str) -> MessageContainer {
+        check_source(src, check_ownership)
+    }
+
+    #[test]
+    fn valid_ownership() {
+        println!("== Testing valid uses of ownership ==");
+
+        let cases = vec![
+            "let mut x = 1\nx = 2\nx += 3",
+            "let x: Num\nif c then x = 1 else x = 2 end",
+            "let mut p = get()\np.name = \"Kevin\"\np.items[0] = 1",
+            "let sh p = get()\np.name = \"Kevin\"",
+            "let a = get()\nlet b = a\nlet ref c = a",
+            "let sh a = get()\nlet sh b = a",
+            "let mut a = get()\nlet ref b = a\nlet c = in a",
+            "let mut a = get()\nlet b = in a\na = get()\na.x = 1",
+            "let n = 3\nlet mut m = n",
+            "let n: Num = f()\nlet sh m = n",
+            "func rename (mut p) => p.name = \"Kevin\"\nlet mut p = get()\nrename(p)",
+            "func show (p) => p\nlet a = get()\nlet mut b = get()\nlet ref c = a\nshow(a)\nshow(b)\nshow(c)",
+            "func keep (final p) => p\nlet a = get()\nkeep(a)",
+            "func share (sh p) => p\nlet sh a = get()\nshare(a)",
+            "func take (in p) => p.x = 1\nlet mut a = get()\ntake(in a)",
+            "func own (in p)\n    let q = in p\nend",
+            "let mut x = 1\nwhile c do x = x + 1 end",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.count(), 0);
+        }
+    }
+
+    #[test]
+    fn invalid_ownership() {
+        println!("== Testing invalid uses of ownership ==");
+
+        let name = |name: &str| String::from(name);
+        let cases = vec![
+            // Reassignment.
+            ("let x = 1\nx = 2", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            ("let final x = 1\nx = 2", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            ("let x = 1\nx += 2", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            ("let x\nx = 1\nx = 2", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            (
+                "let x\nif c then x = 1 end\nx = 2",
+                MessageCode::ImmutableLocalReassigned { name: name("x") },
+            ),
+            ("let x\nwhile c do x = 1 end", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            ("let x\nlet f = () => x = 1", MessageCode::ImmutableLocalReassigned { name: name("x") }),
+            ("func f (a) => a = 1", MessageCode::ImmutableLocalReassigned { name: name("a") }),
+            // Mutation.
+            ("let p = get()\np.name = \"x\"", MessageCode::ImmutableValueMutated { name: name("p") }),
+            ("let p = get()\np.a.b[0] = 1", MessageCode::ImmutableValueMutated { name: name("p") }),
+            ("let ref p = get()\np[0] += 1", MessageCode::ImmutableValueMutated { name: name("p") }),
+            ("func f (final p) => p.x = 1", MessageCode::ImmutableValueMutated { name: name("p") }),
+            // Aliasing.
+            ("let a = get()\nlet mut b = a", MessageCode::OwnershipAliasMismatch { name: name("a") }),
+            ("let a = get()\nlet sh b = a", MessageCode::OwnershipAliasMismatch { name: name("a") }),
+            ("let mut a = get()\nlet b = a", MessageCode::OwnershipAliasMismatch { name: name("a") }),
+            ("let sh a = get()\nlet b = a", MessageCode::OwnershipAliasMismatch { name: name("a") }),
+            ("let sh a = get()\nlet mut b = a", MessageCode::OwnershipAliasMismatch { name: name("a") }),
+            ("let ref a = get()\nlet b = a", MessageCode::ReferenceCannotBeShared { name: name("a") }),
+            // Arguments.
+            (
+                "func f (mut p) => p\nlet a = get()\nf(a)",
+                MessageCode::MutableArgumentExpected { name: name("a") },
+            ),
+            (
+                "func f (final p) => p\nlet mut a = get()\nf(a)",
+                MessageCode::ImmutableArgumentExpected { name: name("a") },
+            ),
+            (
+                "func f (sh p) => p\nlet mut a = get()\nf(a)",
+                MessageCode::SharedArgumentExpected { name: name("a") },
+            ),
+            (
+                "func f (in p) => p\nlet mut a = get()\nf(a)",
+                MessageCode::ExplicitTransferExpected { name: name("a") },
+            ),
+            (
+                "func f (in p) => p\nlet sh a = get()\nf(a)",
+                MessageCode::OwnershipCannotBeTransferred { name: name("a") },
+            ),
+            (
+                "func f (mut p) => p\nfunc g (p) => f(p)",
+                MessageCode::ReferenceCannotBeShared { name: name("p") },
+            ),
+            // Transfers.
+            ("let a = get()\nlet b = in a", MessageCode::OwnershipCannotBeTransferred { name: name("a") }),
+            (
+                "func f (mut p)\n    let q = in p\nend",
+                MessageCode::OwnershipCannotBeTransferred { name: name("p") },
+            ),
+            (
+                "let mut a = get()\nlet b = in a\na.x",
+                MessageCode::UseOfTransferredLocal { name: name("a") },
+            ),
+            (
+                "let mut a = get()\nif c then let b = in a end\na",
+                MessageCode::UseOfTransferredLocal { name: name("a") },
+            ),
+            (
+                "func take (in p) => p\nlet mut a = get()\nloop take(in a) end",
+                MessageCode::UseOfTransferredLocal { name: name("a") },
+            ),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let messages = check(input);
+            for msg in messages.all_messages() {
+                println!("{}", msg.message);
+            }
+            assert_eq!(messages.errors.len(), 1);
+            assert_eq!(messages.errors[0].code, expected);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use serde::Serialize;
 use crate::SourceSpan;
 use strum_macros::{EnumDiscriminants, EnumString, AsRefStr};
-use crate::judith::lexical::token::Token;
+use crate::judith::lexical::token::{Token, TokenKind};
 
 #[derive(Debug, Serialize)]
 pub enum MessageKind {
@@ -14,19 +14,33 @@ pub enum MessageKind {
 pub enum MessageOrigin {
     Lexer,
     Parser,
+    Analyzer,
+    Linter,
+    Optimizer,
+    Interpreter,
 }
 
 #[derive(Debug, Serialize)]
 pub enum MessageSource {
     Span(SourceSpan),
-    Token(Token),
+    /// A token, of which only the kind and location are kept, as messages outlive the source the
+    /// token was read from.
+    Token { kind: TokenKind, span: SourceSpan },
 }
 
 impl MessageSource {
-    fn get_line(&self) -> i64 {
+    pub fn from_token(tok: &Token) -> MessageSource {
+        let base = tok.base();
+        MessageSource::Token {
+            kind: base.kind,
+            span: SourceSpan::new(base.start, base.end, base.line),
+        }
+    }
+
+    pub fn get_line(&self) -> i64 {
         match self {
             MessageSource::Span(span) => span.line,
-            MessageSource::Token(tok) => tok.base().line,
+            MessageSource::Token { span, .. } => span.line,
         }
     }
 }
@@ -55,7 +69,7 @@ impl CompilerMessage {
     pub fn get_elaborate_message (&self, src: Option<&str>) -> String {
         let location = match &self.source {
             MessageSource::Span(span) => format!("line: {}", span.line),
-            MessageSource::Token(tok) => format!("line: {}", tok.base().line), // TODO: More complex.
+            MessageSource::Token { span, .. } => format!("line: {}", span.line), // TODO: More complex.
         };
 
         format!(
@@ -77,6 +91,7 @@ pub enum MessageCode {
     UnexpectedCharacter{ character: char } = 1_000,
     InvalidNumber{ lexeme: String },
     UnterminatedString,
+    UnsupportedDirective,
 
     // 2xxx - Parsing errors
     UnexpectedToken = 2_000,
@@ -109,6 +124,65 @@ pub enum MessageCode {
     FieldMustBeInitialized,
     ParameterTypeListExpected,
     ReturnTypeExpected,
+    EmptyDestructuringPattern,
+    DestructuringMustBeInitialized,
+    NestingTooDeep,
+
+    // 3xxx - Semantic errors
+    DeclaratorCountMismatch{ declarators: usize, values: usize } = 3_000,
+    TupleSpreadLengthMismatch{ declarators: usize, elements: usize },
+    ValueNotSpreadable,
+    UseOfUnassignedLocal{ name: String },
+    ImmutableLocalReassigned{ name: String },
+    ImmutableValueMutated{ name: String },
+    OwnershipAliasMismatch{ name: String },
+    ReferenceCannotBeShared{ name: String },
+    MutableArgumentExpected{ name: String },
+    ImmutableArgumentExpected{ name: String },
+    SharedArgumentExpected{ name: String },
+    ExplicitTransferExpected{ name: String },
+    OwnershipCannotBeTransferred{ name: String },
+    UseOfTransferredLocal{ name: String },
+    NullAssignedToNonNullable{ name: String },
+    UndefinedAssignedToNonNullable{ name: String },
+    PossiblyNullAccess{ expr: String },
+    PossiblyUndefinedAccess{ expr: String },
+    UnreachableCode,
+    MissingReturn{ name: String },
+    LoopWithoutExit,
+
+    // 4xxx - Lints
+    UnusedLocal{ name: String } = 4_000,
+    UnusedParameter{ name: String },
+    ShadowedName{ name: String },
+    NamingConvention{ name: String },
+    RedundantParentheses,
+    ComparisonWithBool,
+    UnknownLint{ name: String },
+
+    // 8xxx - Optimizer notes
+    FunctionOptimized{ name: String, old_size: usize, new_size: usize } = 8_000,
+    FunctionNotOptimized{ name: String },
+
+    // 9xxx - Runtime errors (interpreter)
+    UndefinedName{ name: String } = 9_000,
+    UnassignedLocal{ name: String },
+    InvalidOperands,
+    ConditionMustBeBool,
+    NotCallable,
+    WrongArgumentCount,
+    UnknownField{ name: String },
+    InvalidAssignmentTarget,
+    InvalidLiteral,
+    StackOverflow,
+    UnsupportedFeature,
+    InvalidNode,
+    NullValue,
+    NotIndexable,
+    InvalidIndex,
+    CannotDestructure,
+    DestructuringLengthMismatch,
+    CannotInitializeDeclarators,
 }
 
 impl MessageCode {
@@ -165,6 +239,10 @@ impl MessageContainer {
 
 pub struct Lexer;
 pub struct Parser;
+pub struct Analyzer;
+pub struct Linter;
+pub struct Optimizer;
+pub struct Interpreter;
 
 impl Lexer {
     pub fn unexpected_character(span: SourceSpan, unexpected_char: char) -> CompilerMessage {
@@ -177,11 +255,11 @@ impl Lexer {
         }
     }
 
-    pub fn invalid_number(span: SourceSpan, num: String) -> CompilerMessage {
+    pub fn invalid_number(span: SourceSpan, num: &str) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Lexer,
-            code: MessageCode::InvalidNumber {lexeme: num.clone()},
+            code: MessageCode::InvalidNumber {lexeme: num.to_string()},
             message: format!("Invalid number: {}", num),
             source: MessageSource::Span(span),
         }
@@ -196,140 +274,150 @@ impl Lexer {
             source: MessageSource::Span(span),
         }
     }
+
+    pub fn unsupported_directive(span: SourceSpan) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Lexer,
+            code: MessageCode::UnsupportedDirective,
+            message: String::from("Directives are not supported yet."),
+            source: MessageSource::Span(span),
+        }
+    }
 }
 
 impl Parser {
-    pub fn unexpected_token(tok: Token) -> CompilerMessage {
+    pub fn unexpected_token(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::UnexpectedToken,
             message: format!("Unexpected token: '{:?}'", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn identifier_expected(tok: Token) -> CompilerMessage {
+    pub fn identifier_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::IdentifierExpected,
             message: format!("Expected identifier, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn type_expected(tok: Token) -> CompilerMessage {
+    pub fn type_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::TypeExpected,
             message: format!("Expected type, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn right_paren_expected(tok: Token) -> CompilerMessage {
+    pub fn right_paren_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::RightParenExpected,
             message: format!("Expected ')', found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn right_curly_bracket_expected(tok: Token) -> CompilerMessage {
+    pub fn right_curly_bracket_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::RightCurlyBracketExpected,
             message: format!("Expected '}}', found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn right_square_bracket_expected(tok: Token) -> CompilerMessage {
+    pub fn right_square_bracket_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::RightSquareBracketExpected,
             message: format!("Expected ']', found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn expression_expected(tok: Token) -> CompilerMessage {
+    pub fn expression_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ExpressionExpected,
             message: format!("Expected expression, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn body_expected(tok: Token) -> CompilerMessage {
+    pub fn body_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::BodyExpected,
             message: format!("Expected body, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn elsif_body_expected(tok: Token) -> CompilerMessage {
+    pub fn elsif_body_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ElsifBodyExpected,
             message: format!("Expected 'elsif' body, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn end_expected(tok: Token) -> CompilerMessage {
+    pub fn end_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::EndExpected,
             message: format!("Expected end token, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn parameter_expected(tok: Token) -> CompilerMessage {
+    pub fn parameter_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ParameterExpected,
             message: format!("Expected parameter, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn argument_expected(tok: Token) -> CompilerMessage {
+    pub fn argument_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ArgumentExpected,
             message: format!("Expected argument, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn parameter_list_expected(tok: Token) -> CompilerMessage {
+    pub fn parameter_list_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ParameterListExpected,
             message: format!("Expected parameter list, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn variable_declarator_expected(tok: Token) -> CompilerMessage {
+    pub fn variable_declarator_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
@@ -338,47 +426,714 @@ impl Parser {
                 "Expected variable declarator (<name>, [<name>...] or {{ <name>... }}, found '{:?}'.",
                 tok.kind()
             ),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn field_initialization_expected(tok: Token) -> CompilerMessage {
+    pub fn field_initialization_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::FieldInitializationExpected,
             message: format!("Expected field initialization, found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn field_must_be_initialized(tok: Token) -> CompilerMessage {
+    pub fn field_must_be_initialized(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::FieldMustBeInitialized,
             message: String::from("Field must be initialized."),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn parameter_type_list_expected(tok: Token) -> CompilerMessage {
+    pub fn parameter_type_list_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ParameterTypeListExpected,
             message: format!("Expected parameter type list (<type>...) found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
         }
     }
 
-    pub fn return_type_expected(tok: Token) -> CompilerMessage {
+    pub fn return_type_expected(tok: &Token) -> CompilerMessage {
         CompilerMessage {
             kind: MessageKind::Error,
             origin: MessageOrigin::Parser,
             code: MessageCode::ReturnTypeExpected,
             message: format!("Expected return type (-> <type>), found '{:?}'.", tok.kind()),
-            source: MessageSource::Token(tok),
+            source: MessageSource::from_token(tok),
+        }
+    }
+
+    pub fn empty_destructuring_pattern(tok: &Token) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Parser,
+            code: MessageCode::EmptyDestructuringPattern,
+            message: String::from("Destructuring patterns must contain at least one name."),
+            source: MessageSource::from_token(tok),
+        }
+    }
+
+    pub fn destructuring_must_be_initialized(tok: &Token) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Parser,
+            code: MessageCode::DestructuringMustBeInitialized,
+            message: format!(
+                "Destructuring declarations must be initialized, found '{:?}'.", tok.kind()
+            ),
+            source: MessageSource::from_token(tok),
+        }
+    }
+
+    pub fn nesting_too_deep(tok: &Token, max_depth: usize) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Parser,
+            code: MessageCode::NestingTooDeep,
+            message: format!(
+                "Expressions, types and blocks can't be nested more than {} levels deep.",
+                max_depth
+            ),
+            source: MessageSource::from_token(tok),
+        }
+    }
+}
+
+impl Analyzer {
+    pub fn declarator_count_mismatch(
+        span: Option<SourceSpan>, declarators: usize, values: usize
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::DeclaratorCountMismatch { declarators, values },
+            message: format!(
+                "Declaration has {} declarator(s) but {} value(s). Give each declarator its own \
+                value, or initialize all of them with a single tuple.",
+                declarators, values
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn tuple_spread_length_mismatch(
+        span: Option<SourceSpan>, declarators: usize, elements: usize
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::TupleSpreadLengthMismatch { declarators, elements },
+            message: format!(
+                "A tuple of {} element(s) cannot initialize {} declarator(s).",
+                elements, declarators
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn value_not_spreadable(
+        span: Option<SourceSpan>, declarators: usize, found: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ValueNotSpreadable,
+            message: format!(
+                "Only a tuple can initialize {} declarators at once, found {}.", declarators, found
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn use_of_unassigned_local(
+        span: Option<SourceSpan>, name: &str, decl_span: Option<SourceSpan>
+    ) -> CompilerMessage {
+        let decl_line = decl_span.unwrap_or(SourceSpan::no_location()).line;
+
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::UseOfUnassignedLocal { name: name.to_string() },
+            message: format!(
+                "Local '{}' may be read before being assigned a value. It's declared at line {}, \
+                but not every path that leads here assigns it.",
+                name, decl_line
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn immutable_local_reassigned(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ImmutableLocalReassigned { name: name.to_string() },
+            message: format!(
+                "Cannot assign to '{}' more than once, as it's immutable. Declare it with 'mut' or \
+                'sh' to reassign it.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn immutable_value_mutated(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ImmutableValueMutated { name: name.to_string() },
+            message: format!(
+                "Cannot modify the value of '{}', as it's immutable. Mutability is absolute, so the \
+                fields and elements of an immutable value can't change either.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn ownership_alias_mismatch(
+        span: Option<SourceSpan>, name: &str, source: &str, target: &str
+    ) -> CompilerMessage {
+        let hint = match source {
+            "mut" => format!(" Use 'in {}' to transfer its ownership instead.", name),
+            _ => String::new(),
+        };
+
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::OwnershipAliasMismatch { name: name.to_string() },
+            message: format!(
+                "'{}' holds a '{}' value, which cannot be given to a '{}' variable.{}",
+                name, source, target, hint
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn reference_cannot_be_shared(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ReferenceCannotBeShared { name: name.to_string() },
+            message: format!(
+                "'{}' is a 'ref' value, which can only be given to other 'ref' variables and \
+                parameters.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn mutable_argument_expected(
+        span: Option<SourceSpan>, name: &str, param: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::MutableArgumentExpected { name: name.to_string() },
+            message: format!(
+                "Parameter '{}' may mutate its argument, but '{}' is immutable.", param, name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn immutable_argument_expected(
+        span: Option<SourceSpan>, name: &str, param: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ImmutableArgumentExpected { name: name.to_string() },
+            message: format!(
+                "Parameter '{}' requires an immutable value, but '{}' may be mutated.", param, name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
         }
     }
-}
\ No newline at end of file
+
+    pub fn shared_argument_expected(
+        span: Option<SourceSpan>, name: &str, param: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::SharedArgumentExpected { name: name.to_string() },
+            message: format!(
+                "Parameter '{}' requires a shared ('sh') value, but '{}' isn't shared.", param, name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn explicit_transfer_expected(
+        span: Option<SourceSpan>, name: &str, param: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::ExplicitTransferExpected { name: name.to_string() },
+            message: format!(
+                "Parameter '{}' takes ownership of its argument. Use 'in {}' to transfer it \
+                explicitly.",
+                param, name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn ownership_cannot_be_transferred(
+        span: Option<SourceSpan>, name: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::OwnershipCannotBeTransferred { name: name.to_string() },
+            message: format!(
+                "'{}' doesn't own its value, so it cannot transfer it. Only 'mut' locals and 'in' \
+                parameters can.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn use_of_transferred_local(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::UseOfTransferredLocal { name: name.to_string() },
+            message: format!(
+                "'{}' may have transferred the ownership of its value, so it can't be used until \
+                it's assigned a new one.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn null_assigned_to_non_nullable(
+        span: Option<SourceSpan>, name: &str, expr: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::NullAssignedToNonNullable { name: name.to_string() },
+            message: format!(
+                "'{}' is not nullable, but '{}' may be null. Declare it as nullable (e.g. 'Num?') \
+                to allow null.",
+                name, expr
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn undefined_assigned_to_non_nullable(
+        span: Option<SourceSpan>, name: &str, expr: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::UndefinedAssignedToNonNullable { name: name.to_string() },
+            message: format!("'{}' is not nullable, but '{}' may be undefined.", name, expr),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn possibly_null_access(span: Option<SourceSpan>, expr: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::PossiblyNullAccess { expr: expr.to_string() },
+            message: format!(
+                "'{}' may be null here. Check it against null first, or access it with '?.' or \
+                '!'.",
+                expr
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn possibly_undefined_access(span: Option<SourceSpan>, expr: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::PossiblyUndefinedAccess { expr: expr.to_string() },
+            message: format!(
+                "'{}' may be undefined here. Check it against undefined first, or access it with \
+                '?.' or '!'.",
+                expr
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unreachable_code(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::UnreachableCode,
+            message: String::from(
+                "Unreachable code. The code before it never continues to this point."
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn missing_return(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::MissingReturn { name: name.to_string() },
+            message: format!(
+                "Function '{}' declares a return type, but it may reach its end without returning \
+                a value.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn loop_without_exit(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Analyzer,
+            code: MessageCode::LoopWithoutExit,
+            message: String::from(
+                "This loop never ends, as nothing breaks out of it or returns from inside it."
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+}
+
+impl Linter {
+    pub fn unused_local(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::UnusedLocal { name: name.to_string() },
+            message: format!(
+                "Local '{}' is never used. Prefix its name with '_' if this is intentional.", name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unused_parameter(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::UnusedParameter { name: name.to_string() },
+            message: format!(
+                "Parameter '{}' is never used. Prefix its name with '_' if this is intentional.",
+                name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn shadowed_name(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::ShadowedName { name: name.to_string() },
+            message: format!(
+                "'{}' shadows a variable with the same name declared before it.", name
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    /// * `case` - The name of the naming convention expected (e.g. "snake_case").
+    /// * `suggestion` - The name given, written in the convention expected.
+    pub fn naming_convention(
+        span: Option<SourceSpan>, name: &str, case: &str, suggestion: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::NamingConvention { name: name.to_string() },
+            message: format!(
+                "'{}' should be written in {}: '{}'.", name, case, suggestion
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn redundant_parentheses(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::RedundantParentheses,
+            message: String::from("These parentheses are redundant."),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    /// * `is_negated` - Whether the comparison is true when the value is false.
+    pub fn comparison_with_bool(span: Option<SourceSpan>, is_negated: bool) -> CompilerMessage {
+        let suggestion = match is_negated {
+            true => "Use 'not' on the value instead.",
+            false => "Use the value itself instead.",
+        };
+
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::ComparisonWithBool,
+            message: format!("Comparing with 'true' or 'false' is redundant. {}", suggestion),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unknown_lint(span: SourceSpan, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Linter,
+            code: MessageCode::UnknownLint { name: name.to_string() },
+            message: format!("Unknown lint: '{}'.", name),
+            source: MessageSource::Span(span),
+        }
+    }
+}
+
+impl Optimizer {
+    pub fn function_optimized(name: &str, old_size: usize, new_size: usize) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Information,
+            origin: MessageOrigin::Optimizer,
+            code: MessageCode::FunctionOptimized {
+                name: name.to_string(),
+                old_size,
+                new_size,
+            },
+            message: format!(
+                "Function '{}': {} -> {} bytes (saved {}).",
+                name, old_size, new_size, old_size - new_size
+            ),
+            source: MessageSource::Span(SourceSpan::no_location()),
+        }
+    }
+
+    pub fn function_not_optimized(name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Warning,
+            origin: MessageOrigin::Optimizer,
+            code: MessageCode::FunctionNotOptimized { name: name.to_string() },
+            message: format!(
+                "Function '{}' contains malformed bytecode and was left as is.", name
+            ),
+            source: MessageSource::Span(SourceSpan::no_location()),
+        }
+    }
+}
+
+impl Interpreter {
+    pub fn undefined_name(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::UndefinedName { name: name.to_string() },
+            message: format!("Undefined name: '{}'.", name),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unassigned_local(span: Option<SourceSpan>, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::UnassignedLocal { name: name.to_string() },
+            message: format!("Local '{}' was read before being assigned a value.", name),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn invalid_operands(
+        span: Option<SourceSpan>, operator: &str, left: &str, right: Option<&str>
+    ) -> CompilerMessage {
+        let operands = match right {
+            Some(right) => format!("'{}' and '{}'", left, right),
+            None => format!("'{}'", left),
+        };
+
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::InvalidOperands,
+            message: format!("Operator '{}' cannot be applied to {}.", operator, operands),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn condition_must_be_bool(span: Option<SourceSpan>, found: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::ConditionMustBeBool,
+            message: format!("Condition must be of type 'Bool', found '{}'.", found),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn not_callable(span: Option<SourceSpan>, found: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::NotCallable,
+            message: format!("Values of type '{}' cannot be called.", found),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn wrong_argument_count(
+        span: Option<SourceSpan>, name: &str, expected: usize, found: usize
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::WrongArgumentCount,
+            message: format!(
+                "Function '{}' expects {} argument(s), but {} were given.", name, expected, found
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unknown_field(span: Option<SourceSpan>, type_name: &str, name: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::UnknownField { name: name.to_string() },
+            message: format!("Type '{}' has no field named '{}'.", type_name, name),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn not_indexable(span: Option<SourceSpan>, found: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::NotIndexable,
+            message: format!("Values of type '{}' cannot be indexed.", found),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn invalid_index(span: Option<SourceSpan>, index: &str, len: usize) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::InvalidIndex,
+            message: format!("Invalid index '{}' for a collection of length {}.", index, len),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn cannot_destructure(
+        span: Option<SourceSpan>, type_name: &str, pattern: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::CannotDestructure,
+            message: format!("Values of type '{}' cannot be destructured by {}.", type_name, pattern),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn destructuring_length_mismatch(
+        span: Option<SourceSpan>, expected: usize, found: usize
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::DestructuringLengthMismatch,
+            message: format!(
+                "Pattern expects {} element(s), but the value has {}.", expected, found
+            ),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn cannot_initialize_declarators(
+        span: Option<SourceSpan>, declarators: usize, found: &str
+    ) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::CannotInitializeDeclarators,
+            message: format!("Cannot initialize {} declarator(s) with {}.", declarators, found),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn null_value(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::NullValue,
+            message: String::from("Value asserted to be non-null was null."),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn invalid_assignment_target(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::InvalidAssignmentTarget,
+            message: String::from("Invalid assignment target."),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn invalid_literal(span: Option<SourceSpan>, lexeme: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::InvalidLiteral,
+            message: format!("Invalid literal: {}", lexeme),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn stack_overflow(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::StackOverflow,
+            message: String::from("Stack overflow: too many nested calls."),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn unsupported_feature(span: Option<SourceSpan>, feature: &str) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::UnsupportedFeature,
+            message: format!("The interpreter doesn't support {} yet.", feature),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+
+    pub fn invalid_node(span: Option<SourceSpan>) -> CompilerMessage {
+        CompilerMessage {
+            kind: MessageKind::Error,
+            origin: MessageOrigin::Interpreter,
+            code: MessageCode::InvalidNode,
+            message: String::from("Cannot evaluate a node that contains syntax errors."),
+            source: MessageSource::Span(span.unwrap_or(SourceSpan::no_location())),
+        }
+    }
+}
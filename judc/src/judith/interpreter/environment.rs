@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::judith::interpreter::value::Value;
+
+/// A scope containing the variables declared in it. Environments are chained: when a name isn't
+/// found in an environment, it's looked up in its parent.
+#[derive(Debug, Default)]
+pub struct Environment<'a> {
+    /// Every variable declared in this scope. Variables that have been declared but not yet
+    /// assigned (e.g. `let x: Num`) map to `None`.
+    values: HashMap<String, Option<Value<'a>>>,
+    parent: Option<Rc<RefCell<Environment<'a>>>>,
+}
+
+/// The result of looking up a variable in an environment.
+pub enum Lookup<'a> {
+    /// No variable with the name given exists.
+    Undefined,
+    /// The variable exists, but hasn't been assigned a value yet.
+    Unassigned,
+    /// The variable exists and contains the value given.
+    Value(Value<'a>),
+}
+
+impl<'a> Environment<'a> {
+    pub fn new() -> Self {
+        Environment {
+            values: HashMap::new(),
+            parent: None,
+        }
+    }
+
+    /// Creates a new environment whose parent is the environment given.
+    pub fn enclosed(parent: Rc<RefCell<Environment<'a>>>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Environment {
+            values: HashMap::new(),
+            parent: Some(parent),
+        }))
+    }
+
+    /// Declares a variable in this scope, shadowing any variable with the same name in parent
+    /// scopes (or replacing it, if it was declared in this very scope).
+    pub fn define(&mut self, name: &str, val: Option<Value<'a>>) {
+        self.values.insert(name.to_string(), val);
+    }
+
+    pub fn get(&self, name: &str) -> Lookup<'a> {
+        match self.values.get(name) {
+            Some(Some(val)) => Lookup::Value(val.clone()),
+            Some(None) => Lookup::Unassigned,
+            None => match &self.parent {
+                Some(parent) => parent.borrow().get(name),
+                None => Lookup::Undefined,
+            },
+        }
+    }
+
+    /// Assigns a value to an existing variable, returning `false` if no variable with that name
+    /// exists in this environment or its parents.
+    pub fn assign(&mut self, name: &str, val: Value<'a>) -> bool {
+        if let Some(slot) = self.values.get_mut(name) {
+            *slot = Some(val);
+            return true;
+        }
+
+        match &self.parent {
+            Some(parent) => parent.borrow_mut().assign(name, val),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod environment;
+pub mod tree_walker;
+pub mod value;
@@ -0,0 +1,1243 @@
+// Runtime errors are reported as compiler messages, the same type the rest of the compiler uses.
+#![allow(clippy::result_large_err)]
+
+use std::cell::RefCell;
+use std::io::{Stdout, Write};
+use std::rc::Rc;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::{CompilerMessage, MessageContainer};
+use crate::judith::interpreter::environment::{Environment, Lookup};
+use crate::judith::interpreter::value::{Function, Object, Value};
+use crate::judith::lexical::token::{StringLiteralKind, TokenKind};
+use crate::judith::syntax::nodes::*;
+use crate::SourceSpan;
+
+/// The maximum amount of nested function calls before the interpreter gives up. Each call in
+/// Judith takes several frames of the host's stack, so this is kept well below the point where
+/// the host itself would overflow.
+const MAX_CALL_DEPTH: usize = 128;
+
+type EvalResult<'a> = Result<Value<'a>, CompilerMessage>;
+
+/// Executes syntax trees directly, without compiling them first. Anything written by the program
+/// (e.g. through `__p_print`) is written to `out`.
+pub struct Interpreter<'a, W: Write> {
+    /// The environment of the top level of the program.
+    globals: Rc<RefCell<Environment<'a>>>,
+    /// The environment of the scope currently being executed.
+    env: Rc<RefCell<Environment<'a>>>,
+    /// The amount of function calls currently being executed.
+    call_depth: usize,
+    out: W,
+}
+
+pub struct InterpreterResult {
+    pub messages: MessageContainer,
+}
+
+/// Interprets the nodes given, writing their output to the standard output.
+pub fn interpret(nodes: &[SyntaxNode]) -> InterpreterResult {
+    let mut interpreter: Interpreter<Stdout> = Interpreter::new(std::io::stdout());
+    let mut messages = MessageContainer::new();
+
+    if let Err(msg) = interpreter.run(nodes) {
+        messages.add(msg);
+    }
+
+    InterpreterResult { messages }
+}
+
+impl<'a, W: Write> Interpreter<'a, W> {
+    pub fn new(out: W) -> Self {
+        let globals = Rc::new(RefCell::new(Environment::new()));
+
+        Interpreter {
+            env: globals.clone(),
+            globals,
+            call_depth: 0,
+            out,
+        }
+    }
+
+    pub fn output(&self) -> &W {
+        &self.out
+    }
+
+    pub fn output_mut(&mut self) -> &mut W {
+        &mut self.out
+    }
+
+    pub fn into_output(self) -> W {
+        self.out
+    }
+
+    /// Executes the nodes given at the top level of the program, returning the value of the last
+    /// one. Functions are declared before anything is executed, so they can be called before the
+    /// point where they are defined. Globals declared in previous runs are kept.
+    pub fn run(&mut self, nodes: &'a [SyntaxNode]) -> EvalResult<'a> {
+        // A previous run may have failed in the middle of a call.
+        self.env = self.globals.clone();
+        self.call_depth = 0;
+
+        self.exec_nodes(nodes)
+    }
+
+    // region Nodes
+    /// Declares the items among the nodes given in the current environment, and then executes
+    /// every node in order. Returns the value of the last node.
+    fn exec_nodes(&mut self, nodes: &'a [SyntaxNode]) -> EvalResult<'a> {
+        self.declare_items(nodes);
+
+        let mut last = Value::Void;
+        for node in nodes {
+            last = self.exec_node(node)?;
+        }
+
+        Ok(last)
+    }
+
+    fn declare_items(&mut self, nodes: &'a [SyntaxNode]) {
+        for node in nodes {
+            if let SyntaxNode::Item(Item::FuncDef(decl)) = node {
+                let func = Value::Function(Rc::new(Function {
+                    name: Some(&decl.name.name),
+                    params: &decl.params,
+                    body: &decl.body,
+                    closure: self.env.clone(),
+                }));
+
+                self.env.borrow_mut().define(&decl.name.name, Some(func));
+            }
+        }
+    }
+
+    fn exec_node(&mut self, node: &'a SyntaxNode<'a>) -> EvalResult<'a> {
+        match node {
+            // Items have already been declared by the time their scope is executed.
+            SyntaxNode::Item(_) => Ok(Value::Void),
+            SyntaxNode::Stmt(stmt) => self.exec_stmt(stmt),
+            SyntaxNode::Expr(expr) => self.eval_expr(expr),
+            SyntaxNode::Error(err) => Err(compiler_messages::Interpreter::invalid_node(err.span)),
+        }
+    }
+
+    /// Executes the body given and returns its value. Block bodies get their own scope.
+    fn eval_body(&mut self, body: &'a Body<'a>) -> EvalResult<'a> {
+        match body {
+            Body::Block(block) => {
+                let env = Environment::enclosed(self.env.clone());
+                self.exec_in_env(env, |this| this.exec_nodes(block.nodes))
+            }
+            Body::Arrow(arrow) => self.eval_expr(&arrow.expr),
+            Body::Expr(expr) => self.eval_expr(&expr.expr),
+        }
+    }
+
+    /// Runs `f` with `env` as the current environment, restoring the previous one afterward even
+    /// if `f` fails.
+    fn exec_in_env<T>(
+        &mut self,
+        env: Rc<RefCell<Environment<'a>>>,
+        f: impl FnOnce(&mut Self) -> Result<T, CompilerMessage>,
+    ) -> Result<T, CompilerMessage> {
+        let previous = std::mem::replace(&mut self.env, env);
+        let result = f(self);
+        self.env = previous;
+
+        result
+    }
+    // endregion Nodes
+
+    // region Statements
+    fn exec_stmt(&mut self, stmt: &'a Stmt<'a>) -> EvalResult<'a> {
+        match stmt {
+            Stmt::Expr(stmt) => self.eval_expr(&stmt.expr),
+            Stmt::LocalDecl(stmt) => self.exec_local_decl_stmt(stmt),
+            Stmt::PrivPrint(stmt) => self.exec_priv_print_stmt(stmt),
+            Stmt::Error(err) => Err(compiler_messages::Interpreter::invalid_node(err.span)),
+        }
+    }
+
+    fn exec_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) -> EvalResult<'a> {
+        let Some(init) = &stmt.initializer else {
+            // The parser doesn't accept destructuring declarations without a value.
+            for decl in stmt.declarators {
+                let PartialLocalDecl::Regular(decl) = decl else {
+                    return Err(compiler_messages::Interpreter::invalid_node(*decl.span()));
+                };
+                self.env.borrow_mut().define(&decl.declarator.name.name, None);
+            }
+
+            return Ok(Value::Void);
+        };
+
+        let mut values = self.eval_exprs(init.values)?;
+
+        // A single tuple is spread across all the declarators.
+        if values.len() == 1 && stmt.declarators.len() > 1 {
+            values = match values.pop().unwrap() {
+                Value::Tuple(elements) if elements.len() == stmt.declarators.len() => {
+                    elements.to_vec()
+                }
+                Value::Tuple(elements) => {
+                    return Err(compiler_messages::Interpreter::cannot_initialize_declarators(
+                        stmt.span,
+                        stmt.declarators.len(),
+                        &format!("a tuple of {} element(s)", elements.len()),
+                    ));
+                }
+                val => {
+                    return Err(compiler_messages::Interpreter::cannot_initialize_declarators(
+                        stmt.span,
+                        stmt.declarators.len(),
+                        &format!("a value of type '{}'", val.type_name()),
+                    ));
+                }
+            };
+        }
+
+        if values.len() != stmt.declarators.len() {
+            return Err(compiler_messages::Interpreter::cannot_initialize_declarators(
+                stmt.span, stmt.declarators.len(), &format!("{} values", values.len())
+            ));
+        }
+
+        for (decl, val) in stmt.declarators.iter().zip(values) {
+            match decl {
+                PartialLocalDecl::Regular(decl) => {
+                    self.env.borrow_mut().define(&decl.declarator.name.name, Some(val));
+                }
+                PartialLocalDecl::Destructured(decl) => {
+                    let values = self.destructure(decl, val)?;
+                    for (declarator, val) in decl.declarators.iter().zip(values) {
+                        self.env.borrow_mut().define(&declarator.name.name, Some(val));
+                    }
+                }
+            }
+        }
+
+        Ok(Value::Void)
+    }
+
+    /// Returns the values the pattern given extracts from a value, in the order of its declarators.
+    fn destructure(
+        &self, decl: &'a DestructuredLocalDecl<'a>, val: Value<'a>
+    ) -> Result<Vec<Value<'a>>, CompilerMessage> {
+        match decl.destructuring_kind {
+            DestructuringKind::ArrayPattern => {
+                let elements = match &val {
+                    Value::Array(elements) => elements.borrow().clone(),
+                    Value::Tuple(elements) => elements.to_vec(),
+                    _ => return Err(compiler_messages::Interpreter::cannot_destructure(
+                        decl.span, &val.type_name(), "an array pattern"
+                    )),
+                };
+
+                if elements.len() != decl.declarators.len() {
+                    return Err(compiler_messages::Interpreter::destructuring_length_mismatch(
+                        decl.span, decl.declarators.len(), elements.len()
+                    ));
+                }
+
+                Ok(elements)
+            }
+            DestructuringKind::ObjectPattern => {
+                let Value::Object(obj) = &val else {
+                    return Err(compiler_messages::Interpreter::cannot_destructure(
+                        decl.span, &val.type_name(), "an object pattern"
+                    ));
+                };
+
+                let obj = obj.borrow();
+                decl.declarators.iter().map(|declarator| {
+                    let name = &declarator.name.name;
+                    obj.get(name).cloned().ok_or_else(|| {
+                        compiler_messages::Interpreter::unknown_field(
+                            declarator.name.span, &val.type_name(), name
+                        )
+                    })
+                }).collect()
+            }
+        }
+    }
+
+    fn exec_priv_print_stmt(&mut self, stmt: &'a PrivPrintStmt<'a>) -> EvalResult<'a> {
+        let val = self.eval_expr(&stmt.expr)?;
+
+        // The program has no way to react to a failed write, so it's not treated as an error.
+        let _ = writeln!(self.out, "{}", val);
+
+        Ok(Value::Void)
+    }
+    // endregion Statements
+
+    // region Expressions
+    fn eval_expr(&mut self, expr: &'a Expr<'a>) -> EvalResult<'a> {
+        match expr {
+            Expr::If(expr) => self.eval_if_expr(expr),
+            Expr::Loop(expr) => self.eval_loop_expr(expr),
+            Expr::While(expr) => self.eval_while_expr(expr),
+            Expr::Assignment(expr) => self.eval_assignment_expr(expr),
+            Expr::Binary(expr) => self.eval_binary_expr(expr),
+            Expr::LeftUnary(expr) => self.eval_left_unary_expr(expr),
+            Expr::RightUnary(expr) => self.eval_right_unary_expr(expr),
+            Expr::Group(expr) => self.eval_expr(&expr.expr),
+            Expr::Tuple(expr) => {
+                let elements = self.eval_exprs(expr.elements)?;
+                Ok(Value::Tuple(Rc::new(elements)))
+            }
+            Expr::Array(expr) => {
+                let elements = self.eval_exprs(expr.elements)?;
+                Ok(Value::Array(Rc::new(RefCell::new(elements))))
+            }
+            // Arrays are the only collection the interpreter knows about.
+            Expr::EmptyCollection(_) => Ok(Value::Array(Rc::new(RefCell::new(Vec::new())))),
+            Expr::ObjectInit(expr) => self.eval_object_init_expr(expr),
+            Expr::Access(_) | Expr::Call(_) | Expr::Index(_) => {
+                Ok(self.eval_chain(expr)?.unwrap_or(Value::Null))
+            }
+            Expr::Lambda(expr) => Ok(Value::Function(Rc::new(Function {
+                name: None,
+                params: &expr.params,
+                body: &expr.body,
+                closure: self.env.clone(),
+            }))),
+            Expr::Identifier(expr) => self.eval_identifier(&expr.identifier),
+            Expr::Literal(expr) => self.eval_literal(&expr.literal),
+            Expr::Error(err) => Err(compiler_messages::Interpreter::invalid_node(err.span)),
+        }
+    }
+
+    fn eval_if_expr(&mut self, expr: &'a IfExpr<'a>) -> EvalResult<'a> {
+        if self.eval_condition(&expr.test)? {
+            self.eval_body(&expr.consequent)
+        }
+        else if let Some(alternate) = &expr.alternate {
+            self.eval_body(alternate)
+        }
+        else {
+            Ok(Value::Void)
+        }
+    }
+
+    fn eval_loop_expr(&mut self, expr: &'a LoopExpr<'a>) -> EvalResult<'a> {
+        loop {
+            self.eval_body(&expr.body)?;
+        }
+    }
+
+    fn eval_while_expr(&mut self, expr: &'a WhileExpr<'a>) -> EvalResult<'a> {
+        while self.eval_condition(&expr.test)? {
+            self.eval_body(&expr.body)?;
+        }
+
+        Ok(Value::Void)
+    }
+
+    fn eval_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) -> EvalResult<'a> {
+        match &expr.left {
+            Expr::Identifier(id) => {
+                let Identifier::Simple(name) = &id.identifier else {
+                    return Err(compiler_messages::Interpreter::invalid_assignment_target(id.span));
+                };
+
+                let mut val = self.eval_expr(&expr.right)?;
+                if let Some(kind) = Self::compound_assignment_op(&expr.operator.kind) {
+                    let current = self.eval_identifier(&id.identifier)?;
+                    val = Self::eval_binary_op(expr.span, &expr.operator, &kind, &current, &val)?;
+                }
+
+                if !self.env.borrow_mut().assign(&name.name, val.clone()) {
+                    return Err(compiler_messages::Interpreter::undefined_name(
+                        name.span, &name.name
+                    ));
+                }
+
+                Ok(val)
+            }
+            Expr::Access(access) => {
+                let Some(receiver) = &access.receiver else {
+                    return Err(compiler_messages::Interpreter::unsupported_feature(
+                        access.span, "implicit member access"
+                    ));
+                };
+
+                let receiver_val = self.eval_expr(receiver)?;
+                let mut val = self.eval_expr(&expr.right)?;
+
+                let unknown_field = || compiler_messages::Interpreter::unknown_field(
+                    access.member.span, &receiver_val.type_name(), &access.member.name
+                );
+                let Value::Object(obj) = &receiver_val else {
+                    return Err(unknown_field());
+                };
+
+                if let Some(kind) = Self::compound_assignment_op(&expr.operator.kind) {
+                    let Some(current) = obj.borrow().get(&access.member.name).cloned() else {
+                        return Err(unknown_field());
+                    };
+                    val = Self::eval_binary_op(expr.span, &expr.operator, &kind, &current, &val)?;
+                }
+
+                match obj.borrow_mut().set(&access.member.name, val.clone()) {
+                    true => Ok(val),
+                    false => Err(unknown_field()),
+                }
+            }
+            Expr::Index(index_expr) => {
+                let receiver_val = self.eval_expr(&index_expr.receiver)?;
+                let index = self.eval_expr(&index_expr.index)?;
+                let mut val = self.eval_expr(&expr.right)?;
+
+                // Tuples can't be modified, so only arrays can be assigned to.
+                let Value::Array(elements) = &receiver_val else {
+                    return Err(compiler_messages::Interpreter::invalid_assignment_target(
+                        index_expr.span
+                    ));
+                };
+
+                let mut elements = elements.borrow_mut();
+                let i = Self::collection_index(index_expr.index.span(), &index, elements.len())?;
+                if let Some(kind) = Self::compound_assignment_op(&expr.operator.kind) {
+                    val = Self::eval_binary_op(
+                        expr.span, &expr.operator, &kind, &elements[i], &val
+                    )?;
+                }
+                elements[i] = val.clone();
+
+                Ok(val)
+            }
+            _ => Err(compiler_messages::Interpreter::invalid_assignment_target(*expr.left.span())),
+        }
+    }
+
+    fn eval_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) -> EvalResult<'a> {
+        let op = &expr.operator;
+
+        // Logical operators short-circuit, so the right side may never be evaluated.
+        if op.kind == OperatorKind::LogicalAnd || op.kind == OperatorKind::LogicalOr {
+            let left = self.eval_expr(&expr.left)?;
+            let Value::Bool(left) = left else {
+                return Err(Self::invalid_operands(expr.span, op, &left, None));
+            };
+
+            if left == (op.kind == OperatorKind::LogicalOr) {
+                return Ok(Value::Bool(left));
+            }
+
+            let right = self.eval_expr(&expr.right)?;
+            return match right {
+                Value::Bool(_) => Ok(right),
+                _ => Err(Self::invalid_operands(expr.span, op, &right, None)),
+            };
+        }
+
+        // The right side of "??" is only evaluated when the left side is null or undefined.
+        if op.kind == OperatorKind::NullCoalescing {
+            return match self.eval_expr(&expr.left)? {
+                Value::Null | Value::Undefined => self.eval_expr(&expr.right),
+                left => Ok(left),
+            };
+        }
+
+        let left = self.eval_expr(&expr.left)?;
+        let right = self.eval_expr(&expr.right)?;
+
+        Self::eval_binary_op(expr.span, op, &op.kind, &left, &right)
+    }
+
+    /// Applies the binary operator of the kind given to two values that have already been
+    /// evaluated. `op` is the operator as it appears in the source, used in error messages.
+    fn eval_binary_op(
+        span: Option<SourceSpan>, op: &Operator, kind: &OperatorKind, left: &Value<'a>, right: &Value<'a>
+    ) -> EvalResult<'a> {
+        let val = match (kind, left, right) {
+            (OperatorKind::Add, Value::Num(a), Value::Num(b)) => Value::Num(a + b),
+            (OperatorKind::Add, Value::String(a), Value::String(b)) => {
+                Value::String(format!("{}{}", a, b))
+            }
+            (OperatorKind::Subtract, Value::Num(a), Value::Num(b)) => Value::Num(a - b),
+            (OperatorKind::Multiply, Value::Num(a), Value::Num(b)) => Value::Num(a * b),
+            (OperatorKind::Divide, Value::Num(a), Value::Num(b)) => Value::Num(a / b),
+            (OperatorKind::Modulo, Value::Num(a), Value::Num(b)) => Value::Num(a % b),
+
+            (
+                OperatorKind::BitwiseAnd
+                | OperatorKind::BitwiseOr
+                | OperatorKind::BitwiseXor
+                | OperatorKind::LeftShift
+                | OperatorKind::RightShift,
+                Value::Num(a),
+                Value::Num(b),
+            ) => match Self::eval_integer_op(kind, *a, *b) {
+                Some(n) => Value::Num(n),
+                None => return Err(Self::invalid_operands(span, op, left, Some(right))),
+            },
+            (OperatorKind::BitwiseAnd, Value::Bool(a), Value::Bool(b)) => Value::Bool(a & b),
+            (OperatorKind::BitwiseOr, Value::Bool(a), Value::Bool(b)) => Value::Bool(a | b),
+            (OperatorKind::BitwiseXor, Value::Bool(a), Value::Bool(b)) => Value::Bool(a ^ b),
+
+            (OperatorKind::Equals, _, _) => Value::Bool(left.equals(right)),
+            (OperatorKind::NotEquals, _, _) => Value::Bool(!left.equals(right)),
+            (OperatorKind::ReferenceEquals, _, _) => Value::Bool(left.reference_equals(right)),
+            (OperatorKind::ReferenceNotEquals, _, _) => {
+                Value::Bool(!left.reference_equals(right))
+            }
+
+            (OperatorKind::LessThan, Value::Num(a), Value::Num(b)) => Value::Bool(a < b),
+            (OperatorKind::LessThan, Value::String(a), Value::String(b)) => Value::Bool(a < b),
+            (OperatorKind::LessThanOrEqualsTo, Value::Num(a), Value::Num(b)) => Value::Bool(a <= b),
+            (OperatorKind::LessThanOrEqualsTo, Value::String(a), Value::String(b)) => {
+                Value::Bool(a <= b)
+            }
+            (OperatorKind::GreaterThan, Value::Num(a), Value::Num(b)) => Value::Bool(a > b),
+            (OperatorKind::GreaterThan, Value::String(a), Value::String(b)) => Value::Bool(a > b),
+            (OperatorKind::GreaterThanOrEqualsTo, Value::Num(a), Value::Num(b)) => {
+                Value::Bool(a >= b)
+            }
+            (OperatorKind::GreaterThanOrEqualsTo, Value::String(a), Value::String(b)) => {
+                Value::Bool(a >= b)
+            }
+
+            (OperatorKind::Like | OperatorKind::NotLike, _, _) => {
+                return Err(compiler_messages::Interpreter::unsupported_feature(
+                    op.span, "the '~~' and '!~' operators"
+                ));
+            }
+
+            _ => return Err(Self::invalid_operands(span, op, left, Some(right))),
+        };
+
+        Ok(val)
+    }
+
+    /// Applies a bitwise or shift operator to two numbers. Returns `None` if either number isn't
+    /// an integer that fits in 64 bits, or if a shift is by less than 0 or more than 63 bits.
+    fn eval_integer_op(kind: &OperatorKind, a: f64, b: f64) -> Option<f64> {
+        let (a, b) = (Self::to_integer(a)?, Self::to_integer(b)?);
+        let n = match kind {
+            OperatorKind::BitwiseAnd => a & b,
+            OperatorKind::BitwiseOr => a | b,
+            OperatorKind::BitwiseXor => a ^ b,
+            OperatorKind::LeftShift => a.checked_shl(u32::try_from(b).ok()?)?,
+            OperatorKind::RightShift => a.checked_shr(u32::try_from(b).ok()?)?,
+            _ => return None,
+        };
+
+        Some(n as f64)
+    }
+
+    /// Returns the number given as an integer, if it is one and it fits in 64 bits.
+    fn to_integer(n: f64) -> Option<i64> {
+        // i64::MAX isn't representable as a float, and rounds up to 2^63.
+        if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+            Some(n as i64)
+        }
+        else {
+            None
+        }
+    }
+
+    fn eval_left_unary_expr(&mut self, expr: &'a LeftUnaryExpr<'a>) -> EvalResult<'a> {
+        let op = &expr.operator;
+        let val = self.eval_expr(&expr.expr)?;
+
+        // Transferring ownership only matters to the analyzer.
+        if op.kind == OperatorKind::OwnershipTransfer {
+            return Ok(val);
+        }
+
+        match (&op.kind, &val) {
+            (OperatorKind::Subtract, Value::Num(n)) => Ok(Value::Num(-n)),
+            (OperatorKind::LogicalNot, Value::Bool(b)) => Ok(Value::Bool(!b)),
+            (OperatorKind::BitwiseNot, Value::Num(n)) => match Self::to_integer(*n) {
+                Some(n) => Ok(Value::Num(!n as f64)),
+                None => Err(Self::invalid_operands(expr.span, op, &val, None)),
+            },
+            _ => Err(Self::invalid_operands(expr.span, op, &val, None)),
+        }
+    }
+
+    fn eval_object_init_expr(&mut self, expr: &'a ObjectInitExpr<'a>) -> EvalResult<'a> {
+        let type_name = match &expr.provider {
+            None => None,
+            Some(Expr::Identifier(id)) => Some(Self::identifier_to_string(&id.identifier)),
+            Some(provider) => {
+                return Err(compiler_messages::Interpreter::unsupported_feature(
+                    *provider.span(), "computed object providers"
+                ));
+            }
+        };
+
+        let mut obj = Object::new(type_name);
+        for field_init in expr.initializer.field_inits {
+            let values = &field_init.initializer.values;
+            if values.len() != 1 {
+                return Err(compiler_messages::Interpreter::unsupported_feature(
+                    field_init.span, "initializing fields with multiple values"
+                ));
+            }
+
+            let val = self.eval_expr(&values[0])?;
+            obj.init_field(&field_init.field_name.name, val);
+        }
+
+        Ok(Value::Object(Rc::new(RefCell::new(obj))))
+    }
+
+    fn eval_right_unary_expr(&mut self, expr: &'a RightUnaryExpr<'a>) -> EvalResult<'a> {
+        let op = &expr.operator;
+        let val = self.eval_expr(&expr.expr)?;
+
+        match (&op.kind, &val) {
+            (OperatorKind::NonNullAssertion, Value::Null | Value::Undefined) => {
+                Err(compiler_messages::Interpreter::null_value(expr.span))
+            }
+            (OperatorKind::NonNullAssertion, _) => Ok(val),
+            _ => Err(Self::invalid_operands(expr.span, op, &val, None)),
+        }
+    }
+
+    /// Evaluates a chain of member accesses and calls. Returns `None` when a safe member access
+    /// ("?.") in the chain found a null receiver, which skips the rest of the chain.
+    fn eval_chain(&mut self, expr: &'a Expr<'a>) -> Result<Option<Value<'a>>, CompilerMessage> {
+        match expr {
+            Expr::Access(expr) => self.eval_access_expr(expr),
+            Expr::Call(expr) => self.eval_call_expr(expr),
+            Expr::Index(expr) => self.eval_index_expr(expr),
+            _ => self.eval_expr(expr).map(Some),
+        }
+    }
+
+    fn eval_index_expr(
+        &mut self, expr: &'a IndexExpr<'a>
+    ) -> Result<Option<Value<'a>>, CompilerMessage> {
+        let Some(receiver_val) = self.eval_chain(&expr.receiver)? else {
+            return Ok(None);
+        };
+        let index = self.eval_expr(&expr.index)?;
+
+        let val = match &receiver_val {
+            Value::Array(elements) => {
+                let elements = elements.borrow();
+                let i = Self::collection_index(expr.index.span(), &index, elements.len())?;
+                elements[i].clone()
+            }
+            Value::Tuple(elements) => {
+                let i = Self::collection_index(expr.index.span(), &index, elements.len())?;
+                elements[i].clone()
+            }
+            _ => return Err(compiler_messages::Interpreter::not_indexable(
+                *expr.receiver.span(), &receiver_val.type_name()
+            )),
+        };
+
+        Ok(Some(val))
+    }
+
+    fn eval_access_expr(
+        &mut self, expr: &'a AccessExpr<'a>
+    ) -> Result<Option<Value<'a>>, CompilerMessage> {
+        let Some(receiver) = &expr.receiver else {
+            return Err(compiler_messages::Interpreter::unsupported_feature(
+                expr.span, "implicit member access"
+            ));
+        };
+        if expr.operator.kind == OperatorKind::ScopeResolution {
+            return Err(compiler_messages::Interpreter::unsupported_feature(
+                expr.span, "scope resolution"
+            ));
+        }
+
+        let Some(receiver_val) = self.eval_chain(receiver)? else {
+            return Ok(None);
+        };
+
+        if expr.operator.kind == OperatorKind::SafeMemberAccess
+            && let Value::Null | Value::Undefined = receiver_val
+        {
+            return Ok(None);
+        }
+
+        if let Value::Object(obj) = &receiver_val
+            && let Some(val) = obj.borrow().get(&expr.member.name)
+        {
+            return Ok(Some(val.clone()));
+        }
+
+        Err(compiler_messages::Interpreter::unknown_field(
+            expr.member.span, &receiver_val.type_name(), &expr.member.name
+        ))
+    }
+
+    fn eval_call_expr(
+        &mut self, expr: &'a CallExpr<'a>
+    ) -> Result<Option<Value<'a>>, CompilerMessage> {
+        let Some(callee) = self.eval_chain(&expr.callee)? else {
+            return Ok(None);
+        };
+        let Value::Function(func) = callee else {
+            return Err(compiler_messages::Interpreter::not_callable(
+                *expr.callee.span(), &callee.type_name()
+            ));
+        };
+
+        let mut args = Vec::with_capacity(expr.arguments.arguments.len());
+        for arg in expr.arguments.arguments {
+            args.push(self.eval_expr(&arg.expr)?);
+        }
+
+        self.call_function(&func, args, expr.span).map(Some)
+    }
+
+    fn call_function(
+        &mut self, func: &Function<'a>, args: Vec<Value<'a>>, span: Option<SourceSpan>
+    ) -> EvalResult<'a> {
+        let params = func.params.params;
+
+        if args.len() > params.len() {
+            return Err(compiler_messages::Interpreter::wrong_argument_count(
+                span, func.display_name(), params.len(), args.len()
+            ));
+        }
+
+        if self.call_depth >= MAX_CALL_DEPTH {
+            return Err(compiler_messages::Interpreter::stack_overflow(span));
+        }
+
+        let env = Environment::enclosed(func.closure.clone());
+        let arg_count = args.len();
+
+        self.call_depth += 1;
+        let result = self.exec_in_env(env, |this| {
+            let mut args = args.into_iter();
+
+            for param in params {
+                // Default values are evaluated inside the function's scope, so they can refer to
+                // the parameters that come before them.
+                let val = match (args.next(), &param.default_val) {
+                    (Some(val), _) => val,
+                    (None, Some(default)) => this.eval_expr(&default.values[0])?,
+                    (None, None) => {
+                        return Err(compiler_messages::Interpreter::wrong_argument_count(
+                            span, func.display_name(), params.len(), arg_count
+                        ));
+                    }
+                };
+
+                this.env.borrow_mut().define(&param.declarator.declarator.name.name, Some(val));
+            }
+
+            this.eval_body(func.body)
+        });
+        self.call_depth -= 1;
+
+        result
+    }
+
+    fn eval_identifier(&mut self, identifier: &'a Identifier<'a>) -> EvalResult<'a> {
+        let Identifier::Simple(id) = identifier else {
+            return Err(compiler_messages::Interpreter::undefined_name(
+                *identifier.span(), &Self::identifier_to_string(identifier)
+            ));
+        };
+
+        match self.env.borrow().get(&id.name) {
+            Lookup::Value(val) => Ok(val),
+            Lookup::Unassigned => {
+                Err(compiler_messages::Interpreter::unassigned_local(id.span, &id.name))
+            }
+            Lookup::Undefined => {
+                Err(compiler_messages::Interpreter::undefined_name(id.span, &id.name))
+            }
+        }
+    }
+
+    fn eval_literal(&mut self, literal: &'a Literal<'a>) -> EvalResult<'a> {
+        let val = match (literal.kind, &literal.delimiters) {
+            (TokenKind::String, Some(delimiters)) => Some(Value::String(Self::parse_string(
+                literal.source, delimiters.delimiter, delimiters.count, &delimiters.string_kind
+            ))),
+            (TokenKind::KwTrue, _) => Some(Value::Bool(true)),
+            (TokenKind::KwFalse, _) => Some(Value::Bool(false)),
+            (TokenKind::KwNull, _) => Some(Value::Null),
+            (TokenKind::KwUndefined, _) => Some(Value::Undefined),
+            (TokenKind::Number, _) => Self::parse_number(literal.source).map(Value::Num),
+            _ => None,
+        };
+
+        val.ok_or_else(|| {
+            compiler_messages::Interpreter::invalid_literal(literal.span, literal.source)
+        })
+    }
+    // endregion Expressions
+
+    // region Helpers
+    /// Evaluates an expression that must produce a boolean.
+    fn eval_condition(&mut self, expr: &'a Expr<'a>) -> Result<bool, CompilerMessage> {
+        match self.eval_expr(expr)? {
+            Value::Bool(b) => Ok(b),
+            val => Err(compiler_messages::Interpreter::condition_must_be_bool(
+                *expr.span(), &val.type_name()
+            )),
+        }
+    }
+
+    fn eval_exprs(&mut self, exprs: &'a [Expr]) -> Result<Vec<Value<'a>>, CompilerMessage> {
+        exprs.iter().map(|expr| self.eval_expr(expr)).collect()
+    }
+
+    /// Converts the value given to an index into a collection of the length given. Indices must be
+    /// whole numbers inside the collection.
+    fn collection_index(
+        span: &Option<SourceSpan>, index: &Value, len: usize
+    ) -> Result<usize, CompilerMessage> {
+        if let Value::Num(n) = index
+            && n.fract() == 0.0
+            && *n >= 0.0
+            && (*n as usize) < len
+        {
+            return Ok(*n as usize);
+        }
+
+        Err(compiler_messages::Interpreter::invalid_index(*span, &index.to_string(), len))
+    }
+
+    /// Returns the operator applied by a compound assignment operator (e.g. "+" for "+="), or
+    /// `None` if the operator given isn't one.
+    fn compound_assignment_op(kind: &OperatorKind) -> Option<OperatorKind> {
+        match kind {
+            OperatorKind::AddAssignment => Some(OperatorKind::Add),
+            OperatorKind::SubtractAssignment => Some(OperatorKind::Subtract),
+            OperatorKind::MultiplyAssignment => Some(OperatorKind::Multiply),
+            OperatorKind::DivideAssignment => Some(OperatorKind::Divide),
+            OperatorKind::ModuloAssignment => Some(OperatorKind::Modulo),
+            _ => None,
+        }
+    }
+
+    fn invalid_operands(
+        span: Option<SourceSpan>, op: &Operator, left: &Value, right: Option<&Value>
+    ) -> CompilerMessage {
+        compiler_messages::Interpreter::invalid_operands(
+            span, op.kind.lexeme(), &left.type_name(), right.map(|r| r.type_name()).as_deref()
+        )
+    }
+
+    fn identifier_to_string(identifier: &Identifier) -> String {
+        match identifier {
+            Identifier::Simple(id) => id.name.to_string(),
+            Identifier::Qualified(id) => format!(
+                "{}::{}", Self::identifier_to_string(&id.qualifier), id.name.name
+            ),
+        }
+    }
+
+    /// Parses the lexeme of a numeric literal. Underscores, radix prefixes (0x, 0b, 0o) and type
+    /// suffixes (e.g. "f32") are accepted.
+    fn parse_number(lexeme: &str) -> Option<f64> {
+        let (negative, digits) = match lexeme.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, lexeme),
+        };
+        let digits: String = digits.chars().filter(|c| *c != '_').collect();
+
+        let radix = match digits.get(..2) {
+            Some("0x") => 16,
+            Some("0b") => 2,
+            Some("0o") => 8,
+            _ => 10,
+        };
+
+        let val = if radix != 10 {
+            let body = &digits[2..];
+            let end = body.find(|c: char| !c.is_digit(radix)).unwrap_or(body.len());
+
+            u64::from_str_radix(&body[..end], radix).ok()? as f64
+        }
+        else {
+            // The number ends at the first character that isn't a digit, a dot or an exponent
+            // followed by a digit. Anything after that is the suffix.
+            let bytes = digits.as_bytes();
+            let mut end = 0;
+
+            while end < bytes.len() {
+                let c = bytes[end];
+                let is_exponent = (c == b'e' || c == b'E')
+                    && bytes.get(end + 1).is_some_and(|n| n.is_ascii_digit());
+
+                if c.is_ascii_digit() || c == b'.' || is_exponent {
+                    end += 1;
+                }
+                else {
+                    break;
+                }
+            }
+
+            digits[..end].parse::<f64>().ok()?
+        };
+
+        Some(if negative { -val } else { val })
+    }
+
+    /// Extracts the content of a string literal from its lexeme, removing its flags and
+    /// delimiters. Escape sequences are only processed in regular strings.
+    fn parse_string(
+        lexeme: &str, delimiter: char, delimiter_count: usize, kind: &StringLiteralKind
+    ) -> String {
+        let start = lexeme.find(delimiter).unwrap_or(0) + delimiter_count;
+        let end = lexeme.len().saturating_sub(delimiter_count).max(start);
+        let content = &lexeme[start..end];
+
+        if let StringLiteralKind::Raw = kind {
+            return content.to_string();
+        }
+
+        let mut s = String::with_capacity(content.len());
+        let mut chars = content.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                s.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('0') => s.push('\0'),
+                Some(c) => s.push(c),
+                None => s.push('\\'),
+            }
+        }
+
+        s
+    }
+    // endregion Helpers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    /// Runs the source given and returns everything it printed, or the error it produced.
+    fn run(src: &str) -> Result<String, CompilerMessage> {
+        let lexer_res = tokenize(src);
+        assert_eq!(lexer_res.messages.count(), 0);
+
+        let arena = Arena::new();
+        let parser_res = parse(lexer_res.tokens, &arena);
+        for msg in parser_res.messages.all_messages() {
+            println!("{}", msg.to_string());
+        }
+        assert_eq!(parser_res.messages.count(), 0);
+
+        let mut interpreter = Interpreter::new(Vec::new());
+        interpreter.run(&parser_res.nodes)?;
+
+        Ok(String::from_utf8(interpreter.into_output()).unwrap())
+    }
+
+    #[test]
+    fn arithmetic_and_strings () {
+        let out = run(r#"
+            __p_print 1 + 2 * 3
+            __p_print (1 + 2) * 3
+            __p_print -4 / 2
+            __p_print 0x1f + 1_000
+            __p_print "Hello, " + "world!"
+            __p_print 3 < 4 and not (2 == 2)
+        "#).unwrap();
+
+        assert_eq!(out, "7\n9\n-2\n1031\nHello, world!\nfalse\n");
+    }
+
+    #[test]
+    fn locals_and_control_flow () {
+        let out = run(r#"
+            let i = 0
+            let total = 0
+            while i < 5 do
+                total = total + i
+                i = i + 1
+            end
+
+            if total == 10 then
+                __p_print "ten"
+            elsif total > 10 then
+                __p_print "more"
+            else
+                __p_print "less"
+            end
+        "#).unwrap();
+
+        assert_eq!(out, "ten\n");
+    }
+
+    #[test]
+    fn functions () {
+        let out = run(r#"
+            __p_print fib(10)
+
+            func fib (n: Num) -> Num
+                if n < 2 then
+                    n
+                else
+                    fib(n - 1) + fib(n - 2)
+                end
+            end
+
+            func greet (name: String, greeting: String = "Hi") -> String
+                greeting + ", " + name
+            end
+
+            __p_print greet("Kevin")
+            __p_print greet("Kevin", "Bye")
+        "#).unwrap();
+
+        assert_eq!(out, "55\nHi, Kevin\nBye, Kevin\n");
+    }
+
+    #[test]
+    fn lambdas () {
+        let out = run(r#"
+            func apply_twice (f: (Num) => Num, x: Num) -> Num
+                f(f(x))
+            end
+
+            let offset = 10
+            let add_offset = (x) => x + offset
+            __p_print apply_twice(add_offset, 1)
+            __p_print apply_twice((x: Num) -> Num => x * x, 3)
+
+            let counter = func ()
+                let count = 0
+                func ()
+                    count = count + 1
+                    count
+                end
+            end ()
+            counter()
+            __p_print counter()
+            __p_print add_offset
+        "#).unwrap();
+
+        assert_eq!(out, "21\n81\n2\n<func <lambda>>\n");
+    }
+
+    #[test]
+    fn objects () {
+        let out = run(r#"
+            let p = Point { x = 1, y = 2 }
+            p.x = p.x + 10
+            __p_print p.x
+            __p_print p
+        "#).unwrap();
+
+        assert_eq!(out, "11\nPoint { x = 11, y = 2 }\n");
+    }
+
+    #[test]
+    fn postfix_chains () {
+        let out = run(r#"
+            func adder (a: Num) -> Num
+                func add (b: Num) -> Num
+                    a + b
+                end
+                add
+            end
+
+            let p = Point { x = 1, twice = adder(1), next = Point { x = 2 } }
+            __p_print adder(2)(3)
+            __p_print p.twice(p.next.x)
+            __p_print p.next.x
+        "#).unwrap();
+
+        assert_eq!(out, "5\n3\n2\n");
+    }
+
+    #[test]
+    fn null_operators () {
+        let out = run(r#"
+            let p = Point { x = 1, next = null }
+            __p_print p.next ?? "none"
+            __p_print p.next?.x ?? "none"
+            __p_print p.next?.next.x
+            __p_print p.x ?? 2
+            __p_print null ?? null ?? 3
+            __p_print p.x!
+            __p_print p.next == null
+
+            p.next = Point { x = 2, next = null }
+            __p_print p.next?.x
+        "#).unwrap();
+
+        assert_eq!(out, "none\nnone\nnull\n1\n3\n1\ntrue\n2\n");
+
+        let err = run("let p = Point { next = null }\n__p_print p.next!").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::NullValue);
+
+        let out = run(r#"
+            let p = Point { next = undefined }
+            __p_print undefined ?? 1
+            __p_print p.next
+            __p_print p.next?.x ?? "none"
+            __p_print p.next == undefined
+            __p_print p.next == null
+        "#).unwrap();
+        assert_eq!(out, "1\nundefined\nnone\ntrue\nfalse\n");
+
+        let err = run("__p_print undefined!").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::NullValue);
+
+        let err = run("let p = Point { next = null }\n__p_print p.next.x").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::UnknownField {
+            name: String::from("x")
+        });
+    }
+
+    #[test]
+    fn collections () {
+        let out = run(r#"
+            let list = [1, 2, 3,]
+            list[1] = list[0] + list[2]
+            __p_print list
+            __p_print list[1]
+
+            let pair = ("a", [])
+            __p_print pair
+            __p_print (pair[1],)
+            __p_print pair == ("a", [])
+            __p_print pair === ("a", [])
+        "#).unwrap();
+
+        assert_eq!(out, "[1, 4, 3]\n4\n(a, [])\n([],)\ntrue\nfalse\n");
+
+        let err = run("let list = [1, 2]\n__p_print list[2]").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::InvalidIndex);
+
+        let err = run("let pair = (1, 2)\npair[0] = 3").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::InvalidAssignmentTarget);
+
+        let err = run("__p_print 5[0]").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::NotIndexable);
+    }
+
+    #[test]
+    fn arithmetic_and_bitwise_operators () {
+        let out = run(r#"
+            __p_print 17 % 5
+            __p_print 6 & 3 | 8
+            __p_print 6 ^ 3
+            __p_print 1 << 4 >> 2
+            __p_print true ^ false
+
+            let x = 10
+            x += 5
+            x -= 3
+            x *= 2
+            x /= 4
+            x %= 4
+            __p_print x
+
+            let obj = { count = 1 }
+            obj.count += 1
+            let list = [1, 2]
+            list[1] *= 10
+            __p_print obj.count + list[1]
+        "#).unwrap();
+
+        assert_eq!(out, "2\n10\n5\n4\ntrue\n2\n22\n");
+
+        let err = run("let x = \"a\"\nx -= 1").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::InvalidOperands);
+
+        let out = run("__p_print 1 << 63 >> 63\n__p_print ~-1\n__p_print -8 >> 1").unwrap();
+        assert_eq!(out, "-1\n0\n-4\n");
+
+        let invalid = [
+            "1 << -1", "1 << 64", "1 >> 64", "1 << 0.5", "2.5 | 1", "1 & 0.5", "1.5 ^ 1", "~1.5",
+            "1e20 & 1", "(0 / 0) | 1",
+        ];
+        for src in invalid {
+            println!("Testing '{}'.", src);
+            let err = run(&format!("__p_print {}", src)).unwrap_err();
+            assert_eq!(err.code, compiler_messages::MessageCode::InvalidOperands);
+        }
+    }
+
+    #[test]
+    fn destructuring () {
+        let out = run(r#"
+            let [first, mut second] = [1, "two"]
+            let [a, b, c] = (true, first, [])
+            let { y, x: Num } = Point { x = 3, y = 4 }
+            __p_print second
+            __p_print a
+            __p_print x * y
+        "#).unwrap();
+
+        assert_eq!(out, "two\ntrue\n12\n");
+
+        let err = run("let [a, b] = [1, 2, 3]").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::DestructuringLengthMismatch);
+
+        let err = run("let [a] = 5").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::CannotDestructure);
+
+        let err = run("let { z } = { x = 1 }").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::UnknownField {
+            name: String::from("z")
+        });
+    }
+
+    #[test]
+    fn multiple_declarators () {
+        let out = run(r#"
+            let a, mut b: Num = 1, 2
+            let pair = ("x", [3, 4])
+            let name, [c, d] = pair
+            let e, f
+            __p_print a + b
+            __p_print name
+            __p_print c * d
+        "#).unwrap();
+
+        assert_eq!(out, "3\nx\n12\n");
+
+        let err = run("let a, b = 1, 2, 3").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::CannotInitializeDeclarators);
+
+        let pair = "let pair = (1, 2)\n";
+        let err = run(&format!("{}let a, b, c = pair", pair)).unwrap_err();
+        println!("{}", err.message);
+        assert_eq!(err.code, compiler_messages::MessageCode::CannotInitializeDeclarators);
+
+        let err = run("let arr = [1, 2]\nlet a, b = arr").unwrap_err();
+        println!("{}", err.message);
+        assert_eq!(err.code, compiler_messages::MessageCode::CannotInitializeDeclarators);
+    }
+
+    #[test]
+    fn runtime_errors () {
+        let err = run("__p_print foo").unwrap_err();
+        println!("{}", err.message);
+        assert_eq!(err.code, compiler_messages::MessageCode::UndefinedName {
+            name: String::from("foo")
+        });
+
+        let err = run("let x: Num\n__p_print x").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::UnassignedLocal {
+            name: String::from("x")
+        });
+
+        let err = run("if 1 then 2 end").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::ConditionMustBeBool);
+
+        let err = run("func f () -> Num f() end\nf()").unwrap_err();
+        assert_eq!(err.code, compiler_messages::MessageCode::StackOverflow);
+    }
+}
@@ -0,0 +1,208 @@
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use crate::judith::interpreter::environment::Environment;
+use crate::judith::syntax::nodes::{Body, ParameterList};
+
+/// A value produced by the tree-walking interpreter.
+#[derive(Debug, Clone)]
+pub enum Value<'a> {
+    /// The value of expressions that don't produce anything (e.g. an `if` without `else` whose
+    /// test was false, or a `while` loop).
+    Void,
+    Null,
+    /// The value of `undefined`, which is absent like `null` but kept apart from it.
+    Undefined,
+    Num(f64),
+    String(String),
+    Bool(bool),
+    Object(Rc<RefCell<Object<'a>>>),
+    /// An array, created by an array literal or an empty collection.
+    Array(Rc<RefCell<Vec<Value<'a>>>>),
+    /// A tuple. Tuples can't be modified, so they don't need to be shared.
+    Tuple(Rc<Vec<Value<'a>>>),
+    Function(Rc<Function<'a>>),
+}
+
+/// An object created by an object initialization expression.
+#[derive(Debug)]
+pub struct Object<'a> {
+    /// The name of the type given as the provider of the initializer, if any.
+    pub type_name: Option<String>,
+    /// The fields of this object, in the order they were initialized.
+    pub fields: Vec<(String, Value<'a>)>,
+}
+
+/// A function value, along with the environment it was declared in.
+#[derive(Debug)]
+pub struct Function<'a> {
+    /// The name of the function, or `None` if it was created by a lambda expression.
+    pub name: Option<&'a str>,
+    pub params: &'a ParameterList<'a>,
+    pub body: &'a Body<'a>,
+    pub closure: Rc<RefCell<Environment<'a>>>,
+}
+
+impl<'a> Value<'a> {
+    /// The name of this value's type, as it would be written in Judith.
+    pub fn type_name(&self) -> String {
+        match self {
+            Value::Void => String::from("Void"),
+            Value::Null => String::from("Null"),
+            Value::Undefined => String::from("Undefined"),
+            Value::Num(_) => String::from("Num"),
+            Value::String(_) => String::from("String"),
+            Value::Bool(_) => String::from("Bool"),
+            Value::Object(obj) => match &obj.borrow().type_name {
+                Some(name) => name.clone(),
+                None => String::from("Object"),
+            },
+            Value::Array(_) => String::from("Array"),
+            Value::Tuple(_) => String::from("Tuple"),
+            Value::Function(_) => String::from("Function"),
+        }
+    }
+
+    /// Compares two values by value. Objects are equal when they have the same type and their
+    /// fields are equal, arrays and tuples when their elements are equal, while functions are only
+    /// equal to themselves.
+    pub fn equals(&self, other: &Value<'a>) -> bool {
+        match (self, other) {
+            (Value::Void, Value::Void) => true,
+            (Value::Null, Value::Null) => true,
+            (Value::Undefined, Value::Undefined) => true,
+            (Value::Num(a), Value::Num(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => {
+                if Rc::ptr_eq(a, b) {
+                    return true;
+                }
+
+                let (a, b) = (a.borrow(), b.borrow());
+                a.type_name == b.type_name
+                    && a.fields.len() == b.fields.len()
+                    && a.fields.iter().all(|(name, val)| match b.get(name) {
+                        Some(other_val) => val.equals(other_val),
+                        None => false,
+                    })
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                Rc::ptr_eq(a, b) || Self::elements_equal(&a.borrow(), &b.borrow())
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => Self::elements_equal(a, b),
+            (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    fn elements_equal(a: &[Value<'a>], b: &[Value<'a>]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.equals(b))
+    }
+
+    /// Compares two values by reference. Values that aren't references are compared by value.
+    pub fn reference_equals(&self, other: &Value<'a>) -> bool {
+        match (self, other) {
+            (Value::Object(a), Value::Object(b)) => Rc::ptr_eq(a, b),
+            (Value::Object(_), _) | (_, Value::Object(_)) => false,
+            (Value::Array(a), Value::Array(b)) => Rc::ptr_eq(a, b),
+            (Value::Array(_), _) | (_, Value::Array(_)) => false,
+            // Tuples are values, but the references they contain are still compared as such.
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.reference_equals(b))
+            }
+            _ => self.equals(other),
+        }
+    }
+}
+
+impl Function<'_> {
+    /// The name used to refer to this function in messages.
+    pub fn display_name(&self) -> &str {
+        self.name.unwrap_or("<lambda>")
+    }
+}
+
+impl<'a> Object<'a> {
+    pub fn new(type_name: Option<String>) -> Self {
+        Object {
+            type_name,
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value<'a>> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Sets the value of the field given, returning `false` if the field doesn't exist.
+    pub fn set(&mut self, name: &str, val: Value<'a>) -> bool {
+        match self.fields.iter_mut().find(|(n, _)| n == name) {
+            Some((_, field)) => {
+                *field = val;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a field to the object, or replaces its value if it was already initialized.
+    pub fn init_field(&mut self, name: &str, val: Value<'a>) {
+        if !self.set(name, val.clone()) {
+            self.fields.push((name.to_string(), val));
+        }
+    }
+}
+
+impl Display for Value<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Void => write!(f, "void"),
+            Value::Null => write!(f, "null"),
+            Value::Undefined => write!(f, "undefined"),
+            Value::Num(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Object(obj) => {
+                let obj = obj.borrow();
+                if let Some(name) = &obj.type_name {
+                    write!(f, "{} ", name)?;
+                }
+
+                write!(f, "{{")?;
+                for (i, (name, val)) in obj.fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {} = {}", name, val)?;
+                }
+                write!(f, " }}")
+            }
+            Value::Array(elements) => {
+                write!(f, "[")?;
+                for (i, val) in elements.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", val)?;
+                }
+                write!(f, "]")
+            }
+            Value::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, val) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", val)?;
+                }
+                // Like in source code, a tuple with a single element needs a trailing comma.
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Value::Function(func) => write!(f, "<func {}>", func.display_name()),
+        }
+    }
+}
@@ -0,0 +1,667 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::judith::jasm::assembly::{Assembly, Block, Function, ItemRef, StringTable, Version};
+use crate::judith::jasm::opcodes::{encode_instruction, operand_fits, ConstantType, OpCode, OperandKind};
+
+/// An error found while assembling JASM text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    /// The line where the error was found, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for AssembleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+/// A token in a line of JASM text.
+#[derive(Debug, Clone, PartialEq)]
+enum Word {
+    Plain(String),
+    Str(String),
+}
+
+/// The section of the file being assembled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Top,
+    Names,
+    TypeRefs,
+    FuncRefs,
+    Block,
+    Strings,
+    Func,
+}
+
+/// An element of a function's code, before labels are resolved.
+enum CodeItem {
+    Label(String),
+    Instruction { opcode: OpCode, operand: Option<Word>, line: usize },
+    Byte(u8),
+}
+
+/// Converts JASM text, as produced by `disassemble`, into an assembly.
+pub fn assemble(src: &str) -> Result<Assembly, AssembleError> {
+    let mut asm = Assembler {
+        assembly: Assembly::default(),
+        section: Section::Top,
+        code: Vec::new(),
+        line: 0,
+    };
+
+    for (i, line) in src.lines().enumerate() {
+        asm.line = i + 1;
+        let words = split_line(line).map_err(|msg| asm.error(msg))?;
+
+        if !words.is_empty() {
+            asm.assemble_line(words)?;
+        }
+    }
+
+    if asm.section != Section::Top {
+        return Err(asm.error(format!("Unexpected end of file, missing '.end' ({:?}).", asm.section)));
+    }
+
+    Ok(asm.assembly)
+}
+
+struct Assembler {
+    assembly: Assembly,
+    section: Section,
+    /// The code of the function currently being assembled.
+    code: Vec<CodeItem>,
+    line: usize,
+}
+
+impl Assembler {
+    fn error(&self, message: impl Into<String>) -> AssembleError {
+        AssembleError { line: self.line, message: message.into() }
+    }
+
+    fn assemble_line(&mut self, words: Vec<Word>) -> Result<(), AssembleError> {
+        let first = match &words[0] {
+            Word::Plain(w) => w.as_str(),
+            // Lines that start with a string are entries in a string table.
+            Word::Str(s) => return self.assemble_string(s.clone(), &words[1..]),
+        };
+
+        if first == ".end" {
+            self.expect_count(&words, 1)?;
+            return self.end_section();
+        }
+
+        match self.section {
+            Section::Top => self.assemble_top_level(first, &words),
+            Section::Names | Section::Strings => {
+                Err(self.error(format!("Expected a string, found '{}'.", first)))
+            }
+            Section::TypeRefs | Section::FuncRefs => self.assemble_ref(first, &words),
+            Section::Block => self.assemble_block_line(first, &words),
+            Section::Func => self.assemble_func_line(first, &words),
+        }
+    }
+
+    fn end_section(&mut self) -> Result<(), AssembleError> {
+        self.section = match self.section {
+            Section::Top => return Err(self.error("Unexpected '.end'.")),
+            Section::Names | Section::TypeRefs | Section::FuncRefs | Section::Block => Section::Top,
+            Section::Strings => Section::Block,
+            Section::Func => {
+                let code = self.assemble_code()?;
+                self.current_func().code = code;
+                Section::Block
+            }
+        };
+
+        Ok(())
+    }
+
+    fn assemble_top_level(&mut self, directive: &str, words: &[Word]) -> Result<(), AssembleError> {
+        match directive {
+            ".judith_version" => {
+                self.expect_count(words, 2)?;
+                self.assembly.judith_version = self.parse_int(&words[1])?;
+            }
+            ".version" => {
+                self.expect_count(words, 2)?;
+                self.assembly.version = self.parse_version(&words[1])?;
+            }
+            ".names" => {
+                self.expect_count(words, 1)?;
+                self.section = Section::Names;
+            }
+            ".type_refs" => {
+                self.expect_count(words, 1)?;
+                self.section = Section::TypeRefs;
+            }
+            ".func_refs" => {
+                self.expect_count(words, 1)?;
+                self.section = Section::FuncRefs;
+            }
+            ".block" => {
+                self.expect_count(words, 2)?;
+                let name_index = self.parse_int(&words[1])?;
+                self.assembly.blocks.push(Block {
+                    name_index,
+                    string_table: StringTable::default(),
+                    functions: Vec::new(),
+                });
+                self.section = Section::Block;
+            }
+            _ => return Err(self.error(format!("Unknown directive '{}'.", directive))),
+        }
+
+        Ok(())
+    }
+
+    fn assemble_string(&mut self, s: String, rest: &[Word]) -> Result<(), AssembleError> {
+        if !rest.is_empty() {
+            return Err(self.error("Expected a single string."));
+        }
+
+        match self.section {
+            Section::Names => self.assembly.name_table.strings.push(s),
+            Section::Strings => self.current_block().string_table.strings.push(s),
+            _ => return Err(self.error("Strings can only appear in string tables.")),
+        }
+
+        Ok(())
+    }
+
+    fn assemble_ref(&mut self, kind: &str, words: &[Word]) -> Result<(), AssembleError> {
+        let item_ref = match kind {
+            "internal" => {
+                self.expect_count(words, 3)?;
+                ItemRef::Internal {
+                    block: self.parse_int(&words[1])?,
+                    index: self.parse_int(&words[2])?,
+                }
+            }
+            "native" => {
+                self.expect_count(words, 2)?;
+                ItemRef::Native { index: self.parse_int(&words[1])? }
+            }
+            "external" => {
+                self.expect_count(words, 3)?;
+                ItemRef::External {
+                    block_name: self.parse_int(&words[1])?,
+                    item_name: self.parse_int(&words[2])?,
+                }
+            }
+            _ => return Err(self.error(format!("Unknown reference type '{}'.", kind))),
+        };
+
+        match self.section {
+            Section::TypeRefs => self.assembly.type_refs.push(item_ref),
+            _ => self.assembly.func_refs.push(item_ref),
+        }
+
+        Ok(())
+    }
+
+    fn assemble_block_line(&mut self, directive: &str, words: &[Word]) -> Result<(), AssembleError> {
+        match directive {
+            ".strings" => {
+                self.expect_count(words, 1)?;
+                self.section = Section::Strings;
+            }
+            ".func" => {
+                self.expect_count(words, 2)?;
+                let name_index = self.parse_int(&words[1])?;
+                self.current_block().functions.push(Function {
+                    name_index,
+                    ..Function::default()
+                });
+                self.code.clear();
+                self.section = Section::Func;
+            }
+            _ => return Err(self.error(format!("Unknown directive inside block '{}'.", directive))),
+        }
+
+        Ok(())
+    }
+
+    fn assemble_func_line(&mut self, first: &str, words: &[Word]) -> Result<(), AssembleError> {
+        match first {
+            ".params" => {
+                let mut params = Vec::new();
+                for word in &words[1..] {
+                    params.push(self.parse_int(word)?);
+                }
+                self.current_func().params = params;
+            }
+            ".max_locals" => {
+                self.expect_count(words, 2)?;
+                let max_locals = self.parse_int(&words[1])?;
+                self.current_func().max_locals = max_locals;
+            }
+            ".max_stack" => {
+                self.expect_count(words, 2)?;
+                let max_stack = self.parse_int(&words[1])?;
+                self.current_func().max_stack = max_stack;
+            }
+            ".byte" => {
+                self.expect_count(words, 2)?;
+                let val = self.parse_int(&words[1])?;
+                self.code.push(CodeItem::Byte(val));
+            }
+            label if label.ends_with(':') => {
+                self.expect_count(words, 1)?;
+                self.code.push(CodeItem::Label(label.trim_end_matches(':').to_string()));
+            }
+            mnemonic => {
+                let Ok(opcode) = OpCode::from_str(mnemonic) else {
+                    return Err(self.error(format!("Unknown instruction '{}'.", mnemonic)));
+                };
+
+                let has_operand = opcode.operand_kind() != OperandKind::None;
+                self.expect_count(words, if has_operand { 2 } else { 1 })?;
+
+                self.code.push(CodeItem::Instruction {
+                    opcode,
+                    operand: words.get(1).cloned(),
+                    line: self.line,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the code of the current function, resolving the labels used by jumps.
+    fn assemble_code(&mut self) -> Result<Vec<u8>, AssembleError> {
+        let items = std::mem::take(&mut self.code);
+
+        // Every instruction has a fixed size, so labels can be resolved before encoding.
+        let mut labels = HashMap::new();
+        let mut offset = 0;
+        for item in &items {
+            match item {
+                CodeItem::Label(name) => {
+                    if labels.insert(name.as_str(), offset).is_some() {
+                        return Err(self.error(format!("Label '{}' is defined twice.", name)));
+                    }
+                }
+                CodeItem::Instruction { opcode, .. } => offset += opcode.instruction_size(),
+                CodeItem::Byte(_) => offset += 1,
+            }
+        }
+
+        let mut code = Vec::new();
+        for item in &items {
+            match item {
+                CodeItem::Label(_) => {}
+                CodeItem::Byte(val) => code.push(*val),
+                CodeItem::Instruction { opcode, operand, line } => {
+                    self.line = *line;
+                    let end = code.len() + opcode.instruction_size();
+                    let val = match operand {
+                        Some(word) => self.parse_operand(*opcode, word, end, &labels)?,
+                        None => 0,
+                    };
+
+                    encode_instruction(&mut code, *opcode, val);
+                }
+            }
+        }
+
+        Ok(code)
+    }
+
+    fn parse_operand(
+        &self, opcode: OpCode, word: &Word, end: usize, labels: &HashMap<&str, usize>
+    ) -> Result<i64, AssembleError> {
+        let kind = opcode.operand_kind();
+        let Word::Plain(text) = word else {
+            return Err(self.error("Instructions can't take strings as operands."));
+        };
+
+        let val = match kind {
+            OperandKind::Jump(_) => match labels.get(text.as_str()) {
+                Some(target) => *target as i64 - end as i64,
+                None => parse_number(text).ok_or_else(|| {
+                    self.error(format!("Unknown label '{}'.", text))
+                })?,
+            },
+            OperandKind::ConstantType => match ConstantType::from_str(text) {
+                Ok(ty) => ty as i64,
+                Err(_) => self.parse_int::<u8>(word)? as i64,
+            },
+            // Immediates can also be written as floats, which are stored as their bits.
+            OperandKind::Immediate(8) if text.contains('.') => match f64::from_str(text) {
+                Ok(f) => f.to_bits() as i64,
+                Err(_) => return Err(self.error(format!("Invalid float '{}'.", text))),
+            },
+            _ => parse_number(text).ok_or_else(|| {
+                self.error(format!("Invalid operand '{}'.", text))
+            })?,
+        };
+
+        // Immediates are raw bytes, so they may be written either as signed or unsigned values.
+        let fits = match kind {
+            OperandKind::Immediate(size) if size < 8 => {
+                operand_fits(kind, val) || (val >= -(1i64 << (size * 8 - 1)) && val < 0)
+            }
+            _ => operand_fits(kind, val),
+        };
+
+        if !fits {
+            return Err(self.error(format!(
+                "Operand {} doesn't fit in {}'s operand ({} bytes).",
+                val, opcode.mnemonic(), kind.size()
+            )));
+        }
+
+        Ok(val)
+    }
+
+    fn parse_int<T: TryFrom<i64>>(&self, word: &Word) -> Result<T, AssembleError> {
+        let Word::Plain(text) = word else {
+            return Err(self.error("Expected a number, found a string."));
+        };
+
+        parse_number(text)
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or_else(|| self.error(format!("Invalid number '{}'.", text)))
+    }
+
+    fn parse_version(&self, word: &Word) -> Result<Version, AssembleError> {
+        let parts: Vec<u16> = match word {
+            Word::Plain(text) => text.split('.').filter_map(|p| p.parse().ok()).collect(),
+            Word::Str(_) => Vec::new(),
+        };
+
+        match parts[..] {
+            [major, minor, patch, build] => Ok(Version::new(major, minor, patch, build)),
+            _ => Err(self.error("Versions must have the form 'major.minor.patch.build'.")),
+        }
+    }
+
+    fn expect_count(&self, words: &[Word], count: usize) -> Result<(), AssembleError> {
+        if words.len() == count {
+            Ok(())
+        }
+        else {
+            Err(self.error(format!(
+                "Expected {} operand(s), found {}.", count - 1, words.len().saturating_sub(1)
+            )))
+        }
+    }
+
+    fn current_block(&mut self) -> &mut Block {
+        self.assembly.blocks.last_mut().expect("Assembling a block that doesn't exist.")
+    }
+
+    fn current_func(&mut self) -> &mut Function {
+        self.current_block().functions.last_mut().expect("Assembling a function that doesn't exist.")
+    }
+}
+
+/// Parses a decimal number, or a hexadecimal one when prefixed with "0x". Hexadecimal numbers
+/// are read as raw 64-bit values.
+fn parse_number(text: &str) -> Option<i64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(|n| n as i64),
+        None => text.parse().ok(),
+    }
+}
+
+/// Splits a line into words, discarding its comment (everything after a ';' that isn't inside a
+/// string).
+fn split_line(line: &str) -> Result<Vec<Word>, String> {
+    let mut words = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        }
+        else if c == ';' {
+            break;
+        }
+        else if c == '"' {
+            chars.next();
+            words.push(Word::Str(read_string(&mut chars)?));
+        }
+        else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == ';' || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            words.push(Word::Plain(word));
+        }
+    }
+
+    Ok(words)
+}
+
+/// Reads a string literal, assuming its opening quote has already been consumed.
+fn read_string(chars: &mut impl Iterator<Item = char>) -> Result<String, String> {
+    let mut s = String::new();
+
+    loop {
+        match chars.next() {
+            None => return Err(String::from("Unterminated string.")),
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('"') => s.push('"'),
+                Some('\\') => s.push('\\'),
+                Some('u') => {
+                    if chars.next() != Some('{') {
+                        return Err(String::from("Expected '{' after '\\u'."));
+                    }
+
+                    let hex: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                    match c {
+                        Some(c) => s.push(c),
+                        None => return Err(format!("Invalid unicode escape '\\u{{{}}}'.", hex)),
+                    }
+                }
+                Some(c) => return Err(format!("Invalid escape sequence '\\{}'.", c)),
+                None => return Err(String::from("Unterminated string.")),
+            },
+            Some(c) => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::jasm::disassembler::disassemble;
+    use crate::judith::jasm::reader::read_assembly;
+    use super::*;
+
+    fn sample_assembly() -> Assembly {
+        let mut main_code = Vec::new();
+        encode_instruction(&mut main_code, OpCode::ConstLL, 2.5f64.to_bits() as i64);
+        encode_instruction(&mut main_code, OpCode::Store, 5);
+        encode_instruction(&mut main_code, OpCode::Load, 5);
+        encode_instruction(&mut main_code, OpCode::IConst1, 0);
+        encode_instruction(&mut main_code, OpCode::JFalse, 9); // to the RET.
+        encode_instruction(&mut main_code, OpCode::StrConst, 1);
+        encode_instruction(&mut main_code, OpCode::Print, ConstantType::StringUtf8 as i64);
+        encode_instruction(&mut main_code, OpCode::Call, 0);
+        encode_instruction(&mut main_code, OpCode::Ret, 0);
+        encode_instruction(&mut main_code, OpCode::JmpL, -18); // to the I_CONST_1.
+
+        let mut add_code = Vec::new();
+        encode_instruction(&mut add_code, OpCode::Load0, 0);
+        encode_instruction(&mut add_code, OpCode::Load1, 0);
+        encode_instruction(&mut add_code, OpCode::FAdd, 0);
+        encode_instruction(&mut add_code, OpCode::Const, 0xff);
+        encode_instruction(&mut add_code, OpCode::ConstL, -1);
+        encode_instruction(&mut add_code, OpCode::Jmp, 100); // invalid target.
+        encode_instruction(&mut add_code, OpCode::Native, 0x7fff_0000_1234);
+        add_code.push(0xf0); // invalid opcode.
+        encode_instruction(&mut add_code, OpCode::Ret, 0);
+        add_code.push(OpCode::StrConstL as u8); // truncated instruction.
+        add_code.push(0x01);
+
+        Assembly {
+            judith_version: 0,
+            version: Version::new(0, 1, 2, 3),
+            name_table: StringTable::new(vec![
+                String::from("main"),
+                String::from("std"),
+                String::from("print"),
+            ]),
+            type_refs: vec![ItemRef::Internal { block: 0, index: 3 }],
+            func_refs: vec![
+                ItemRef::Internal { block: 0, index: 1 },
+                ItemRef::Native { index: 7 },
+                ItemRef::External { block_name: 1, item_name: 2 },
+            ],
+            blocks: vec![Block {
+                name_index: 0,
+                string_table: StringTable::new(vec![
+                    String::from("main"),
+                    String::from("Hello, \"world\"\n\t; not a comment \\ \u{1} ñ"),
+                    String::from("add"),
+                    String::from("a"),
+                    String::from("b"),
+                    String::new(),
+                ]),
+                functions: vec![
+                    Function {
+                        name_index: 0,
+                        params: vec![],
+                        max_locals: 6,
+                        max_stack: 2,
+                        code: main_code,
+                    },
+                    Function {
+                        name_index: 2,
+                        params: vec![3, 4],
+                        max_locals: 2,
+                        max_stack: 2,
+                        code: add_code,
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn binary_round_trip () {
+        let assembly = sample_assembly();
+        let bytes = assembly.to_bytes();
+
+        assert_eq!(&bytes[..6], b"JUDITH");
+        assert_eq!(read_assembly(&bytes), Ok(assembly));
+    }
+
+    #[test]
+    fn text_round_trip () {
+        let assembly = sample_assembly();
+        let text = disassemble(&assembly);
+        println!("{}", text);
+
+        assert_eq!(assemble(&text), Ok(assembly.clone()));
+        // Disassembling the result again must produce the exact same text.
+        assert_eq!(disassemble(&assemble(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn every_opcode_round_trips () {
+        use strum::IntoEnumIterator;
+
+        for opcode in OpCode::iter() {
+            let mut code = Vec::new();
+            let operand = match opcode.operand_kind() {
+                OperandKind::None => 0,
+                OperandKind::Jump(_) => -(opcode.instruction_size() as i64), // jumps to itself.
+                OperandKind::ConstantType => ConstantType::Bool as i64,
+                _ => 1,
+            };
+            encode_instruction(&mut code, opcode, operand);
+
+            let assembly = Assembly {
+                blocks: vec![Block {
+                    functions: vec![Function { code, ..Function::default() }],
+                    ..Block::default()
+                }],
+                ..Assembly::default()
+            };
+
+            let text = disassemble(&assembly);
+            assert!(text.contains(opcode.mnemonic()));
+            assert_eq!(assemble(&text), Ok(assembly));
+        }
+    }
+
+    #[test]
+    fn hand_written_jasm () {
+        let src = r#"
+            .judith_version 0
+            .version 1.0.0.0
+            .names
+                "main"
+            .end
+            .block 0
+            .strings
+                "main"
+                "counter"
+            .end
+            .func 0
+                .max_locals 1
+                .max_stack 2
+                CONST_0
+                STORE_0
+            loop:
+                LOAD_0
+                F_CONST_1
+                F_ADD
+                STORE_0
+                LOAD_0
+                CONST_LL 10.0
+                F_LT
+                JTRUE loop   ; back to the start of the loop.
+                LOAD_0
+                PRINT FLOAT_64
+                RET
+            .end
+            .end
+        "#;
+
+        let assembly = assemble(src).unwrap();
+        let code = &assembly.blocks[0].functions[0].code;
+        assert_eq!(code[..2], [OpCode::Const0 as u8, OpCode::Store0 as u8]);
+
+        // JTRUE is followed by LOAD_0, PRINT and RET (4 bytes). The loop starts at offset 2.
+        let jtrue = code.len() - 6;
+        assert_eq!(code[jtrue], OpCode::JTrue as u8);
+        assert_eq!(code[jtrue + 1] as i8 as i64, 2 - (jtrue as i64 + 2));
+    }
+
+    #[test]
+    fn assemble_errors () {
+        let err = assemble(".block 0\n.func 0\nFOO\n.end\n.end").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = assemble(".block 0\n.func 0\nJMP nowhere\n.end\n.end").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = assemble(".block 0\n.func 0\nCONST 256\n.end\n.end").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = assemble(".names\n\"unterminated\n.end").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = assemble(".block 0").unwrap_err();
+        assert_eq!(err.line, 1);
+    }
+}
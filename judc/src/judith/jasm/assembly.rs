@@ -0,0 +1,203 @@
+/// The magic number every .jdll file starts with.
+pub const MAGIC_NUMBER: &[u8; 6] = b"JUDITH";
+
+/// An in-memory representation of a .jdll file, mirroring the VM's `AssemblyFile`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Assembly {
+    pub judith_version: u32,
+    pub version: Version,
+    /// The names of blocks and items referenced by this assembly.
+    pub name_table: StringTable,
+    pub type_refs: Vec<ItemRef>,
+    pub func_refs: Vec<ItemRef>,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub build: u16,
+}
+
+/// A table of UTF-8 strings. In binary form, each string is stored as its length (u64) followed
+/// by its bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StringTable {
+    pub strings: Vec<String>,
+}
+
+/// A reference to a type or function, which may live in this assembly, in the VM or in another
+/// assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemRef {
+    /// An item in this assembly, located by its block and its index inside that block.
+    Internal { block: u32, index: u32 },
+    /// An item provided by the VM.
+    Native { index: u32 },
+    /// An item in a different assembly, located by the indices of its block's name and its own
+    /// name in the name table.
+    External { block_name: u32, item_name: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Block {
+    /// The index of this block's name in the assembly's name table.
+    pub name_index: u32,
+    pub string_table: StringTable,
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Function {
+    /// The index of this function's name in its block's string table.
+    pub name_index: u32,
+    /// The index of each parameter's name in the block's string table.
+    pub params: Vec<u32>,
+    pub max_locals: u16,
+    pub max_stack: u16,
+    pub code: Vec<u8>,
+}
+
+impl ItemRef {
+    pub const TYPE_INTERNAL: u32 = 0;
+    pub const TYPE_NATIVE: u32 = 1;
+    pub const TYPE_EXTERNAL: u32 = 2;
+
+    pub fn ref_type(&self) -> u32 {
+        match self {
+            ItemRef::Internal { .. } => Self::TYPE_INTERNAL,
+            ItemRef::Native { .. } => Self::TYPE_NATIVE,
+            ItemRef::External { .. } => Self::TYPE_EXTERNAL,
+        }
+    }
+}
+
+impl StringTable {
+    pub fn new(strings: Vec<String>) -> Self {
+        StringTable { strings }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.strings.get(index).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// The size, in bytes, of this table's contents in binary form.
+    pub fn byte_size(&self) -> usize {
+        self.strings.iter().map(|s| 8 + s.len()).sum()
+    }
+}
+
+impl Version {
+    pub fn new(major: u16, minor: u16, patch: u16, build: u16) -> Self {
+        Version { major, minor, patch, build }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.patch, self.build)
+    }
+}
+
+impl Assembly {
+    /// Serializes this assembly into the .jdll format read by the VM.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        buffer.extend_from_slice(MAGIC_NUMBER); // magic_number: byte[6]
+        buffer.push(0); // endianness: byte = 0 -- little-endian
+        write_u32(&mut buffer, self.judith_version); // judith_version: Ui32
+        write_u16(&mut buffer, self.version.major); // version: Version
+        write_u16(&mut buffer, self.version.minor);
+        write_u16(&mut buffer, self.version.patch);
+        write_u16(&mut buffer, self.version.build);
+
+        write_string_table(&mut buffer, &self.name_table); // name_count and name_table
+
+        write_u32(&mut buffer, 0); // dep_count: Ui32 -- dependencies aren't supported yet.
+
+        write_ref_table(&mut buffer, &self.type_refs); // type_ref_count and type_ref
+        write_ref_table(&mut buffer, &self.func_refs); // func_ref_count and func_ref
+
+        write_u32(&mut buffer, self.blocks.len() as u32); // block_count
+        for block in &self.blocks {
+            write_block(&mut buffer, block);
+        }
+
+        buffer
+    }
+}
+
+fn write_u16(buffer: &mut Vec<u8>, val: u16) {
+    buffer.extend_from_slice(&val.to_le_bytes());
+}
+
+fn write_u32(buffer: &mut Vec<u8>, val: u32) {
+    buffer.extend_from_slice(&val.to_le_bytes());
+}
+
+fn write_u64(buffer: &mut Vec<u8>, val: u64) {
+    buffer.extend_from_slice(&val.to_le_bytes());
+}
+
+fn write_string_table(buffer: &mut Vec<u8>, table: &StringTable) {
+    write_u32(buffer, table.byte_size() as u32); // table_size
+    write_u32(buffer, table.len() as u32); // string_count
+
+    for s in &table.strings {
+        write_u64(buffer, s.len() as u64);
+        buffer.extend_from_slice(s.as_bytes());
+    }
+}
+
+fn write_ref_table(buffer: &mut Vec<u8>, table: &[ItemRef]) {
+    write_u32(buffer, table.len() as u32); // ref_count
+
+    for item_ref in table {
+        write_u32(buffer, item_ref.ref_type()); // ref_type
+
+        match item_ref {
+            ItemRef::Internal { block, index } => {
+                write_u32(buffer, *block);
+                write_u32(buffer, *index);
+            }
+            ItemRef::Native { index } => {
+                write_u32(buffer, *index);
+            }
+            ItemRef::External { block_name, item_name } => {
+                write_u32(buffer, *block_name);
+                write_u32(buffer, *item_name);
+            }
+        }
+    }
+}
+
+fn write_block(buffer: &mut Vec<u8>, block: &Block) {
+    write_u32(buffer, block.name_index); // block_name
+    write_string_table(buffer, &block.string_table); // string_count and string_table
+
+    write_u32(buffer, 0); // type_count -- types aren't supported yet.
+
+    write_u32(buffer, block.functions.len() as u32); // func_count
+    for func in &block.functions {
+        write_u32(buffer, func.name_index); // name
+        write_u16(buffer, func.params.len() as u16); // param_count
+        for param in &func.params {
+            write_u32(buffer, *param); // name
+        }
+        write_u16(buffer, func.max_locals); // max_locals
+        write_u16(buffer, func.max_stack); // max_stack
+        write_u32(buffer, func.code.len() as u32); // code_length
+        buffer.extend_from_slice(&func.code); // code
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use crate::judith::jasm::assembly::{Assembly, Block, Function, ItemRef, StringTable};
+use crate::judith::jasm::opcodes::{decode_instruction, ConstantType, Instruction, OperandKind};
+
+/// An element of a disassembled chunk: either an instruction, or a byte that couldn't be decoded
+/// as one.
+enum ChunkItem {
+    Instruction(Instruction),
+    Byte { offset: usize, val: u8 },
+}
+
+/// Converts the assembly given to JASM text. The text contains everything in the assembly, so
+/// assembling it back with `assemble` produces an identical assembly.
+pub fn disassemble(assembly: &Assembly) -> String {
+    let mut str = String::new();
+
+    let _ = writeln!(str, "; JASM disassembly generated by judc.");
+    let _ = writeln!(str, ".judith_version {}", assembly.judith_version);
+    let _ = writeln!(str, ".version {}", assembly.version);
+    let _ = writeln!(str);
+
+    let _ = writeln!(str, ".names ; {} bytes", assembly.name_table.byte_size());
+    disassemble_string_table(&mut str, &assembly.name_table);
+    let _ = writeln!(str, ".end");
+    let _ = writeln!(str);
+
+    let _ = writeln!(str, ".type_refs");
+    disassemble_ref_table(&mut str, assembly, &assembly.type_refs, false);
+    let _ = writeln!(str, ".end");
+    let _ = writeln!(str);
+
+    let _ = writeln!(str, ".func_refs");
+    disassemble_ref_table(&mut str, assembly, &assembly.func_refs, true);
+    let _ = writeln!(str, ".end");
+
+    for (b, block) in assembly.blocks.iter().enumerate() {
+        let _ = writeln!(str);
+        disassemble_block(&mut str, assembly, block, b);
+    }
+
+    str
+}
+
+fn disassemble_string_table(str: &mut String, table: &StringTable) {
+    for (i, s) in table.strings.iter().enumerate() {
+        let _ = writeln!(str, "    {:<32} ; #{}", escape_string(s), i);
+    }
+}
+
+fn disassemble_ref_table(str: &mut String, assembly: &Assembly, table: &[ItemRef], is_func: bool) {
+    for (i, item_ref) in table.iter().enumerate() {
+        let (text, target) = match item_ref {
+            ItemRef::Internal { block, index } => (
+                format!("internal {} {}", block, index),
+                describe_internal_ref(assembly, *block, *index, is_func),
+            ),
+            ItemRef::Native { index } => (format!("native {}", index), None),
+            ItemRef::External { block_name, item_name } => (
+                format!("external {} {}", block_name, item_name),
+                Some(format!(
+                    "{}::{}",
+                    quoted_or_unknown(&assembly.name_table, *block_name),
+                    quoted_or_unknown(&assembly.name_table, *item_name),
+                )),
+            ),
+        };
+
+        match target {
+            Some(target) => { let _ = writeln!(str, "    {:<32} ; #{} -> {}", text, i, target); }
+            None => { let _ = writeln!(str, "    {:<32} ; #{}", text, i); }
+        }
+    }
+}
+
+/// Describes the item an internal reference points to, if it exists.
+fn describe_internal_ref(
+    assembly: &Assembly, block: u32, index: u32, is_func: bool
+) -> Option<String> {
+    let target_block = assembly.blocks.get(block as usize)?;
+    let block_name = quoted_or_unknown(&assembly.name_table, target_block.name_index);
+
+    if !is_func {
+        return Some(block_name);
+    }
+
+    let func = target_block.functions.get(index as usize)?;
+    Some(format!(
+        "{}::{}", block_name, quoted_or_unknown(&target_block.string_table, func.name_index)
+    ))
+}
+
+fn disassemble_block(str: &mut String, assembly: &Assembly, block: &Block, index: usize) {
+    let _ = writeln!(
+        str, ".block {:<25} ; #{} {}",
+        block.name_index, index, quoted_or_unknown(&assembly.name_table, block.name_index)
+    );
+
+    let _ = writeln!(str, ".strings ; {} bytes", block.string_table.byte_size());
+    disassemble_string_table(str, &block.string_table);
+    let _ = writeln!(str, ".end");
+
+    for (f, func) in block.functions.iter().enumerate() {
+        let _ = writeln!(str);
+        disassemble_function(str, assembly, block, func, f);
+    }
+
+    let _ = writeln!(str, ".end");
+}
+
+fn disassemble_function(
+    str: &mut String, assembly: &Assembly, block: &Block, func: &Function, index: usize
+) {
+    let table = &block.string_table;
+
+    let _ = writeln!(
+        str, ".func {:<26} ; #{} {}",
+        func.name_index, index, quoted_or_unknown(table, func.name_index)
+    );
+
+    let params: Vec<String> = func.params.iter().map(|p| p.to_string()).collect();
+    let param_names: Vec<String> = func.params.iter().map(|p| quoted_or_unknown(table, *p)).collect();
+    let _ = writeln!(str, "    {:<32} ; ({})", format!(".params {}", params.join(" ")).trim_end(), param_names.join(", "));
+    let _ = writeln!(str, "    .max_locals {}", func.max_locals);
+    let _ = writeln!(str, "    .max_stack {}", func.max_stack);
+    let _ = writeln!(str, "    ; code: {} bytes", func.code.len());
+
+    let items = decode_chunk(&func.code);
+    let labels = collect_labels(&items, func.code.len());
+
+    for item in &items {
+        let offset = match item {
+            ChunkItem::Instruction(instr) => instr.offset,
+            ChunkItem::Byte { offset, .. } => *offset,
+        };
+
+        if labels.contains(&offset) {
+            let _ = writeln!(str, "{}:", label_name(offset));
+        }
+
+        let (text, comment) = match item {
+            ChunkItem::Instruction(instr) => disassemble_instruction(assembly, block, instr, &labels),
+            ChunkItem::Byte { val, .. } => (
+                format!(".byte 0x{:02x}", val),
+                Some(String::from("not a valid instruction")),
+            ),
+        };
+
+        match comment {
+            Some(comment) => { let _ = writeln!(str, "    {:<32} ; {:04x} | {}", text, offset, comment); }
+            None => { let _ = writeln!(str, "    {:<32} ; {:04x}", text, offset); }
+        }
+    }
+
+    // Jumps may point to the end of the chunk.
+    if labels.contains(&func.code.len()) {
+        let _ = writeln!(str, "{}:", label_name(func.code.len()));
+    }
+
+    let _ = writeln!(str, ".end");
+}
+
+/// Returns the text of the instruction given, along with a comment describing its operand.
+fn disassemble_instruction(
+    assembly: &Assembly, block: &Block, instr: &Instruction, labels: &BTreeSet<usize>
+) -> (String, Option<String>) {
+    let mnemonic = instr.opcode.mnemonic();
+    let val = instr.operand;
+
+    let (operand, comment) = match instr.opcode.operand_kind() {
+        OperandKind::None => return (mnemonic.to_string(), None),
+        OperandKind::Immediate(size) => {
+            let raw = val as u64;
+            let comment = match size {
+                8 => format!("i64 = {}, f64 = {}", val, f64::from_bits(raw)),
+                4 => format!("i32 = {}", val as u32 as i32),
+                _ => format!("{}", val),
+            };
+
+            (format!("0x{:0width$x}", raw, width = size * 2), Some(comment))
+        }
+        OperandKind::String(_) => {
+            let comment = match usize::try_from(val).ok().and_then(|i| block.string_table.get(i)) {
+                Some(s) => escape_string(s),
+                None => String::from("<invalid string index>"),
+            };
+
+            (val.to_string(), Some(comment))
+        }
+        OperandKind::Local(_) => (val.to_string(), None),
+        OperandKind::Jump(_) => {
+            let target = instr.jump_target().unwrap_or_default();
+            if target >= 0 && labels.contains(&(target as usize)) {
+                (label_name(target as usize), None)
+            }
+            else {
+                (val.to_string(), Some(String::from("<invalid jump target>")))
+            }
+        }
+        OperandKind::FuncRef => {
+            let comment = match assembly.func_refs.get(val as usize) {
+                Some(ItemRef::Internal { block, index }) => {
+                    describe_internal_ref(assembly, *block, *index, true)
+                }
+                Some(ItemRef::Native { index }) => Some(format!("native #{}", index)),
+                Some(ItemRef::External { block_name, item_name }) => Some(format!(
+                    "{}::{}",
+                    quoted_or_unknown(&assembly.name_table, *block_name),
+                    quoted_or_unknown(&assembly.name_table, *item_name),
+                )),
+                None => Some(String::from("<invalid function reference>")),
+            };
+
+            (val.to_string(), comment)
+        }
+        OperandKind::NativeAddress => (format!("0x{:016x}", val as u64), None),
+        OperandKind::ConstantType => match ConstantType::from_repr(val as u8) {
+            Some(ty) => (ty.as_ref().to_string(), None),
+            None => (val.to_string(), Some(String::from("<unknown constant type>"))),
+        },
+    };
+
+    let text = format!("{:<12} {}", mnemonic, operand);
+    (text, comment)
+}
+
+/// Decodes every instruction in the chunk given. Bytes that can't be decoded are kept as they
+/// are, so no information is lost.
+fn decode_chunk(code: &[u8]) -> Vec<ChunkItem> {
+    let mut items = Vec::new();
+    let mut offset = 0;
+
+    while offset < code.len() {
+        match decode_instruction(code, offset) {
+            Some(instr) => {
+                offset = instr.end();
+                items.push(ChunkItem::Instruction(instr));
+            }
+            None => {
+                items.push(ChunkItem::Byte { offset, val: code[offset] });
+                offset += 1;
+            }
+        }
+    }
+
+    items
+}
+
+/// Returns the offsets that are the target of a valid jump. A target is valid when it's the
+/// start of an item in the chunk, or the end of the chunk.
+fn collect_labels(items: &[ChunkItem], code_len: usize) -> BTreeSet<usize> {
+    let mut boundaries = BTreeSet::new();
+    boundaries.insert(code_len);
+    for item in items {
+        match item {
+            ChunkItem::Instruction(instr) => boundaries.insert(instr.offset),
+            ChunkItem::Byte { offset, .. } => boundaries.insert(*offset),
+        };
+    }
+
+    let mut labels = BTreeSet::new();
+    for item in items {
+        if let ChunkItem::Instruction(instr) = item
+            && let Some(target) = instr.jump_target()
+            && target >= 0
+            && boundaries.contains(&(target as usize))
+        {
+            labels.insert(target as usize);
+        }
+    }
+
+    labels
+}
+
+fn label_name(offset: usize) -> String {
+    format!("L{:04x}", offset)
+}
+
+fn quoted_or_unknown(table: &StringTable, index: u32) -> String {
+    match table.get(index as usize) {
+        Some(s) => escape_string(s),
+        None => String::from("<invalid name index>"),
+    }
+}
+
+/// Writes the string given as a JASM string literal.
+pub fn escape_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{{{:x}}}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
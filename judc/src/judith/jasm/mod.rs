@@ -0,0 +1,7 @@
+pub mod assembler;
+pub mod assembly;
+pub mod disassembler;
+pub mod opcodes;
+pub mod optimizer;
+pub mod reader;
+pub mod verifier;
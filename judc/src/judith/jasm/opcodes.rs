@@ -0,0 +1,330 @@
+use strum_macros::{AsRefStr, EnumIter, EnumString, FromRepr, IntoStaticStr};
+
+/// The instructions understood by the Judith VM, as defined in the VM's `jasm/opcodes.hpp`. The
+/// string representation of each opcode is the mnemonic used in JASM text.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, IntoStaticStr, EnumIter, EnumString, FromRepr
+)]
+#[repr(u8)]
+pub enum OpCode {
+    #[strum(serialize = "NOOP")]
+    Noop = 0,
+
+    #[strum(serialize = "NATIVE")]
+    Native,
+
+    #[strum(serialize = "CONST")]
+    Const,
+    #[strum(serialize = "CONST_L")]
+    ConstL,
+    #[strum(serialize = "CONST_LL")]
+    ConstLL,
+    #[strum(serialize = "CONST_0")]
+    Const0,
+    #[strum(serialize = "F_CONST_1")]
+    FConst1,
+    #[strum(serialize = "F_CONST_2")]
+    FConst2,
+    #[strum(serialize = "I_CONST_1")]
+    IConst1,
+    #[strum(serialize = "I_CONST_2")]
+    IConst2,
+    #[strum(serialize = "STR_CONST")]
+    StrConst,
+    #[strum(serialize = "STR_CONST_L")]
+    StrConstL,
+
+    #[strum(serialize = "RET")]
+    Ret,
+
+    #[strum(serialize = "F_NEG")]
+    FNeg,
+    #[strum(serialize = "F_ADD")]
+    FAdd,
+    #[strum(serialize = "F_SUB")]
+    FSub,
+    #[strum(serialize = "F_MUL")]
+    FMul,
+    #[strum(serialize = "F_DIV")]
+    FDiv,
+    #[strum(serialize = "F_GT")]
+    FGt,
+    #[strum(serialize = "F_GE")]
+    FGe,
+    #[strum(serialize = "F_LT")]
+    FLt,
+    #[strum(serialize = "F_LE")]
+    FLe,
+
+    #[strum(serialize = "I_NEG")]
+    INeg,
+    #[strum(serialize = "I_ADD")]
+    IAdd,
+    #[strum(serialize = "I_ADD_CHECKED")]
+    IAddChecked,
+    #[strum(serialize = "I_SUB")]
+    ISub,
+    #[strum(serialize = "I_SUB_CHECKED")]
+    ISubChecked,
+    #[strum(serialize = "I_MUL")]
+    IMul,
+    #[strum(serialize = "I_MUL_CHECKED")]
+    IMulChecked,
+    #[strum(serialize = "I_DIV")]
+    IDiv,
+    #[strum(serialize = "I_DIV_CHECKED")]
+    IDivChecked,
+    #[strum(serialize = "I_GT")]
+    IGt,
+    #[strum(serialize = "I_GE")]
+    IGe,
+    #[strum(serialize = "I_LT")]
+    ILt,
+    #[strum(serialize = "I_LE")]
+    ILe,
+
+    #[strum(serialize = "EQ")]
+    Eq,
+    #[strum(serialize = "NEQ")]
+    Neq,
+
+    #[strum(serialize = "STORE_0")]
+    Store0,
+    #[strum(serialize = "STORE_1")]
+    Store1,
+    #[strum(serialize = "STORE_2")]
+    Store2,
+    #[strum(serialize = "STORE_3")]
+    Store3,
+    #[strum(serialize = "STORE_4")]
+    Store4,
+    #[strum(serialize = "STORE")]
+    Store,
+    #[strum(serialize = "STORE_L")]
+    StoreL,
+
+    #[strum(serialize = "LOAD_0")]
+    Load0,
+    #[strum(serialize = "LOAD_1")]
+    Load1,
+    #[strum(serialize = "LOAD_2")]
+    Load2,
+    #[strum(serialize = "LOAD_3")]
+    Load3,
+    #[strum(serialize = "LOAD_4")]
+    Load4,
+    #[strum(serialize = "LOAD")]
+    Load,
+    #[strum(serialize = "LOAD_L")]
+    LoadL,
+
+    #[strum(serialize = "POP")]
+    Pop,
+
+    #[strum(serialize = "JMP")]
+    Jmp,
+    #[strum(serialize = "JMP_L")]
+    JmpL,
+    #[strum(serialize = "JTRUE")]
+    JTrue,
+    #[strum(serialize = "JTRUE_L")]
+    JTrueL,
+    #[strum(serialize = "JTRUE_K")]
+    JTrueK,
+    #[strum(serialize = "JTRUE_K_L")]
+    JTrueKL,
+    #[strum(serialize = "JFALSE")]
+    JFalse,
+    #[strum(serialize = "JFALSE_L")]
+    JFalseL,
+    #[strum(serialize = "JFALSE_K")]
+    JFalseK,
+    #[strum(serialize = "JFALSE_K_L")]
+    JFalseKL,
+
+    #[strum(serialize = "CALL")]
+    Call,
+
+    #[strum(serialize = "PRINT")]
+    Print,
+}
+
+/// The kind of operand that follows an opcode in bytecode. All operands are little-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    None,
+    /// A value pushed as it appears in the bytecode, 1, 4 or 8 bytes long.
+    Immediate(usize),
+    /// An index into the block's string table, 1 or 4 bytes long.
+    String(usize),
+    /// The index of a local, 1 or 2 bytes long.
+    Local(usize),
+    /// A signed offset, relative to the end of the instruction, 1 or 4 bytes long.
+    Jump(usize),
+    /// An index into the assembly's function reference table.
+    FuncRef,
+    /// The address of a VM function. Only the VM itself creates these.
+    NativeAddress,
+    /// A `ConstantType`, describing how to print the value on top of the stack.
+    ConstantType,
+}
+
+impl OperandKind {
+    /// The size of this operand, in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            OperandKind::None => 0,
+            OperandKind::Immediate(size) => *size,
+            OperandKind::String(size) => *size,
+            OperandKind::Local(size) => *size,
+            OperandKind::Jump(size) => *size,
+            OperandKind::FuncRef => 4,
+            OperandKind::NativeAddress => 8,
+            OperandKind::ConstantType => 1,
+        }
+    }
+
+    /// Whether this operand is read as a signed integer.
+    pub fn is_signed(&self) -> bool {
+        match self {
+            OperandKind::Jump(_) => true,
+            OperandKind::String(size) => *size == 4,
+            _ => false,
+        }
+    }
+}
+
+impl OpCode {
+    pub fn mnemonic(&self) -> &'static str {
+        (*self).into()
+    }
+
+    pub fn operand_kind(&self) -> OperandKind {
+        match self {
+            OpCode::Native => OperandKind::NativeAddress,
+            OpCode::Const => OperandKind::Immediate(1),
+            OpCode::ConstL => OperandKind::Immediate(4),
+            OpCode::ConstLL => OperandKind::Immediate(8),
+            OpCode::StrConst => OperandKind::String(1),
+            OpCode::StrConstL => OperandKind::String(4),
+            OpCode::Store | OpCode::Load => OperandKind::Local(1),
+            OpCode::StoreL | OpCode::LoadL => OperandKind::Local(2),
+            OpCode::Jmp | OpCode::JTrue | OpCode::JTrueK | OpCode::JFalse | OpCode::JFalseK => {
+                OperandKind::Jump(1)
+            }
+            OpCode::JmpL
+            | OpCode::JTrueL
+            | OpCode::JTrueKL
+            | OpCode::JFalseL
+            | OpCode::JFalseKL => OperandKind::Jump(4),
+            OpCode::Call => OperandKind::FuncRef,
+            OpCode::Print => OperandKind::ConstantType,
+            _ => OperandKind::None,
+        }
+    }
+
+    /// The size of the instruction, including its operand, in bytes.
+    pub fn instruction_size(&self) -> usize {
+        1 + self.operand_kind().size()
+    }
+}
+
+/// The types of values the `PRINT` instruction knows how to print, as defined in the VM's
+/// `runtime/ConstantType.hpp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumString, FromRepr)]
+#[repr(u8)]
+pub enum ConstantType {
+    #[strum(serialize = "ERROR")]
+    Error = 0,
+    #[strum(serialize = "INT_64")]
+    Int64,
+    #[strum(serialize = "FLOAT_64")]
+    Float64,
+    #[strum(serialize = "UNSIGNED_INT_64")]
+    UnsignedInt64,
+    #[strum(serialize = "STRING_UTF8")]
+    StringUtf8,
+    #[strum(serialize = "BOOL")]
+    Bool,
+}
+
+/// A single decoded instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    /// The offset of the instruction in its chunk.
+    pub offset: usize,
+    pub opcode: OpCode,
+    /// The value of the operand, sign-extended when the operand is signed. Instructions without an
+    /// operand have 0 here.
+    pub operand: i64,
+}
+
+impl Instruction {
+    pub fn size(&self) -> usize {
+        self.opcode.instruction_size()
+    }
+
+    /// The offset of the instruction that comes after this one.
+    pub fn end(&self) -> usize {
+        self.offset + self.size()
+    }
+
+    /// The offset this instruction jumps to, if it's a jump. This offset may be outside the chunk.
+    pub fn jump_target(&self) -> Option<i64> {
+        match self.opcode.operand_kind() {
+            OperandKind::Jump(_) => Some(self.end() as i64 + self.operand),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the instruction at the offset given. Returns `None` if the byte at that offset isn't a
+/// valid opcode, or if its operand doesn't fit in the chunk.
+pub fn decode_instruction(code: &[u8], offset: usize) -> Option<Instruction> {
+    let opcode = OpCode::from_repr(*code.get(offset)?)?;
+    let kind = opcode.operand_kind();
+    let bytes = code.get(offset + 1..offset + 1 + kind.size())?;
+
+    let mut raw = [0u8; 8];
+    raw[..bytes.len()].copy_from_slice(bytes);
+    let mut operand = i64::from_le_bytes(raw);
+
+    // Sign-extend signed operands.
+    if kind.is_signed() && !bytes.is_empty() && bytes.len() < 8 {
+        let shift = 64 - bytes.len() * 8;
+        operand = (operand << shift) >> shift;
+    }
+
+    Some(Instruction { offset, opcode, operand })
+}
+
+/// Writes the instruction given at the end of the chunk. The operand is truncated to the size of
+/// the operand the opcode takes.
+pub fn encode_instruction(code: &mut Vec<u8>, opcode: OpCode, operand: i64) {
+    code.push(opcode as u8);
+
+    let size = opcode.operand_kind().size();
+    code.extend_from_slice(&operand.to_le_bytes()[..size]);
+}
+
+/// Returns whether the value given can be encoded in an operand of the kind given without losing
+/// information.
+pub fn operand_fits(kind: OperandKind, val: i64) -> bool {
+    let size = kind.size();
+    if size == 0 {
+        return val == 0;
+    }
+    if size == 8 {
+        return true;
+    }
+
+    let bits = size as u32 * 8;
+    if kind.is_signed() {
+        let min = -(1i64 << (bits - 1));
+        let max = (1i64 << (bits - 1)) - 1;
+        (min..=max).contains(&val)
+    }
+    else {
+        (0..(1i64 << bits)).contains(&val)
+    }
+}
@@ -0,0 +1,603 @@
+use std::collections::HashSet;
+use crate::judith::compiler_messages;
+use crate::judith::compiler_messages::MessageContainer;
+use crate::judith::jasm::assembly::{Assembly, Function};
+use crate::judith::jasm::opcodes::{decode_instruction, encode_instruction, operand_fits, OpCode, OperandKind};
+
+pub struct OptimizerResult {
+    pub messages: MessageContainer,
+}
+
+/// An instruction whose jump target, if any, is the index of another instruction rather than a
+/// relative offset. An index equal to the amount of instructions points to the end of the chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Op {
+    opcode: OpCode,
+    operand: i64,
+    target: Option<usize>,
+}
+
+/// Optimizes every function in the assembly given, reporting how much each one shrank.
+pub fn optimize(assembly: &mut Assembly) -> OptimizerResult {
+    let mut messages = MessageContainer::new();
+
+    for block in &mut assembly.blocks {
+        for (f, func) in block.functions.iter_mut().enumerate() {
+            let name = match block.string_table.get(func.name_index as usize) {
+                Some(name) => name.to_string(),
+                None => format!("#{}", f),
+            };
+
+            let old_size = func.code.len();
+            if optimize_function(func) {
+                messages.add(compiler_messages::Optimizer::function_optimized(
+                    &name, old_size, func.code.len()
+                ));
+            }
+            else {
+                messages.add(compiler_messages::Optimizer::function_not_optimized(&name));
+            }
+        }
+    }
+
+    OptimizerResult { messages }
+}
+
+/// Rewrites the function's code to a smaller, equivalent version. Returns `false`, leaving the
+/// function untouched, if its code can't be decoded or contains jumps that don't land on an
+/// instruction.
+pub fn optimize_function(func: &mut Function) -> bool {
+    let Some(mut ops) = decode_ops(&func.code) else {
+        return false;
+    };
+
+    loop {
+        let mut changed = thread_jumps(&mut ops);
+        changed |= remove_dead_code(&mut ops);
+        changed |= fold_store_load(&mut ops);
+
+        if !changed {
+            break;
+        }
+    }
+
+    for op in &mut ops {
+        shorten(op);
+    }
+
+    func.code = encode_ops(&ops);
+    true
+}
+
+fn decode_ops(code: &[u8]) -> Option<Vec<Op>> {
+    let mut instructions = Vec::new();
+    let mut offset = 0;
+
+    while offset < code.len() {
+        let instr = decode_instruction(code, offset)?;
+        offset = instr.end();
+        instructions.push(instr);
+    }
+
+    let index_of = |target: i64| -> Option<usize> {
+        if target == code.len() as i64 {
+            return Some(instructions.len());
+        }
+        instructions.binary_search_by_key(&target, |i| i.offset as i64).ok()
+    };
+
+    instructions.iter().map(|instr| {
+        let target = match instr.jump_target() {
+            Some(target) => Some(index_of(target)?),
+            None => None,
+        };
+
+        Some(Op { opcode: instr.opcode, operand: instr.operand, target })
+    }).collect()
+}
+
+/// Whether execution never continues to the next instruction after the op given.
+fn is_terminal(op: &Op) -> bool {
+    matches!(op.opcode, OpCode::Ret | OpCode::Jmp | OpCode::JmpL)
+}
+
+/// Makes jumps that land on an unconditional jump go directly to that jump's target.
+fn thread_jumps(ops: &mut [Op]) -> bool {
+    let mut changed = false;
+
+    for i in 0..ops.len() {
+        let Some(mut target) = ops[i].target else {
+            continue;
+        };
+
+        // Jumps can form cycles, so no chain is followed for longer than the amount of ops.
+        let mut steps = 0;
+        while let Some(next) = ops.get(target)
+            && matches!(next.opcode, OpCode::Jmp | OpCode::JmpL)
+            && let Some(next_target) = next.target
+            && next_target != target
+            && steps < ops.len()
+        {
+            target = next_target;
+            steps += 1;
+        }
+
+        if ops[i].target != Some(target) {
+            ops[i].target = Some(target);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Removes every op that can't be reached from the start of the function, as well as
+/// unconditional jumps to the op right after them.
+fn remove_dead_code(ops: &mut Vec<Op>) -> bool {
+    let mut reachable = vec![false; ops.len()];
+    let mut pending = vec![0];
+
+    while let Some(i) = pending.pop() {
+        if i >= ops.len() || reachable[i] {
+            continue;
+        }
+        reachable[i] = true;
+
+        if let Some(target) = ops[i].target {
+            pending.push(target);
+        }
+        if !is_terminal(&ops[i]) {
+            pending.push(i + 1);
+        }
+    }
+
+    for (i, op) in ops.iter().enumerate() {
+        if matches!(op.opcode, OpCode::Jmp | OpCode::JmpL) && op.target == Some(i + 1) {
+            reachable[i] = false;
+        }
+    }
+
+    remove_ops(ops, &reachable)
+}
+
+/// Removes `STORE n; LOAD n` pairs when local `n` is only ever read by the `LOAD` in those pairs.
+/// The value is left on the stack instead, and the local is never observed without it.
+fn fold_store_load(ops: &mut Vec<Op>) -> bool {
+    let targets: HashSet<usize> = ops.iter().filter_map(|op| op.target).collect();
+
+    let mut pairs = Vec::new();
+    let mut paired_loads: Vec<(u16, usize)> = Vec::new();
+    for i in 1..ops.len() {
+        if let Some(stored) = stored_local(&ops[i - 1])
+            && loaded_local(&ops[i]) == Some(stored)
+            && !targets.contains(&i)
+        {
+            pairs.push((stored, i - 1));
+            paired_loads.push((stored, i));
+        }
+    }
+
+    let mut keep = vec![true; ops.len()];
+    let mut changed = false;
+    for (local, store) in pairs {
+        let loads = ops.iter().filter(|op| loaded_local(op) == Some(local)).count();
+        let paired = paired_loads.iter().filter(|(l, _)| *l == local).count();
+
+        if loads == paired {
+            keep[store] = false;
+            keep[store + 1] = false;
+            changed = true;
+        }
+    }
+
+    if changed {
+        remove_ops(ops, &keep);
+    }
+    changed
+}
+
+/// Removes the ops that aren't marked to be kept. Jumps to removed ops are redirected to the
+/// next op that is kept. Returns whether any op was removed.
+fn remove_ops(ops: &mut Vec<Op>, keep: &[bool]) -> bool {
+    if keep.iter().all(|k| *k) {
+        return false;
+    }
+
+    // new_index[i] is the index, after removal, of the first kept op at or after i.
+    let mut new_index = vec![0; ops.len() + 1];
+    let mut count = 0;
+    for i in 0..ops.len() {
+        new_index[i] = count;
+        if keep[i] {
+            count += 1;
+        }
+    }
+    new_index[ops.len()] = count;
+
+    let mut i = 0;
+    ops.retain(|_| {
+        i += 1;
+        keep[i - 1]
+    });
+
+    for op in ops.iter_mut() {
+        if let Some(target) = op.target {
+            op.target = Some(new_index[target]);
+        }
+    }
+
+    true
+}
+
+fn stored_local(op: &Op) -> Option<u16> {
+    match op.opcode {
+        OpCode::Store0 => Some(0),
+        OpCode::Store1 => Some(1),
+        OpCode::Store2 => Some(2),
+        OpCode::Store3 => Some(3),
+        OpCode::Store4 => Some(4),
+        OpCode::Store | OpCode::StoreL => Some(op.operand as u16),
+        _ => None,
+    }
+}
+
+fn loaded_local(op: &Op) -> Option<u16> {
+    match op.opcode {
+        OpCode::Load0 => Some(0),
+        OpCode::Load1 => Some(1),
+        OpCode::Load2 => Some(2),
+        OpCode::Load3 => Some(3),
+        OpCode::Load4 => Some(4),
+        OpCode::Load | OpCode::LoadL => Some(op.operand as u16),
+        _ => None,
+    }
+}
+
+/// Replaces the op with the shortest instruction that has the same effect. Jumps are made short
+/// here, and made long again later if their offset doesn't fit.
+fn shorten(op: &mut Op) {
+    let (opcode, operand) = match op.opcode {
+        OpCode::Const | OpCode::ConstL | OpCode::ConstLL | OpCode::Const0 => {
+            shortest_constant(constant_value(op))
+        }
+        OpCode::Store | OpCode::StoreL => shortest_local(op.operand, [
+            OpCode::Store0, OpCode::Store1, OpCode::Store2, OpCode::Store3, OpCode::Store4,
+        ], OpCode::Store, OpCode::StoreL),
+        OpCode::Load | OpCode::LoadL => shortest_local(op.operand, [
+            OpCode::Load0, OpCode::Load1, OpCode::Load2, OpCode::Load3, OpCode::Load4,
+        ], OpCode::Load, OpCode::LoadL),
+        OpCode::StrConst | OpCode::StrConstL => {
+            if operand_fits(OperandKind::String(1), op.operand) {
+                (OpCode::StrConst, op.operand)
+            }
+            else {
+                (OpCode::StrConstL, op.operand)
+            }
+        }
+        OpCode::JmpL => (OpCode::Jmp, 0),
+        OpCode::JTrueL => (OpCode::JTrue, 0),
+        OpCode::JTrueKL => (OpCode::JTrueK, 0),
+        OpCode::JFalseL => (OpCode::JFalse, 0),
+        OpCode::JFalseKL => (OpCode::JFalseK, 0),
+        _ => return,
+    };
+
+    op.opcode = opcode;
+    op.operand = operand;
+}
+
+/// The 64 bits a constant instruction pushes to the stack, for the instructions whose value is
+/// known exactly. `CONST` and `CONST_L` push their operand as it appears in the bytecode.
+fn constant_value(op: &Op) -> i64 {
+    match op.opcode {
+        OpCode::Const0 => 0,
+        _ => op.operand,
+    }
+}
+
+/// Finds the shortest instruction that pushes the 64 bits given. `F_CONST_1` and `F_CONST_2` are
+/// used when the bits are those of 1.0 and 2.0. `CONST_L` is only used for non-negative values
+/// that fit in a signed 32-bit integer, as those don't depend on how the VM extends the operand.
+fn shortest_constant(bits: i64) -> (OpCode, i64) {
+    if bits == 0 {
+        (OpCode::Const0, 0)
+    }
+    else if bits == 1 {
+        (OpCode::IConst1, 0)
+    }
+    else if bits == 2 {
+        (OpCode::IConst2, 0)
+    }
+    else if bits == 1f64.to_bits() as i64 {
+        (OpCode::FConst1, 0)
+    }
+    else if bits == 2f64.to_bits() as i64 {
+        (OpCode::FConst2, 0)
+    }
+    else if (0..=u8::MAX as i64).contains(&bits) {
+        (OpCode::Const, bits)
+    }
+    else if (0..=i32::MAX as i64).contains(&bits) {
+        (OpCode::ConstL, bits)
+    }
+    else {
+        (OpCode::ConstLL, bits)
+    }
+}
+
+fn shortest_local(index: i64, short: [OpCode; 5], byte: OpCode, long: OpCode) -> (OpCode, i64) {
+    match usize::try_from(index).ok().and_then(|i| short.get(i)) {
+        Some(opcode) => (*opcode, 0),
+        None if operand_fits(byte.operand_kind(), index) => (byte, index),
+        None => (long, index),
+    }
+}
+
+fn long_jump(opcode: OpCode) -> OpCode {
+    match opcode {
+        OpCode::Jmp => OpCode::JmpL,
+        OpCode::JTrue => OpCode::JTrueL,
+        OpCode::JTrueK => OpCode::JTrueKL,
+        OpCode::JFalse => OpCode::JFalseL,
+        OpCode::JFalseK => OpCode::JFalseKL,
+        _ => opcode,
+    }
+}
+
+/// Encodes the ops given, using short jumps wherever their offset fits in a byte. Making a jump
+/// long moves the ops after it, which may push other jumps out of range, so sizes are
+/// recalculated until no more jumps need to grow.
+fn encode_ops(ops: &[Op]) -> Vec<u8> {
+    let mut opcodes: Vec<OpCode> = ops.iter().map(|op| op.opcode).collect();
+
+    let offsets = loop {
+        let mut offsets = Vec::with_capacity(ops.len() + 1);
+        let mut offset = 0;
+        for opcode in &opcodes {
+            offsets.push(offset);
+            offset += opcode.instruction_size();
+        }
+        offsets.push(offset);
+
+        let mut grown = false;
+        for (i, op) in ops.iter().enumerate() {
+            let Some(target) = op.target else {
+                continue;
+            };
+
+            let relative = offsets[target] as i64 - offsets[i + 1] as i64;
+            if !operand_fits(opcodes[i].operand_kind(), relative) {
+                opcodes[i] = long_jump(opcodes[i]);
+                grown = true;
+            }
+        }
+
+        if !grown {
+            break offsets;
+        }
+    };
+
+    let mut code = Vec::with_capacity(offsets[ops.len()]);
+    for (i, op) in ops.iter().enumerate() {
+        let operand = match op.target {
+            Some(target) => offsets[target] as i64 - offsets[i + 1] as i64,
+            None => op.operand,
+        };
+        encode_instruction(&mut code, opcodes[i], operand);
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::jasm::assembler::assemble;
+    use crate::judith::jasm::disassembler::disassemble;
+    use crate::judith::jasm::verifier::verify;
+    use super::*;
+
+    fn assemble_func(code: &str) -> Assembly {
+        let src = format!(r#"
+            .names
+                "main"
+            .end
+            .block 0
+            .strings
+                "main"
+                "a"
+            .end
+            .func 0
+                .params {}
+            .end
+            .end
+        "#, code);
+
+        assemble(&src).unwrap()
+    }
+
+    /// Optimizes the first function of `src` and checks that it's equal to the first function of
+    /// `expected`.
+    fn assert_optimizes_to(src: &str, expected: &str) {
+        let mut assembly = assemble_func(src);
+        let expected = assemble_func(expected);
+
+        let res = optimize(&mut assembly);
+        res.messages.dump_all();
+        println!("{}", disassemble(&assembly));
+
+        assert_eq!(assembly.blocks[0].functions[0], expected.blocks[0].functions[0]);
+    }
+
+    #[test]
+    fn shortest_encodings () {
+        assert_optimizes_to(r#"
+                .max_locals 8
+                .max_stack 8
+                CONST_LL 0x0
+                CONST_LL 0x1
+                CONST_L 0x2
+                CONST_LL 0x3ff0000000000000
+                CONST_LL 2.0
+                CONST_LL 0xc8
+                CONST_LL 0x10000
+                CONST_LL 0xffffffff
+                LOAD_L 3
+                STORE 7
+                STR_CONST_L 1
+                RET
+            "#, r#"
+                .max_locals 8
+                .max_stack 8
+                CONST_0
+                I_CONST_1
+                I_CONST_2
+                F_CONST_1
+                F_CONST_2
+                CONST 0xc8
+                CONST_L 0x00010000
+                CONST_LL 0xffffffff
+                LOAD_3
+                STORE 7
+                STR_CONST 1
+                RET
+            "#);
+    }
+
+    #[test]
+    fn store_load_folding () {
+        // Local 0 is only read right after being stored, local 1 is read again later.
+        assert_optimizes_to(r#"
+                .max_locals 2
+                .max_stack 2
+                F_CONST_1
+                STORE_0
+                LOAD_0
+                F_CONST_2
+                STORE_1
+                LOAD_1
+                F_ADD
+                LOAD_1
+                F_ADD
+                PRINT FLOAT_64
+                RET
+            "#, r#"
+                .max_locals 2
+                .max_stack 2
+                F_CONST_1
+                F_CONST_2
+                STORE_1
+                LOAD_1
+                F_ADD
+                LOAD_1
+                F_ADD
+                PRINT FLOAT_64
+                RET
+            "#);
+
+        // A load that can be jumped to may run without the store before it.
+        let src = r#"
+                .max_locals 1
+                .max_stack 1
+                F_CONST_1
+                STORE_0
+            again:
+                LOAD_0
+                JTRUE again
+                RET
+            "#;
+        assert_optimizes_to(src, src);
+    }
+
+    #[test]
+    fn dead_code () {
+        assert_optimizes_to(r#"
+                .max_stack 1
+                JMP_L skip
+                F_CONST_1
+                PRINT FLOAT_64
+            skip:
+                RET
+                F_CONST_2
+                PRINT FLOAT_64
+                RET
+            "#, r#"
+                .max_stack 1
+                RET
+            "#);
+    }
+
+    #[test]
+    fn jump_threading () {
+        assert_optimizes_to(r#"
+                .max_stack 1
+                I_CONST_1
+                JTRUE_L first
+                RET
+            first:
+                JMP_L second
+            second:
+                JMP_L end
+            end:
+                RET
+            "#, r#"
+                .max_stack 1
+                I_CONST_1
+                JTRUE end
+                RET
+            end:
+                RET
+            "#);
+
+        // Jumps that form a cycle stay a cycle.
+        assert_optimizes_to(r#"
+            start:
+                JMP other
+            other:
+                JMP start
+            "#, r#"
+            start:
+                JMP start
+            "#);
+    }
+
+    #[test]
+    fn long_jumps () {
+        let padding = "    NOOP\n".repeat(200);
+        let src = format!(r#"
+                .max_stack 1
+                I_CONST_1
+                JFALSE_L far
+                I_CONST_1
+                JTRUE_L near
+            near:
+                {}
+            far:
+                RET
+            "#, padding);
+
+        let mut assembly = assemble_func(&src);
+        let res = optimize(&mut assembly);
+        let func = &assembly.blocks[0].functions[0];
+        println!("{}", disassemble(&assembly));
+
+        assert_eq!(func.code.len(), 1 + 5 + 1 + 2 + 200 + 1);
+        assert_eq!(func.code[1], OpCode::JFalseL as u8);
+        assert_eq!(func.code[7], OpCode::JTrue as u8);
+        assert_eq!(verify(&assembly), Ok(()));
+        assert_eq!(res.messages.infos.len(), 1);
+        assert_eq!(res.messages.infos[0].message, "Function 'main': 213 -> 210 bytes (saved 3).");
+    }
+
+    #[test]
+    fn malformed_functions () {
+        let mut assembly = assemble_func(r#"
+                JMP 2
+                RET
+            "#);
+        let original = assembly.clone();
+
+        let res = optimize(&mut assembly);
+        assert_eq!(assembly, original);
+        assert_eq!(res.messages.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt::{Display, Formatter};
+use crate::judith::jasm::assembly::{Assembly, Block, Function, ItemRef, StringTable, Version, MAGIC_NUMBER};
+
+/// An error found while reading a .jdll file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadError {
+    /// The offset, in bytes, where the error was found.
+    pub offset: usize,
+    /// The part of the file being read when the error was found (e.g. "block #0 > function #2 >
+    /// code_length").
+    pub context: String,
+    pub kind: ReadErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadErrorKind {
+    /// The file doesn't start with "JUDITH".
+    InvalidMagicNumber,
+    /// The endianness byte isn't 0 (little-endian), the only endianness supported.
+    UnsupportedEndianness(u8),
+    /// The file ended before the value being read was complete.
+    UnexpectedEnd,
+    /// An item reference has a type other than internal, native or external.
+    InvalidRefType(u32),
+    /// A string in a string table isn't valid UTF-8.
+    InvalidUtf8,
+    /// The size declared by a string table doesn't match the size of its strings.
+    TableSizeMismatch { declared: u32, actual: usize },
+    /// The file declares dependencies, which aren't supported yet.
+    UnsupportedDependencies(u32),
+    /// A block declares types, which aren't supported yet.
+    UnsupportedTypes(u32),
+    /// There's data after the last block.
+    TrailingBytes(usize),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ReadErrorKind::InvalidMagicNumber => write!(f, "Invalid magic number")?,
+            ReadErrorKind::UnsupportedEndianness(e) => write!(f, "Unsupported endianness: {}", e)?,
+            ReadErrorKind::UnexpectedEnd => write!(f, "Unexpected end of file")?,
+            ReadErrorKind::InvalidRefType(t) => write!(f, "Invalid reference type: {}", t)?,
+            ReadErrorKind::InvalidUtf8 => write!(f, "String is not valid UTF-8")?,
+            ReadErrorKind::TableSizeMismatch { declared, actual } => write!(
+                f, "String table declares a size of {} bytes, but its strings take {}",
+                declared, actual
+            )?,
+            ReadErrorKind::UnsupportedDependencies(count) => {
+                write!(f, "Dependencies are not supported (found {})", count)?
+            }
+            ReadErrorKind::UnsupportedTypes(count) => {
+                write!(f, "Types are not supported (found {})", count)?
+            }
+            ReadErrorKind::TrailingBytes(count) => {
+                write!(f, "Found {} unexpected byte(s) after the last block", count)?
+            }
+        }
+
+        write!(f, " at offset 0x{:04x} ({}).", self.offset, self.context)
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Reads the .jdll file given. Files with anything the VM wouldn't be able to load (including
+/// features that aren't supported yet) are rejected.
+pub fn read_assembly(bytes: &[u8]) -> Result<Assembly, ReadError> {
+    let mut reader = Reader { bytes, cursor: 0, context: Vec::new() };
+
+    reader.enter("header");
+    let magic_number = reader.read_bytes(MAGIC_NUMBER.len(), "magic_number")?;
+    if magic_number != MAGIC_NUMBER {
+        return Err(reader.error_at(0, ReadErrorKind::InvalidMagicNumber));
+    }
+
+    let start = reader.cursor;
+    let endianness = reader.read_u8("endianness")?;
+    if endianness != 0 {
+        return Err(reader.error_at(start, ReadErrorKind::UnsupportedEndianness(endianness)));
+    }
+
+    let judith_version = reader.read_u32("judith_version")?;
+    let version = Version {
+        major: reader.read_u16("version.major")?,
+        minor: reader.read_u16("version.minor")?,
+        patch: reader.read_u16("version.patch")?,
+        build: reader.read_u16("version.build")?,
+    };
+    reader.exit();
+
+    reader.enter("name_table");
+    let name_table = reader.read_string_table()?;
+    reader.exit();
+
+    let start = reader.cursor;
+    let dep_count = reader.read_u32("dep_count")?;
+    if dep_count != 0 {
+        return Err(reader.error_at(start, ReadErrorKind::UnsupportedDependencies(dep_count)));
+    }
+
+    reader.enter("type_ref_table");
+    let type_refs = reader.read_ref_table()?;
+    reader.exit();
+
+    reader.enter("func_ref_table");
+    let func_refs = reader.read_ref_table()?;
+    reader.exit();
+
+    let block_count = reader.read_u32("block_count")?;
+    let mut blocks = Vec::new();
+    for b in 0..block_count {
+        reader.enter(&format!("block #{}", b));
+        blocks.push(reader.read_block()?);
+        reader.exit();
+    }
+
+    let remaining = bytes.len() - reader.cursor;
+    if remaining != 0 {
+        return Err(reader.error_at(reader.cursor, ReadErrorKind::TrailingBytes(remaining)));
+    }
+
+    Ok(Assembly {
+        judith_version,
+        version,
+        name_table,
+        type_refs,
+        func_refs,
+        blocks,
+    })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+    /// The parts of the file being read, from outermost to innermost.
+    context: Vec<String>,
+}
+
+impl<'a> Reader<'a> {
+    fn enter(&mut self, context: &str) {
+        self.context.push(context.to_string());
+    }
+
+    fn exit(&mut self) {
+        self.context.pop();
+    }
+
+    fn error_at(&self, offset: usize, kind: ReadErrorKind) -> ReadError {
+        ReadError {
+            offset,
+            context: self.context.join(" > "),
+            kind,
+        }
+    }
+
+    /// Reads the amount of bytes given. `field` is the name of the value being read, used to
+    /// report errors.
+    fn read_bytes(&mut self, count: usize, field: &str) -> Result<&'a [u8], ReadError> {
+        let end = self.cursor.checked_add(count).filter(|end| *end <= self.bytes.len());
+        let Some(end) = end else {
+            self.enter(field);
+            let err = self.error_at(self.cursor, ReadErrorKind::UnexpectedEnd);
+            self.exit();
+            return Err(err);
+        };
+
+        let bytes = &self.bytes[self.cursor..end];
+        self.cursor = end;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self, field: &str) -> Result<u8, ReadError> {
+        Ok(self.read_bytes(1, field)?[0])
+    }
+
+    fn read_u16(&mut self, field: &str) -> Result<u16, ReadError> {
+        let bytes = self.read_bytes(2, field)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self, field: &str) -> Result<u32, ReadError> {
+        let bytes = self.read_bytes(4, field)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u64(&mut self, field: &str) -> Result<u64, ReadError> {
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(self.read_bytes(8, field)?);
+        Ok(u64::from_le_bytes(raw))
+    }
+
+    fn read_string_table(&mut self) -> Result<StringTable, ReadError> {
+        let size_offset = self.cursor;
+        let declared_size = self.read_u32("table_size")?;
+        let count = self.read_u32("string_count")?;
+        let table_start = self.cursor;
+
+        let mut strings = Vec::new();
+        for i in 0..count {
+            self.enter(&format!("string #{}", i));
+
+            let len = self.read_u64("size")?;
+            let start = self.cursor;
+            let bytes = self.read_bytes(usize::try_from(len).unwrap_or(usize::MAX), "bytes")?;
+
+            match std::str::from_utf8(bytes) {
+                Ok(s) => strings.push(s.to_string()),
+                Err(err) => {
+                    let offset = start + err.valid_up_to();
+                    return Err(self.error_at(offset, ReadErrorKind::InvalidUtf8));
+                }
+            }
+
+            self.exit();
+        }
+
+        let actual = self.cursor - table_start;
+        if declared_size as usize != actual {
+            return Err(self.error_at(
+                size_offset, ReadErrorKind::TableSizeMismatch { declared: declared_size, actual }
+            ));
+        }
+
+        Ok(StringTable::new(strings))
+    }
+
+    fn read_ref_table(&mut self) -> Result<Vec<ItemRef>, ReadError> {
+        let count = self.read_u32("ref_count")?;
+
+        let mut table = Vec::new();
+        for i in 0..count {
+            self.enter(&format!("ref #{}", i));
+
+            let start = self.cursor;
+            let item_ref = match self.read_u32("ref_type")? {
+                ItemRef::TYPE_INTERNAL => ItemRef::Internal {
+                    block: self.read_u32("block")?,
+                    index: self.read_u32("index")?,
+                },
+                ItemRef::TYPE_NATIVE => ItemRef::Native {
+                    index: self.read_u32("index")?,
+                },
+                ItemRef::TYPE_EXTERNAL => ItemRef::External {
+                    block_name: self.read_u32("block_name")?,
+                    item_name: self.read_u32("item_name")?,
+                },
+                ref_type => {
+                    return Err(self.error_at(start, ReadErrorKind::InvalidRefType(ref_type)));
+                }
+            };
+
+            table.push(item_ref);
+            self.exit();
+        }
+
+        Ok(table)
+    }
+
+    fn read_block(&mut self) -> Result<Block, ReadError> {
+        let name_index = self.read_u32("block_name")?;
+
+        self.enter("string_table");
+        let string_table = self.read_string_table()?;
+        self.exit();
+
+        let start = self.cursor;
+        let type_count = self.read_u32("type_count")?;
+        if type_count != 0 {
+            return Err(self.error_at(start, ReadErrorKind::UnsupportedTypes(type_count)));
+        }
+
+        let func_count = self.read_u32("func_count")?;
+        let mut functions = Vec::new();
+        for f in 0..func_count {
+            self.enter(&format!("function #{}", f));
+            functions.push(self.read_function()?);
+            self.exit();
+        }
+
+        Ok(Block {
+            name_index,
+            string_table,
+            functions,
+        })
+    }
+
+    fn read_function(&mut self) -> Result<Function, ReadError> {
+        let name_index = self.read_u32("name")?;
+
+        let param_count = self.read_u16("param_count")?;
+        let mut params = Vec::new();
+        for p in 0..param_count {
+            params.push(self.read_u32(&format!("param #{}", p))?);
+        }
+
+        let max_locals = self.read_u16("max_locals")?;
+        let max_stack = self.read_u16("max_stack")?;
+
+        let code_length = self.read_u32("code_length")?;
+        let code = self.read_bytes(code_length as usize, "code")?.to_vec();
+
+        Ok(Function {
+            name_index,
+            params,
+            max_locals,
+            max_stack,
+            code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::jasm::assembly::ItemRef;
+    use super::*;
+
+    fn sample_assembly() -> Assembly {
+        Assembly {
+            name_table: StringTable::new(vec![String::from("main")]),
+            func_refs: vec![ItemRef::Internal { block: 0, index: 0 }],
+            blocks: vec![Block {
+                name_index: 0,
+                string_table: StringTable::new(vec![String::from("main")]),
+                functions: vec![Function {
+                    code: vec![0x0c], // RET
+                    ..Function::default()
+                }],
+            }],
+            ..Assembly::default()
+        }
+    }
+
+    #[test]
+    fn truncated_files () {
+        let bytes = sample_assembly().to_bytes();
+
+        // Every prefix of a valid file must be rejected, and never panic.
+        for len in 0..bytes.len() {
+            let err = read_assembly(&bytes[..len]).unwrap_err();
+            assert!(err.offset <= len);
+        }
+
+        // The last byte is the function's code.
+        let err = read_assembly(&bytes[..bytes.len() - 1]).unwrap_err();
+        println!("{}", err);
+        assert_eq!(err.kind, ReadErrorKind::UnexpectedEnd);
+        assert_eq!(err.offset, bytes.len() - 1);
+        assert_eq!(err.context, "block #0 > function #0 > code");
+    }
+
+    #[test]
+    fn malformed_files () {
+        let bytes = sample_assembly().to_bytes();
+
+        let mut bad = bytes.clone();
+        bad[0] = b'X';
+        assert_eq!(read_assembly(&bad).unwrap_err().kind, ReadErrorKind::InvalidMagicNumber);
+
+        let mut bad = bytes.clone();
+        bad.push(0);
+        let err = read_assembly(&bad).unwrap_err();
+        assert_eq!(err.kind, ReadErrorKind::TrailingBytes(1));
+        assert_eq!(err.offset, bytes.len());
+
+        // The name table starts right after the header (6 + 1 + 4 + 8 bytes).
+        let mut bad = bytes.clone();
+        bad[19] += 1;
+        let err = read_assembly(&bad).unwrap_err();
+        assert_eq!(err.kind, ReadErrorKind::TableSizeMismatch { declared: 13, actual: 12 });
+        assert_eq!(err.offset, 19);
+
+        // The first character of "main" in the name table.
+        let mut bad = bytes.clone();
+        bad[19 + 8 + 8] = 0xff;
+        let err = read_assembly(&bad).unwrap_err();
+        assert_eq!(err.kind, ReadErrorKind::InvalidUtf8);
+        assert_eq!(err.offset, 35);
+        assert_eq!(err.context, "name_table > string #0");
+
+        // The ref type of the first function reference.
+        let offset = 19 + 8 + 12 + 4 + 4 + 4;
+        let mut bad = bytes.clone();
+        bad[offset] = 7;
+        let err = read_assembly(&bad).unwrap_err();
+        assert_eq!(err.kind, ReadErrorKind::InvalidRefType(7));
+        assert_eq!(err.offset, offset);
+        assert_eq!(err.context, "func_ref_table > ref #0");
+    }
+}
@@ -0,0 +1,817 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use crate::judith::jasm::assembly::{Assembly, Block, Function, ItemRef};
+use crate::judith::jasm::opcodes::{decode_instruction, ConstantType, Instruction, OpCode, OperandKind};
+use crate::judith::jasm::reader::{read_assembly, ReadError};
+
+/// A problem found in an assembly that would make the VM misbehave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    pub location: Location,
+    pub kind: VerifyErrorKind,
+}
+
+/// The place in an assembly where a problem was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    TypeRef(usize),
+    FuncRef(usize),
+    Block(usize),
+    Function { block: usize, function: usize },
+    Instruction { block: usize, function: usize, offset: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyErrorKind {
+    /// An index into the assembly's name table is out of range.
+    NameIndexOutOfRange { index: u32, count: usize },
+    /// An index into a block's string table is out of range.
+    StringIndexOutOfRange { index: i64, count: usize },
+    /// An internal reference points to a block or item that doesn't exist.
+    DanglingInternalRef { block: u32, index: u32 },
+    /// A function reference index is out of range.
+    FuncRefOutOfRange { index: i64, count: usize },
+    /// A byte that should be an opcode isn't one.
+    InvalidOpcode(u8),
+    /// The last instruction's operand goes beyond the end of the chunk.
+    TruncatedInstruction,
+    /// A jump points outside the chunk.
+    JumpOutOfBounds { target: i64 },
+    /// A jump points to the middle of an instruction.
+    JumpIntoInstruction { target: usize },
+    /// A local index isn't smaller than `max_locals`.
+    LocalOutOfRange { index: i64, max_locals: u16 },
+    /// `PRINT` received a value that isn't a `ConstantType`.
+    InvalidConstantType(i64),
+    /// An instruction pops more values than there are in the stack.
+    StackUnderflow { depth: usize, needed: usize },
+    /// The stack grows beyond `max_stack`.
+    StackOverflow { depth: usize, max_stack: u16 },
+    /// The instruction can be reached with different stack depths.
+    InconsistentStackDepth { first: usize, second: usize },
+    /// Different `RET`s in the function leave a different amount of values on the stack.
+    InconsistentReturn { first: usize, second: usize },
+    /// Execution can reach the end of the chunk without returning.
+    FallsOffEnd,
+}
+
+/// The error produced when loading an assembly from its binary form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    Read(ReadError),
+    Verify(Vec<VerifyError>),
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::TypeRef(i) => write!(f, "type ref #{}", i),
+            Location::FuncRef(i) => write!(f, "func ref #{}", i),
+            Location::Block(b) => write!(f, "block #{}", b),
+            Location::Function { block, function } => {
+                write!(f, "block #{} > function #{}", block, function)
+            }
+            Location::Instruction { block, function, offset } => {
+                write!(f, "block #{} > function #{} > 0x{:04x}", block, function, offset)
+            }
+        }
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] ", self.location)?;
+
+        match &self.kind {
+            VerifyErrorKind::NameIndexOutOfRange { index, count } => write!(
+                f, "Name index {} is out of range (the name table has {} names).", index, count
+            ),
+            VerifyErrorKind::StringIndexOutOfRange { index, count } => write!(
+                f, "String index {} is out of range (the string table has {} strings).",
+                index, count
+            ),
+            VerifyErrorKind::DanglingInternalRef { block, index } => write!(
+                f, "Internal reference points to item #{} in block #{}, which doesn't exist.",
+                index, block
+            ),
+            VerifyErrorKind::FuncRefOutOfRange { index, count } => write!(
+                f, "Function reference {} is out of range (there are {} references).", index, count
+            ),
+            VerifyErrorKind::InvalidOpcode(op) => write!(f, "Invalid opcode 0x{:02x}.", op),
+            VerifyErrorKind::TruncatedInstruction => {
+                write!(f, "Instruction is cut off by the end of the chunk.")
+            }
+            VerifyErrorKind::JumpOutOfBounds { target } => {
+                write!(f, "Jump target {} is outside the chunk.", target)
+            }
+            VerifyErrorKind::JumpIntoInstruction { target } => write!(
+                f, "Jump target 0x{:04x} is in the middle of an instruction.", target
+            ),
+            VerifyErrorKind::LocalOutOfRange { index, max_locals } => write!(
+                f, "Local {} is out of range (max_locals is {}).", index, max_locals
+            ),
+            VerifyErrorKind::InvalidConstantType(ty) => write!(f, "Invalid constant type {}.", ty),
+            VerifyErrorKind::StackUnderflow { depth, needed } => write!(
+                f, "Stack underflow: the instruction needs {} value(s), but the stack has {}.",
+                needed, depth
+            ),
+            VerifyErrorKind::StackOverflow { depth, max_stack } => write!(
+                f, "Stack depth {} exceeds max_stack ({}).", depth, max_stack
+            ),
+            VerifyErrorKind::InconsistentStackDepth { first, second } => write!(
+                f, "Instruction is reached with stack depths {} and {}.", first, second
+            ),
+            VerifyErrorKind::InconsistentReturn { first, second } => write!(
+                f, "Function returns with {} value(s) here, but with {} elsewhere.", second, first
+            ),
+            VerifyErrorKind::FallsOffEnd => {
+                write!(f, "Execution reaches the end of the chunk without returning.")
+            }
+        }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Read(err) => write!(f, "{}", err),
+            LoadError::Verify(errors) => {
+                for (i, err) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", err)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+impl std::error::Error for LoadError {}
+
+/// Reads and verifies the .jdll file given.
+pub fn load_assembly(bytes: &[u8]) -> Result<Assembly, LoadError> {
+    let assembly = read_assembly(bytes).map_err(LoadError::Read)?;
+    verify(&assembly).map_err(LoadError::Verify)?;
+
+    Ok(assembly)
+}
+
+/// Checks that the assembly given is well-formed: every index points to something that exists,
+/// every jump lands on an instruction and the stack of every function stays within bounds.
+///
+/// The VM keeps a single operand stack for all calls, so a function starts with its arguments on
+/// the stack and leaves its return values there when it executes `RET`. The amount of values each
+/// function returns is inferred from its code. Calls to native and external functions are assumed
+/// not to change the stack, as their signatures aren't known.
+pub fn verify(assembly: &Assembly) -> Result<(), Vec<VerifyError>> {
+    let mut errors = Vec::new();
+
+    verify_tables(assembly, &mut errors);
+
+    // How many values each function leaves on the stack when it returns. Finding these requires
+    // knowing the return counts of the functions it calls, so functions are analyzed repeatedly
+    // until the counts stop changing.
+    let mut returns: HashMap<(usize, usize), usize> = HashMap::new();
+    let func_count: usize = assembly.blocks.iter().map(|b| b.functions.len()).sum();
+
+    for _ in 0..=func_count {
+        let mut new_returns = HashMap::new();
+        for (b, block) in assembly.blocks.iter().enumerate() {
+            for (f, func) in block.functions.iter().enumerate() {
+                let mut verifier = FunctionVerifier::new(assembly, block, func, b, f, &returns);
+                verifier.verify();
+
+                if let Some(count) = verifier.return_count {
+                    new_returns.insert((b, f), count);
+                }
+            }
+        }
+
+        if new_returns == returns {
+            break;
+        }
+        returns = new_returns;
+    }
+
+    for (b, block) in assembly.blocks.iter().enumerate() {
+        for (f, func) in block.functions.iter().enumerate() {
+            let mut verifier = FunctionVerifier::new(assembly, block, func, b, f, &returns);
+            verifier.verify();
+            errors.append(&mut verifier.errors);
+        }
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+fn verify_tables(assembly: &Assembly, errors: &mut Vec<VerifyError>) {
+    let names = assembly.name_table.len();
+    let check_name = |errors: &mut Vec<VerifyError>, location, index: u32| {
+        if index as usize >= names {
+            errors.push(VerifyError {
+                location,
+                kind: VerifyErrorKind::NameIndexOutOfRange { index, count: names },
+            });
+        }
+    };
+
+    for (i, type_ref) in assembly.type_refs.iter().enumerate() {
+        match type_ref {
+            // Blocks don't contain types yet, so only the block can be checked.
+            ItemRef::Internal { block, index } => {
+                if *block as usize >= assembly.blocks.len() {
+                    errors.push(VerifyError {
+                        location: Location::TypeRef(i),
+                        kind: VerifyErrorKind::DanglingInternalRef { block: *block, index: *index },
+                    });
+                }
+            }
+            ItemRef::Native { .. } => {}
+            ItemRef::External { block_name, item_name } => {
+                check_name(errors, Location::TypeRef(i), *block_name);
+                check_name(errors, Location::TypeRef(i), *item_name);
+            }
+        }
+    }
+
+    for (i, func_ref) in assembly.func_refs.iter().enumerate() {
+        match func_ref {
+            ItemRef::Internal { block, index } => {
+                let exists = assembly.blocks.get(*block as usize)
+                    .is_some_and(|b| (*index as usize) < b.functions.len());
+
+                if !exists {
+                    errors.push(VerifyError {
+                        location: Location::FuncRef(i),
+                        kind: VerifyErrorKind::DanglingInternalRef { block: *block, index: *index },
+                    });
+                }
+            }
+            ItemRef::Native { .. } => {}
+            ItemRef::External { block_name, item_name } => {
+                check_name(errors, Location::FuncRef(i), *block_name);
+                check_name(errors, Location::FuncRef(i), *item_name);
+            }
+        }
+    }
+
+    for (b, block) in assembly.blocks.iter().enumerate() {
+        check_name(errors, Location::Block(b), block.name_index);
+
+        let strings = block.string_table.len();
+        for (f, func) in block.functions.iter().enumerate() {
+            for index in std::iter::once(func.name_index).chain(func.params.iter().copied()) {
+                if index as usize >= strings {
+                    errors.push(VerifyError {
+                        location: Location::Function { block: b, function: f },
+                        kind: VerifyErrorKind::StringIndexOutOfRange {
+                            index: index as i64, count: strings
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Verifies the code of a single function.
+struct FunctionVerifier<'a> {
+    assembly: &'a Assembly,
+    block: &'a Block,
+    func: &'a Function,
+    block_index: usize,
+    func_index: usize,
+    /// The known return counts of every function.
+    returns: &'a HashMap<(usize, usize), usize>,
+    /// The amount of values this function leaves on the stack when it returns, once a reachable
+    /// `RET` has been found.
+    return_count: Option<usize>,
+    errors: Vec<VerifyError>,
+}
+
+impl<'a> FunctionVerifier<'a> {
+    fn new(
+        assembly: &'a Assembly,
+        block: &'a Block,
+        func: &'a Function,
+        block_index: usize,
+        func_index: usize,
+        returns: &'a HashMap<(usize, usize), usize>,
+    ) -> Self {
+        FunctionVerifier {
+            assembly,
+            block,
+            func,
+            block_index,
+            func_index,
+            returns,
+            return_count: None,
+            errors: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, offset: usize, kind: VerifyErrorKind) {
+        self.errors.push(VerifyError {
+            location: Location::Instruction {
+                block: self.block_index,
+                function: self.func_index,
+                offset,
+            },
+            kind,
+        });
+    }
+
+    fn verify(&mut self) {
+        let Some(instructions) = self.decode() else {
+            return;
+        };
+
+        for instr in instructions.values() {
+            self.verify_operand(instr, &instructions);
+        }
+
+        if self.errors.is_empty() {
+            self.verify_stack(&instructions);
+        }
+    }
+
+    /// Decodes every instruction in the chunk, indexed by offset. Returns `None` if the chunk
+    /// contains bytes that can't be decoded.
+    fn decode(&mut self) -> Option<BTreeMap<usize, Instruction>> {
+        let code = &self.func.code;
+        let mut instructions = BTreeMap::new();
+        let mut offset = 0;
+
+        while offset < code.len() {
+            let Some(instr) = decode_instruction(code, offset) else {
+                if OpCode::from_repr(code[offset]).is_some() {
+                    self.error(offset, VerifyErrorKind::TruncatedInstruction);
+                }
+                else {
+                    self.error(offset, VerifyErrorKind::InvalidOpcode(code[offset]));
+                }
+                return None;
+            };
+
+            offset = instr.end();
+            instructions.insert(instr.offset, instr);
+        }
+
+        Some(instructions)
+    }
+
+    fn verify_operand(&mut self, instr: &Instruction, instructions: &BTreeMap<usize, Instruction>) {
+        let val = instr.operand;
+
+        let kind = match instr.opcode.operand_kind() {
+            OperandKind::String(_) => {
+                let count = self.block.string_table.len();
+                if val < 0 || val as usize >= count {
+                    Some(VerifyErrorKind::StringIndexOutOfRange { index: val, count })
+                }
+                else {
+                    None
+                }
+            }
+            OperandKind::Local(_) => {
+                let max_locals = self.func.max_locals;
+                if val >= max_locals as i64 {
+                    Some(VerifyErrorKind::LocalOutOfRange { index: val, max_locals })
+                }
+                else {
+                    None
+                }
+            }
+            OperandKind::Jump(_) => {
+                let target = instr.jump_target().unwrap_or_default();
+                if target < 0 || target as usize >= self.func.code.len() {
+                    Some(VerifyErrorKind::JumpOutOfBounds { target })
+                }
+                else if !instructions.contains_key(&(target as usize)) {
+                    Some(VerifyErrorKind::JumpIntoInstruction { target: target as usize })
+                }
+                else {
+                    None
+                }
+            }
+            OperandKind::FuncRef => {
+                let count = self.assembly.func_refs.len();
+                if val as usize >= count {
+                    Some(VerifyErrorKind::FuncRefOutOfRange { index: val, count })
+                }
+                else {
+                    None
+                }
+            }
+            OperandKind::ConstantType => {
+                match ConstantType::from_repr(val as u8) {
+                    None | Some(ConstantType::Error) => {
+                        Some(VerifyErrorKind::InvalidConstantType(val))
+                    }
+                    _ => None,
+                }
+            }
+            OperandKind::None
+            | OperandKind::Immediate(_)
+            | OperandKind::NativeAddress => None,
+        };
+
+        // Shorthand loads and stores carry their local in the opcode.
+        let kind = kind.or_else(|| {
+            let index = match instr.opcode {
+                OpCode::Store0 | OpCode::Load0 => 0,
+                OpCode::Store1 | OpCode::Load1 => 1,
+                OpCode::Store2 | OpCode::Load2 => 2,
+                OpCode::Store3 | OpCode::Load3 => 3,
+                OpCode::Store4 | OpCode::Load4 => 4,
+                _ => return None,
+            };
+
+            let max_locals = self.func.max_locals;
+            (index >= max_locals as i64)
+                .then_some(VerifyErrorKind::LocalOutOfRange { index, max_locals })
+        });
+
+        if let Some(kind) = kind {
+            self.error(instr.offset, kind);
+        }
+    }
+
+    /// Follows every path through the function, tracking the depth of the stack.
+    fn verify_stack(&mut self, instructions: &BTreeMap<usize, Instruction>) {
+        let mut depths: HashMap<usize, usize> = HashMap::new();
+        let mut pending = vec![(0, self.func.params.len())];
+        let mut overflowed = false;
+
+        if self.func.code.is_empty() {
+            self.error(0, VerifyErrorKind::FallsOffEnd);
+            return;
+        }
+
+        while let Some((offset, depth)) = pending.pop() {
+            if let Some(&known) = depths.get(&offset) {
+                if known != depth {
+                    self.error(offset, VerifyErrorKind::InconsistentStackDepth {
+                        first: known,
+                        second: depth,
+                    });
+                }
+                continue;
+            }
+            depths.insert(offset, depth);
+
+            let instr = instructions[&offset];
+            let (pops, pushes) = self.stack_effect(&instr);
+
+            if depth < pops {
+                self.error(offset, VerifyErrorKind::StackUnderflow { depth, needed: pops });
+                continue;
+            }
+
+            let new_depth = depth - pops + pushes;
+            if new_depth > self.func.max_stack as usize && !overflowed {
+                overflowed = true;
+                self.error(offset, VerifyErrorKind::StackOverflow {
+                    depth: new_depth,
+                    max_stack: self.func.max_stack,
+                });
+            }
+
+            if instr.opcode == OpCode::Ret {
+                match self.return_count {
+                    None => self.return_count = Some(depth),
+                    Some(first) if first != depth => {
+                        self.error(offset, VerifyErrorKind::InconsistentReturn {
+                            first,
+                            second: depth,
+                        });
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(target) = instr.jump_target() {
+                // Conditional jumps that keep their condition only pop it when they don't jump.
+                let taken_depth = match instr.opcode {
+                    OpCode::JTrueK | OpCode::JTrueKL | OpCode::JFalseK | OpCode::JFalseKL => depth,
+                    _ => new_depth,
+                };
+                pending.push((target as usize, taken_depth));
+            }
+
+            let is_unconditional = matches!(instr.opcode, OpCode::Jmp | OpCode::JmpL);
+            if !is_unconditional {
+                if instr.end() >= self.func.code.len() {
+                    self.error(offset, VerifyErrorKind::FallsOffEnd);
+                }
+                else {
+                    pending.push((instr.end(), new_depth));
+                }
+            }
+        }
+    }
+
+    /// The amount of values the instruction given pops from the stack and pushes to it, when
+    /// it doesn't jump.
+    fn stack_effect(&self, instr: &Instruction) -> (usize, usize) {
+        match instr.opcode {
+            OpCode::Noop | OpCode::Native | OpCode::Ret | OpCode::Jmp | OpCode::JmpL => (0, 0),
+
+            OpCode::Const
+            | OpCode::ConstL
+            | OpCode::ConstLL
+            | OpCode::Const0
+            | OpCode::FConst1
+            | OpCode::FConst2
+            | OpCode::IConst1
+            | OpCode::IConst2
+            | OpCode::StrConst
+            | OpCode::StrConstL
+            | OpCode::Load0
+            | OpCode::Load1
+            | OpCode::Load2
+            | OpCode::Load3
+            | OpCode::Load4
+            | OpCode::Load
+            | OpCode::LoadL => (0, 1),
+
+            OpCode::FNeg | OpCode::INeg => (1, 1),
+
+            OpCode::FAdd
+            | OpCode::FSub
+            | OpCode::FMul
+            | OpCode::FDiv
+            | OpCode::FGt
+            | OpCode::FGe
+            | OpCode::FLt
+            | OpCode::FLe
+            | OpCode::IAdd
+            | OpCode::IAddChecked
+            | OpCode::ISub
+            | OpCode::ISubChecked
+            | OpCode::IMul
+            | OpCode::IMulChecked
+            | OpCode::IDiv
+            | OpCode::IDivChecked
+            | OpCode::IGt
+            | OpCode::IGe
+            | OpCode::ILt
+            | OpCode::ILe
+            | OpCode::Eq
+            | OpCode::Neq => (2, 1),
+
+            OpCode::Store0
+            | OpCode::Store1
+            | OpCode::Store2
+            | OpCode::Store3
+            | OpCode::Store4
+            | OpCode::Store
+            | OpCode::StoreL
+            | OpCode::Pop
+            | OpCode::Print => (1, 0),
+
+            OpCode::JTrue
+            | OpCode::JTrueL
+            | OpCode::JTrueK
+            | OpCode::JTrueKL
+            | OpCode::JFalse
+            | OpCode::JFalseL
+            | OpCode::JFalseK
+            | OpCode::JFalseKL => (1, 0),
+
+            OpCode::Call => match self.assembly.func_refs.get(instr.operand as usize) {
+                Some(ItemRef::Internal { block, index }) => {
+                    let (block, index) = (*block as usize, *index as usize);
+                    let params = self.assembly.blocks[block].functions[index].params.len();
+                    let returns = self.returns.get(&(block, index)).copied().unwrap_or(0);
+
+                    (params, returns)
+                }
+                _ => (0, 0),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::jasm::assembler::assemble;
+    use super::*;
+
+    /// Assembles a block named "main" whose string table contains "main", "a" and "b", with the
+    /// functions given.
+    fn assemble_funcs(funcs: &str) -> Assembly {
+        let src = format!(r#"
+            .names
+                "main"
+            .end
+            .func_refs
+                internal 0 0
+                internal 0 1
+            .end
+            .block 0
+            .strings
+                "main"
+                "a"
+                "b"
+            .end
+            {}
+            .end
+        "#, funcs);
+
+        assemble(&src).unwrap()
+    }
+
+    fn error_kinds(assembly: &Assembly) -> Vec<VerifyErrorKind> {
+        match verify(assembly) {
+            Ok(()) => vec![],
+            Err(errors) => {
+                for err in &errors {
+                    println!("{}", err);
+                }
+                errors.into_iter().map(|e| e.kind).collect()
+            }
+        }
+    }
+
+    #[test]
+    fn valid_functions () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                .max_locals 1
+                .max_stack 3
+                F_CONST_1
+                F_CONST_2
+                CALL 1
+                STORE_0
+                LOAD_0
+                JTRUE_K end
+                I_CONST_2
+            end:
+                PRINT FLOAT_64
+                STR_CONST 1
+                PRINT STRING_UTF8
+                RET
+            .end
+            .func 0
+                .params 1 2
+                .max_locals 2
+                .max_stack 2
+                STORE_1
+                STORE_0
+                LOAD_0
+                LOAD_1
+                F_ADD
+                RET
+            .end
+        "#);
+
+        assert_eq!(error_kinds(&assembly), vec![]);
+    }
+
+    #[test]
+    fn recursive_functions () {
+        // func f (a) => if a < 2 then a else f(a - 1) + f(a - 2) end
+        let assembly = assemble_funcs(r#"
+            .func 0
+                .max_locals 0
+                .max_stack 1
+                F_CONST_2
+                CALL 1
+                PRINT FLOAT_64
+                RET
+            .end
+            .func 0
+                .params 1
+                .max_locals 1
+                .max_stack 3
+                STORE_0
+                LOAD_0
+                F_CONST_2
+                F_LT
+                JFALSE else
+                LOAD_0
+                RET
+            else:
+                LOAD_0
+                F_CONST_1
+                F_SUB
+                CALL 1
+                LOAD_0
+                F_CONST_2
+                F_SUB
+                CALL 1
+                F_ADD
+                RET
+            .end
+        "#);
+
+        assert_eq!(error_kinds(&assembly), vec![]);
+    }
+
+    #[test]
+    fn invalid_indices () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                .max_locals 2
+                .max_stack 1
+                LOAD 2
+                STORE_4
+                STR_CONST 3
+                POP
+                CALL 9
+                PRINT 0
+                RET
+            .end
+        "#);
+
+        assert_eq!(error_kinds(&assembly), vec![
+            VerifyErrorKind::DanglingInternalRef { block: 0, index: 1 },
+            VerifyErrorKind::LocalOutOfRange { index: 2, max_locals: 2 },
+            VerifyErrorKind::LocalOutOfRange { index: 4, max_locals: 2 },
+            VerifyErrorKind::StringIndexOutOfRange { index: 3, count: 3 },
+            VerifyErrorKind::FuncRefOutOfRange { index: 9, count: 2 },
+            VerifyErrorKind::InvalidConstantType(0),
+        ]);
+    }
+
+    #[test]
+    fn invalid_jumps () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                JMP 1
+                JMP -10
+                JMP 0
+                RET
+            .end
+        "#);
+
+        assert_eq!(error_kinds(&assembly), vec![
+            VerifyErrorKind::DanglingInternalRef { block: 0, index: 1 },
+            VerifyErrorKind::JumpIntoInstruction { target: 3 },
+            VerifyErrorKind::JumpOutOfBounds { target: -6 },
+        ]);
+    }
+
+    #[test]
+    fn stack_errors () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                .max_stack 1
+                F_CONST_1
+                F_CONST_2
+                F_ADD
+                F_ADD
+                RET
+            .end
+            .func 0
+                .max_stack 1
+                I_CONST_1
+                JTRUE skip
+                F_CONST_1
+            skip:
+                RET
+            .end
+        "#);
+
+        assert_eq!(error_kinds(&assembly), vec![
+            VerifyErrorKind::StackOverflow { depth: 2, max_stack: 1 },
+            VerifyErrorKind::StackUnderflow { depth: 1, needed: 2 },
+            VerifyErrorKind::InconsistentStackDepth { first: 1, second: 0 },
+        ]);
+    }
+
+    #[test]
+    fn falls_off_end () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                NOOP
+            .end
+        "#);
+
+        let kinds = error_kinds(&assembly);
+        assert!(kinds.contains(&VerifyErrorKind::FallsOffEnd));
+    }
+
+    #[test]
+    fn load_rejects_malformed_assemblies () {
+        let assembly = assemble_funcs(r#"
+            .func 0
+                LOAD_0
+                RET
+            .end
+            .func 0
+                RET
+            .end
+        "#);
+
+        let err = load_assembly(&assembly.to_bytes()).unwrap_err();
+        println!("{}", err);
+        assert!(matches!(err, LoadError::Verify(_)));
+        assert!(matches!(load_assembly(b"JUDITH"), Err(LoadError::Read(_))));
+    }
+}
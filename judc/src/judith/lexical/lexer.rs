@@ -1,99 +1,82 @@
-use std::collections::HashMap;
-use std::iter::{Enumerate, Peekable};
-use std::str::Chars;
-use once_cell::sync::Lazy;
+use serde::Serialize;
 use crate::judith::compiler_messages;
 use crate::judith::compiler_messages::{CompilerMessage, MessageContainer};
-use crate::judith::lexical::token::{RegularToken, StringLiteralKind, StringToken, Token, TokenKind, Trivia, TriviaKind};
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::lexical::token::{
+    RegularToken, StringLiteralKind, StringToken, Token, TokenKind, Trivia, TriviaKind, TriviaRange
+};
 use crate::SourceSpan;
 
 const FIRST_LINE: usize = 1;
 const FIRST_COLUMN: usize = 1;
-
-/// Maps every string that represents a keyword to its keyword token kind.
-static KEYWORD_MAP: Lazy<KeywordMap> = Lazy::new(KeywordMap::new);
-
-struct KeywordMap {
-    map: HashMap<&'static str, TokenKind>,
-}
-
-impl KeywordMap {
-    fn new() -> Self {
-        let mut map = HashMap::new();
-        map.insert("and", TokenKind::KwAnd);
-        map.insert("break", TokenKind::KwBreak);
-        map.insert("class", TokenKind::KwClass);
-        map.insert("continue", TokenKind::KwContinue);
-        map.insert("do", TokenKind::KwDo);
-        map.insert("else", TokenKind::KwElse);
-        map.insert("elsif", TokenKind::KwElsif);
-        map.insert("end", TokenKind::KwEnd);
-        map.insert("false", TokenKind::KwFalse);
-        map.insert("final", TokenKind::KwFinal);
-        map.insert("for", TokenKind::KwFor);
-        map.insert("func", TokenKind::KwFunc);
-        map.insert("generator", TokenKind::KwGenerator);
-        map.insert("goto", TokenKind::KwGoto);
-        map.insert("hid", TokenKind::KwHid);
-        map.insert("if", TokenKind::KwIf);
-        map.insert("in", TokenKind::KwIn);
-        map.insert("interface", TokenKind::KwInterface);
-        map.insert("let", TokenKind::KwLet);
-        map.insert("loop", TokenKind::KwLoop);
-        map.insert("match", TokenKind::KwMatch);
-        map.insert("mut", TokenKind::KwMut);
-        map.insert("not", TokenKind::KwNot);
-        map.insert("or", TokenKind::KwOr);
-        map.insert("pub", TokenKind::KwPub);
-        map.insert("ref", TokenKind::KwRef);
-        map.insert("return", TokenKind::KwReturn);
-        map.insert("sh", TokenKind::KwSh);
-        map.insert("struct", TokenKind::KwStruct);
-        map.insert("then", TokenKind::KwThen);
-        map.insert("true", TokenKind::KwTrue);
-        map.insert("typedef", TokenKind::KwTypedef);
-        map.insert("undefined", TokenKind::KwUndefined);
-        map.insert("while", TokenKind::KwWhile);
-        map.insert("yield", TokenKind::KwYield);
-        map.insert("__p_print", TokenKind::PkwPrint);
-
-        KeywordMap { map }
-    }
-
-    fn get(&self, str: &str) -> Option<TokenKind> {
-        self.map.get(str).cloned()
-    }
-}
+/// The byte order mark, which is skipped when a source starts with it.
+const BOM: char = '\u{feff}';
 
 pub struct Lexer<'a> {
     src: &'a str,
-    chars: Peekable<Enumerate<Chars<'a>>>,
+    /// The byte offset of the character at the cursor's position.
+    current: usize,
     start: usize,
     line: usize,
     column: usize,
+    /// The trivia of every token read so far, which tokens reference by range.
+    trivia: Vec<Trivia<'a>>,
     has_errors: bool,
     messages: MessageContainer,
 }
 
-pub struct LexerResult {
-    pub tokens: Vec<Token>,
+#[derive(Serialize)]
+pub struct LexerResult<'src> {
+    pub tokens: Vec<Token<'src>>,
+    /// The trivia of every token, in the order it appears in the source. Each token references its
+    /// leading and trailing trivia as a range of this list.
+    pub trivia: Vec<Trivia<'src>>,
+    #[serde(skip)]
     pub messages: MessageContainer,
 }
 
+impl<'src> LexerResult<'src> {
+    /// Returns the trivia before the token given.
+    pub fn leading_trivia(&self, tok: &Token) -> &[Trivia<'src>] {
+        &self.trivia[tok.base().leading_trivia.range()]
+    }
+
+    /// Returns the trivia after the token given, up to the end of its line.
+    pub fn trailing_trivia(&self, tok: &Token) -> &[Trivia<'src>] {
+        &self.trivia[tok.base().trailing_trivia.range()]
+    }
+}
+
 impl<'a> Lexer<'a> {
     pub fn new(src: &'a str) -> Self {
+        let start = match src.starts_with(BOM) {
+            true => BOM.len_utf8(),
+            false => 0,
+        };
+
         Self {
             src,
-            chars: src.chars().enumerate().peekable(),
-            start: 0,
+            current: start,
+            start,
             line: FIRST_LINE,
             column: FIRST_COLUMN,
+            trivia: Vec::new(),
             has_errors: false,
             messages: MessageContainer::new(),
         }
     }
 
-    pub fn next_token(&mut self) -> Token {
+    /// Returns the trivia of every token read so far.
+    pub fn trivia(&self) -> &[Trivia<'a>] {
+        &self.trivia
+    }
+
+    /// Returns the messages produced while reading the tokens read so far.
+    pub fn messages(&self) -> &MessageContainer {
+        &self.messages
+    }
+
+    pub fn next_token(&mut self) -> Token<'a> {
         let leading_trivia = self.consume_leading_trivia();
         let mut token = self.consume_token();
         let trailing_trivia = self.consume_trailing_trivia();
@@ -112,22 +95,22 @@ impl<'a> Lexer<'a> {
         token
     }
 
-    fn consume_leading_trivia(&mut self) -> Vec<Trivia> {
-        let mut trivia: Vec<Trivia> = Vec::new();
+    fn consume_leading_trivia(&mut self) -> TriviaRange {
+        let start = self.trivia.len();
 
         while let Some(t) = self.next_trivia() {
-            trivia.push(t);
+            self.trivia.push(t);
         }
 
-        trivia
+        TriviaRange::new(start, self.trivia.len())
     }
 
-    fn consume_trailing_trivia(&mut self) -> Vec<Trivia> {
-        let mut trivia: Vec<Trivia> = Vec::new();
+    fn consume_trailing_trivia(&mut self) -> TriviaRange {
+        let start = self.trivia.len();
 
         while let Some(t) = self.next_trivia() {
             let trivia_kind = t.kind.clone();
-            trivia.push(t);
+            self.trivia.push(t);
 
             // When we encounter a line break trivia, any subsequent trivia becomes leading trivia
             // for the next token.
@@ -144,10 +127,10 @@ impl<'a> Lexer<'a> {
             }
         }
 
-        trivia
+        TriviaRange::new(start, self.trivia.len())
     }
 
-    fn consume_token(&mut self) -> Token {
+    fn consume_token(&mut self) -> Token<'a> {
         self.start = self.cursor();
 
         if self.is_at_end() {
@@ -167,14 +150,27 @@ impl<'a> Lexer<'a> {
             '}' => self.make_token(TokenKind::RightCurlyBracket), // }
             '[' => self.make_token(TokenKind::LeftSquareBracket), // [
             ']' => self.make_token(TokenKind::RightSquareBracket), // ]
-            '+' => self.make_token(TokenKind::Plus), // +
+            '+' => match self.try_match('=') {
+                true => self.make_token(TokenKind::PlusEqual), // +=
+                false => self.make_token(TokenKind::Plus), // +
+            },
             '-' => {
+                // Comments are read as trivia before getting here, so this can't be one.
                 if self.try_match('-') {
-                    panic!("Comments should've been caught as trivia!");
+                    let cursor = self.cursor() as i64;
+                    self.error(compiler_messages::Lexer::unexpected_character(SourceSpan {
+                        start: cursor - 1,
+                        end: cursor,
+                        line: self.line as i64,
+                    }, '-'));
+                    return self.make_token(TokenKind::Invalid);
                 }
                 if self.try_match('>') {
                     return self.make_token(TokenKind::MinusArrow) // ->
                 }
+                if self.try_match('=') {
+                    return self.make_token(TokenKind::MinusEqual) // -=
+                }
 
                 let c = self.peek();
                 if c != None && Self::is_number_leading_char(c.unwrap()) {
@@ -184,8 +180,18 @@ impl<'a> Lexer<'a> {
 
                 self.make_token(TokenKind::Minus) // -
             },
-            '*' => self.make_token(TokenKind::Asterisk),
-            '/' => self.make_token(TokenKind::Slash),
+            '*' => match self.try_match('=') {
+                true => self.make_token(TokenKind::AsteriskEqual), // *=
+                false => self.make_token(TokenKind::Asterisk), // *
+            },
+            '/' => match self.try_match('=') {
+                true => self.make_token(TokenKind::SlashEqual), // /=
+                false => self.make_token(TokenKind::Slash), // /
+            },
+            '%' => match self.try_match('=') {
+                true => self.make_token(TokenKind::PercentEqual), // %=
+                false => self.make_token(TokenKind::Percent), // %
+            },
             '=' => match self.try_match('=') {
                 true => match self.try_match('=') {
                     true => self.make_token(TokenKind::EqualEqualEqual), // ===
@@ -202,13 +208,16 @@ impl<'a> Lexer<'a> {
                     false => self.make_token(TokenKind::BangEqual), // !=
                 },
                 false => match self.try_match('~') {
-                    true => self.make_token(TokenKind::BangEqual), // !~
+                    true => self.make_token(TokenKind::BangTilde), // !~
                     false => self.make_token(TokenKind::Bang), // !
                 }
             },
             '?' => match self.try_match('?') {
                 true => self.make_token(TokenKind::DoubleQuestionMark), // ??
-                false => self.make_token(TokenKind::QuestionMark), // ?
+                false => match self.try_match('.') {
+                    true => self.make_token(TokenKind::QuestionDot), // ?.
+                    false => self.make_token(TokenKind::QuestionMark), // ?
+                }
             },
             '~' => match self.try_match('~') {
                 true => self.make_token(TokenKind::TildeTilde), // ~~
@@ -225,15 +234,22 @@ impl<'a> Lexer<'a> {
                 self.make_token(TokenKind::Dot)
             }
             '<' => match self.try_match('=') {
-                true => self.make_token(TokenKind::LessEqual),
-                false => self.make_token(TokenKind::Less),
+                true => self.make_token(TokenKind::LessEqual), // <=
+                false => match self.try_match('<') {
+                    true => self.make_token(TokenKind::LessLess), // <<
+                    false => self.make_token(TokenKind::Less), // <
+                }
             },
             '>' => match self.try_match('=') {
-                true => self.make_token(TokenKind::GreaterEqual),
-                false => self.make_token(TokenKind::Greater),
+                true => self.make_token(TokenKind::GreaterEqual), // >=
+                false => match self.try_match('>') {
+                    true => self.make_token(TokenKind::GreaterGreater), // >>
+                    false => self.make_token(TokenKind::Greater), // >
+                }
             },
             '&' => self.make_token(TokenKind::Ampersand),
             '|' => self.make_token(TokenKind::Pipe),
+            '^' => self.make_token(TokenKind::Caret),
             '"' => self.scan_string('"', self.column - 1),
             '`' => self.scan_string('`', self.column - 1),
             _ if Self::is_number_leading_char(c) => {
@@ -254,7 +270,7 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn next_trivia(&mut self) -> Option<Trivia> {
+    fn next_trivia(&mut self) -> Option<Trivia<'a>> {
         self.start = self.cursor();
         let char = self.peek();
 
@@ -274,7 +290,7 @@ impl<'a> Lexer<'a> {
         }
         // Consume directive trivia.
         else if char == '#' {
-            todo!("Directives.");
+            return Some(self.scan_directive());
         }
         // Consume comment trivia (single line or multiline)
         else if char == '-' {
@@ -305,7 +321,7 @@ impl<'a> Lexer<'a> {
     /// Scans a numeric literal. This function assumes that the first character in the literal has
     /// already been consumed, and is being passed as the first parameter.
     /// * `first` The first character of this number, which is already consumed.
-    fn scan_number(&mut self, first: char) -> Token {
+    fn scan_number(&mut self, first: char) -> Token<'a> {
         // Whether we've already found a "." character in this literal.
         let mut dot_found = first == '.';
         // Whether we've already found the "e" character in this literal, used for scientific
@@ -438,7 +454,7 @@ impl<'a> Lexer<'a> {
     /// |ff\`| (all flags and the first quote) has already been consumed.
     /// * `quoting_char` The character used to start the string (either '"' or '`').
     /// * `start_column` The column the where the first delimiter of the string is.
-    fn scan_string (&mut self, quoting_char: char, start_column: usize) -> Token {
+    fn scan_string (&mut self, quoting_char: char, start_column: usize) -> Token<'a> {
         let mut opening_quotes = 1; // the one that triggered this scan.
         while let Some(c) = self.peek() {
             if c == quoting_char {
@@ -482,7 +498,7 @@ impl<'a> Lexer<'a> {
         self.make_string_token(quoting_char, opening_quotes, start_column)
     }
 
-    fn scan_literal_like (&mut self) -> Token {
+    fn scan_literal_like (&mut self) -> Token<'a> {
         let start_column = self.column - 1;
 
         // We may be scanning an identifier, a keyword or the flags at the start of a string literal
@@ -500,8 +516,7 @@ impl<'a> Lexer<'a> {
         }
 
         let cursor = self.cursor();
-        let lexeme = self.extract_lexeme(self.start, cursor);
-        match KEYWORD_MAP.get(&lexeme) {
+        match Symbol::keyword(self.extract_lexeme(self.start, cursor)) {
             Some(kw) => self.make_token(kw),
             None => self.make_token(TokenKind::Identifier),
         }
@@ -509,7 +524,7 @@ impl<'a> Lexer<'a> {
 
     /// Scans whitespace trivia. This doesn't care whether any whitespace has already been consumed
     /// or not.
-    fn scan_whitespace_trivia (&mut self) -> Trivia {
+    fn scan_whitespace_trivia (&mut self) -> Trivia<'a> {
         while let Some(c) = self.peek() {
             if Self::is_whitespace(c) == false {
                 break;
@@ -521,7 +536,7 @@ impl<'a> Lexer<'a> {
     }
 
     /// Scans a multiline comment, assuming the cursor is already past the initial "--!".
-    fn scan_multiline_comment(&mut self) -> Trivia {
+    fn scan_multiline_comment(&mut self) -> Trivia<'a> {
         let mut last_char = None;
 
         while let Some(c) = self.advance() {
@@ -536,7 +551,7 @@ impl<'a> Lexer<'a> {
     }
 
     /// Scans a single line comment, assuming the cursor is already past the initial "--".
-    fn scan_single_line_comment(&mut self) -> Trivia {
+    fn scan_single_line_comment(&mut self) -> Trivia<'a> {
         while let Some(c) = self.peek() {
             // Keep going until there's a line break. There the comment ends.
             if Self::is_newline(c) {
@@ -550,7 +565,23 @@ impl<'a> Lexer<'a> {
         self.make_trivia(TriviaKind::SingleLineComment)
     }
 
-    fn make_token(&mut self, kind: TokenKind) -> Token {
+    /// Reads a directive up to the end of its line. Directives aren't supported yet, so an error is
+    /// reported and the directive is otherwise ignored.
+    fn scan_directive(&mut self) -> Trivia<'a> {
+        while let Some(c) = self.peek() {
+            if Self::is_newline(c) {
+                break;
+            }
+            self.advance();
+        }
+
+        let trivia = self.make_trivia(TriviaKind::Directive);
+        self.error(compiler_messages::Lexer::unsupported_directive(trivia.span));
+
+        trivia
+    }
+
+    fn make_token(&mut self, kind: TokenKind) -> Token<'a> {
         let cursor = self.cursor();
         Token::Regular(RegularToken {
             kind,
@@ -558,12 +589,12 @@ impl<'a> Lexer<'a> {
             start: self.start as i64,
             end: self.cursor() as i64,
             line: self.line as i64,
-            leading_trivia: vec![], // TODO: This is dirty.
-            trailing_trivia: vec![],
+            leading_trivia: TriviaRange::default(), // TODO: This is dirty.
+            trailing_trivia: TriviaRange::default(),
         })
     }
 
-    fn make_string_token(&mut self, quoting_char: char, quote_count: i32, column: usize) -> Token {
+    fn make_string_token(&mut self, quoting_char: char, quote_count: i32, column: usize) -> Token<'a> {
         let cursor = self.cursor();
 
         Token::String(StringToken {
@@ -573,8 +604,8 @@ impl<'a> Lexer<'a> {
                 start: self.start as i64,
                 end: cursor as i64,
                 line: self.line as i64,
-                leading_trivia: vec![], // TODO: This is dirty.
-                trailing_trivia: vec![],
+                leading_trivia: TriviaRange::default(), // TODO: This is dirty.
+                trailing_trivia: TriviaRange::default(),
             },
             string_kind: match quote_count {
                 1 => StringLiteralKind::Regular,
@@ -586,13 +617,11 @@ impl<'a> Lexer<'a> {
         })
     }
 
-    fn make_trivia(&mut self, kind: TriviaKind) -> Trivia {
+    fn make_trivia(&mut self, kind: TriviaKind) -> Trivia<'a> {
         let cursor = self.cursor();
-        let lexeme = self.extract_lexeme(self.start, cursor);
-
         Trivia {
             kind,
-            lexeme,
+            lexeme: self.extract_lexeme(self.start, cursor),
             span: SourceSpan::new(self.start as i64, self.cursor() as i64, self.line as i64),
         }
     }
@@ -606,26 +635,22 @@ impl<'a> Lexer<'a> {
     // region Helper functions
     /// Moves the character forwards
     fn move_chars_forwards (&mut self) -> Option<char> {
-        if let Some((_, char)) = self.chars.next() {
-            self.column += 1;
-            Some(char)
-        }
-        else {
-            None
-        }
+        let char = self.peek()?;
+        self.current += char.len_utf8();
+        self.column += 1;
+
+        Some(char)
     }
 
-    fn cursor(&mut self) -> usize {
-        if let Some((index, _)) = self.chars.peek() {
-            return *index;
-        }
-        self.src.len()
+    /// Returns the byte offset of the cursor in the source.
+    fn cursor(&self) -> usize {
+        self.current
     }
 
     /// Returns true if the cursor has reached the end of the source.
     #[inline(always)]
-    fn is_at_end(&mut self) -> bool {
-        self.chars.peek().is_none()
+    fn is_at_end(&self) -> bool {
+        self.current >= self.src.len()
     }
 
     /// Returns the character at the cursor's position and moves it forward. If a newline is found,
@@ -638,13 +663,13 @@ impl<'a> Lexer<'a> {
         match char {
             // Newline: either Windows's \r\n or old Mac's \r.
             Some('\r') => {
-                self.line += 1;
-                self.column = FIRST_COLUMN;
-
                 // We have "\r\n", so we advance past the \n, too.
                 if self.peek() == Some('\n') {
                     self.move_chars_forwards();
                 }
+
+                self.line += 1;
+                self.column = FIRST_COLUMN;
             }
             // Newline: Linux's \n.
             Some('\n') => {
@@ -678,31 +703,32 @@ impl<'a> Lexer<'a> {
     }
 
     /// Returns the character at the cursor's position, without moving the cursor.
-    fn peek (&mut self) -> Option<char> {
-        if self.is_at_end() {
-            return None;
-        }
-
-        if let Some((_, char)) = self.chars.peek() {
-            Some(*char)
-        }
-        else {
-            None
-        }
+    fn peek (&self) -> Option<char> {
+        self.char_at(self.current)
     }
 
     /// Returns the next character after the cursor's position, without moving the cursor.
-    fn peek_next (&mut self) -> Option<char> {
-        let mut slice = self.src[self.cursor()..].chars();
-        slice.next();
+    fn peek_next (&self) -> Option<char> {
+        let char = self.peek()?;
+        self.char_at(self.current + char.len_utf8())
+    }
 
-        slice.next()
+    /// Returns the character that starts at the byte offset given, which must be the start of a
+    /// character.
+    #[inline(always)]
+    fn char_at (&self, index: usize) -> Option<char> {
+        let byte = *self.src.as_bytes().get(index)?;
+        if byte.is_ascii() {
+            return Some(byte as char);
+        }
+
+        self.src[index..].chars().next()
     }
 
-    /// Creates a new string with contents of the source string between the start (inclusive) and
-    /// end (exclusive) given.
-    fn extract_lexeme (&self, start: usize, end: usize) -> String {
-        self.src[start..end].to_string()
+    /// Returns the slice of the source string between the start (inclusive) and end (exclusive)
+    /// byte offsets given.
+    fn extract_lexeme (&self, start: usize, end: usize) -> &'a str {
+        &self.src[start..end]
     }
     // endregion
 
@@ -743,23 +769,25 @@ impl<'a> Lexer<'a> {
         Self::is_digit(c) || c == '.'
     }
 
-    /// Returns true if the character given can be the first character in an identifier. This
-    /// includes the identifier escape character ('\').
+    /// Returns true if the character given can be the first character in an identifier, which is
+    /// any character with the XID_Start property (UAX #31), the underscore and the identifier
+    /// escape character ('\').
     #[inline(always)]
     fn is_identifier_leading_char (c: char) -> bool {
-        Self::is_letter(c) || c == '_' || c == '\\'
+        unicode_ident::is_xid_start(c) || c == '_' || c == '\\'
     }
 
     /// Returns true if the character given can be part of an identifier in any part other than the
-    /// leading character in it. This is ASCII letters, numbers and underscore.
+    /// leading character in it, which is any character with the XID_Continue property (UAX #31).
+    /// This includes digits and the underscore.
     #[inline(always)]
     fn is_identifier_char (c: char) -> bool {
-        Self::is_letter(c) || c == '_' || Self::is_digit(c)
+        unicode_ident::is_xid_continue(c)
     }
     // endregion
 }
 
-pub fn tokenize(src: &str) -> LexerResult {
+pub fn tokenize(src: &str) -> LexerResult<'_> {
     let mut lexer = Lexer::new(src);
     let mut tokens: Vec<Token> = vec![];
 
@@ -769,6 +797,7 @@ pub fn tokenize(src: &str) -> LexerResult {
 
     LexerResult {
         tokens,
+        trivia: lexer.trivia,
         messages: lexer.messages,
     }
 }
@@ -822,6 +851,7 @@ mod tests {
             ("and", TokenKind::KwAnd),
             ("break", TokenKind::KwBreak),
             ("do", TokenKind::KwDo),
+            ("null", TokenKind::KwNull),
             ("__p_print", TokenKind::PkwPrint),
         ];
 
@@ -855,8 +885,8 @@ mod tests {
         let res = tokenize("--");
         assert_eq!(res.tokens.len(), 1);
         assert_eq!(res.tokens[0].kind(), TokenKind::EOF);
-        assert_eq!(res.tokens[0].base().leading_trivia.len(), 1);
-        assert_eq!(res.tokens[0].base().leading_trivia[0].kind, TriviaKind::SingleLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0]).len(), 1);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].kind, TriviaKind::SingleLineComment);
 
         println!("Testing '= >'");
         let res = tokenize("= >");
@@ -871,6 +901,41 @@ mod tests {
         assert_eq!(res.tokens[0].kind(), TokenKind::EqualArrow);
     }
 
+    #[test]
+    fn test_operator_tokens() {
+        let cases = vec![
+            ("%", TokenKind::Percent),
+            ("^", TokenKind::Caret),
+            ("<<", TokenKind::LessLess),
+            (">>", TokenKind::GreaterGreater),
+            ("+=", TokenKind::PlusEqual),
+            ("-=", TokenKind::MinusEqual),
+            ("*=", TokenKind::AsteriskEqual),
+            ("/=", TokenKind::SlashEqual),
+            ("%=", TokenKind::PercentEqual),
+            ("~~", TokenKind::TildeTilde),
+            ("!~", TokenKind::BangTilde),
+            ("!=", TokenKind::BangEqual),
+            ("<=", TokenKind::LessEqual),
+            (">=", TokenKind::GreaterEqual),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let res = tokenize(input);
+            assert_eq!(res.tokens.len(), 2); // Should contain [operator, EOF].
+            assert_eq!(res.tokens[0].kind(), expected);
+            assert_eq!(res.tokens[0].base().lexeme, input);
+        }
+
+        println!("Testing '< <'");
+        let res = tokenize("< <");
+        assert_eq!(res.tokens.len(), 3);
+        assert_eq!(res.tokens[0].kind(), TokenKind::Less);
+        assert_eq!(res.tokens[1].kind(), TokenKind::Less);
+    }
+
     #[test]
     fn test_correct_numeric_literals() {
         let cases = vec![
@@ -1004,52 +1069,144 @@ mod tests {
             assert_eq!(res.tokens.len(), 2); // Should contain [if, EOF].
 
             println!("Testing token's trivia.");
-            assert_eq!(res.tokens[0].base().leading_trivia.len(), leading_count, "Incorrect leading trivia count.");
-            assert_eq!(res.tokens[0].base().trailing_trivia.len(), trailing_count, "Incorrect trailing trivia count.");
+            assert_eq!(res.leading_trivia(&res.tokens[0]).len(), leading_count, "Incorrect leading trivia count.");
+            assert_eq!(res.trailing_trivia(&res.tokens[0]).len(), trailing_count, "Incorrect trailing trivia count.");
 
             println!("Testing EOF's trivia.");
-            assert_eq!(res.tokens[1].base().leading_trivia.len(), 0, "Incorrect EOF leading trivia count.");
-            assert_eq!(res.tokens[1].base().trailing_trivia.len(), 0, "Incorrect EOF trailing trivia count (????).");
+            assert_eq!(res.leading_trivia(&res.tokens[1]).len(), 0, "Incorrect EOF leading trivia count.");
+            assert_eq!(res.trailing_trivia(&res.tokens[1]).len(), 0, "Incorrect EOF trailing trivia count (????).");
         }
     }
 
+    #[test]
+    fn test_unicode_spans () {
+        println!("Testing spans after characters outside of ASCII.");
+        let src = "-- ünïcödé\n\"añb\" `🦀` x";
+        let res = tokenize(src);
+        assert_eq!(res.messages.count(), 0);
+
+        // Offsets are in bytes, so they can be used to slice the source.
+        for tok in &res.tokens {
+            let tok = tok.base();
+            assert_eq!(&src[tok.start as usize..tok.end as usize], tok.lexeme);
+        }
+        for trivia in &res.trivia {
+            assert_eq!(&src[trivia.span.start as usize..trivia.span.end as usize], trivia.lexeme);
+        }
+
+        assert_eq!(res.tokens.len(), 4);
+        assert_eq!(res.tokens[0].base().lexeme, "\"añb\"");
+        assert_eq!((res.tokens[0].base().start, res.tokens[0].base().end), (15, 21));
+        assert_eq!(res.tokens[1].base().lexeme, "`🦀`");
+        assert_eq!((res.tokens[1].base().start, res.tokens[1].base().end), (22, 28));
+        assert_eq!(res.tokens[2].base().lexeme, "x");
+        assert_eq!((res.tokens[2].base().start, res.tokens[2].base().line), (29, 2));
+
+        println!("Testing an unexpected character outside of ASCII.");
+        let res = tokenize("a € b");
+        assert_eq!(res.messages.errors.len(), 1);
+        assert_eq!(res.messages.errors[0].code, MessageCode::UnexpectedCharacter { character: '€' });
+        assert_eq!(res.tokens[1].kind(), TokenKind::Invalid);
+        assert_eq!(res.tokens[1].base().lexeme, "€");
+        assert_eq!(res.tokens[2].base().start, 6);
+    }
+
+    #[test]
+    fn test_unicode_identifiers () {
+        println!("Testing identifiers outside of ASCII.");
+        let res = tokenize("let número = größe + 面积 + _x1 + \\ñ");
+        assert_eq!(res.messages.count(), 0);
+
+        let identifiers: Vec<&str> = res.tokens.iter()
+            .filter(|tok| tok.kind() == TokenKind::Identifier)
+            .map(|tok| tok.base().lexeme)
+            .collect();
+        assert_eq!(identifiers, ["número", "größe", "面积", "_x1", "\\ñ"]);
+
+        println!("Testing characters that can't start an identifier.");
+        let res = tokenize("\u{301}a");
+        assert_eq!(res.messages.errors.len(), 1);
+        assert_eq!(res.tokens[1].base().lexeme, "a");
+    }
+
+    #[test]
+    fn test_line_breaks () {
+        for src in ["a\nb\n", "a\r\nb\r\n", "a\rb\r"] {
+            println!("Testing line breaks in {:?}.", src);
+            let res = tokenize(src);
+            let newline = &src[1..src.find('b').unwrap()];
+
+            assert_eq!(res.tokens.len(), 3);
+            assert_eq!(res.trailing_trivia(&res.tokens[0]).len(), 1);
+            assert_eq!(res.trailing_trivia(&res.tokens[0])[0].kind, TriviaKind::LineBreak);
+            assert_eq!(res.trailing_trivia(&res.tokens[0])[0].lexeme, newline);
+            assert_eq!(res.trailing_trivia(&res.tokens[1])[0].lexeme, newline);
+            assert_eq!(res.tokens[1].base().line, 2);
+        }
+
+        println!("Testing the column of a string after \"\\r\\n\".");
+        let res = tokenize("a\r\n\"b\"");
+        let Token::String(str) = &res.tokens[1] else { panic!("Expected a string.") };
+        assert_eq!(str.column, 1);
+    }
+
+    #[test]
+    fn test_byte_order_mark () {
+        let res = tokenize("\u{feff}let a");
+        assert_eq!(res.messages.count(), 0);
+        assert_eq!(res.tokens[0].kind(), TokenKind::KwLet);
+        assert_eq!(res.tokens[0].base().start, 3);
+        assert_eq!(res.leading_trivia(&res.tokens[0]).len(), 0);
+    }
+
     #[test]
     fn test_comment_trivia () {
         println!("Testing first comment.");
         let res = tokenize("-- comment until next line\n`backticks`");
 
-        assert_eq!(res.tokens[0].base().leading_trivia.len(), 2);
-        assert_eq!(res.tokens[0].base().leading_trivia[0].kind, TriviaKind::SingleLineComment);
-        assert_eq!(res.tokens[0].base().leading_trivia[0].lexeme, "-- comment until next line");
-        assert_eq!(res.tokens[0].base().leading_trivia[1].kind, TriviaKind::LineBreak);
+        assert_eq!(res.leading_trivia(&res.tokens[0]).len(), 2);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].kind, TriviaKind::SingleLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].lexeme, "-- comment until next line");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[1].kind, TriviaKind::LineBreak);
         println!("Testing second comment.");
         let res = tokenize("--! com -- --! com2 -- -- com\n--com do\nelse");
 
-        assert_eq!(res.tokens[0].base().leading_trivia.len(), 8);
+        assert_eq!(res.leading_trivia(&res.tokens[0]).len(), 8);
         assert_eq!(res.tokens[0].kind(), TokenKind::KwElse);
 
-        assert_eq!(res.tokens[0].base().leading_trivia[0].kind, TriviaKind::MultiLineComment);
-        assert_eq!(res.tokens[0].base().leading_trivia[0].lexeme, "--! com --");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].kind, TriviaKind::MultiLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].lexeme, "--! com --");
+
+        assert_eq!(res.leading_trivia(&res.tokens[0])[1].kind, TriviaKind::Whitespace);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[1].lexeme, " ");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[1].kind, TriviaKind::Whitespace);
-        assert_eq!(res.tokens[0].base().leading_trivia[1].lexeme, " ");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[2].kind, TriviaKind::MultiLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[2].lexeme, "--! com2 --");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[2].kind, TriviaKind::MultiLineComment);
-        assert_eq!(res.tokens[0].base().leading_trivia[2].lexeme, "--! com2 --");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[3].kind, TriviaKind::Whitespace);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[3].lexeme, " ");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[3].kind, TriviaKind::Whitespace);
-        assert_eq!(res.tokens[0].base().leading_trivia[3].lexeme, " ");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[4].kind, TriviaKind::SingleLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[4].lexeme, "-- com");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[4].kind, TriviaKind::SingleLineComment);
-        assert_eq!(res.tokens[0].base().leading_trivia[4].lexeme, "-- com");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[5].kind, TriviaKind::LineBreak);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[5].lexeme, "\n");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[5].kind, TriviaKind::LineBreak);
-        assert_eq!(res.tokens[0].base().leading_trivia[5].lexeme, "\n");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[6].kind, TriviaKind::SingleLineComment);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[6].lexeme, "--com do");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[6].kind, TriviaKind::SingleLineComment);
-        assert_eq!(res.tokens[0].base().leading_trivia[6].lexeme, "--com do");
+        assert_eq!(res.leading_trivia(&res.tokens[0])[7].kind, TriviaKind::LineBreak);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[7].lexeme, "\n");
+    }
+
+    #[test]
+    fn test_directive_trivia () {
+        let res = tokenize("#if debug\nelse");
 
-        assert_eq!(res.tokens[0].base().leading_trivia[7].kind, TriviaKind::LineBreak);
-        assert_eq!(res.tokens[0].base().leading_trivia[7].lexeme, "\n");
+        assert_eq!(res.tokens[0].kind(), TokenKind::KwElse);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].kind, TriviaKind::Directive);
+        assert_eq!(res.leading_trivia(&res.tokens[0])[0].lexeme, "#if debug");
+        assert_eq!(res.messages.errors.len(), 1);
+        assert_eq!(res.messages.errors[0].code, MessageCode::UnsupportedDirective);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,166 @@
+/// The unit a column is counted in. Tools disagree on this: the Language Server Protocol counts
+/// UTF-16 code units by default, while most terminals count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnUnit {
+    /// Bytes of UTF-8.
+    Utf8,
+    /// UTF-16 code units, where characters outside the Basic Multilingual Plane take two.
+    Utf16,
+    /// Unicode code points, which is what the lexer counts.
+    CodePoint,
+}
+
+/// Knows where each line of a source starts, to convert the byte offsets used by spans into lines
+/// and columns and back. Lines end at "\r\n", "\r" or "\n", the same as in the lexer. Both lines
+/// and columns start at 1.
+pub struct LineIndex<'src> {
+    src: &'src str,
+    /// The byte offset at which each line starts.
+    line_starts: Vec<usize>,
+}
+
+impl<'src> LineIndex<'src> {
+    pub fn new(src: &'src str) -> LineIndex<'src> {
+        // The byte order mark isn't part of the first line.
+        let first = match src.starts_with('\u{feff}') {
+            true => '\u{feff}'.len_utf8(),
+            false => 0,
+        };
+
+        let bytes = src.as_bytes();
+        let mut line_starts = vec![first];
+        let mut i = first;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    i += 1;
+                    line_starts.push(i + 1);
+                }
+                b'\r' | b'\n' => line_starts.push(i + 1),
+                _ => {}
+            }
+            i += 1;
+        }
+
+        LineIndex { src, line_starts }
+    }
+
+    /// Returns the amount of lines in the source. A source that ends with a line break has an empty
+    /// line after it.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the line the byte offset given is in.
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset).max(1)
+    }
+
+    /// Returns the line and column of the byte offset given, with the column counted in the unit
+    /// given. Offsets past the end of the source are moved to its end, and offsets inside a
+    /// character are moved to its start.
+    pub fn line_col(&self, offset: usize, unit: ColumnUnit) -> (usize, usize) {
+        let mut offset = offset.min(self.src.len());
+        while !self.src.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line(offset);
+        let start = self.line_starts[line - 1].min(offset);
+        let text = &self.src[start..offset];
+
+        let column = match unit {
+            ColumnUnit::Utf8 => text.len(),
+            ColumnUnit::Utf16 => text.encode_utf16().count(),
+            ColumnUnit::CodePoint => text.chars().count(),
+        };
+
+        (line, column + 1)
+    }
+
+    /// Returns the byte offset of the line and column given, with the column counted in the unit
+    /// given. Lines and columns past the end of the source or of their line are moved to that end,
+    /// and columns inside a character are moved to its start.
+    pub fn offset(&self, line: usize, column: usize, unit: ColumnUnit) -> usize {
+        let line = line.clamp(1, self.line_count());
+        let text = self.line_text(line);
+        let start = self.line_starts[line - 1];
+
+        let mut units = 0;
+        for (i, c) in text.char_indices() {
+            units += match unit {
+                ColumnUnit::Utf8 => c.len_utf8(),
+                ColumnUnit::Utf16 => c.len_utf16(),
+                ColumnUnit::CodePoint => 1,
+            };
+            if units >= column {
+                return start + i;
+            }
+        }
+
+        start + text.len()
+    }
+
+    /// Returns the text of the line given, without the line break that ends it.
+    pub fn line_text(&self, line: usize) -> &'src str {
+        let start = self.line_starts[line - 1];
+        let end = self.line_starts.get(line).copied().unwrap_or(self.src.len());
+
+        self.src[start..end].trim_end_matches(['\r', '\n'])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_breaks () {
+        let index = LineIndex::new("a\nb\r\nc\rd\n");
+
+        assert_eq!(index.line_count(), 5);
+        assert_eq!(index.line_text(1), "a");
+        assert_eq!(index.line_text(2), "b");
+        assert_eq!(index.line_text(3), "c");
+        assert_eq!(index.line_text(4), "d");
+        assert_eq!(index.line_text(5), "");
+
+        assert_eq!(index.line_col(0, ColumnUnit::Utf8), (1, 1));
+        assert_eq!(index.line_col(2, ColumnUnit::Utf8), (2, 1));
+        assert_eq!(index.line_col(5, ColumnUnit::Utf8), (3, 1));
+        assert_eq!(index.line_col(7, ColumnUnit::Utf8), (4, 1));
+        assert_eq!(index.line_col(100, ColumnUnit::Utf8), (5, 1));
+    }
+
+    #[test]
+    fn test_column_units () {
+        // "é" is 2 bytes and 1 UTF-16 unit, "𝔸" is 4 bytes and 2 UTF-16 units.
+        let src = "let é𝔸x = 1";
+        let index = LineIndex::new(src);
+        let x = src.find('x').unwrap();
+
+        assert_eq!(index.line_col(x, ColumnUnit::Utf8), (1, 11));
+        assert_eq!(index.line_col(x, ColumnUnit::Utf16), (1, 8));
+        assert_eq!(index.line_col(x, ColumnUnit::CodePoint), (1, 7));
+
+        assert_eq!(index.offset(1, 11, ColumnUnit::Utf8), x);
+        assert_eq!(index.offset(1, 8, ColumnUnit::Utf16), x);
+        assert_eq!(index.offset(1, 7, ColumnUnit::CodePoint), x);
+
+        // Offsets and columns inside a character are moved to its start.
+        assert_eq!(index.line_col(x - 1, ColumnUnit::Utf16), (1, 6));
+        assert_eq!(index.offset(1, 7, ColumnUnit::Utf16), x - 4);
+        assert_eq!(index.offset(1, 100, ColumnUnit::Utf8), src.len());
+    }
+
+    #[test]
+    fn test_byte_order_mark () {
+        let src = "\u{feff}a\nb";
+        let index = LineIndex::new(src);
+
+        assert_eq!(index.line_text(1), "a");
+        assert_eq!(index.line_col(3, ColumnUnit::Utf8), (1, 1));
+        assert_eq!(index.offset(1, 1, ColumnUnit::CodePoint), 3);
+        assert_eq!(index.offset(2, 1, ColumnUnit::CodePoint), 5);
+    }
+}
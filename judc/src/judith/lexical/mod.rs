@@ -1,2 +1,5 @@
 pub mod lexer;
-pub mod token;
\ No newline at end of file
+pub mod line_index;
+pub mod symbol;
+pub mod token;
+pub mod token_source;
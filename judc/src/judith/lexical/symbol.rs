@@ -0,0 +1,230 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Serializer};
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+use crate::judith::lexical::token::TokenKind;
+use crate::judith::syntax::arena::Arena;
+
+/// Every keyword and the token kind it produces.
+const KEYWORDS: [(&str, TokenKind); 37] = [
+    ("and", TokenKind::KwAnd),
+    ("break", TokenKind::KwBreak),
+    ("class", TokenKind::KwClass),
+    ("continue", TokenKind::KwContinue),
+    ("do", TokenKind::KwDo),
+    ("else", TokenKind::KwElse),
+    ("elsif", TokenKind::KwElsif),
+    ("end", TokenKind::KwEnd),
+    ("false", TokenKind::KwFalse),
+    ("final", TokenKind::KwFinal),
+    ("for", TokenKind::KwFor),
+    ("func", TokenKind::KwFunc),
+    ("generator", TokenKind::KwGenerator),
+    ("goto", TokenKind::KwGoto),
+    ("hid", TokenKind::KwHid),
+    ("if", TokenKind::KwIf),
+    ("in", TokenKind::KwIn),
+    ("interface", TokenKind::KwInterface),
+    ("let", TokenKind::KwLet),
+    ("loop", TokenKind::KwLoop),
+    ("match", TokenKind::KwMatch),
+    ("mut", TokenKind::KwMut),
+    ("not", TokenKind::KwNot),
+    ("null", TokenKind::KwNull),
+    ("or", TokenKind::KwOr),
+    ("pub", TokenKind::KwPub),
+    ("ref", TokenKind::KwRef),
+    ("return", TokenKind::KwReturn),
+    ("sh", TokenKind::KwSh),
+    ("struct", TokenKind::KwStruct),
+    ("then", TokenKind::KwThen),
+    ("true", TokenKind::KwTrue),
+    ("typedef", TokenKind::KwTypedef),
+    ("undefined", TokenKind::KwUndefined),
+    ("while", TokenKind::KwWhile),
+    ("yield", TokenKind::KwYield),
+    ("__p_print", TokenKind::PkwPrint),
+];
+
+/// The keywords, for the lexer and symbols to look them up.
+static KEYWORD_KINDS: Lazy<HashMap<&str, TokenKind>> = Lazy::new(|| KEYWORDS.into_iter().collect());
+
+/// A string stored once for a compilation, such as the name of an identifier. Symbols are as cheap
+/// to copy as a reference, and symbols from the same interner are compared by address.
+#[derive(Clone, Copy, Eq)]
+pub struct Symbol<'ast>(&'ast str);
+
+impl<'ast> Symbol<'ast> {
+    /// Returns a symbol for a name the compiler makes up, rather than one read from the source.
+    pub const fn from_static(str: &'static str) -> Symbol<'static> {
+        Symbol(str)
+    }
+
+    pub fn as_str(self) -> &'ast str {
+        self.0
+    }
+
+    /// Returns the kind of token produced by this symbol, if it's a keyword.
+    pub fn as_keyword(self) -> Option<TokenKind> {
+        Symbol::keyword(self.0)
+    }
+
+    /// Returns the kind of token produced by the string given, if it's a keyword.
+    pub fn keyword(str: &str) -> Option<TokenKind> {
+        KEYWORD_KINDS.get(str).copied()
+    }
+}
+
+impl PartialEq for Symbol<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0) || self.0 == other.0
+    }
+}
+
+impl Hash for Symbol<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Symbol<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+impl Deref for Symbol<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Display for Symbol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Debug for Symbol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Serialize for Symbol<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl PartialEq<str> for Symbol<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Stores the strings of the symbols of a compilation in the arena its syntax trees live in, once
+/// each. The parser creates one and returns it with the trees it parsed, so that later stages can
+/// keep interning into it.
+pub struct Interner<'ast> {
+    arena: &'ast Arena,
+    strings: RefCell<HashSet<&'ast str>>,
+}
+
+impl<'ast> Interner<'ast> {
+    pub fn new(arena: &'ast Arena) -> Interner<'ast> {
+        Interner {
+            arena,
+            strings: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Returns the arena the strings are stored in.
+    pub fn arena(&self) -> &'ast Arena {
+        self.arena
+    }
+
+    /// Returns the symbol of the string given, storing the string if this is the first time it's
+    /// seen.
+    pub fn intern(&self, str: &str) -> Symbol<'ast> {
+        let mut strings = self.strings.borrow_mut();
+        if let Some(interned) = strings.get(str) {
+            return Symbol(interned);
+        }
+
+        let interned = self.arena.alloc_str(str);
+        strings.insert(interned);
+
+        Symbol(interned)
+    }
+
+    /// Returns the symbol of the identifier given, normalized to NFC. This makes names that are
+    /// spelled the same but composed differently (e.g. "é" as one character or as "e" followed by
+    /// an accent) the same symbol.
+    pub fn intern_identifier(&self, name: &str) -> Symbol<'ast> {
+        match is_nfc_quick(name.chars()) {
+            IsNormalized::Yes => self.intern(name),
+            _ => self.intern(&name.nfc().collect::<String>()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning() {
+        println!("== Testing the symbol interner ==");
+
+        let arena = Arena::new();
+        let interner = Interner::new(&arena);
+
+        let a = interner.intern("interned_name");
+        let b = interner.intern(&String::from("interned_name"));
+        assert_eq!(a, b);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_ne!(a, interner.intern("other_name"));
+        assert_eq!(a.as_str(), "interned_name");
+        assert_eq!(a, "interned_name");
+        assert_eq!(a.as_keyword(), None);
+
+        for (keyword, kind) in KEYWORDS {
+            assert_eq!(interner.intern(keyword).as_keyword(), Some(kind));
+            assert_eq!(Symbol::keyword(keyword), Some(kind));
+        }
+        assert_eq!(Symbol::keyword("interned_name"), None);
+        assert_eq!(serde_json::to_string(&interner.intern("if")).unwrap(), "\"if\"");
+
+        println!("Testing symbols from different interners.");
+        let other_arena = Arena::new();
+        let other = Interner::new(&other_arena);
+        assert_eq!(other.intern("interned_name"), a);
+        assert_eq!(Symbol::from_static("interned_name"), a);
+
+        println!("Testing identifiers composed differently.");
+        let composed = interner.intern_identifier("caf\u{e9}");
+        let decomposed = interner.intern_identifier("cafe\u{301}");
+        assert_eq!(composed, decomposed);
+        assert_eq!(decomposed.as_str(), "caf\u{e9}");
+    }
+}
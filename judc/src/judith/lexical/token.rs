@@ -1,40 +1,43 @@
 use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, Range};
 use serde::Serialize;
 use crate::SourceSpan;
 
-#[derive(Clone, Serialize)]
-pub struct RegularToken {
+/// A token, whose lexeme is borrowed from the source it was read from. `start` and `end` are byte
+/// offsets into that source.
+#[derive(Clone, Copy, Serialize)]
+pub struct RegularToken<'src> {
     pub kind: TokenKind,
-    pub lexeme: String,
+    pub lexeme: &'src str,
     pub start: i64,
     pub end: i64,
     pub line: i64,
-    pub leading_trivia: Vec<Trivia>,
-    pub trailing_trivia: Vec<Trivia>,
+    pub leading_trivia: TriviaRange,
+    pub trailing_trivia: TriviaRange,
 }
 
-#[derive(Clone, Serialize)]
-pub struct StringToken {
-    pub base: RegularToken,
+#[derive(Clone, Copy, Serialize)]
+pub struct StringToken<'src> {
+    pub base: RegularToken<'src>,
     pub string_kind: StringLiteralKind,
     pub delimiter: char,
     pub delimiter_count: i32,
     pub column: i32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum StringLiteralKind {
     Regular,
     Raw,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub enum Token {
-    Regular(RegularToken),
-    String(StringToken),
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum Token<'src> {
+    Regular(RegularToken<'src>),
+    String(StringToken<'src>),
 }
 
-impl Token {
+impl<'src> Token<'src> {
     pub fn kind(&self) -> TokenKind {
         match self {
             Token::Regular(t) => t.kind.clone(),
@@ -42,7 +45,7 @@ impl Token {
         }
     }
 
-    pub fn base(&self) -> &RegularToken {
+    pub fn base(&self) -> &RegularToken<'src> {
         match self {
             Token::Regular(t) => t,
             Token::String(t) => &t.base,
@@ -50,10 +53,60 @@ impl Token {
     }
 }
 
+/// Identifies a token by its position in the list of tokens it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct TokenId(pub u32);
+
+impl TokenId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A copy of a token, along with its id in the list of tokens it belongs to.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenRef<'src> {
+    pub id: TokenId,
+    pub token: Token<'src>,
+}
+
+impl<'src> Deref for TokenRef<'src> {
+    type Target = Token<'src>;
+
+    fn deref(&self) -> &Token<'src> {
+        &self.token
+    }
+}
+
+/// A range of the list of trivia produced by the lexer, such as the trivia before a token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TriviaRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl TriviaRange {
+    pub fn new(start: usize, end: usize) -> TriviaRange {
+        TriviaRange { start: start as u32, end: end as u32 }
+    }
+
+    pub fn len(self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn range(self) -> Range<usize> {
+        self.start as usize..self.end as usize
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
-pub struct Trivia {
+pub struct Trivia<'src> {
     pub kind: TriviaKind,
-    pub lexeme: String,
+    pub lexeme: &'src str,
     pub span: SourceSpan,
 }
 
@@ -84,6 +137,7 @@ pub enum TokenKind {
     QuestionMark,
     Ampersand,
     Pipe,
+    Caret,
 
     // Two-character tokens.
     EqualEqual,
@@ -98,12 +152,21 @@ pub enum TokenKind {
     MinusArrow,
     DoubleColon,
     DoubleQuestionMark,
+    QuestionDot,
+    LessLess,
+    GreaterGreater,
+    PlusEqual,
+    MinusEqual,
+    AsteriskEqual,
+    SlashEqual,
 
     // Three-character tokens.
     EqualEqualEqual,
     BangEqualEqual,
 
     // % operators
+    Percent,
+    PercentEqual,
 
     // Identifier
     Identifier,
@@ -187,6 +250,7 @@ impl TokenKind {
             TokenKind::QuestionMark => "'?'",
             TokenKind::Ampersand => "'&'",
             TokenKind::Pipe => "'|'",
+            TokenKind::Caret => "'^'",
             TokenKind::EqualEqual => "'=='",
             TokenKind::BangEqual => "'!='",
             TokenKind::TildeTilde => "'~~'",
@@ -199,8 +263,17 @@ impl TokenKind {
             TokenKind::MinusArrow => "'->'",
             TokenKind::DoubleColon => "'::'",
             TokenKind::DoubleQuestionMark => "'??'",
+            TokenKind::QuestionDot => "'?.'",
+            TokenKind::LessLess => "'<<'",
+            TokenKind::GreaterGreater => "'>>'",
+            TokenKind::PlusEqual => "'+='",
+            TokenKind::MinusEqual => "'-='",
+            TokenKind::AsteriskEqual => "'*='",
+            TokenKind::SlashEqual => "'/='",
             TokenKind::EqualEqualEqual => "'==='",
             TokenKind::BangEqualEqual => "'!=='",
+            TokenKind::Percent => "'%'",
+            TokenKind::PercentEqual => "'%='",
             TokenKind::Identifier => "<identifier>",
             TokenKind::String => "<string literal>",
             TokenKind::Number => "<number literal>",
@@ -257,7 +330,7 @@ pub enum TriviaKind {
     Directive,
 }
 
-impl Debug for RegularToken {
+impl Debug for RegularToken<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RegularToken")
             .field("kind", &self.kind)
@@ -271,7 +344,7 @@ impl Debug for RegularToken {
     }
 }
 
-impl Debug for StringToken {
+impl Debug for StringToken<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StringToken")
             .field("base", &self.base)
@@ -0,0 +1,42 @@
+use std::slice;
+use std::vec;
+use crate::judith::lexical::lexer::Lexer;
+use crate::judith::lexical::token::Token;
+
+/// Something the parser can pull tokens from one at a time, such as a lexer reading the source as
+/// it goes or a list of tokens that were already read.
+///
+/// Pulling tokens lazily saves reading the whole source before parsing it, but not memory: the
+/// parser keeps every token it pulls, as nodes reference them by id, and a lexer keeps the trivia
+/// of every token it reads, as tokens reference it by range. Both grow with the source.
+pub trait TokenSource<'src> {
+    /// Returns the next token, or `None` once there are no more tokens. A source doesn't need to
+    /// end with an EOF token, as the parser adds one when it's missing.
+    fn next_token(&mut self) -> Option<Token<'src>>;
+}
+
+/// Reads tokens from the source lazily. Once the lexer reaches the end of the source, it keeps
+/// returning EOF tokens.
+impl<'src> TokenSource<'src> for Lexer<'src> {
+    fn next_token(&mut self) -> Option<Token<'src>> {
+        Some(Lexer::next_token(self))
+    }
+}
+
+impl<'a, 'src> TokenSource<'src> for slice::Iter<'a, Token<'src>> {
+    fn next_token(&mut self) -> Option<Token<'src>> {
+        self.next().copied()
+    }
+}
+
+impl<'src> TokenSource<'src> for vec::IntoIter<Token<'src>> {
+    fn next_token(&mut self) -> Option<Token<'src>> {
+        self.next()
+    }
+}
+
+impl<'src, S: TokenSource<'src> + ?Sized> TokenSource<'src> for &mut S {
+    fn next_token(&mut self) -> Option<Token<'src>> {
+        (**self).next_token()
+    }
+}
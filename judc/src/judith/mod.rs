@@ -1,4 +1,7 @@
 pub mod lexical;
 pub mod syntax;
 pub mod compiler_messages;
-mod analysis;
\ No newline at end of file
+pub mod analysis;
+pub mod interpreter;
+pub mod jasm;
+pub mod repl;
@@ -0,0 +1,378 @@
+use std::io::{BufRead, Write};
+use crate::judith::analysis::analyze;
+use crate::judith::analysis::cfg::ControlFlowGraph;
+use crate::judith::compiler_messages::{CompilerMessage, MessageCode, MessageContainer, MessageSource};
+use crate::judith::interpreter::tree_walker::Interpreter;
+use crate::judith::interpreter::value::Value;
+use crate::judith::lexical::lexer::tokenize;
+use crate::judith::lexical::symbol::Interner;
+use crate::judith::lexical::token::TokenKind;
+use crate::judith::syntax::arena::Arena;
+use crate::judith::syntax::nodes::SyntaxNode;
+use crate::judith::syntax::parser::{parse, parse_source_with};
+
+const HELP: &str = "\
+Enter Judith code to evaluate it. Declarations are kept between inputs.
+Input that isn't complete yet (e.g. an unclosed 'if' or '(') continues on the next line. An empty
+line forces incomplete input to be evaluated.
+
+Commands:
+  :tokens <code>  Dumps the tokens of the code given, and the trivia around them, as JSON.
+  :ast <code>     Dumps the syntax tree of the code given as JSON.
+  :cfg <code>     Dumps the control-flow graph of the code given in Graphviz's DOT format.
+  :help           Shows this message.
+  :quit           Exits the REPL.";
+
+/// What the REPL expects after receiving a line of input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReplStatus {
+    /// The input was handled, and the REPL is ready for new input.
+    Ready,
+    /// The input received so far is incomplete, and the next line continues it.
+    Continue,
+    /// The user asked to exit the REPL.
+    Quit,
+}
+
+/// An interactive session. Lines of input are fed to it one by one, and it writes anything it
+/// has to show (results, errors and the output of the program) to `out`.
+pub struct Repl<W: Write> {
+    /// The interpreter is shared by every input, so declarations persist between them. The nodes
+    /// it executes are leaked, as functions declared in one input can be called in later ones.
+    interpreter: Interpreter<'static, W>,
+    /// The arena of the whole session, which is leaked too. The nodes and names of every input
+    /// live in it.
+    arena: &'static Arena,
+    /// Interns the names of every input, so the same name is stored once per session.
+    interner: Interner<'static>,
+    /// The nodes of every input run so far. New input is analyzed after them, so it sees the
+    /// locals and functions they declared.
+    history: Vec<SyntaxNode<'static>>,
+    /// The input received so far, when it spans multiple lines.
+    pending: String,
+}
+
+impl<W: Write> Repl<W> {
+    pub fn new(out: W) -> Self {
+        let arena: &'static Arena = Box::leak(Box::new(Arena::new()));
+
+        Repl {
+            interpreter: Interpreter::new(out),
+            arena,
+            interner: Interner::new(arena),
+            history: Vec::new(),
+            pending: String::new(),
+        }
+    }
+
+    pub fn into_output(self) -> W {
+        self.interpreter.into_output()
+    }
+
+    /// The prompt that should be shown before reading the next line.
+    pub fn prompt(&self) -> &'static str {
+        if self.pending.is_empty() { "> " } else { ". " }
+    }
+
+    /// Feeds a line of input to the REPL.
+    pub fn feed_line(&mut self, line: &str) -> ReplStatus {
+        if self.pending.is_empty() {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() {
+                return ReplStatus::Ready;
+            }
+            if let Some(command) = trimmed.strip_prefix(':') {
+                return self.run_command(command);
+            }
+        }
+
+        // An empty line while continuing input forces it to be evaluated as it is.
+        let force = !self.pending.is_empty() && line.trim().is_empty();
+
+        self.pending.push_str(line);
+        self.pending.push('\n');
+
+        let src = std::mem::take(&mut self.pending);
+        if !force && is_incomplete(&src) {
+            self.pending = src;
+            return ReplStatus::Continue;
+        }
+
+        self.eval(&src);
+        ReplStatus::Ready
+    }
+
+    fn run_command(&mut self, command: &str) -> ReplStatus {
+        let (name, arg) = match command.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (command, ""),
+        };
+
+        match name {
+            "q" | "quit" => return ReplStatus::Quit,
+            "h" | "help" => self.write_line(HELP),
+            "tokens" => {
+                // Tokens reference their trivia by range, so the trivia they index is dumped too.
+                let lexer_res = tokenize(arg);
+                self.write_json(&lexer_res);
+                self.write_messages(&lexer_res.messages);
+            }
+            "ast" => {
+                let lexer_res = tokenize(arg);
+                let arena = Arena::new();
+                let parser_res = parse(lexer_res.tokens, &arena);
+                self.write_json(&parser_res.nodes);
+                self.write_messages(&lexer_res.messages);
+                self.write_messages(&parser_res.messages);
+            }
+            "cfg" => {
+                let lexer_res = tokenize(arg);
+                let arena = Arena::new();
+                let parser_res = parse(lexer_res.tokens, &arena);
+                self.write_line(ControlFlowGraph::from_nodes(&parser_res.nodes).to_dot().trim_end());
+                self.write_messages(&lexer_res.messages);
+                self.write_messages(&parser_res.messages);
+            }
+            _ => self.write_line(&format!("Unknown command ':{}'. Type :help for help.", name)),
+        }
+
+        ReplStatus::Ready
+    }
+
+    fn eval(&mut self, src: &str) {
+        let lexer_res = tokenize(src);
+        if lexer_res.messages.count() != 0 {
+            self.write_messages(&lexer_res.messages);
+            return;
+        }
+
+        let interner = std::mem::replace(&mut self.interner, Interner::new(self.arena));
+        let parser_res = parse_source_with(lexer_res.tokens.into_iter(), interner);
+        self.interner = parser_res.interner;
+        if parser_res.messages.count() != 0 {
+            self.write_messages(&parser_res.messages);
+            return;
+        }
+
+        // The history only holds inputs that ran without errors, so any error found belongs to this
+        // input. Warnings may come from the history too, so they aren't shown.
+        let history_len = self.history.len();
+        self.history.extend_from_slice(&parser_res.nodes);
+        let analyzer_res = analyze(&self.history);
+        if !analyzer_res.messages.errors.is_empty() {
+            self.history.truncate(history_len);
+            for msg in &analyzer_res.messages.errors {
+                self.write_message(msg);
+            }
+            return;
+        }
+
+        let nodes: &'static [SyntaxNode] = Box::leak(parser_res.nodes.into_boxed_slice());
+        match self.interpreter.run(nodes) {
+            Ok(Value::Void) => {}
+            Ok(Value::String(s)) => self.write_line(&format!("{:?} : String", s)),
+            Ok(val) => self.write_line(&format!("{} : {}", val, val.type_name())),
+            Err(msg) => {
+                self.history.truncate(history_len);
+                self.write_message(&msg);
+            }
+        }
+    }
+
+    fn write_line(&mut self, line: &str) {
+        let _ = writeln!(self.interpreter.output_mut(), "{}", line);
+    }
+
+    fn write_json<T: serde::Serialize>(&mut self, val: &T) {
+        match serde_json::to_string_pretty(val) {
+            Ok(json) => self.write_line(&json),
+            Err(err) => self.write_line(&format!("Couldn't serialize to JSON: {}", err)),
+        }
+    }
+
+    fn write_message(&mut self, msg: &CompilerMessage) {
+        self.write_line(&msg.get_elaborate_message(None));
+    }
+
+    fn write_messages(&mut self, messages: &MessageContainer) {
+        for msg in messages.all_messages() {
+            self.write_message(msg);
+        }
+    }
+}
+
+/// Returns `true` if the source given looks like the beginning of valid code, rather than
+/// invalid code: i.e. it contains an unterminated string, unclosed brackets or the parser
+/// reached its end while still expecting something (e.g. an `end` keyword).
+pub fn is_incomplete(src: &str) -> bool {
+    let lexer_res = tokenize(src);
+
+    if lexer_res.messages.all_messages().any(|msg| msg.code == MessageCode::UnterminatedString) {
+        return true;
+    }
+
+    let mut depth = 0;
+    for tok in &lexer_res.tokens {
+        match tok.kind() {
+            TokenKind::LeftParen | TokenKind::LeftSquareBracket | TokenKind::LeftCurlyBracket => {
+                depth += 1
+            }
+            TokenKind::RightParen | TokenKind::RightSquareBracket | TokenKind::RightCurlyBracket => {
+                depth -= 1
+            }
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        return true;
+    }
+
+    if lexer_res.messages.count() != 0 {
+        return false;
+    }
+
+    let arena = Arena::new();
+    let parser_res = parse(lexer_res.tokens, &arena);
+    parser_res.messages.all_messages().any(|msg| match &msg.source {
+        MessageSource::Token { kind, .. } => *kind == TokenKind::EOF,
+        MessageSource::Span(_) => false,
+    })
+}
+
+/// Runs a REPL that reads from `input` and writes to `out` until the input ends or the user
+/// quits.
+pub fn run<R: BufRead, W: Write>(mut input: R, out: W) {
+    let mut repl = Repl::new(out);
+
+    loop {
+        let prompt = repl.prompt();
+        let _ = write!(repl.interpreter.output_mut(), "{}", prompt);
+        let _ = repl.interpreter.output_mut().flush();
+
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+
+        if repl.feed_line(line.trim_end_matches(['\n', '\r'])) == ReplStatus::Quit {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(repl: Repl<Vec<u8>>) -> String {
+        String::from_utf8(repl.into_output()).unwrap()
+    }
+
+    #[test]
+    fn keeps_declarations_between_inputs () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line("let x = 20"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("func double (n: Num) => n * 2"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("double(x) + 2"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("\"a\" + \"b\""), ReplStatus::Ready);
+
+        assert_eq!(output(repl), "42 : Num\n\"ab\" : String\n");
+    }
+
+    #[test]
+    fn reports_semantic_errors () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line("let a, b = 1, 2, 3"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("let a, b = (1, 2)"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("a + b"), ReplStatus::Ready);
+
+        let out = output(repl);
+        println!("{}", out);
+        assert!(out.contains("[Analyzer / Error] 3000"));
+        assert!(out.ends_with("3 : Num\n"));
+    }
+
+    #[test]
+    fn analyzes_inputs_with_previous_declarations () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line("let x = 1"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("x = 2"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("let y: Num"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("y"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("let mut z = 1"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("z = x + 2"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("z"), ReplStatus::Ready);
+
+        let out = output(repl);
+        println!("{}", out);
+        assert!(out.contains("[Analyzer / Error] 3004"));
+        assert!(out.contains("[Analyzer / Error] 3003"));
+        assert!(out.ends_with("3 : Num\n"));
+    }
+
+    #[test]
+    fn forgets_inputs_that_fail_at_runtime () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line("let x: Num"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("x = 1 + \"a\""), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("x = 2"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line("x"), ReplStatus::Ready);
+
+        let out = output(repl);
+        println!("{}", out);
+        assert!(out.contains("[Interpreter / Error] 9"));
+        assert!(!out.contains("[Analyzer / Error]"));
+        assert!(out.ends_with("2 : Num\n"));
+    }
+
+    #[test]
+    fn multi_line_input () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line("if 1 < 2 then"), ReplStatus::Continue);
+        assert_eq!(repl.prompt(), ". ");
+        assert_eq!(repl.feed_line("    \"yes\""), ReplStatus::Continue);
+        assert_eq!(repl.feed_line("end"), ReplStatus::Ready);
+        assert_eq!(repl.prompt(), "> ");
+
+        assert_eq!(repl.feed_line("(1 +"), ReplStatus::Continue);
+        assert_eq!(repl.feed_line("2)"), ReplStatus::Ready);
+
+        assert_eq!(output(repl), "\"yes\" : String\n3 : Num\n");
+    }
+
+    #[test]
+    fn incomplete_input () {
+        assert!(is_incomplete("if x then"));
+        assert!(is_incomplete("func f (a: Num)"));
+        assert!(is_incomplete("\"\"\"raw"));
+        assert!(is_incomplete("f(1, "));
+        assert!(is_incomplete("let p = Point {"));
+
+        assert!(!is_incomplete("1 + 2"));
+        assert!(!is_incomplete("if x then y end"));
+        assert!(!is_incomplete("1 + )"));
+    }
+
+    #[test]
+    fn commands () {
+        let mut repl = Repl::new(Vec::new());
+
+        assert_eq!(repl.feed_line(":tokens 1 + 2 -- sum"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line(":cfg while x do y end"), ReplStatus::Ready);
+        assert_eq!(repl.feed_line(":quit"), ReplStatus::Quit);
+
+        let out = output(repl);
+        println!("{}", out);
+        assert!(out.contains("\"Number\""));
+        assert!(out.contains("\"Plus\""));
+        assert!(out.contains("\"-- sum\""));
+        assert!(out.contains("digraph cfg {"));
+    }
+}
@@ -0,0 +1,37 @@
+use bumpalo::Bump;
+
+/// Owns the nodes of syntax trees. Nodes are allocated one after another in large chunks, which is
+/// much cheaper than allocating each of them on its own, and are all freed at once when the arena
+/// is dropped. Trees borrow their nodes from the arena, so it must outlive them.
+///
+/// Only `Copy` values can be allocated, as the arena never runs destructors.
+#[derive(Default)]
+pub struct Arena {
+    bump: Bump,
+}
+
+impl Arena {
+    pub fn new() -> Arena {
+        Arena {
+            bump: Bump::new(),
+        }
+    }
+
+    pub fn alloc<T: Copy>(&self, val: T) -> &T {
+        self.bump.alloc(val)
+    }
+
+    /// Moves the values given into the arena.
+    pub fn alloc_slice<T: Copy>(&self, vals: &[T]) -> &[T] {
+        self.bump.alloc_slice_copy(vals)
+    }
+
+    pub fn alloc_str(&self, str: &str) -> &str {
+        self.bump.alloc_str(str)
+    }
+
+    /// Returns the amount of bytes taken by the chunks of this arena.
+    pub fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes()
+    }
+}
@@ -0,0 +1,969 @@
+use crate::judith::syntax::arena::Arena;
+use crate::judith::syntax::nodes::*;
+
+/// Rebuilds a syntax tree, taking ownership of each node and returning the node that replaces it.
+/// Each `fold_*` method, by default, calls the `walk_*` function of the same name, which folds the
+/// children of the node and returns it with them. To replace some kind of node, override its
+/// method and return the new node (calling the `walk_*` function first if its children should be
+/// folded too). Methods that return an enum, such as `fold_expr`, can replace a node with one of a
+/// different kind. Nodes created by a fold keep whatever id they are given, so ids should be
+/// assigned again (see `node_id::assign_node_ids`) before using the tree with side tables.
+pub trait Fold<'ast> {
+    /// Returns the arena where the nodes rebuilt are allocated.
+    fn arena(&self) -> &'ast Arena;
+
+    fn fold_node(&mut self, node: SyntaxNode<'ast>) -> SyntaxNode<'ast> {
+        walk_node(self, node)
+    }
+
+    // region Items
+    fn fold_item(&mut self, item: Item<'ast>) -> Item<'ast> {
+        walk_item(self, item)
+    }
+
+    fn fold_func_def(&mut self, def: FuncDef<'ast>) -> FuncDef<'ast> {
+        walk_func_def(self, def)
+    }
+    // endregion Items
+
+    // region Bodies
+    fn fold_body(&mut self, body: Body<'ast>) -> Body<'ast> {
+        walk_body(self, body)
+    }
+
+    fn fold_block_body(&mut self, body: BlockBody<'ast>) -> BlockBody<'ast> {
+        walk_block_body(self, body)
+    }
+
+    fn fold_arrow_body(&mut self, body: ArrowBody<'ast>) -> ArrowBody<'ast> {
+        walk_arrow_body(self, body)
+    }
+
+    fn fold_expr_body(&mut self, body: ExprBody<'ast>) -> ExprBody<'ast> {
+        walk_expr_body(self, body)
+    }
+    // endregion Bodies
+
+    // region Statements
+    fn fold_stmt(&mut self, stmt: Stmt<'ast>) -> Stmt<'ast> {
+        walk_stmt(self, stmt)
+    }
+
+    fn fold_expr_stmt(&mut self, stmt: ExprStmt<'ast>) -> ExprStmt<'ast> {
+        walk_expr_stmt(self, stmt)
+    }
+
+    fn fold_local_decl_stmt(&mut self, stmt: LocalDeclStmt<'ast>) -> LocalDeclStmt<'ast> {
+        walk_local_decl_stmt(self, stmt)
+    }
+
+    fn fold_priv_print_stmt(&mut self, stmt: PrivPrintStmt<'ast>) -> PrivPrintStmt<'ast> {
+        walk_priv_print_stmt(self, stmt)
+    }
+
+    fn fold_partial_local_decl(&mut self, decl: PartialLocalDecl<'ast>) -> PartialLocalDecl<'ast> {
+        walk_partial_local_decl(self, decl)
+    }
+
+    fn fold_regular_local_decl(&mut self, decl: RegularLocalDecl<'ast>) -> RegularLocalDecl<'ast> {
+        walk_regular_local_decl(self, decl)
+    }
+
+    fn fold_destructured_local_decl(
+        &mut self, decl: DestructuredLocalDecl<'ast>
+    ) -> DestructuredLocalDecl<'ast> {
+        walk_destructured_local_decl(self, decl)
+    }
+    // endregion Statements
+
+    // region Expressions
+    fn fold_expr(&mut self, expr: Expr<'ast>) -> Expr<'ast> {
+        walk_expr(self, expr)
+    }
+
+    fn fold_if_expr(&mut self, expr: IfExpr<'ast>) -> IfExpr<'ast> {
+        walk_if_expr(self, expr)
+    }
+
+    fn fold_loop_expr(&mut self, expr: LoopExpr<'ast>) -> LoopExpr<'ast> {
+        walk_loop_expr(self, expr)
+    }
+
+    fn fold_while_expr(&mut self, expr: WhileExpr<'ast>) -> WhileExpr<'ast> {
+        walk_while_expr(self, expr)
+    }
+
+    fn fold_assignment_expr(&mut self, expr: AssignmentExpr<'ast>) -> AssignmentExpr<'ast> {
+        walk_assignment_expr(self, expr)
+    }
+
+    fn fold_binary_expr(&mut self, expr: BinaryExpr<'ast>) -> BinaryExpr<'ast> {
+        walk_binary_expr(self, expr)
+    }
+
+    fn fold_left_unary_expr(&mut self, expr: LeftUnaryExpr<'ast>) -> LeftUnaryExpr<'ast> {
+        walk_left_unary_expr(self, expr)
+    }
+
+    fn fold_right_unary_expr(&mut self, expr: RightUnaryExpr<'ast>) -> RightUnaryExpr<'ast> {
+        walk_right_unary_expr(self, expr)
+    }
+
+    fn fold_group_expr(&mut self, expr: GroupExpr<'ast>) -> GroupExpr<'ast> {
+        walk_group_expr(self, expr)
+    }
+
+    fn fold_tuple_expr(&mut self, expr: TupleExpr<'ast>) -> TupleExpr<'ast> {
+        walk_tuple_expr(self, expr)
+    }
+
+    fn fold_array_expr(&mut self, expr: ArrayExpr<'ast>) -> ArrayExpr<'ast> {
+        walk_array_expr(self, expr)
+    }
+
+    fn fold_empty_collection_expr(&mut self, expr: EmptyCollectionExpr) -> EmptyCollectionExpr {
+        expr
+    }
+
+    fn fold_object_init_expr(&mut self, expr: ObjectInitExpr<'ast>) -> ObjectInitExpr<'ast> {
+        walk_object_init_expr(self, expr)
+    }
+
+    fn fold_access_expr(&mut self, expr: AccessExpr<'ast>) -> AccessExpr<'ast> {
+        walk_access_expr(self, expr)
+    }
+
+    fn fold_call_expr(&mut self, expr: CallExpr<'ast>) -> CallExpr<'ast> {
+        walk_call_expr(self, expr)
+    }
+
+    fn fold_index_expr(&mut self, expr: IndexExpr<'ast>) -> IndexExpr<'ast> {
+        walk_index_expr(self, expr)
+    }
+
+    fn fold_lambda_expr(&mut self, expr: LambdaExpr<'ast>) -> LambdaExpr<'ast> {
+        walk_lambda_expr(self, expr)
+    }
+
+    fn fold_identifier_expr(&mut self, expr: IdentifierExpr<'ast>) -> IdentifierExpr<'ast> {
+        walk_identifier_expr(self, expr)
+    }
+
+    fn fold_literal_expr(&mut self, expr: LiteralExpr<'ast>) -> LiteralExpr<'ast> {
+        walk_literal_expr(self, expr)
+    }
+
+    /// Folds an error node, in any position (node, statement, expression or type).
+    fn fold_error_node(&mut self, node: ErrorNode) -> ErrorNode {
+        node
+    }
+    // endregion Expressions
+
+    // region Fragments
+    fn fold_identifier(&mut self, identifier: Identifier<'ast>) -> Identifier<'ast> {
+        walk_identifier(self, identifier)
+    }
+
+    fn fold_simple_identifier(
+        &mut self, identifier: SimpleIdentifier<'ast>
+    ) -> SimpleIdentifier<'ast> {
+        identifier
+    }
+
+    fn fold_qualified_identifier(
+        &mut self, identifier: QualifiedIdentifier<'ast>
+    ) -> QualifiedIdentifier<'ast> {
+        walk_qualified_identifier(self, identifier)
+    }
+
+    fn fold_literal(&mut self, literal: Literal<'ast>) -> Literal<'ast> {
+        literal
+    }
+
+    fn fold_operator(&mut self, operator: Operator) -> Operator {
+        operator
+    }
+
+    fn fold_equals_value_clause(
+        &mut self, clause: EqualsValueClause<'ast>
+    ) -> EqualsValueClause<'ast> {
+        walk_equals_value_clause(self, clause)
+    }
+
+    fn fold_type_annotation(&mut self, annotation: TypeAnnotation<'ast>) -> TypeAnnotation<'ast> {
+        walk_type_annotation(self, annotation)
+    }
+
+    fn fold_parameter_list(&mut self, params: ParameterList<'ast>) -> ParameterList<'ast> {
+        walk_parameter_list(self, params)
+    }
+
+    fn fold_parameter(&mut self, param: Parameter<'ast>) -> Parameter<'ast> {
+        walk_parameter(self, param)
+    }
+
+    fn fold_argument_list(&mut self, args: ArgumentList<'ast>) -> ArgumentList<'ast> {
+        walk_argument_list(self, args)
+    }
+
+    fn fold_argument(&mut self, arg: Argument<'ast>) -> Argument<'ast> {
+        walk_argument(self, arg)
+    }
+
+    fn fold_local_declarator(
+        &mut self, declarator: LocalDeclarator<'ast>
+    ) -> LocalDeclarator<'ast> {
+        walk_local_declarator(self, declarator)
+    }
+
+    fn fold_field_init(&mut self, field_init: FieldInit<'ast>) -> FieldInit<'ast> {
+        walk_field_init(self, field_init)
+    }
+
+    fn fold_object_initializer(
+        &mut self, initializer: ObjectInitializer<'ast>
+    ) -> ObjectInitializer<'ast> {
+        walk_object_initializer(self, initializer)
+    }
+    // endregion Fragments
+
+    // region Type nodes
+    fn fold_type_node(&mut self, ty: TypeNode<'ast>) -> TypeNode<'ast> {
+        walk_type_node(self, ty)
+    }
+
+    fn fold_partial_type(&mut self, ty: PartialType<'ast>) -> PartialType<'ast> {
+        walk_partial_type(self, ty)
+    }
+
+    fn fold_identifier_type(&mut self, ty: IdentifierType<'ast>) -> IdentifierType<'ast> {
+        walk_identifier_type(self, ty)
+    }
+
+    fn fold_group_type(&mut self, ty: GroupType<'ast>) -> GroupType<'ast> {
+        walk_group_type(self, ty)
+    }
+
+    fn fold_function_type(&mut self, ty: FunctionType<'ast>) -> FunctionType<'ast> {
+        walk_function_type(self, ty)
+    }
+
+    fn fold_tuple_array_type(&mut self, ty: TupleArrayType<'ast>) -> TupleArrayType<'ast> {
+        walk_tuple_array_type(self, ty)
+    }
+
+    fn fold_raw_array_type(&mut self, ty: RawArrayType<'ast>) -> RawArrayType<'ast> {
+        walk_raw_array_type(self, ty)
+    }
+
+    fn fold_literal_type(&mut self, ty: LiteralType<'ast>) -> LiteralType<'ast> {
+        walk_literal_type(self, ty)
+    }
+
+    fn fold_sum_type(&mut self, ty: SumType<'ast>) -> SumType<'ast> {
+        walk_sum_type(self, ty)
+    }
+
+    fn fold_product_type(&mut self, ty: ProductType<'ast>) -> ProductType<'ast> {
+        walk_product_type(self, ty)
+    }
+    // endregion Type nodes
+}
+
+/// Folds every node given, in order.
+pub fn fold_nodes<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, nodes: Vec<SyntaxNode<'ast>>
+) -> Vec<SyntaxNode<'ast>> {
+    nodes.into_iter().map(|node| folder.fold_node(node)).collect()
+}
+
+/// Folds a node allocated in the arena, allocating the node that replaces it.
+fn fold_ref<'ast, F: Fold<'ast> + ?Sized, T: Copy>(
+    folder: &mut F, node: &'ast T, fold: impl FnOnce(&mut F, T) -> T
+) -> &'ast T {
+    let node = fold(folder, *node);
+    folder.arena().alloc(node)
+}
+
+/// Folds every node in a slice allocated in the arena, allocating the slice that replaces it.
+fn fold_slice<'ast, F: Fold<'ast> + ?Sized, T: Copy>(
+    folder: &mut F, nodes: &'ast [T], mut fold: impl FnMut(&mut F, T) -> T
+) -> &'ast [T] {
+    let nodes: Vec<T> = nodes.iter().map(|node| fold(folder, *node)).collect();
+    folder.arena().alloc_slice(&nodes)
+}
+
+pub fn walk_node<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, node: SyntaxNode<'ast>
+) -> SyntaxNode<'ast> {
+    match node {
+        SyntaxNode::Item(item) => SyntaxNode::Item(folder.fold_item(item)),
+        SyntaxNode::Stmt(stmt) => SyntaxNode::Stmt(folder.fold_stmt(stmt)),
+        SyntaxNode::Expr(expr) => SyntaxNode::Expr(folder.fold_expr(expr)),
+        SyntaxNode::Error(err) => SyntaxNode::Error(folder.fold_error_node(err)),
+    }
+}
+
+// region Items
+pub fn walk_item<'ast, F: Fold<'ast> + ?Sized>(folder: &mut F, item: Item<'ast>) -> Item<'ast> {
+    match item {
+        Item::FuncDef(def) => Item::FuncDef(fold_ref(folder, def, F::fold_func_def)),
+    }
+}
+
+pub fn walk_func_def<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, def: FuncDef<'ast>
+) -> FuncDef<'ast> {
+    FuncDef {
+        name: folder.fold_simple_identifier(def.name),
+        params: folder.fold_parameter_list(def.params),
+        return_type: def.return_type.map(|ty| folder.fold_type_node(ty)),
+        body: folder.fold_body(def.body),
+        ..def
+    }
+}
+// endregion Items
+
+// region Bodies
+pub fn walk_body<'ast, F: Fold<'ast> + ?Sized>(folder: &mut F, body: Body<'ast>) -> Body<'ast> {
+    match body {
+        Body::Block(body) => Body::Block(folder.fold_block_body(body)),
+        Body::Arrow(body) => Body::Arrow(folder.fold_arrow_body(body)),
+        Body::Expr(body) => Body::Expr(folder.fold_expr_body(body)),
+    }
+}
+
+pub fn walk_block_body<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, body: BlockBody<'ast>
+) -> BlockBody<'ast> {
+    BlockBody {
+        nodes: fold_slice(folder, body.nodes, F::fold_node),
+        ..body
+    }
+}
+
+pub fn walk_arrow_body<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, body: ArrowBody<'ast>
+) -> ArrowBody<'ast> {
+    ArrowBody {
+        expr: folder.fold_expr(body.expr),
+        ..body
+    }
+}
+
+pub fn walk_expr_body<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, body: ExprBody<'ast>
+) -> ExprBody<'ast> {
+    ExprBody {
+        expr: folder.fold_expr(body.expr),
+        ..body
+    }
+}
+// endregion Bodies
+
+// region Statements
+pub fn walk_stmt<'ast, F: Fold<'ast> + ?Sized>(folder: &mut F, stmt: Stmt<'ast>) -> Stmt<'ast> {
+    match stmt {
+        Stmt::Expr(stmt) => Stmt::Expr(folder.fold_expr_stmt(stmt)),
+        Stmt::LocalDecl(stmt) => Stmt::LocalDecl(fold_ref(folder, stmt, F::fold_local_decl_stmt)),
+        Stmt::PrivPrint(stmt) => Stmt::PrivPrint(folder.fold_priv_print_stmt(stmt)),
+        Stmt::Error(err) => Stmt::Error(folder.fold_error_node(err)),
+    }
+}
+
+pub fn walk_expr_stmt<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, stmt: ExprStmt<'ast>
+) -> ExprStmt<'ast> {
+    ExprStmt {
+        expr: folder.fold_expr(stmt.expr),
+        ..stmt
+    }
+}
+
+pub fn walk_local_decl_stmt<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, stmt: LocalDeclStmt<'ast>
+) -> LocalDeclStmt<'ast> {
+    LocalDeclStmt {
+        declarators: fold_slice(folder, stmt.declarators, F::fold_partial_local_decl),
+        initializer: stmt.initializer.map(|init| folder.fold_equals_value_clause(init)),
+        ..stmt
+    }
+}
+
+pub fn walk_priv_print_stmt<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, stmt: PrivPrintStmt<'ast>
+) -> PrivPrintStmt<'ast> {
+    PrivPrintStmt {
+        expr: folder.fold_expr(stmt.expr),
+        ..stmt
+    }
+}
+
+pub fn walk_partial_local_decl<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, decl: PartialLocalDecl<'ast>
+) -> PartialLocalDecl<'ast> {
+    match decl {
+        PartialLocalDecl::Regular(decl) => {
+            PartialLocalDecl::Regular(folder.fold_regular_local_decl(decl))
+        }
+        PartialLocalDecl::Destructured(decl) => {
+            PartialLocalDecl::Destructured(folder.fold_destructured_local_decl(decl))
+        }
+    }
+}
+
+pub fn walk_regular_local_decl<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, decl: RegularLocalDecl<'ast>
+) -> RegularLocalDecl<'ast> {
+    RegularLocalDecl {
+        declarator: folder.fold_local_declarator(decl.declarator),
+        ..decl
+    }
+}
+
+pub fn walk_destructured_local_decl<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, decl: DestructuredLocalDecl<'ast>
+) -> DestructuredLocalDecl<'ast> {
+    DestructuredLocalDecl {
+        declarators: fold_slice(folder, decl.declarators, F::fold_local_declarator),
+        ..decl
+    }
+}
+// endregion Statements
+
+// region Expressions
+pub fn walk_expr<'ast, F: Fold<'ast> + ?Sized>(folder: &mut F, expr: Expr<'ast>) -> Expr<'ast> {
+    match expr {
+        Expr::If(expr) => Expr::If(fold_ref(folder, expr, F::fold_if_expr)),
+        Expr::Loop(expr) => Expr::Loop(fold_ref(folder, expr, F::fold_loop_expr)),
+        Expr::While(expr) => Expr::While(fold_ref(folder, expr, F::fold_while_expr)),
+        Expr::Assignment(expr) => Expr::Assignment(fold_ref(folder, expr, F::fold_assignment_expr)),
+        Expr::Binary(expr) => Expr::Binary(fold_ref(folder, expr, F::fold_binary_expr)),
+        Expr::LeftUnary(expr) => Expr::LeftUnary(fold_ref(folder, expr, F::fold_left_unary_expr)),
+        Expr::RightUnary(expr) => Expr::RightUnary(fold_ref(folder, expr, F::fold_right_unary_expr)),
+        Expr::Group(expr) => Expr::Group(fold_ref(folder, expr, F::fold_group_expr)),
+        Expr::Tuple(expr) => Expr::Tuple(fold_ref(folder, expr, F::fold_tuple_expr)),
+        Expr::Array(expr) => Expr::Array(fold_ref(folder, expr, F::fold_array_expr)),
+        Expr::EmptyCollection(expr) => {
+            Expr::EmptyCollection(fold_ref(folder, expr, F::fold_empty_collection_expr))
+        }
+        Expr::ObjectInit(expr) => Expr::ObjectInit(fold_ref(folder, expr, F::fold_object_init_expr)),
+        Expr::Access(expr) => Expr::Access(fold_ref(folder, expr, F::fold_access_expr)),
+        Expr::Call(expr) => Expr::Call(fold_ref(folder, expr, F::fold_call_expr)),
+        Expr::Index(expr) => Expr::Index(fold_ref(folder, expr, F::fold_index_expr)),
+        Expr::Lambda(expr) => Expr::Lambda(fold_ref(folder, expr, F::fold_lambda_expr)),
+        Expr::Identifier(expr) => Expr::Identifier(fold_ref(folder, expr, F::fold_identifier_expr)),
+        Expr::Literal(expr) => Expr::Literal(fold_ref(folder, expr, F::fold_literal_expr)),
+        Expr::Error(err) => Expr::Error(folder.fold_error_node(err)),
+    }
+}
+
+pub fn walk_if_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: IfExpr<'ast>
+) -> IfExpr<'ast> {
+    IfExpr {
+        test: folder.fold_expr(expr.test),
+        consequent: folder.fold_body(expr.consequent),
+        alternate: expr.alternate.map(|alternate| folder.fold_body(alternate)),
+        ..expr
+    }
+}
+
+pub fn walk_loop_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: LoopExpr<'ast>
+) -> LoopExpr<'ast> {
+    LoopExpr {
+        body: folder.fold_body(expr.body),
+        ..expr
+    }
+}
+
+pub fn walk_while_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: WhileExpr<'ast>
+) -> WhileExpr<'ast> {
+    WhileExpr {
+        test: folder.fold_expr(expr.test),
+        body: folder.fold_body(expr.body),
+        ..expr
+    }
+}
+
+pub fn walk_assignment_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: AssignmentExpr<'ast>
+) -> AssignmentExpr<'ast> {
+    AssignmentExpr {
+        left: folder.fold_expr(expr.left),
+        operator: folder.fold_operator(expr.operator),
+        right: folder.fold_expr(expr.right),
+        ..expr
+    }
+}
+
+pub fn walk_binary_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: BinaryExpr<'ast>
+) -> BinaryExpr<'ast> {
+    BinaryExpr {
+        left: folder.fold_expr(expr.left),
+        operator: folder.fold_operator(expr.operator),
+        right: folder.fold_expr(expr.right),
+        ..expr
+    }
+}
+
+pub fn walk_left_unary_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: LeftUnaryExpr<'ast>
+) -> LeftUnaryExpr<'ast> {
+    LeftUnaryExpr {
+        operator: folder.fold_operator(expr.operator),
+        expr: folder.fold_expr(expr.expr),
+        ..expr
+    }
+}
+
+pub fn walk_right_unary_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: RightUnaryExpr<'ast>
+) -> RightUnaryExpr<'ast> {
+    RightUnaryExpr {
+        expr: folder.fold_expr(expr.expr),
+        operator: folder.fold_operator(expr.operator),
+        ..expr
+    }
+}
+
+pub fn walk_group_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: GroupExpr<'ast>
+) -> GroupExpr<'ast> {
+    GroupExpr {
+        expr: folder.fold_expr(expr.expr),
+        ..expr
+    }
+}
+
+pub fn walk_tuple_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: TupleExpr<'ast>
+) -> TupleExpr<'ast> {
+    TupleExpr {
+        elements: fold_slice(folder, expr.elements, F::fold_expr),
+        ..expr
+    }
+}
+
+pub fn walk_array_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: ArrayExpr<'ast>
+) -> ArrayExpr<'ast> {
+    ArrayExpr {
+        elements: fold_slice(folder, expr.elements, F::fold_expr),
+        ..expr
+    }
+}
+
+pub fn walk_object_init_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: ObjectInitExpr<'ast>
+) -> ObjectInitExpr<'ast> {
+    ObjectInitExpr {
+        provider: expr.provider.map(|provider| folder.fold_expr(provider)),
+        initializer: folder.fold_object_initializer(expr.initializer),
+        ..expr
+    }
+}
+
+pub fn walk_access_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: AccessExpr<'ast>
+) -> AccessExpr<'ast> {
+    AccessExpr {
+        receiver: expr.receiver.map(|receiver| folder.fold_expr(receiver)),
+        operator: folder.fold_operator(expr.operator),
+        member: folder.fold_simple_identifier(expr.member),
+        ..expr
+    }
+}
+
+pub fn walk_call_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: CallExpr<'ast>
+) -> CallExpr<'ast> {
+    CallExpr {
+        callee: folder.fold_expr(expr.callee),
+        arguments: folder.fold_argument_list(expr.arguments),
+        ..expr
+    }
+}
+
+pub fn walk_index_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: IndexExpr<'ast>
+) -> IndexExpr<'ast> {
+    IndexExpr {
+        receiver: folder.fold_expr(expr.receiver),
+        index: folder.fold_expr(expr.index),
+        ..expr
+    }
+}
+
+pub fn walk_lambda_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: LambdaExpr<'ast>
+) -> LambdaExpr<'ast> {
+    LambdaExpr {
+        params: folder.fold_parameter_list(expr.params),
+        return_type: expr.return_type.map(|ty| folder.fold_type_node(ty)),
+        body: folder.fold_body(expr.body),
+        ..expr
+    }
+}
+
+pub fn walk_identifier_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: IdentifierExpr<'ast>
+) -> IdentifierExpr<'ast> {
+    IdentifierExpr {
+        identifier: folder.fold_identifier(expr.identifier),
+        ..expr
+    }
+}
+
+pub fn walk_literal_expr<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, expr: LiteralExpr<'ast>
+) -> LiteralExpr<'ast> {
+    LiteralExpr {
+        literal: folder.fold_literal(expr.literal),
+        ..expr
+    }
+}
+// endregion Expressions
+
+// region Fragments
+pub fn walk_identifier<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, identifier: Identifier<'ast>
+) -> Identifier<'ast> {
+    match identifier {
+        Identifier::Simple(identifier) => Identifier::Simple(folder.fold_simple_identifier(identifier)),
+        Identifier::Qualified(identifier) => {
+            Identifier::Qualified(fold_ref(folder, identifier, F::fold_qualified_identifier))
+        }
+    }
+}
+
+pub fn walk_qualified_identifier<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, identifier: QualifiedIdentifier<'ast>
+) -> QualifiedIdentifier<'ast> {
+    QualifiedIdentifier {
+        qualifier: folder.fold_identifier(identifier.qualifier),
+        operator: folder.fold_operator(identifier.operator),
+        name: folder.fold_simple_identifier(identifier.name),
+        ..identifier
+    }
+}
+
+pub fn walk_equals_value_clause<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, clause: EqualsValueClause<'ast>
+) -> EqualsValueClause<'ast> {
+    EqualsValueClause {
+        values: fold_slice(folder, clause.values, F::fold_expr),
+        ..clause
+    }
+}
+
+pub fn walk_type_annotation<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, annotation: TypeAnnotation<'ast>
+) -> TypeAnnotation<'ast> {
+    TypeAnnotation {
+        ty: folder.fold_type_node(annotation.ty),
+        ..annotation
+    }
+}
+
+pub fn walk_parameter_list<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, params: ParameterList<'ast>
+) -> ParameterList<'ast> {
+    ParameterList {
+        params: fold_slice(folder, params.params, F::fold_parameter),
+        ..params
+    }
+}
+
+pub fn walk_parameter<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, param: Parameter<'ast>
+) -> Parameter<'ast> {
+    Parameter {
+        declarator: folder.fold_regular_local_decl(param.declarator),
+        default_val: param.default_val.map(|default_val| folder.fold_equals_value_clause(default_val)),
+        ..param
+    }
+}
+
+pub fn walk_argument_list<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, args: ArgumentList<'ast>
+) -> ArgumentList<'ast> {
+    ArgumentList {
+        arguments: fold_slice(folder, args.arguments, F::fold_argument),
+        ..args
+    }
+}
+
+pub fn walk_argument<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, arg: Argument<'ast>
+) -> Argument<'ast> {
+    Argument {
+        expr: folder.fold_expr(arg.expr),
+        ..arg
+    }
+}
+
+pub fn walk_local_declarator<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, declarator: LocalDeclarator<'ast>
+) -> LocalDeclarator<'ast> {
+    LocalDeclarator {
+        name: folder.fold_simple_identifier(declarator.name),
+        type_annotation: declarator.type_annotation.map(|ann| folder.fold_type_annotation(ann)),
+        ..declarator
+    }
+}
+
+pub fn walk_field_init<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, field_init: FieldInit<'ast>
+) -> FieldInit<'ast> {
+    FieldInit {
+        field_name: folder.fold_simple_identifier(field_init.field_name),
+        initializer: folder.fold_equals_value_clause(field_init.initializer),
+        ..field_init
+    }
+}
+
+pub fn walk_object_initializer<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, initializer: ObjectInitializer<'ast>
+) -> ObjectInitializer<'ast> {
+    ObjectInitializer {
+        field_inits: fold_slice(folder, initializer.field_inits, F::fold_field_init),
+        ..initializer
+    }
+}
+// endregion Fragments
+
+// region Type nodes
+pub fn walk_type_node<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: TypeNode<'ast>
+) -> TypeNode<'ast> {
+    TypeNode {
+        ty: folder.fold_partial_type(ty.ty),
+        ..ty
+    }
+}
+
+pub fn walk_partial_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: PartialType<'ast>
+) -> PartialType<'ast> {
+    match ty {
+        PartialType::Identifier(ty) => PartialType::Identifier(folder.fold_identifier_type(ty)),
+        PartialType::Group(ty) => PartialType::Group(fold_ref(folder, ty, F::fold_group_type)),
+        PartialType::Function(ty) => PartialType::Function(fold_ref(folder, ty, F::fold_function_type)),
+        PartialType::TupleArray(ty) => {
+            PartialType::TupleArray(fold_ref(folder, ty, F::fold_tuple_array_type))
+        }
+        PartialType::RawArray(ty) => PartialType::RawArray(fold_ref(folder, ty, F::fold_raw_array_type)),
+        PartialType::Literal(ty) => PartialType::Literal(folder.fold_literal_type(ty)),
+        PartialType::Sum(ty) => PartialType::Sum(fold_ref(folder, ty, F::fold_sum_type)),
+        PartialType::Product(ty) => PartialType::Product(fold_ref(folder, ty, F::fold_product_type)),
+        PartialType::Error(err) => PartialType::Error(folder.fold_error_node(err)),
+    }
+}
+
+pub fn walk_identifier_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: IdentifierType<'ast>
+) -> IdentifierType<'ast> {
+    IdentifierType {
+        name: folder.fold_identifier(ty.name),
+        ..ty
+    }
+}
+
+pub fn walk_group_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: GroupType<'ast>
+) -> GroupType<'ast> {
+    GroupType {
+        ty: folder.fold_type_node(ty.ty),
+        ..ty
+    }
+}
+
+pub fn walk_function_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: FunctionType<'ast>
+) -> FunctionType<'ast> {
+    FunctionType {
+        param_types: fold_slice(folder, ty.param_types, F::fold_type_node),
+        return_type: folder.fold_type_node(ty.return_type),
+        ..ty
+    }
+}
+
+pub fn walk_tuple_array_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: TupleArrayType<'ast>
+) -> TupleArrayType<'ast> {
+    TupleArrayType {
+        member_types: fold_slice(folder, ty.member_types, F::fold_type_node),
+        ..ty
+    }
+}
+
+pub fn walk_raw_array_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: RawArrayType<'ast>
+) -> RawArrayType<'ast> {
+    RawArrayType {
+        member_type: folder.fold_type_node(ty.member_type),
+        length: folder.fold_expr(ty.length),
+        ..ty
+    }
+}
+
+pub fn walk_literal_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: LiteralType<'ast>
+) -> LiteralType<'ast> {
+    LiteralType {
+        literal: folder.fold_literal(ty.literal),
+        ..ty
+    }
+}
+
+pub fn walk_sum_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: SumType<'ast>
+) -> SumType<'ast> {
+    SumType {
+        member_types: fold_slice(folder, ty.member_types, F::fold_type_node),
+        ..ty
+    }
+}
+
+pub fn walk_product_type<'ast, F: Fold<'ast> + ?Sized>(
+    folder: &mut F, ty: ProductType<'ast>
+) -> ProductType<'ast> {
+    ProductType {
+        member_types: fold_slice(folder, ty.member_types, F::fold_type_node),
+        ..ty
+    }
+}
+// endregion Type nodes
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::lexical::symbol::Interner;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    fn parse_src<'a>(src: &str, arena: &'a Arena) -> Vec<SyntaxNode<'a>> {
+        let parser_res = parse(tokenize(src).tokens, arena);
+        assert_eq!(parser_res.messages.count(), 0, "'{}' should parse.", src);
+
+        parser_res.nodes
+    }
+
+    /// Rebuilds the tree without changing anything.
+    struct Identity<'a> {
+        arena: &'a Arena,
+    }
+
+    impl<'a> Fold<'a> for Identity<'a> {
+        fn arena(&self) -> &'a Arena {
+            self.arena
+        }
+    }
+
+    /// Removes parentheses around expressions and renames every name in `renames`.
+    struct Rewriter<'a> {
+        arena: &'a Arena,
+        interner: Interner<'a>,
+        renames: Vec<(&'static str, &'static str)>,
+    }
+
+    impl<'a> Fold<'a> for Rewriter<'a> {
+        fn arena(&self) -> &'a Arena {
+            self.arena
+        }
+
+        fn fold_expr(&mut self, expr: Expr<'a>) -> Expr<'a> {
+            match walk_expr(self, expr) {
+                Expr::Group(group) => group.expr,
+                expr => expr,
+            }
+        }
+
+        fn fold_simple_identifier(
+            &mut self, identifier: SimpleIdentifier<'a>
+        ) -> SimpleIdentifier<'a> {
+            match self.renames.iter().find(|(from, _)| identifier.name == *from) {
+                Some((_, to)) => SimpleIdentifier {
+                    name: self.interner.intern(to),
+                    ..identifier
+                },
+                None => identifier,
+            }
+        }
+    }
+
+    #[test]
+    fn fold_resources() {
+        println!("== Testing that folding the example resources rebuilds the same trees ==");
+
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/resources");
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            println!("Testing '{}'.", path.display());
+
+            let arena = Arena::new();
+            let nodes = parse_src(&fs::read_to_string(&path).unwrap(), &arena);
+            let before = serde_json::to_value(&nodes).unwrap();
+            let folded = fold_nodes(&mut Identity { arena: &arena }, nodes);
+            let after = serde_json::to_value(folded).unwrap();
+            assert_eq!(before, after);
+        }
+    }
+
+    #[test]
+    fn fold_replacements() {
+        println!("== Testing folds that replace nodes ==");
+
+        let cases = vec![
+            ("let a = ((b) + 1) + c", "let x = y + 1 + c"),
+            ("f((a), [(b)])", "f(x, [y])"),
+            ("if (a) then b = (c) end", "if x then y = c end"),
+            ("func g (a: Num) => (a)", "func g (x: Num) => x"),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let arena = Arena::new();
+            let mut rewriter = Rewriter {
+                arena: &arena,
+                interner: Interner::new(&arena),
+                renames: vec![("a", "x"), ("b", "y")],
+            };
+            let folded = fold_nodes(&mut rewriter, parse_src(input, &arena));
+
+            // Spans are kept from the original nodes, so only the shape of the trees is compared.
+            let folded = serde_json::to_string(&folded).unwrap();
+            let expected = serde_json::to_string(&parse_src(expected, &arena)).unwrap();
+            assert_eq!(strip_positions(&folded), strip_positions(&expected));
+        }
+    }
+
+    /// Removes the ids, spans, tokens and other parts of a tree's JSON that depend on where each node
+    /// is in the source.
+    fn strip_positions(json: &str) -> serde_json::Value {
+        fn strip(value: serde_json::Value) -> serde_json::Value {
+            match value {
+                serde_json::Value::Object(map) => serde_json::Value::Object(
+                    map.into_iter()
+                        .filter(|(key, _)| key != "span" && key != "id")
+                        .filter(|(key, _)| !key.ends_with("token") && !key.ends_with("tokens"))
+                        .map(|(key, value)| (key, strip(value)))
+                        .collect()
+                ),
+                serde_json::Value::Array(values) => {
+                    serde_json::Value::Array(values.into_iter().map(strip).collect())
+                }
+                value => value,
+            }
+        }
+
+        strip(serde_json::from_str(json).unwrap())
+    }
+}
@@ -1,3 +1,8 @@
+pub mod arena;
 pub mod nodes;
+pub mod node_id;
 pub mod parser;
+pub mod precedence;
 pub mod syntax_factory;
+pub mod visitor;
+pub mod fold;
@@ -0,0 +1,389 @@
+use serde::Serialize;
+use crate::judith::syntax::arena::Arena;
+use crate::judith::syntax::fold;
+use crate::judith::syntax::fold::Fold;
+use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::visitor;
+use crate::judith::syntax::visitor::Visitor;
+
+/// Identifies a node inside the tree it was parsed in. Ids are given in the order nodes are
+/// finished, children before their parents, starting at 0; so they can be used as indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct NodeId(pub u32);
+
+impl NodeId {
+    /// The id of nodes that haven't been given one, such as nodes created after parsing.
+    pub const DUMMY: NodeId = NodeId(u32::MAX);
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn is_dummy(self) -> bool {
+        self == NodeId::DUMMY
+    }
+}
+
+/// Maps the nodes of a tree to values of some kind, such as the symbol a name resolves to, the type
+/// of an expression or its constant value. This allows passes to attach data to nodes without
+/// changing the tree.
+#[derive(Debug, Clone)]
+pub struct NodeMap<T> {
+    values: Vec<Option<T>>,
+    len: usize,
+}
+
+impl<T> Default for NodeMap<T> {
+    fn default() -> Self {
+        NodeMap::new()
+    }
+}
+
+impl<T> NodeMap<T> {
+    pub fn new() -> NodeMap<T> {
+        NodeMap {
+            values: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Creates an empty map with room for a tree with the amount of nodes given.
+    pub fn with_capacity(node_count: usize) -> NodeMap<T> {
+        NodeMap {
+            values: Vec::with_capacity(node_count),
+            len: 0,
+        }
+    }
+
+    /// Sets the value of the node given, returning the value it had before, if any.
+    pub fn insert(&mut self, id: NodeId, value: T) -> Option<T> {
+        assert!(!id.is_dummy(), "Nodes without an id can't be added to a node map.");
+
+        if id.index() >= self.values.len() {
+            self.values.resize_with(id.index() + 1, || None);
+        }
+
+        let old = self.values[id.index()].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.values.get(id.index()).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.values.get_mut(id.index()).and_then(Option::as_mut)
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        let old = self.values.get_mut(id.index()).and_then(Option::take);
+        if old.is_some() {
+            self.len -= 1;
+        }
+
+        old
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns the amount of nodes that have a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over the nodes that have a value, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.values.iter().enumerate().filter_map(|(i, value)| {
+            value.as_ref().map(|value| (NodeId(i as u32), value))
+        })
+    }
+}
+
+/// Calls the macro given with every type of node that has an id, along with the names of the
+/// methods and functions used to visit and fold it. Types without a `walk_*` function don't have
+/// children. Types that borrow from an arena use the lifetime `'a`, which the macro given must
+/// declare.
+macro_rules! with_node_types {
+    ($callback:ident) => {
+        $callback! {
+            (visit_func_def, fold_func_def, FuncDef<'a>) => walk_func_def;
+            (visit_block_body, fold_block_body, BlockBody<'a>) => walk_block_body;
+            (visit_arrow_body, fold_arrow_body, ArrowBody<'a>) => walk_arrow_body;
+            (visit_expr_body, fold_expr_body, ExprBody<'a>) => walk_expr_body;
+            (visit_expr_stmt, fold_expr_stmt, ExprStmt<'a>) => walk_expr_stmt;
+            (visit_local_decl_stmt, fold_local_decl_stmt, LocalDeclStmt<'a>) => walk_local_decl_stmt;
+            (visit_priv_print_stmt, fold_priv_print_stmt, PrivPrintStmt<'a>) => walk_priv_print_stmt;
+            (visit_regular_local_decl, fold_regular_local_decl, RegularLocalDecl<'a>)
+                => walk_regular_local_decl;
+            (visit_destructured_local_decl, fold_destructured_local_decl, DestructuredLocalDecl<'a>)
+                => walk_destructured_local_decl;
+            (visit_if_expr, fold_if_expr, IfExpr<'a>) => walk_if_expr;
+            (visit_loop_expr, fold_loop_expr, LoopExpr<'a>) => walk_loop_expr;
+            (visit_while_expr, fold_while_expr, WhileExpr<'a>) => walk_while_expr;
+            (visit_assignment_expr, fold_assignment_expr, AssignmentExpr<'a>) => walk_assignment_expr;
+            (visit_binary_expr, fold_binary_expr, BinaryExpr<'a>) => walk_binary_expr;
+            (visit_left_unary_expr, fold_left_unary_expr, LeftUnaryExpr<'a>) => walk_left_unary_expr;
+            (visit_right_unary_expr, fold_right_unary_expr, RightUnaryExpr<'a>) => walk_right_unary_expr;
+            (visit_group_expr, fold_group_expr, GroupExpr<'a>) => walk_group_expr;
+            (visit_tuple_expr, fold_tuple_expr, TupleExpr<'a>) => walk_tuple_expr;
+            (visit_array_expr, fold_array_expr, ArrayExpr<'a>) => walk_array_expr;
+            (visit_empty_collection_expr, fold_empty_collection_expr, EmptyCollectionExpr);
+            (visit_object_init_expr, fold_object_init_expr, ObjectInitExpr<'a>) => walk_object_init_expr;
+            (visit_access_expr, fold_access_expr, AccessExpr<'a>) => walk_access_expr;
+            (visit_call_expr, fold_call_expr, CallExpr<'a>) => walk_call_expr;
+            (visit_index_expr, fold_index_expr, IndexExpr<'a>) => walk_index_expr;
+            (visit_lambda_expr, fold_lambda_expr, LambdaExpr<'a>) => walk_lambda_expr;
+            (visit_identifier_expr, fold_identifier_expr, IdentifierExpr<'a>) => walk_identifier_expr;
+            (visit_literal_expr, fold_literal_expr, LiteralExpr<'a>) => walk_literal_expr;
+            (visit_error_node, fold_error_node, ErrorNode);
+            (visit_simple_identifier, fold_simple_identifier, SimpleIdentifier<'a>);
+            (visit_qualified_identifier, fold_qualified_identifier, QualifiedIdentifier<'a>)
+                => walk_qualified_identifier;
+            (visit_literal, fold_literal, Literal<'a>);
+            (visit_operator, fold_operator, Operator);
+            (visit_equals_value_clause, fold_equals_value_clause, EqualsValueClause<'a>)
+                => walk_equals_value_clause;
+            (visit_type_annotation, fold_type_annotation, TypeAnnotation<'a>) => walk_type_annotation;
+            (visit_parameter_list, fold_parameter_list, ParameterList<'a>) => walk_parameter_list;
+            (visit_parameter, fold_parameter, Parameter<'a>) => walk_parameter;
+            (visit_argument_list, fold_argument_list, ArgumentList<'a>) => walk_argument_list;
+            (visit_argument, fold_argument, Argument<'a>) => walk_argument;
+            (visit_local_declarator, fold_local_declarator, LocalDeclarator<'a>) => walk_local_declarator;
+            (visit_field_init, fold_field_init, FieldInit<'a>) => walk_field_init;
+            (visit_object_initializer, fold_object_initializer, ObjectInitializer<'a>)
+                => walk_object_initializer;
+            (visit_identifier_type, fold_identifier_type, IdentifierType<'a>) => walk_identifier_type;
+            (visit_group_type, fold_group_type, GroupType<'a>) => walk_group_type;
+            (visit_function_type, fold_function_type, FunctionType<'a>) => walk_function_type;
+            (visit_tuple_array_type, fold_tuple_array_type, TupleArrayType<'a>) => walk_tuple_array_type;
+            (visit_raw_array_type, fold_raw_array_type, RawArrayType<'a>) => walk_raw_array_type;
+            (visit_literal_type, fold_literal_type, LiteralType<'a>) => walk_literal_type;
+            (visit_sum_type, fold_sum_type, SumType<'a>) => walk_sum_type;
+            (visit_product_type, fold_product_type, ProductType<'a>) => walk_product_type;
+        }
+    };
+}
+
+/// Gives every node in the nodes given a new id, in the same order the parser does, rebuilding
+/// them in the arena given. Returns the nodes and the amount of ids given.
+pub fn assign_node_ids<'ast>(
+    nodes: Vec<SyntaxNode<'ast>>, arena: &'ast Arena
+) -> (Vec<SyntaxNode<'ast>>, usize) {
+    let mut assigner = IdAssigner { arena, next_id: 0 };
+    let nodes = fold::fold_nodes(&mut assigner, nodes);
+
+    (nodes, assigner.next_id as usize)
+}
+
+struct IdAssigner<'ast> {
+    arena: &'ast Arena,
+    next_id: u32,
+}
+
+macro_rules! assign_ids {
+    ($(($visit:ident, $fold:ident, $ty:ty) $(=> $walk:ident)?;)*) => {
+        impl<'a> Fold<'a> for IdAssigner<'a> {
+            fn arena(&self) -> &'a Arena {
+                self.arena
+            }
+
+            $(
+                fn $fold(&mut self, node: $ty) -> $ty {
+                    // The id is taken after folding the children, so children come first.
+                    $(let node = fold::$walk(self, node);)?
+                    let mut node = node;
+                    node.id = NodeId(self.next_id);
+                    self.next_id += 1;
+
+                    node
+                }
+            )*
+        }
+    };
+}
+
+with_node_types!(assign_ids);
+
+/// Maps each node to the node that contains it. Top-level nodes don't have a parent.
+#[derive(Debug, Default)]
+pub struct ParentMap {
+    parents: NodeMap<NodeId>,
+}
+
+impl ParentMap {
+    pub fn build(nodes: &[SyntaxNode]) -> ParentMap {
+        let mut collector = ParentCollector {
+            parents: NodeMap::new(),
+            stack: Vec::new(),
+        };
+
+        for node in nodes {
+            collector.visit_node(node);
+        }
+
+        ParentMap {
+            parents: collector.parents,
+        }
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.parents.get(id).copied()
+    }
+
+    /// Iterates over the nodes that contain the node given, from its parent to the top-level node
+    /// it's in.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), |id| self.parent(*id))
+    }
+}
+
+struct ParentCollector {
+    parents: NodeMap<NodeId>,
+    /// The nodes being visited, from the top-level node to the innermost one.
+    stack: Vec<NodeId>,
+}
+
+macro_rules! collect_parents {
+    ($(($visit:ident, $fold:ident, $ty:ty) $(=> $walk:ident)?;)*) => {
+        impl<'a> Visitor<'a> for ParentCollector {
+            $(
+                fn $visit(&mut self, node: &'a $ty) {
+                    if let Some(parent) = self.stack.last() {
+                        self.parents.insert(node.id, *parent);
+                    }
+                    $(
+                        self.stack.push(node.id);
+                        visitor::$walk(self, node);
+                        self.stack.pop();
+                    )?
+                }
+            )*
+        }
+    };
+}
+
+with_node_types!(collect_parents);
+
+#[cfg(test)]
+mod tests {
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    /// Collects the id of every node, in the order they are finished.
+    #[derive(Default)]
+    struct IdCollector {
+        ids: Vec<NodeId>,
+    }
+
+    macro_rules! collect_ids {
+        ($(($visit:ident, $fold:ident, $ty:ty) $(=> $walk:ident)?;)*) => {
+            impl<'a> Visitor<'a> for IdCollector {
+                $(
+                    fn $visit(&mut self, node: &'a $ty) {
+                        $(visitor::$walk(self, node);)?
+                        self.ids.push(node.id);
+                    }
+                )*
+            }
+        };
+    }
+
+    with_node_types!(collect_ids);
+
+    #[test]
+    fn node_ids() {
+        println!("== Testing the ids given to nodes ==");
+
+        let cases = vec![
+            "let a: Num = b + 1",
+            "func f (a, b: Num = 2) -> Num => a * b\nf(1)",
+            "if a then b elsif c then d else e end",
+            "let p = Point { x = [1, 2][0], y = (a?.b)! }",
+        ];
+
+        for input in cases {
+            println!("Testing '{}'.", input.replace('\n', "; "));
+
+            let arena = Arena::new();
+            let parser_res = parse(tokenize(input).tokens, &arena);
+            let mut collector = IdCollector::default();
+            for node in &parser_res.nodes {
+                collector.visit_node(node);
+            }
+
+            // Every node has an id, and they are given after the ids of the node's children.
+            let expected: Vec<_> = (0..parser_res.node_count as u32).map(NodeId).collect();
+            assert_eq!(collector.ids, expected);
+        }
+    }
+
+    #[test]
+    fn parent_map() {
+        println!("== Testing the parent of each node ==");
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("let a = b + 1\nf(a)").tokens, &arena);
+        let parents = ParentMap::build(&parser_res.nodes);
+
+        let SyntaxNode::Stmt(Stmt::LocalDecl(decl)) = &parser_res.nodes[0] else {
+            panic!("Expected a local declaration.");
+        };
+        let init = decl.initializer.as_ref().unwrap();
+        let Expr::Binary(binary) = &init.values[0] else {
+            panic!("Expected a binary expression.");
+        };
+        let Expr::Identifier(b) = &binary.left else {
+            panic!("Expected an identifier.");
+        };
+
+        assert_eq!(parents.parent(decl.id), None);
+        assert_eq!(parents.parent(binary.id), Some(init.id));
+        assert_eq!(
+            parents.ancestors(b.id).collect::<Vec<_>>(),
+            vec![binary.id, init.id, decl.id]
+        );
+
+        // Top-level nodes are siblings, not parents of each other.
+        let SyntaxNode::Stmt(Stmt::Expr(call)) = &parser_res.nodes[1] else {
+            panic!("Expected an expression statement.");
+        };
+        assert_eq!(parents.parent(call.id), None);
+        assert!(call.id > b.id);
+    }
+
+    #[test]
+    fn node_map() {
+        println!("== Testing node maps ==");
+
+        let mut map: NodeMap<&str> = NodeMap::with_capacity(8);
+        assert!(map.is_empty());
+
+        assert_eq!(map.insert(NodeId(3), "three"), None);
+        assert_eq!(map.insert(NodeId(1), "one"), None);
+        assert_eq!(map.insert(NodeId(3), "tres"), Some("three"));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get(NodeId(3)), Some(&"tres"));
+        assert_eq!(map.get(NodeId(2)), None);
+        assert_eq!(map.get(NodeId(100)), None);
+        assert!(map.contains(NodeId(1)));
+
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(NodeId(1), &"one"), (NodeId(3), &"tres")]);
+
+        assert_eq!(map.remove(NodeId(1)), Some("one"));
+        assert_eq!(map.remove(NodeId(1)), None);
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -1,26 +1,29 @@
 use serde::*;
-use crate::judith::lexical::token::Token;
+use crate::judith::lexical::symbol::Symbol;
+use crate::judith::lexical::token::{StringLiteralKind, TokenId, TokenKind};
+use crate::judith::syntax::arena::Arena;
+use crate::judith::syntax::node_id::NodeId;
 use crate::SourceSpan;
 
 extern crate serde;
 
-pub struct CompilerUnit {
+pub struct CompilerUnit<'ast> {
     pub file_name: String,
-    pub top_level_items: Vec<SyntaxNode>,
-    pub impl_func: Option<FuncDef>,
+    pub top_level_items: &'ast [SyntaxNode<'ast>],
+    pub impl_func: Option<FuncDef<'ast>>,
 }
 
 // region Nodes
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "node_kind")]
-pub enum SyntaxNode {
-    Item(Item),
-    Stmt(Stmt),
-    Expr(Expr),
+pub enum SyntaxNode<'ast> {
+    Item(Item<'ast>),
+    Stmt(Stmt<'ast>),
+    Expr(Expr<'ast>),
     Error(ErrorNode),
 }
 
-impl SyntaxNode {
+impl<'ast> SyntaxNode<'ast> {
     pub fn span (&self) -> &Option<SourceSpan> {
         match self {
             SyntaxNode::Item(item) => item.span(),
@@ -31,14 +34,14 @@ impl SyntaxNode {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "item_kind")]
 // region Items
-pub enum Item {
-    FuncDef(FuncDef),
+pub enum Item<'ast> {
+    FuncDef(&'ast FuncDef<'ast>),
 }
 
-impl Item {
+impl<'ast> Item<'ast> {
     pub fn span (&self) -> &Option<SourceSpan> {
         match self {
             Item::FuncDef(def) => &def.span,
@@ -46,34 +49,35 @@ impl Item {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct FuncDef {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FuncDef<'ast> {
     pub is_implicit: bool,
-    pub name: SimpleIdentifier,
-    pub params: ParameterList,
-    pub return_type: Option<TypeNode>,
-    pub body: Body,
+    pub name: SimpleIdentifier<'ast>,
+    pub params: ParameterList<'ast>,
+    pub return_type: Option<TypeNode<'ast>>,
+    pub body: Body<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub func_token: Option<Token>,
-    pub return_type_arrow_token: Option<Token>,
+    pub func_token: Option<TokenId>,
+    pub return_type_arrow_token: Option<TokenId>,
 }
 // endregion
 
 // region Bodies
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "block_kind")]
-pub enum Body {
+pub enum Body<'ast> {
     #[serde(rename = "Block")]
-    Block(BlockBody),
+    Block(BlockBody<'ast>),
 
     #[serde(rename = "Arrow")]
-    Arrow(ArrowBody),
+    Arrow(ArrowBody<'ast>),
 
     #[serde(rename = "Expr")]
-    Expr(ExprBody),
+    Expr(ExprBody<'ast>),
 }
 
-impl Body {
+impl<'ast> Body<'ast> {
     pub fn span (&self) -> &Option<SourceSpan> {
         match self {
             Body::Block(b) => &b.span,
@@ -83,69 +87,87 @@ impl Body {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct BlockBody {
-    pub nodes: Vec<SyntaxNode>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BlockBody<'ast> {
+    pub nodes: &'ast [SyntaxNode<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub opening_token: Option<Token>,
-    pub closing_token: Option<Token>,
+    pub opening_token: Option<TokenId>,
+    pub closing_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ArrowBody {
-    pub expr: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ArrowBody<'ast> {
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub arrow_token: Option<Token>,
+    pub arrow_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ExprBody {
-    pub expr: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ExprBody<'ast> {
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 // endregion Bodies
 
 // region Statements
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "stmt_kind")]
-pub enum Stmt {
-    Expr(ExprStmt),
-    LocalDecl(LocalDeclStmt),
+pub enum Stmt<'ast> {
+    Expr(ExprStmt<'ast>),
+    LocalDecl(&'ast LocalDeclStmt<'ast>),
+    PrivPrint(PrivPrintStmt<'ast>),
     Error(ErrorNode),
 }
 
-impl Stmt {
+impl<'ast> Stmt<'ast> {
     pub fn span (&self) -> &Option<SourceSpan> {
         match self {
             Stmt::Expr(stmt) => &stmt.span,
             Stmt::LocalDecl(stmt) => &stmt.span,
+            Stmt::PrivPrint(stmt) => &stmt.span,
             Stmt::Error(stmt) => &stmt.span,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct ExprStmt {
-    pub expr: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ExprStmt<'ast> {
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LocalDeclStmt {
-    pub decl: PartialLocalDecl,
-    pub initializer: Option<EqualsValueClause>,
+/// A local declaration, such as `let a, mut b: Num = 1, 2`. When there are more declarators than
+/// values, the declaration must be initialized with a single tuple, which is spread across them.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LocalDeclStmt<'ast> {
+    pub declarators: &'ast [PartialLocalDecl<'ast>],
+    pub initializer: Option<EqualsValueClause<'ast>>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub let_token: Option<Token>,
+    pub let_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PrivPrintStmt<'ast> {
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+    pub p_print_token: Option<TokenId>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "decl_type")]
-pub enum PartialLocalDecl {
-    Regular(RegularLocalDecl),
-    Destructured(DestructuredLocalDecl),
+pub enum PartialLocalDecl<'ast> {
+    Regular(RegularLocalDecl<'ast>),
+    Destructured(DestructuredLocalDecl<'ast>),
 }
 
-impl PartialLocalDecl {
+impl<'ast> PartialLocalDecl<'ast> {
     pub fn span(&self) -> &Option<SourceSpan> {
         match &self {
             PartialLocalDecl::Regular(decl) => &decl.span,
@@ -154,43 +176,52 @@ impl PartialLocalDecl {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct RegularLocalDecl {
-    pub declarator: LocalDeclarator,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RegularLocalDecl<'ast> {
+    pub declarator: LocalDeclarator<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct DestructuredLocalDecl {
-    pub declarators: Vec<LocalDeclarator>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DestructuredLocalDecl<'ast> {
+    pub declarators: &'ast [LocalDeclarator<'ast>],
     pub destructuring_kind: DestructuringKind,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub opening_token: Option<Token>,
-    pub closing_token: Option<Token>,
+    pub opening_token: Option<TokenId>,
+    pub closing_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
 // endregion Statements
 
 // region Expressions
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "expr_kind")]
-pub enum Expr {
-    If(Box<IfExpr>),
-    Loop(Box<LoopExpr>),
-    While(Box<WhileExpr>),
-    Assignment(Box<AssignmentExpr>),
-    Binary(Box<BinaryExpr>),
-    LeftUnary(Box<LeftUnaryExpr>),
-    Group(Box<GroupExpr>),
-    ObjectInit(Box<ObjectInitExpr>),
-    Access(Box<AccessExpr>),
-    Call(Box<CallExpr>),
-    Identifier(Box<IdentifierExpr>),
-    Literal(Box<LiteralExpr>),
+pub enum Expr<'ast> {
+    If(&'ast IfExpr<'ast>),
+    Loop(&'ast LoopExpr<'ast>),
+    While(&'ast WhileExpr<'ast>),
+    Assignment(&'ast AssignmentExpr<'ast>),
+    Binary(&'ast BinaryExpr<'ast>),
+    LeftUnary(&'ast LeftUnaryExpr<'ast>),
+    RightUnary(&'ast RightUnaryExpr<'ast>),
+    Group(&'ast GroupExpr<'ast>),
+    Tuple(&'ast TupleExpr<'ast>),
+    Array(&'ast ArrayExpr<'ast>),
+    EmptyCollection(&'ast EmptyCollectionExpr),
+    ObjectInit(&'ast ObjectInitExpr<'ast>),
+    Access(&'ast AccessExpr<'ast>),
+    Call(&'ast CallExpr<'ast>),
+    Index(&'ast IndexExpr<'ast>),
+    Lambda(&'ast LambdaExpr<'ast>),
+    Identifier(&'ast IdentifierExpr<'ast>),
+    Literal(&'ast LiteralExpr<'ast>),
     Error(ErrorNode),
 }
 
-impl Expr {
+impl<'ast> Expr<'ast> {
     pub fn span (&self) -> &Option<SourceSpan> {
         match self {
             Expr::If(expr) => &expr.span,
@@ -199,10 +230,16 @@ impl Expr {
             Expr::Assignment(expr) => &expr.span,
             Expr::Binary(expr) => &expr.span,
             Expr::LeftUnary(expr) => &expr.span,
+            Expr::RightUnary(expr) => &expr.span,
             Expr::Group(expr) => &expr.span,
+            Expr::Tuple(expr) => &expr.span,
+            Expr::Array(expr) => &expr.span,
+            Expr::EmptyCollection(expr) => &expr.span,
             Expr::ObjectInit(expr) => &expr.span,
             Expr::Access(expr) => &expr.span,
             Expr::Call(expr) => &expr.span,
+            Expr::Index(expr) => &expr.span,
+            Expr::Lambda(expr) => &expr.span,
             Expr::Identifier(expr) => &expr.span,
             Expr::Literal(expr) => &expr.span,
             Expr::Error(expr) => &expr.span,
@@ -210,114 +247,192 @@ impl Expr {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct IfExpr {
-    pub test: Expr,
-    pub consequent: Body,
-    pub alternate: Option<Body>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct IfExpr<'ast> {
+    pub test: Expr<'ast>,
+    pub consequent: Body<'ast>,
+    pub alternate: Option<Body<'ast>>,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+    pub if_token: Option<TokenId>,
+    pub else_token: Option<TokenId>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LoopExpr<'ast> {
+    pub body: Body<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub if_token: Option<Token>,
-    pub else_token: Option<Token>,
+    pub loop_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LoopExpr {
-    pub body: Body,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct WhileExpr<'ast> {
+    pub test: Expr<'ast>,
+    pub body: Body<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub loop_token: Option<Token>,
+    pub while_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct WhileExpr {
-    pub test: Expr,
-    pub body: Body,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AssignmentExpr<'ast> {
+    pub left: Expr<'ast>,
+    pub operator: Operator,
+    pub right: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub while_token: Option<Token>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AssignmentExpr {
-    pub left: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BinaryExpr<'ast> {
+    pub left: Expr<'ast>,
     pub operator: Operator,
-    pub right: Expr,
+    pub right: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct BinaryExpr {
-    pub left: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LeftUnaryExpr<'ast> {
     pub operator: Operator,
-    pub right: Expr,
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LeftUnaryExpr {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RightUnaryExpr<'ast> {
+    pub expr: Expr<'ast>,
     pub operator: Operator,
-    pub expr: Expr,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct GroupExpr<'ast> {
+    pub expr: Expr<'ast>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+}
+
+/// A tuple literal, such as `(a, b)`. A tuple with a single element must have a trailing comma
+/// (`(a,)`), or it would be a group expression.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TupleExpr<'ast> {
+    pub elements: &'ast [Expr<'ast>],
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
+}
+
+/// An array literal with at least one element, such as `[1, 2, 3]`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ArrayExpr<'ast> {
+    pub elements: &'ast [Expr<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
+    pub left_square_bracket_token: Option<TokenId>,
+    pub right_square_bracket_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct GroupExpr {
-    pub expr: Expr,
-    pub left_paren_token: Option<Token>,
-    pub right_paren_token: Option<Token>,
+/// An empty collection, `[]`. The kind of collection it creates is given by the type expected
+/// where it's used.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct EmptyCollectionExpr {
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
+    pub left_square_bracket_token: Option<TokenId>,
+    pub right_square_bracket_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ObjectInitExpr {
-    pub provider: Option<Expr>,
-    pub initializer: ObjectInitializer,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ObjectInitExpr<'ast> {
+    pub provider: Option<Expr<'ast>>,
+    pub initializer: ObjectInitializer<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AccessExpr {
-    pub receiver: Option<Expr>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AccessExpr<'ast> {
+    pub receiver: Option<Expr<'ast>>,
     pub operator: Operator,
-    pub member: SimpleIdentifier,
+    pub member: SimpleIdentifier<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct CallExpr {
-    pub callee: Expr,
-    pub arguments: ArgumentList,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CallExpr<'ast> {
+    pub callee: Expr<'ast>,
+    pub arguments: ArgumentList<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct IdentifierExpr {
-    pub identifier: Identifier,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct IndexExpr<'ast> {
+    pub receiver: Expr<'ast>,
+    pub index: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
+    pub left_square_bracket_token: Option<TokenId>,
+    pub right_square_bracket_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LiteralExpr {
-    pub literal: Literal,
+/// An anonymous function, either in arrow form (`(a: Num, b) => a + b`) or in block form
+/// (`func (x) ... end`). Parameters may omit their types, which are then inferred from the
+/// function type expected where the lambda is used.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LambdaExpr<'ast> {
+    pub params: ParameterList<'ast>,
+    pub return_type: Option<TypeNode<'ast>>,
+    pub body: Body<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
+    pub func_token: Option<TokenId>,
+    pub return_type_arrow_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct IdentifierExpr<'ast> {
+    pub identifier: Identifier<'ast>,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LiteralExpr<'ast> {
+    pub literal: Literal<'ast>,
+    pub id: NodeId,
+    pub span: Option<SourceSpan>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct ErrorNode {
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 // endregion Expressions
 
 // region Fragments
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "identifier_kind")]
-pub enum Identifier {
+pub enum Identifier<'ast> {
     #[serde(rename = "Simple")]
-    Simple(SimpleIdentifier),
+    Simple(SimpleIdentifier<'ast>),
 
     #[serde(rename = "Qualified")]
-    Qualified(Box<QualifiedIdentifier>),
+    Qualified(&'ast QualifiedIdentifier<'ast>),
 }
 
-impl Identifier {
+impl<'ast> Identifier<'ast> {
     pub fn is_meta_name (&self) -> bool {
         match self {
             Identifier::Simple(id) => id.is_meta_name,
@@ -333,121 +448,145 @@ impl Identifier {
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct SimpleIdentifier {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SimpleIdentifier<'ast> {
     pub is_meta_name: bool,
-    pub name: String,
+    pub name: Symbol<'ast>,
     pub is_escaped: bool,
-    pub raw_token: Option<Token>,
+    pub raw_token: Option<TokenId>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct QualifiedIdentifier {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct QualifiedIdentifier<'ast> {
     pub is_meta_name: bool,
-    pub qualifier: Identifier,
+    pub qualifier: Identifier<'ast>,
     pub operator: Operator,
-    pub name: SimpleIdentifier,
+    pub name: SimpleIdentifier<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Literal {
-    pub source: String,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Literal<'ast> {
+    pub kind: TokenKind,
+    pub source: &'ast str,
+    /// The delimiters of the literal, if it's a string.
+    pub delimiters: Option<StringDelimiters>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub raw_token: Option<Token>,
+    pub raw_token: Option<TokenId>,
+}
+
+/// The delimiters that enclose a string literal, such as `"` or `'''`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct StringDelimiters {
+    pub string_kind: StringLiteralKind,
+    pub delimiter: char,
+    pub count: usize,
 }
 
-#[derive(Debug, Serialize)]
-pub struct EqualsValueClause {
-    pub values: Vec<Expr>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct EqualsValueClause<'ast> {
+    pub values: &'ast [Expr<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub equals_token: Option<Token>,
-    pub comma_tokens: Option<Vec<Token>>,
+    pub equals_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct TypeAnnotation {
-    pub ty: TypeNode,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TypeAnnotation<'ast> {
+    pub ty: TypeNode<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub colon_token: Option<Token>,
+    pub colon_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 pub struct Operator {
     pub kind: OperatorKind,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub raw_token: Option<Token>,
+    pub raw_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ParameterList {
-    pub params: Vec<Parameter>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ParameterList<'ast> {
+    pub params: &'ast [Parameter<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_paren_token: Option<Token>,
-    pub right_paren_token: Option<Token>,
-    pub comma_tokens: Option<Vec<Token>>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Parameter {
-    pub declarator: RegularLocalDecl,
-    pub default_val: Option<EqualsValueClause>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Parameter<'ast> {
+    pub declarator: RegularLocalDecl<'ast>,
+    pub default_val: Option<EqualsValueClause<'ast>>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ArgumentList {
-    pub arguments: Vec<Argument>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ArgumentList<'ast> {
+    pub arguments: &'ast [Argument<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_paren_token: Option<Token>,
-    pub right_paren_token: Option<Token>,
-    pub comma_tokens: Option<Vec<Token>>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct Argument {
-    pub expr: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Argument<'ast> {
+    pub expr: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LocalDeclarator {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LocalDeclarator<'ast> {
     pub ownership_kind: OwnershipKind,
-    pub name: SimpleIdentifier,
-    pub type_annotation: Option<TypeAnnotation>,
+    pub name: SimpleIdentifier<'ast>,
+    pub type_annotation: Option<TypeAnnotation<'ast>>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub ownership_token: Option<Token>,
+    pub ownership_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct FieldInit {
-    pub field_name: SimpleIdentifier,
-    pub initializer: EqualsValueClause,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FieldInit<'ast> {
+    pub field_name: SimpleIdentifier<'ast>,
+    pub initializer: EqualsValueClause<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ObjectInitializer {
-    pub field_inits: Vec<FieldInit>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ObjectInitializer<'ast> {
+    pub field_inits: &'ast [FieldInit<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_bracket_token: Option<Token>,
-    pub right_bracket_token: Option<Token>,
-    pub comma_tokens: Option<Vec<Token>>,
+    pub left_bracket_token: Option<TokenId>,
+    pub right_bracket_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 // endregion Fragments
 
 // region Type nodes
-#[derive(Debug, Serialize)]
-pub struct TypeNode {
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TypeNode<'ast> {
     pub is_nullable: bool,
     pub ownership_kind: OwnershipKind,
-    pub ty: PartialType,
-    pub nullable_token: Option<Token>,
-    pub ownership_token: Option<Token>,
+    pub ty: PartialType<'ast>,
+    pub nullable_token: Option<TokenId>,
+    pub ownership_token: Option<TokenId>,
 }
 
-impl TypeNode {
+impl<'ast> TypeNode<'ast> {
     pub fn span(&self) -> &Option<SourceSpan> {
         match &self.ty {
             PartialType::Identifier(ty) => &ty.span,
@@ -463,102 +602,121 @@ impl TypeNode {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize)]
 #[serde(tag = "type_kind")]
-pub enum PartialType {
-    Identifier(IdentifierType),
-    Group(Box<GroupType>),
-    Function(Box<FunctionType>),
+pub enum PartialType<'ast> {
+    Identifier(IdentifierType<'ast>),
+    Group(&'ast GroupType<'ast>),
+    Function(&'ast FunctionType<'ast>),
     // TODO ObjectType
-    TupleArray(Box<TupleArrayType>),
-    RawArray(Box<RawArrayType>),
-    Literal(LiteralType),
-    Sum(Box<SumType>),
-    Product(Box<ProductType>),
+    TupleArray(&'ast TupleArrayType<'ast>),
+    RawArray(&'ast RawArrayType<'ast>),
+    Literal(LiteralType<'ast>),
+    Sum(&'ast SumType<'ast>),
+    Product(&'ast ProductType<'ast>),
     Error(ErrorNode),
 }
 
-#[derive(Debug, Serialize)]
-pub struct IdentifierType {
-    pub name: Identifier,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct IdentifierType<'ast> {
+    pub name: Identifier<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct GroupType {
-    pub ty: TypeNode,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct GroupType<'ast> {
+    pub ty: TypeNode<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_paren_token: Option<Token>,
-    pub right_paren_token: Option<Token>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct FunctionType {
-    pub param_types: Vec<TypeNode>,
-    pub return_type: TypeNode,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FunctionType<'ast> {
+    pub param_types: &'ast [TypeNode<'ast>],
+    pub return_type: TypeNode<'ast>,
     pub is_send: bool,
     pub is_sync: bool,
     pub has_exception: bool,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub ss_token: Option<Token>,
-    pub exception_mark_token: Option<Token>,
-    pub left_paren_token: Option<Token>,
-    pub right_paren_token: Option<Token>,
-    pub param_comma_tokens: Option<Vec<Token>>,
-    pub return_annotation_token: Option<Token>,
+    pub ss_token: Option<TokenId>,
+    pub exception_mark_token: Option<TokenId>,
+    pub left_paren_token: Option<TokenId>,
+    pub right_paren_token: Option<TokenId>,
+    pub param_comma_tokens: Option<&'ast [TokenId]>,
+    pub return_annotation_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct TupleArrayType {
-    pub member_types: Vec<TypeNode>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TupleArrayType<'ast> {
+    pub member_types: &'ast [TypeNode<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_square_bracket_token: Option<Token>,
-    pub right_square_bracket_token: Option<Token>,
-    pub comma_tokens: Option<Vec<Token>>,
+    pub left_square_bracket_token: Option<TokenId>,
+    pub right_square_bracket_token: Option<TokenId>,
+    pub comma_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct RawArrayType {
-    pub member_type: TypeNode,
-    pub length: Expr,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RawArrayType<'ast> {
+    pub member_type: TypeNode<'ast>,
+    pub length: Expr<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub left_square_bracket_token: Option<Token>,
-    pub right_square_bracket_token: Option<Token>,
+    pub left_square_bracket_token: Option<TokenId>,
+    pub right_square_bracket_token: Option<TokenId>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct LiteralType {
-    pub literal: Literal,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct LiteralType<'ast> {
+    pub literal: Literal<'ast>,
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct SumType {
-    pub member_types: Vec<TypeNode>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SumType<'ast> {
+    pub member_types: &'ast [TypeNode<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub or_tokens: Option<Vec<Token>>,
+    pub or_tokens: Option<&'ast [TokenId]>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ProductType {
-    pub member_types: Vec<TypeNode>,
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ProductType<'ast> {
+    pub member_types: &'ast [TypeNode<'ast>],
+    pub id: NodeId,
     pub span: Option<SourceSpan>,
-    pub and_tokens: Option<Vec<Token>>,
+    pub and_tokens: Option<&'ast [TokenId]>,
 }
 // endregion
 
 // endregion Nodes
 
 // region Enums
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum OperatorKind {
     Invalid,
     Add, // +
     Subtract, // -
     Multiply, // *
     Divide, // /
+    Modulo, // %
     BitwiseNot, // ~
+    BitwiseAnd, // &
+    BitwiseOr, // |
+    BitwiseXor, // ^
+    LeftShift, // <<
+    RightShift, // >>
     Assignment, // =
+    AddAssignment, // +=
+    SubtractAssignment, // -=
+    MultiplyAssignment, // *=
+    DivideAssignment, // /=
+    ModuloAssignment, // %=
     Equals, // ==
     NotEquals, // !=
     Like, // ~~
@@ -569,13 +727,63 @@ pub enum OperatorKind {
     LessThanOrEqualsTo, // !==
     GreaterThan, // >
     GreaterThanOrEqualsTo, // >=
+    LogicalNot, // not
+    OwnershipTransfer, // in (prefix)
     LogicalAnd, // and
     LogicalOr, // or
+    NullCoalescing, // ??
+    NonNullAssertion, // ! (postfix)
     MemberAccess, // .
+    SafeMemberAccess, // ?.
     ScopeResolution, // ::
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+impl OperatorKind {
+    /// Returns the text that represents this operator in the source code.
+    pub fn lexeme(&self) -> &'static str {
+        match self {
+            OperatorKind::Invalid => "",
+            OperatorKind::Add => "+",
+            OperatorKind::Subtract => "-",
+            OperatorKind::Multiply => "*",
+            OperatorKind::Divide => "/",
+            OperatorKind::Modulo => "%",
+            OperatorKind::BitwiseNot => "~",
+            OperatorKind::BitwiseAnd => "&",
+            OperatorKind::BitwiseOr => "|",
+            OperatorKind::BitwiseXor => "^",
+            OperatorKind::LeftShift => "<<",
+            OperatorKind::RightShift => ">>",
+            OperatorKind::Assignment => "=",
+            OperatorKind::AddAssignment => "+=",
+            OperatorKind::SubtractAssignment => "-=",
+            OperatorKind::MultiplyAssignment => "*=",
+            OperatorKind::DivideAssignment => "/=",
+            OperatorKind::ModuloAssignment => "%=",
+            OperatorKind::Equals => "==",
+            OperatorKind::NotEquals => "!=",
+            OperatorKind::Like => "~~",
+            OperatorKind::NotLike => "!~",
+            OperatorKind::ReferenceEquals => "===",
+            OperatorKind::ReferenceNotEquals => "!==",
+            OperatorKind::LessThan => "<",
+            OperatorKind::LessThanOrEqualsTo => "<=",
+            OperatorKind::GreaterThan => ">",
+            OperatorKind::GreaterThanOrEqualsTo => ">=",
+            OperatorKind::LogicalNot => "not",
+            OperatorKind::OwnershipTransfer => "in",
+            OperatorKind::LogicalAnd => "and",
+            OperatorKind::LogicalOr => "or",
+            OperatorKind::NullCoalescing => "??",
+            OperatorKind::NonNullAssertion => "!",
+            OperatorKind::MemberAccess => ".",
+            OperatorKind::SafeMemberAccess => "?.",
+            OperatorKind::ScopeResolution => "::",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum OwnershipKind {
     /// This node doesn't define any ownership kind.
     None,
@@ -586,30 +794,33 @@ pub enum OwnershipKind {
     In, // in
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum DestructuringKind {
     ArrayPattern, // let [a, b]
     ObjectPattern, // let { a, b }
 }
 // endregion
 
-impl CompilerUnit {
-    pub fn from_node_collection(filename: &String, ast: Vec<SyntaxNode>) -> CompilerUnit {
+impl<'ast> CompilerUnit<'ast> {
+    pub fn from_node_collection(
+        filename: &String, ast: &[SyntaxNode<'ast>], arena: &'ast Arena
+    ) -> CompilerUnit<'ast> {
         let mut top_level_items: Vec<SyntaxNode> = Vec::new();
         let mut implicit_func_nodes: Vec<SyntaxNode> = Vec::new();
 
         for node in ast {
             match node {
-                SyntaxNode::Item(item) => top_level_items.push(SyntaxNode::Item(item)),
-                SyntaxNode::Stmt(stmt) => implicit_func_nodes.push(SyntaxNode::Stmt(stmt)),
+                SyntaxNode::Item(item) => top_level_items.push(SyntaxNode::Item(*item)),
+                SyntaxNode::Stmt(stmt) => implicit_func_nodes.push(SyntaxNode::Stmt(*stmt)),
                 SyntaxNode::Expr(expr) => panic!("Invalid top level node."),
-                SyntaxNode::Error(err) => top_level_items.push(SyntaxNode::Error(err)),
+                SyntaxNode::Error(err) => top_level_items.push(SyntaxNode::Error(*err)),
             }
         }
 
         let implicit_func = if implicit_func_nodes.len() > 0 {
             let param_list = ParameterList {
-                params: Vec::new(),
+                params: &[],
+                id: NodeId::DUMMY,
                 span: None,
                 left_paren_token: None,
                 right_paren_token: None,
@@ -617,7 +828,8 @@ impl CompilerUnit {
             };
 
             let body = BlockBody {
-                nodes: implicit_func_nodes,
+                nodes: arena.alloc_slice(&implicit_func_nodes),
+                id: NodeId::DUMMY,
                 span: None,
                 opening_token: None,
                 closing_token: None,
@@ -627,15 +839,18 @@ impl CompilerUnit {
                 is_implicit: true,
                 name: SimpleIdentifier {
                     is_meta_name: true,
-                    name: String::from("!implicit_func"),
+                    name: Symbol::from_static("!implicit_func"),
                     is_escaped: false,
                     raw_token: None,
+                    id: NodeId::DUMMY,
                     span: None,
                 },
                 params: param_list,
                 return_type: None,
                 body: Body::Block(body),
 
+                id: NodeId::DUMMY,
+
                 span: None,
                 func_token: None,
                 return_type_arrow_token: None,
@@ -647,8 +862,8 @@ impl CompilerUnit {
 
         CompilerUnit {
             file_name: filename.clone(),
-            top_level_items,
+            top_level_items: arena.alloc_slice(&top_level_items),
             impl_func: implicit_func,
         }
     }
-}
\ No newline at end of file
+}
@@ -1,23 +1,53 @@
-use std::iter::{Enumerate, Peekable};
-use std::slice::Iter;
 use crate::judith::compiler_messages;
 use crate::judith::compiler_messages::{CompilerMessage, MessageContainer};
-use crate::judith::lexical::token::{Token, TokenKind};
+use crate::judith::lexical::symbol::Interner;
+use crate::judith::lexical::token::{RegularToken, Token, TokenId, TokenKind, TokenRef, TriviaRange};
+use crate::judith::lexical::token_source::TokenSource;
+use crate::judith::syntax::arena::Arena;
 use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::precedence;
+use crate::judith::syntax::precedence::Associativity;
 use crate::judith::syntax::syntax_factory::SyntaxFactory;
 
-pub struct Parser<'a> {
-    tokens: &'a Vec<Token>,
-    iter: Peekable<Enumerate<Iter<'a, Token>>>,
-    previous: Option<&'a Token>,
+/// The amount of tokens after the current one that are always available to peek at.
+const LOOKAHEAD: usize = 1;
+/// The most expressions, types and blocks that can be parsed inside each other. Deeper nesting is
+/// reported and skipped rather than parsed, as each level takes stack space.
+const MAX_DEPTH: usize = 80;
+/// The most tokens read ahead to find out whether a "(" opens the parameter list of a lambda
+/// without "func". This is the only place that reads past `LOOKAHEAD`, and the limit keeps it from
+/// reading the whole source (and from doing so again for every nested parenthesis). Parameter lists
+/// longer than this must be preceded by "func".
+const LAMBDA_LOOKAHEAD: usize = 128;
+
+pub struct Parser<'src, 'ast, S: TokenSource<'src>> {
+    source: S,
+    /// Every token pulled from the source so far. The tokens after the current one are the
+    /// lookahead buffer. Tokens aren't dropped once they're passed, as nodes reference them by id
+    /// and they're returned in the `ParserResult`.
+    tokens: Vec<Token<'src>>,
+    /// Whether the EOF token has been pulled, after which the source isn't read anymore.
+    is_source_done: bool,
+    /// The index of the current token.
+    current: usize,
+    factory: SyntaxFactory<'ast>,
+    /// The amount of expressions, types and blocks being parsed inside each other right now.
+    depth: usize,
     has_errors: bool,
     messages: MessageContainer,
 }
 
 /// The result of parsing a list of tokens.
-pub struct ParserResult {
-    pub nodes: Vec<SyntaxNode>,
+pub struct ParserResult<'src, 'ast> {
+    pub nodes: Vec<SyntaxNode<'ast>>,
+    /// The tokens parsed, which nodes reference by their id.
+    pub tokens: Vec<Token<'src>>,
+    /// The amount of nodes in the tree, each of which has an id lower than this.
+    pub node_count: usize,
     pub messages: MessageContainer,
+    /// The interner of the names in the tree, which can keep being used for later stages or later
+    /// sources of the same compilation.
+    pub interner: Interner<'ast>,
 }
 
 /// The result of trying to parse a specific kind of node.
@@ -30,29 +60,116 @@ pub enum ParseAttempt<T> {
     Err(CompilerMessage),
 }
 
-impl<'a> Parser<'a> {
-    pub fn new(tokens: &'a Vec<Token>) -> Self {
-        if tokens.len() == 0 || tokens.last().unwrap().kind() != TokenKind::EOF {
-            panic!("The list of tokens must be ended by an EOF token.");
-        }
-
-        Parser {
-            tokens,
-            iter: tokens.iter().enumerate().peekable(),
-            previous: None,
+impl<'src, 'ast, S: TokenSource<'src>> Parser<'src, 'ast, S> {
+    /// Creates a parser that pulls tokens from the source given as it needs them, and allocates
+    /// the nodes it parses in the arena given.
+    pub fn new(source: S, arena: &'ast Arena) -> Self {
+        Parser::with_interner(source, Interner::new(arena))
+    }
+
+    /// Creates a parser that interns names into the interner given, and allocates nodes in the
+    /// arena it uses.
+    pub fn with_interner(source: S, interner: Interner<'ast>) -> Self {
+        let mut parser = Parser {
+            source,
+            tokens: Vec::new(),
+            is_source_done: false,
+            current: 0,
+            factory: SyntaxFactory::with_interner(interner),
+            depth: 0,
             has_errors: false,
             messages: MessageContainer::new(),
-        }
+        };
+        parser.fill(LOOKAHEAD);
+
+        parser
     }
 
     // region Helper methods
-    fn last_token (&self) -> Token {
-        self.tokens.last().unwrap().clone() // There has to be at least one token, if the Parser was built properly.
+    /// Pulls tokens from the source until the one at the index given is buffered, or until the
+    /// EOF token is reached. If the source runs out before giving an EOF token, one is added right
+    /// after the last token.
+    fn fill(&mut self, index: usize) {
+        while self.tokens.len() <= index && !self.is_source_done {
+            let tok = match self.source.next_token() {
+                Some(tok) => tok,
+                None => self.synthetic_eof(),
+            };
+
+            self.is_source_done = tok.kind() == TokenKind::EOF;
+            self.tokens.push(tok);
+        }
+    }
+
+    /// Builds an EOF token placed at the end of the last token pulled.
+    fn synthetic_eof(&self) -> Token<'src> {
+        let (end, line) = match self.tokens.last() {
+            Some(tok) => (tok.base().end, tok.base().line),
+            None => (0, 1),
+        };
+
+        Token::Regular(RegularToken {
+            kind: TokenKind::EOF,
+            lexeme: "",
+            start: end,
+            end,
+            line,
+            leading_trivia: TriviaRange::default(),
+            trailing_trivia: TriviaRange::default(),
+        })
+    }
+
+    /// Runs `parse` one level deeper. Past `MAX_DEPTH`, reports the nesting instead and skips the
+    /// rest of the bracket the current token is in, so the levels above it can still be closed.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseAttempt<T>) -> ParseAttempt<T> {
+        if self.depth >= MAX_DEPTH {
+            let err = compiler_messages::Parser::nesting_too_deep(&self.now(), MAX_DEPTH);
+            self.skip_bracket();
+            return ParseAttempt::Err(err);
+        }
+
+        self.depth += 1;
+        let res = parse(self);
+        self.depth -= 1;
+
+        res
+    }
+
+    /// Skips tokens until the one that closes the bracket the current token is in, which isn't
+    /// skipped, or until the end of the source.
+    fn skip_bracket(&mut self) {
+        let mut depth = 0;
+        while let Some(kind) = self.peek_kind() {
+            match kind {
+                TokenKind::EOF => return,
+                TokenKind::LeftParen
+                | TokenKind::LeftSquareBracket
+                | TokenKind::LeftCurlyBracket => depth += 1,
+                TokenKind::RightParen
+                | TokenKind::RightSquareBracket
+                | TokenKind::RightCurlyBracket => {
+                    if depth == 0 {
+                        return;
+                    }
+                    depth -= 1;
+                }
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn token (&self, index: usize) -> Option<TokenRef<'src>> {
+        self.tokens.get(index).map(|token| TokenRef { id: TokenId(index as u32), token: *token })
+    }
+
+    fn last_token (&self) -> TokenRef<'src> {
+        self.token(self.tokens.len() - 1).unwrap() // The source is read up to the current token as soon as the Parser is built.
     }
 
     fn match_token (&mut self, kinds: &[TokenKind]) -> bool {
         for kind in kinds {
-            if self.check(kind.clone()) {
+            if self.check(*kind) {
                 self.advance();
                 return true;
             }
@@ -63,17 +180,17 @@ impl<'a> Parser<'a> {
 
     /// Returns true if the current token matches the type given.
     /// * `kind`: The token kind to check for.
-    fn check (&mut self, kind: TokenKind) -> bool {
+    fn check (&self, kind: TokenKind) -> bool {
         if self.is_at_end() {
             return false;
         }
 
-        self.peek().unwrap().kind() == kind // Unwrap shouldn't fail, as is_at_end would've returned true.
+        self.peek_kind() == Some(kind)
     }
 
-    fn check_many (&mut self, kinds: &[TokenKind]) -> bool {
+    fn check_many (&self, kinds: &[TokenKind]) -> bool {
         for kind in kinds {
-            if self.check(kind.clone()) {
+            if self.check(*kind) {
                 return true;
             }
         }
@@ -81,55 +198,66 @@ impl<'a> Parser<'a> {
         false
     }
 
+    /// Returns `true` if the current token is in the same line as the previous one.
+    fn is_on_same_line_as_previous(&self) -> bool {
+        match (self.peek_previous(), self.peek()) {
+            (Some(prev), Some(tok)) => prev.base().line == tok.base().line,
+            _ => false,
+        }
+    }
+
     /// Returns `true` if the current token is the EOF token (or if there's no token).
-    fn is_at_end(&mut self) -> bool {
-        match self.peek() {
-            Some(token) => token.kind() == TokenKind::EOF,
+    fn is_at_end(&self) -> bool {
+        match self.peek_kind() {
+            Some(kind) => kind == TokenKind::EOF,
             None => true,
         }
     }
 
-    fn cursor(&mut self) -> usize {
-        if let Some((index, _)) = self.iter.peek() {
-            return *index;
-        }
-        self.tokens.len()
+    fn cursor(&self) -> usize {
+        self.current.min(self.tokens.len())
     }
 
     /// Returns the current token.
-    fn peek(&mut self) -> Option<Token> {
-        if let Some((_, tok)) = self.iter.peek() {
-            Some((*tok).clone())
-        }
-        else {
-            None
-        }
+    fn peek(&self) -> Option<TokenRef<'src>> {
+        self.token(self.current)
+    }
+
+    /// Returns the kind of the current token.
+    fn peek_kind(&self) -> Option<TokenKind> {
+        self.tokens.get(self.current).map(Token::kind)
+    }
+
+    /// Returns the token `n` positions after the current one, without moving. Only the tokens up
+    /// to `LOOKAHEAD` are sure to be pulled from the source; any further must be pulled with
+    /// `fill` first.
+    fn peek_nth(&self, n: usize) -> Option<TokenRef<'src>> {
+        self.token(self.cursor() + n)
     }
 
     /// Returns the previous token.
-    fn peek_previous (&mut self) -> Option<Token> {
-        self.previous.map(|t| t.clone())
+    fn peek_previous (&self) -> Option<TokenRef<'src>> {
+        self.current.checked_sub(1).and_then(|index| self.token(index))
     }
 
     /// Returns the current token, or the EOF token if there's no tokens.
-    fn now (&mut self) -> Token {
+    fn now (&self) -> TokenRef<'src> {
         self.peek().unwrap_or(self.last_token())
     }
 
     /// Returns the current token and moves into the next one.
-    fn advance(&mut self) -> Option<Token> {
-        if let Some((_, tok)) = self.iter.next() {
-            Some(tok.clone())
-        }
-        else {
-            None
-        }
+    fn advance(&mut self) -> Option<TokenRef<'src>> {
+        let tok = self.peek()?;
+        self.current += 1;
+        self.fill(self.current + LOOKAHEAD);
+
+        Some(tok)
     }
 
     /// Advances (returning the current token) only if the current token is of the kind given.
-    fn try_consume(&mut self, kind: TokenKind) -> Option<Token> {
+    fn try_consume(&mut self, kind: TokenKind) -> Option<TokenRef<'src>> {
         if self.check(kind) {
-            self.advance().map(|t| t.clone())
+            self.advance()
         }
         else {
             None
@@ -138,9 +266,9 @@ impl<'a> Parser<'a> {
 
     /// Advances (returning the current token) only if the currente token matches one of the kinds
     /// given.
-    fn try_consume_many(&mut self, kinds: &[TokenKind]) -> Option<Token> {
+    fn try_consume_many(&mut self, kinds: &[TokenKind]) -> Option<TokenRef<'src>> {
         if self.check_many(kinds) {
-            self.advance().map(|t| t.clone())
+            self.advance()
         }
         else {
             None
@@ -150,10 +278,10 @@ impl<'a> Parser<'a> {
 
     // region Parse methods
     // node ::= expr
-    pub fn parse_top_level_node(&mut self) -> ParseAttempt<SyntaxNode> {
+    pub fn parse_top_level_node(&mut self) -> ParseAttempt<SyntaxNode<'ast>> {
         match self.parse_item() {
             ParseAttempt::Ok(it) => return ParseAttempt::Ok(SyntaxNode::Item(it)),
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            ParseAttempt::Err(err) => return self.register_err_node(err),
             _ => {},
         };
 
@@ -167,9 +295,11 @@ impl<'a> Parser<'a> {
     }
 
     // region Parse items
-    pub fn parse_item(&mut self) -> ParseAttempt<Item> {
+    pub fn parse_item(&mut self) -> ParseAttempt<Item<'ast>> {
         match self.parse_func_def() {
-            ParseAttempt::Ok(func_def) => return ParseAttempt::Ok(Item::FuncDef(func_def)),
+            ParseAttempt::Ok(func_def) => {
+                return ParseAttempt::Ok(Item::FuncDef(self.factory.alloc(func_def)));
+            }
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             _ => {},
         };
@@ -177,7 +307,14 @@ impl<'a> Parser<'a> {
         ParseAttempt::None
     }
 
-    pub fn parse_func_def(&mut self) -> ParseAttempt<FuncDef> {
+    pub fn parse_func_def(&mut self) -> ParseAttempt<FuncDef<'ast>> {
+        // "func" followed by a parameter list is an anonymous function, not a definition.
+        if self.check(TokenKind::KwFunc)
+            && self.peek_nth(1).is_some_and(|tok| tok.kind() == TokenKind::LeftParen)
+        {
+            return ParseAttempt::None;
+        }
+
         let func_tok = match self.try_consume(TokenKind::KwFunc) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -187,7 +324,7 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(name) => name,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::identifier_expected(self.now())
+                compiler_messages::Parser::identifier_expected(&self.now())
             ),
         };
 
@@ -195,7 +332,7 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(param_list) => param_list,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::parameter_list_expected(self.now())
+                compiler_messages::Parser::parameter_list_expected(&self.now())
             )
         };
 
@@ -206,7 +343,7 @@ impl<'a> Parser<'a> {
                     ParseAttempt::Ok(ty) => Some(ty),
                     ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::type_expected(self.now())
+                        compiler_messages::Parser::type_expected(&self.now())
                     )
                 }
             }
@@ -217,18 +354,18 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(body) => body,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::body_expected(self.now())
+                compiler_messages::Parser::body_expected(&self.now())
             )
         };
 
         ParseAttempt::Ok(
-            SyntaxFactory::func_def(func_tok, name, param_list, arrow_tok, return_type, body)
+            self.factory.func_def(func_tok, name, param_list, arrow_tok, return_type, body)
         )
     }
     // endregion Parse items
 
     // region Parse bodies
-    pub fn parse_body(&mut self, opening_token: Option<TokenKind>) -> ParseAttempt<Body> {
+    pub fn parse_body(&mut self, opening_token: Option<TokenKind>) -> ParseAttempt<Body<'ast>> {
         // Arrow must be tried first, since block statement may not have an opening token (and thus
         // would report an error trying to read an arrow).
         match self.parse_arrow_body() {
@@ -246,7 +383,9 @@ impl<'a> Parser<'a> {
         ParseAttempt::None
     }
 
-    pub fn parse_block_body(&mut self, opening_token: Option<TokenKind>) -> ParseAttempt<BlockBody> {
+    pub fn parse_block_body(
+        &mut self, opening_token: Option<TokenKind>
+    ) -> ParseAttempt<BlockBody<'ast>> {
         let opening_token = if let Some(tok_kind) = opening_token {
             match self.try_consume(tok_kind) {
                 Some(tok) => Some(tok),
@@ -258,11 +397,11 @@ impl<'a> Parser<'a> {
         };
 
         let mut nodes: Vec<SyntaxNode> = Vec::new();
-        let closing_token: Option<Token>;
+        let closing_token: Option<TokenRef>;
         loop {
             if self.is_at_end() {
                 return ParseAttempt::Err(
-                    compiler_messages::Parser::end_expected(self.now())
+                    compiler_messages::Parser::end_expected(&self.now())
                 )
             };
             if let Some(tok) = self.try_consume(TokenKind::KwEnd) {
@@ -274,21 +413,26 @@ impl<'a> Parser<'a> {
                 break;
             }
 
-            let node = match self.parse_top_level_node() {
+            let start = self.cursor();
+            let node = match self.nested(Self::parse_top_level_node) {
                 ParseAttempt::Ok(node) => node,
                 ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::unexpected_token(self.now())
+                    compiler_messages::Parser::unexpected_token(&self.now())
                 ),
             };
 
             nodes.push(node);
+            // Skips the token an error node was read at, if it wasn't consumed.
+            if self.cursor() == start {
+                self.advance();
+            }
         }
 
-        ParseAttempt::Ok(SyntaxFactory::block_body(opening_token, nodes, closing_token))
+        ParseAttempt::Ok(self.factory.block_body(opening_token, nodes, closing_token))
     }
 
-    pub fn parse_arrow_body(&mut self) -> ParseAttempt<ArrowBody> {
+    pub fn parse_arrow_body(&mut self) -> ParseAttempt<ArrowBody<'ast>> {
         let arrow_tok = match self.try_consume(TokenKind::EqualArrow) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -298,18 +442,23 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::expression_expected(self.now())
+                compiler_messages::Parser::expression_expected(&self.now())
             ),
         };
 
-        ParseAttempt::Ok(SyntaxFactory::arrow_body(arrow_tok, expr))
+        ParseAttempt::Ok(self.factory.arrow_body(arrow_tok, expr))
     }
     // endregion
 
     // region Parse statements
-    pub fn parse_stmt(&mut self) -> ParseAttempt<Stmt> {
+    pub fn parse_stmt(&mut self) -> ParseAttempt<Stmt<'ast>> {
         match self.parse_local_decl_stmt() {
-            ParseAttempt::Ok(stmt) => return ParseAttempt::Ok(Stmt::LocalDecl(stmt)),
+            ParseAttempt::Ok(stmt) => return ParseAttempt::Ok(Stmt::LocalDecl(self.factory.alloc(stmt))),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {}
+        }
+        match self.parse_priv_print_stmt() {
+            ParseAttempt::Ok(stmt) => return ParseAttempt::Ok(Stmt::PrivPrint(stmt)),
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             _ => {}
         }
@@ -322,59 +471,107 @@ impl<'a> Parser<'a> {
         ParseAttempt::None
     }
 
-    pub fn parse_expr_stmt(&mut self) -> ParseAttempt<ExprStmt> {
+    pub fn parse_expr_stmt(&mut self) -> ParseAttempt<ExprStmt<'ast>> {
         let expr = match self.parse_expr() {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::expr_stmt(expr))
+        ParseAttempt::Ok(self.factory.expr_stmt(expr))
     }
 
-    pub fn parse_local_decl_stmt(&mut self) -> ParseAttempt<LocalDeclStmt> {
+    // local_decl_stmt ::= "let" local_declarator ( "," local_declarator )* equals_value_clause?
+    pub fn parse_local_decl_stmt(&mut self) -> ParseAttempt<LocalDeclStmt<'ast>> {
         let let_tok = match self.try_consume(TokenKind::KwLet) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
-        let declarator = match self.parse_local_declarator() {
-            ParseAttempt::Ok(declarator) => declarator,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::variable_declarator_expected(self.now())
-            ),
-        };
+        let mut declarators: Vec<PartialLocalDecl> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
+        loop {
+            let declarator = match self.parse_local_declarator() {
+                ParseAttempt::Ok(declarator) => declarator,
+                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                ParseAttempt::None => return ParseAttempt::Err(
+                    compiler_messages::Parser::variable_declarator_expected(&self.now())
+                ),
+            };
+            declarators.push(declarator);
+
+            if let Some(tok) = self.try_consume(TokenKind::Comma) {
+                comma_tokens.push(tok.id);
+            }
+            else {
+                break;
+            }
+        }
 
-        let init = match self.parse_equals_value_clause(false) {
+        let init = match self.parse_equals_value_clause(true) {
             ParseAttempt::Ok(init) => Some(init),
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::local_decl_stmt(let_tok, declarator, init))
+        // There's nothing to destructure without a value.
+        let is_destructuring = declarators.iter()
+            .any(|decl| matches!(decl, PartialLocalDecl::Destructured(_)));
+        if init.is_none() && is_destructuring {
+            return ParseAttempt::Err(
+                compiler_messages::Parser::destructuring_must_be_initialized(&self.now())
+            );
+        }
+
+        ParseAttempt::Ok(self.factory.local_decl_stmt(let_tok, declarators, init, comma_tokens))
+    }
+
+    // priv_print_stmt ::= "__p_print" expr
+    pub fn parse_priv_print_stmt(&mut self) -> ParseAttempt<PrivPrintStmt<'ast>> {
+        let p_print_tok = match self.try_consume(TokenKind::PkwPrint) {
+            Some(tok) => tok,
+            None => return ParseAttempt::None,
+        };
+
+        let expr = match self.parse_expr() {
+            ParseAttempt::Ok(expr) => expr,
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            ParseAttempt::None => return ParseAttempt::Err(
+                compiler_messages::Parser::expression_expected(&self.now())
+            ),
+        };
+
+        ParseAttempt::Ok(self.factory.priv_print_stmt(p_print_tok, expr))
     }
     // endregion Parse statements
 
     // region Parse expressions
-    // expr ::= if_expr | loop_expr | while_expr | assignment_expr
-    pub fn parse_expr(&mut self) -> ParseAttempt<Expr> {
+    // expr ::= if_expr | loop_expr | while_expr | binary_expr
+    pub fn parse_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
+        match self.nested(Self::parse_unnested_expr) {
+            ParseAttempt::Err(err) => self.register_err_expr(err),
+            attempt => attempt,
+        }
+    }
+
+    /// Parses an expression without counting it as a level of nesting, which `parse_expr` does.
+    fn parse_unnested_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         match self.parse_if_expr(TokenKind::KwIf) {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::If(Box::from(expr))),
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::If(self.factory.alloc(expr))),
             ParseAttempt::Err(err) => return self.register_err_expr(err),
             _ => {}
         };
         match self.parse_loop_expr() {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::Loop(Box::from(expr))),
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::Loop(self.factory.alloc(expr))),
             ParseAttempt::Err(err) => return self.register_err_expr(err),
             _ => {}
         };
         match self.parse_while_expr() {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::While(Box::from(expr))),
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::While(self.factory.alloc(expr))),
             ParseAttempt::Err(err) => return self.register_err_expr(err),
             _ => {}
         };
-        match self.parse_assignment_expr() {
+        match self.parse_binary_expr(1) {
             ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
             ParseAttempt::Err(err) => return self.register_err_expr(err),
             _ => {},
@@ -384,7 +581,7 @@ impl<'a> Parser<'a> {
     }
 
     // if_expr ::=
-    pub fn parse_if_expr(&mut self, if_token_kind: TokenKind) -> ParseAttempt<IfExpr> {
+    pub fn parse_if_expr(&mut self, if_token_kind: TokenKind) -> ParseAttempt<IfExpr<'ast>> {
         let if_tok = match self.try_consume(if_token_kind) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -394,7 +591,7 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::expression_expected(self.now())
+                compiler_messages::Parser::expression_expected(&self.now())
             ),
         };
 
@@ -402,7 +599,7 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(body) => body,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::body_expected(self.now())
+                compiler_messages::Parser::body_expected(&self.now())
             )
         };
 
@@ -413,17 +610,17 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(elsif) => elsif,
                 ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::elsif_body_expected(self.now())
+                    compiler_messages::Parser::elsif_body_expected(&self.now())
                 )
             };
 
-            ParseAttempt::Ok(SyntaxFactory::if_else_expr(
+            ParseAttempt::Ok(self.factory.if_else_expr(
                 if_tok,
                 test,
                 consequent,
                 None,
-                Body::Expr(SyntaxFactory::expr_body(
-                    Expr::If(Box::from(alternate))
+                Body::Expr(self.factory.expr_body(
+                    Expr::If(self.factory.alloc(alternate))
                 ))
             ))
         }
@@ -434,24 +631,24 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(body) => body,
                 ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::body_expected(self.now())
+                    compiler_messages::Parser::body_expected(&self.now())
                 )
             };
 
             ParseAttempt::Ok(
-                SyntaxFactory::if_else_expr(if_tok, test, consequent, Some(else_tok), alternate)
+                self.factory.if_else_expr(if_tok, test, consequent, Some(else_tok), alternate)
             )
         }
         // If there isn't either an "elsif" nor an "else", we build an if expression without alternate.
         else {
             ParseAttempt::Ok(
-                SyntaxFactory::if_expr(if_tok, test, consequent)
+                self.factory.if_expr(if_tok, test, consequent)
             )
         }
     }
 
     // loop_expr ::=
-    pub fn parse_loop_expr(&mut self) -> ParseAttempt<LoopExpr> {
+    pub fn parse_loop_expr(&mut self) -> ParseAttempt<LoopExpr<'ast>> {
         let loop_tok = match self.try_consume(TokenKind::KwLoop) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -461,15 +658,15 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(body) => body,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::body_expected(self.now())
+                compiler_messages::Parser::body_expected(&self.now())
             )
         };
 
-        ParseAttempt::Ok(SyntaxFactory::loop_expr(loop_tok, body))
+        ParseAttempt::Ok(self.factory.loop_expr(loop_tok, body))
     }
 
     // while_expr ::=
-    pub fn parse_while_expr(&mut self) -> ParseAttempt<WhileExpr> {
+    pub fn parse_while_expr(&mut self) -> ParseAttempt<WhileExpr<'ast>> {
         let while_expr = match self.try_consume(TokenKind::KwWhile) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -479,7 +676,7 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::expression_expected(self.now())
+                compiler_messages::Parser::expression_expected(&self.now())
             ),
         };
 
@@ -487,200 +684,86 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(body) => body,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::body_expected(self.now())
+                compiler_messages::Parser::body_expected(&self.now())
             )
         };
 
-        ParseAttempt::Ok(SyntaxFactory::while_expr(while_expr, test, body))
+        ParseAttempt::Ok(self.factory.while_expr(while_expr, test, body))
     }
 
     // region Parse cascading expressions
-    // assignment_expr ::= or_logical_expr ( "=" or_logical_expr )?
-    pub fn parse_assignment_expr(&mut self) -> ParseAttempt<Expr> {
-        let left = match self.parse_or_logical_expr() {
+    // binary_expr ::= left_unary_expr ( binary_operator left_unary_expr )*
+    // The precedence and associativity of each binary operator are given by the operator table in
+    // the precedence module. Only operators with a precedence of at least `min_precedence` are
+    // parsed, which makes the right operand of an operator stop at operators that bind looser.
+    pub fn parse_binary_expr(&mut self, min_precedence: u8) -> ParseAttempt<Expr<'ast>> {
+        let mut left = match self.parse_left_unary_expr() {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::None,
         };
 
-        let op = match self.parse_operator(&[TokenKind::Equal]) {
-            ParseAttempt::Ok(op) => op,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::Ok(left),
-        };
+        // The precedence of the last non-associative operator parsed, which can't be followed by
+        // another operator with the same precedence.
+        let mut non_associative_precedence: Option<u8> = None;
 
-        let right = match self.parse_or_logical_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::expression_expected(self.now())
-            ),
-        };
-
-        ParseAttempt::Ok(Expr::Assignment(
-            Box::from(SyntaxFactory::assignment_expr(left, op, right))
-        ))
-    }
-
-    // or_logical_expr ::= and_logical_expr ( "or" and_logical_expr )*
-    pub fn parse_or_logical_expr(&mut self) -> ParseAttempt<Expr> {
-        let mut left = match self.parse_and_logical_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
-        };
-
-        while let ParseAttempt::Ok(op) = self.parse_operator(&[
-            TokenKind::KwOr,
-        ]) {
-            let right = match self.parse_and_logical_expr() {
-                ParseAttempt::Ok(expr) => expr,
-                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-                ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
-                ),
+        while let Some(kind) = self.peek_kind() {
+            let Some(info) = precedence::binary_operator_info(&precedence::operator_kind(kind))
+            else {
+                break;
             };
 
-            left = Expr::Binary(
-                Box::from(SyntaxFactory::binary_expr(left, op, right))
-            );
-        }
-
-        ParseAttempt::Ok(left)
-    }
-
-    // and_logical_expr ::= parse_bool_expr ( "and" parse_bool_expr )*
-    pub fn parse_and_logical_expr(&mut self) -> ParseAttempt<Expr> {
-        let mut left = match self.parse_bool_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
-        };
+            if info.precedence < min_precedence || non_associative_precedence == Some(info.precedence) {
+                break;
+            }
 
-        while let ParseAttempt::Ok(op) = self.parse_operator(&[
-            TokenKind::KwAnd,
-        ]) {
-            let right = match self.parse_bool_expr() {
-                ParseAttempt::Ok(expr) => expr,
-                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-                ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
-                ),
+            let Some(op_tok) = self.advance() else {
+                break;
             };
-
-            left = Expr::Binary(
-                Box::from(SyntaxFactory::binary_expr(left, op, right))
-            );
-        }
-
-        ParseAttempt::Ok(left)
-    }
-
-    // bool_expr ::= add_expr ( ( "==" | "!=" | "~=" | "!~" | "===" | "!==" | "<" | "<=" | ">" | ">=" ) add_expr )*
-    pub fn parse_bool_expr(&mut self) -> ParseAttempt<Expr> {
-        let mut left = match self.parse_add_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
-        };
-
-        while let ParseAttempt::Ok(op) = self.parse_operator(&[
-            TokenKind::EqualEqual,
-            TokenKind::BangEqual,
-            TokenKind::TildeTilde,
-            TokenKind::BangTilde,
-            TokenKind::EqualEqualEqual,
-            TokenKind::BangEqualEqual,
-            TokenKind::Less,
-            TokenKind::LessEqual,
-            TokenKind::Greater,
-            TokenKind::GreaterEqual,
-        ]) {
-            let right = match self.parse_add_expr() {
-                ParseAttempt::Ok(expr) => expr,
-                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-                ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
-                ),
+            let op = self.factory.operator(op_tok);
+            let right_precedence = match info.associativity {
+                Associativity::Right => info.precedence,
+                Associativity::Left | Associativity::None => info.precedence + 1,
             };
 
-            left = Expr::Binary(
-                Box::from(SyntaxFactory::binary_expr(left, op, right))
-            );
-        }
-
-        ParseAttempt::Ok(left)
-    }
-
-    // add_expr ::= mult_expr ( ( "+" | "-" ) mult_expr )*
-    pub fn parse_add_expr(&mut self) -> ParseAttempt<Expr> {
-        let mut left = match self.parse_mult_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
-        };
-
-        while let ParseAttempt::Ok(op) = self.parse_operator(
-            &[TokenKind::Plus, TokenKind::Minus]
-        ) {
-            let right = match self.parse_mult_expr() {
+            let right = match self.nested(|this| this.parse_binary_expr(right_precedence)) {
                 ParseAttempt::Ok(expr) => expr,
                 ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
+                    compiler_messages::Parser::expression_expected(&self.now())
                 ),
             };
 
-            left = Expr::Binary(
-                Box::from(SyntaxFactory::binary_expr(left, op, right))
-            );
-        }
-
-        ParseAttempt::Ok(left)
-    }
-
-    // mult_expr ::= left_unary-expr ( ( "*" | "/" ) left_unary_expr )*
-    pub fn parse_mult_expr(&mut self) -> ParseAttempt<Expr> {
-        let mut left = match self.parse_left_unary_expr() {
-            ParseAttempt::Ok(expr) => expr,
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
-        };
-
-        while let ParseAttempt::Ok(op) = self.parse_operator(
-            &[TokenKind::Asterisk, TokenKind::Slash]
-        ) {
-            let right = match self.parse_left_unary_expr() {
-                ParseAttempt::Ok(expr) => expr,
-                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-                ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
-                ),
+            left = if precedence::is_assignment_operator(&op.kind) {
+                Expr::Assignment(self.factory.alloc(self.factory.assignment_expr(left, op, right)))
+            }
+            else {
+                Expr::Binary(self.factory.alloc(self.factory.binary_expr(left, op, right)))
             };
 
-            left = Expr::Binary(
-                Box::from(SyntaxFactory::binary_expr(left, op, right))
-            );
+            if info.associativity == Associativity::None {
+                non_associative_precedence = Some(info.precedence);
+            }
         }
 
         ParseAttempt::Ok(left)
     }
 
-    // left_unary_expr ::= ( ( "not" | "-" | "~" ) left_unary_expr ) | object_init_expr
-    pub fn parse_left_unary_expr(&mut self) -> ParseAttempt<Expr> {
+    // left_unary_expr ::= ( ( "not" | "-" | "~" | "in" ) left_unary_expr ) | object_init_expr
+    pub fn parse_left_unary_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         if let ParseAttempt::Ok(op) = self.parse_operator(
-            &[TokenKind::KwNot, TokenKind::Minus, TokenKind::Tilde]
+            &[TokenKind::KwNot, TokenKind::Minus, TokenKind::Tilde, TokenKind::KwIn]
         ) {
-            let expr = match self.parse_left_unary_expr() {
+            let expr = match self.nested(Self::parse_left_unary_expr) {
                 ParseAttempt::Ok(expr) => expr,
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
+                    compiler_messages::Parser::expression_expected(&self.now())
                 ),
             };
 
             ParseAttempt::Ok(Expr::LeftUnary(
-                Box::from(SyntaxFactory::left_unary_expr(op, expr))
+                self.factory.alloc(self.factory.left_unary_expr(op, expr))
             ))
         }
         else {
@@ -688,12 +771,12 @@ impl<'a> Parser<'a> {
         }
     }
 
-    // object_init_expr ::= call_expr obj_initialization?
-    pub fn parse_object_init_expr(&mut self) -> ParseAttempt<Expr> {
+    // object_init_expr ::= postfix_expr obj_initialization?
+    pub fn parse_object_init_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         // An object initialization may not have any provider (for anonymous structs, or structs whose
         // type can be inferred). For this reason, we can't discard an object initialization even if
         // the provider is not found.
-        let provider = match self.parse_call_expr() {
+        let provider = match self.parse_postfix_expr() {
             ParseAttempt::Ok(expr) => Some(expr),
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => None,
@@ -711,96 +794,263 @@ impl<'a> Parser<'a> {
         };
 
         ParseAttempt::Ok(Expr::ObjectInit(
-            Box::from(SyntaxFactory::object_init_expr(provider, obj_init))
+            self.factory.alloc(self.factory.object_init_expr(provider, obj_init))
         ))
     }
 
-    // call_expr ::= access_expr arg_list*
-    pub fn parse_call_expr(&mut self) -> ParseAttempt<Expr> {
-        let callee = match self.parse_access_expr() {
+    // postfix_expr ::= ( primary_expr | member_access ) ( member_access | arg_list | index | "!" )*
+    pub fn parse_postfix_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
+        let mut expr = match self.parse_primary() {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::None,
+            // Because member access can be implicit (e.g. '.name'), we cannot discard a postfix
+            // expression just because we didn't encounter what is being accessed.
+            ParseAttempt::None => match self.parse_member_access(None) {
+                ParseAttempt::Ok(expr) => expr,
+                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                ParseAttempt::None => return ParseAttempt::None,
+            },
+        };
+
+        loop {
+            if self.check_many(&[TokenKind::Dot, TokenKind::QuestionDot, TokenKind::DoubleColon]) {
+                expr = match self.parse_member_access(Some(expr)) {
+                    ParseAttempt::Ok(expr) => expr,
+                    ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                    ParseAttempt::None => return ParseAttempt::Err(
+                        compiler_messages::Parser::unexpected_token(&self.now())
+                    ),
+                };
+                continue;
+            }
+
+            // A "(" or "[" in a different line starts a new expression rather than calling or
+            // indexing this one.
+            if self.check(TokenKind::LeftParen) && self.is_on_same_line_as_previous() {
+                match self.parse_argument_list() {
+                    ParseAttempt::Ok(arg_list) => {
+                        expr = Expr::Call(
+                            self.factory.alloc(self.factory.call_expr(expr, arg_list))
+                        );
+                        continue;
+                    }
+                    ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                    ParseAttempt::None => {}
+                }
+            }
+
+            if self.check(TokenKind::LeftSquareBracket) && self.is_on_same_line_as_previous() {
+                expr = match self.parse_index_expr(expr) {
+                    ParseAttempt::Ok(expr) => expr,
+                    ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                    ParseAttempt::None => return ParseAttempt::Err(
+                        compiler_messages::Parser::unexpected_token(&self.now())
+                    ),
+                };
+                continue;
+            }
+
+            match self.parse_operator(&[TokenKind::Bang]) {
+                ParseAttempt::Ok(op) => expr = Expr::RightUnary(
+                    self.factory.alloc(self.factory.right_unary_expr(expr, op))
+                ),
+                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                ParseAttempt::None => return ParseAttempt::Ok(expr),
+            }
+        }
+    }
+
+    // member_access ::= ( "." | "?." | "::" ) identifier
+    /// Parses a single member access on the receiver given. Without a receiver, only implicit
+    /// member access ('.name') is valid.
+    fn parse_member_access(&mut self, receiver: Option<Expr<'ast>>) -> ParseAttempt<Expr<'ast>> {
+        let kinds: &[TokenKind] = match receiver {
+            Some(_) => &[TokenKind::Dot, TokenKind::QuestionDot, TokenKind::DoubleColon],
+            None => &[TokenKind::Dot],
         };
 
-        let arg_list = match self.parse_argument_list() {
-            ParseAttempt::Ok(arg_list) => arg_list,
+        // Safe access only makes sense when there's something that may be null.
+        if receiver.is_none() && self.check(TokenKind::QuestionDot) {
+            return ParseAttempt::Err(compiler_messages::Parser::expression_expected(&self.now()));
+        }
+
+        let op = match self.parse_operator(kinds) {
+            ParseAttempt::Ok(op) => op,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            ParseAttempt::None => return ParseAttempt::Ok(callee),
+            ParseAttempt::None => return match receiver {
+                Some(expr) => ParseAttempt::Ok(expr),
+                None => ParseAttempt::None,
+            },
         };
-        
-        ParseAttempt::Ok(Expr::Call(
-            Box::from(SyntaxFactory::call_expr(callee, arg_list))
+
+        let tok = self.now();
+        let name = match self.parse_simple_identifier() {
+            ParseAttempt::Ok(name) => name,
+            ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
+            ParseAttempt::None => {
+                return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(&tok));
+            }
+        };
+
+        ParseAttempt::Ok(Expr::Access(
+            self.factory.alloc(self.factory.access_expr(receiver, op, name))
         ))
     }
 
-    // access_expr ::= primary_expr ( "." primary_expr )*
-    pub fn parse_access_expr(&mut self) -> ParseAttempt<Expr> {
-        // Because member access can be implicit (e.g. '.name'), we cannot discard a member access
-        // just because we didn't encounter what is being accessed.
-        let mut receiver = match self.parse_primary() {
-            ParseAttempt::Ok(expr) => Some(expr),
-            ParseAttempt::None => None,
-            ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
+    // index_expr ::= "[" expr "]"
+    /// Parses an index access on the receiver given.
+    fn parse_index_expr(&mut self, receiver: Expr<'ast>) -> ParseAttempt<Expr<'ast>> {
+        let left_sq_bracket = match self.try_consume(TokenKind::LeftSquareBracket) {
+            Some(tok) => tok,
+            None => return ParseAttempt::None,
         };
 
-        // Now, if we don't find a member access token ('.'):
-        let tok = self.peek();
-        if tok.is_none() || tok.unwrap().kind() != TokenKind::Dot {
-            // If we didn't find a provider, then we aren't parsing an access expression (or anything
-            // below it). If we did, then the provider is the expression we parsed.
-            return match receiver {
-                Some(expr) => ParseAttempt::Ok(expr),
-                None => ParseAttempt::None,
-            };
-        }
+        let index = match self.parse_expr() {
+            ParseAttempt::Ok(expr) => expr,
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            ParseAttempt::None => return ParseAttempt::Err(
+                compiler_messages::Parser::expression_expected(&self.now())
+            ),
+        };
 
-        while let ParseAttempt::Ok(op) = self.parse_operator(&[TokenKind::Dot]) {
-            let tok = self.now();
-            let name = match self.parse_simple_identifier() {
-                ParseAttempt::Ok(name) => name,
-                ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
-                ParseAttempt::None => {
-                    return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(tok));
-                }
-            };
+        let right_sq_bracket = match self.try_consume(TokenKind::RightSquareBracket) {
+            Some(tok) => tok,
+            None => return ParseAttempt::Err(
+                compiler_messages::Parser::right_square_bracket_expected(&self.now())
+            ),
+        };
 
-            receiver = Some(Expr::Access(
-                Box::from(SyntaxFactory::access_expr(receiver, op, name))
-            ));
-        }
+        ParseAttempt::Ok(Expr::Index(self.factory.alloc(self.factory.index_expr(
+            receiver, left_sq_bracket, index, right_sq_bracket
+        ))))
+    }
 
-        match receiver {
-            Some(expr) => ParseAttempt::Ok(expr),
-            None => ParseAttempt::None,
-        }
+    // primary_expr ::= lambda_expr | group_expr | tuple_expr | array_expr | empty_collection_expr
+    //                | identifier_expr | literal_expr
+    pub fn parse_primary(&mut self) -> ParseAttempt<Expr<'ast>> {
+        // Lambdas must be tried before groups, as both may start with "(".
+        match self.parse_lambda_expr() {
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::Lambda(self.factory.alloc(expr))),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {},
+        };
+
+        match self.parse_group_or_tuple_expr() {
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {},
+        };
+
+        match self.parse_array_expr() {
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {},
+        };
+
+        match self.parse_identifier_expr() {
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {},
+        };
+
+        match self.parse_literal_expr() {
+            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {},
+        };
+
+        ParseAttempt::None
     }
 
-    // primary_expr ::= group_expr | identifier_expr | literal_expr
-    pub fn parse_primary(&mut self) -> ParseAttempt<Expr> {
-        match self.parse_group_expr() {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            _ => {},
+    // lambda_expr ::= ( "func" param_list ( "->" type )? body ) | ( param_list ( "->" type )? arrow_body )
+    pub fn parse_lambda_expr(&mut self) -> ParseAttempt<LambdaExpr<'ast>> {
+        let func_tok = self.try_consume(TokenKind::KwFunc);
+
+        // Without "func", a parameter list can't be told apart from a group expression until
+        // we see what comes after the closing parenthesis.
+        if func_tok.is_none() && !self.is_arrow_lambda_ahead() {
+            return ParseAttempt::None;
+        }
+
+        let params = match self.parse_parameter_list() {
+            ParseAttempt::Ok(params) => params,
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            ParseAttempt::None => return ParseAttempt::Err(
+                compiler_messages::Parser::parameter_list_expected(&self.now())
+            ),
+        };
+
+        let arrow_tok = self.try_consume(TokenKind::MinusArrow);
+        let return_type = match &arrow_tok {
+            Some(_) => match self.parse_type() {
+                ParseAttempt::Ok(ty) => Some(ty),
+                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                ParseAttempt::None => return ParseAttempt::Err(
+                    compiler_messages::Parser::type_expected(&self.now())
+                ),
+            },
+            None => None,
         };
 
-        match self.parse_identifier_expr() {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
-            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            _ => {},
+        let body = if func_tok.is_some() {
+            self.parse_body(None)
+        }
+        else {
+            match self.parse_arrow_body() {
+                ParseAttempt::Ok(body) => ParseAttempt::Ok(Body::Arrow(body)),
+                ParseAttempt::Err(err) => ParseAttempt::Err(err),
+                ParseAttempt::None => ParseAttempt::None,
+            }
         };
 
-        match self.parse_literal_expr() {
-            ParseAttempt::Ok(expr) => return ParseAttempt::Ok(expr),
+        let body = match body {
+            ParseAttempt::Ok(body) => body,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
-            _ => {},
+            ParseAttempt::None => return ParseAttempt::Err(
+                compiler_messages::Parser::body_expected(&self.now())
+            ),
         };
 
-        ParseAttempt::None
+        ParseAttempt::Ok(self.factory.lambda_expr(func_tok, params, arrow_tok, return_type, body))
+    }
+
+    /// Returns `true` if the current token opens a parenthesized list whose closing parenthesis is
+    /// followed by "=>" or "->", which means it's the parameter list of a lambda. The closing
+    /// parenthesis must be within `LAMBDA_LOOKAHEAD` tokens.
+    fn is_arrow_lambda_ahead(&mut self) -> bool {
+        if !self.check(TokenKind::LeftParen) {
+            return false;
+        }
+
+        let start = self.cursor();
+        let mut depth = 0;
+        for index in start..start + LAMBDA_LOOKAHEAD {
+            self.fill(index + 1);
+            let Some(tok) = self.tokens.get(index) else {
+                return false;
+            };
+
+            match tok.kind() {
+                TokenKind::LeftParen => depth += 1,
+                TokenKind::RightParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.tokens.get(index + 1).is_some_and(|tok| {
+                            matches!(tok.kind(), TokenKind::EqualArrow | TokenKind::MinusArrow)
+                        });
+                    }
+                }
+                TokenKind::EOF => return false,
+                _ => {}
+            }
+        }
+
+        false
     }
 
     // group_expr ::= "(" expr ")"
-    pub fn parse_group_expr(&mut self) -> ParseAttempt<Expr> {
+    // tuple_expr ::= "(" expr "," ( expr ( "," expr )* ","? )? ")"
+    pub fn parse_group_or_tuple_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         let left_paren = match self.try_consume(TokenKind::LeftParen) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -811,26 +1061,112 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => {
-                return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(tok));
+                return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(&tok));
             }
         };
 
+        // Without a comma after the first expression, this is just a group.
+        let Some(first_comma) = self.try_consume(TokenKind::Comma) else {
+            let tok = self.now();
+            let right_paren = match self.try_consume(TokenKind::RightParen) {
+                Some(tok) => tok,
+                None => {
+                    return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&tok));
+                }
+            };
+
+            return ParseAttempt::Ok(Expr::Group(
+                self.factory.alloc(self.factory.group_expr(left_paren, expr, right_paren))
+            ));
+        };
+
+        let mut elements = vec![expr];
+        let mut comma_tokens = vec![first_comma.id];
+        while !self.check(TokenKind::RightParen) {
+            let tok = self.now();
+            match self.parse_expr() {
+                ParseAttempt::Ok(expr) => elements.push(expr),
+                ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
+                ParseAttempt::None => return ParseAttempt::Err(
+                    compiler_messages::Parser::expression_expected(&tok)
+                ),
+            };
+
+            // Trailing commas are allowed, but elements must be separated by one.
+            match self.try_consume(TokenKind::Comma) {
+                Some(tok) => comma_tokens.push(tok.id),
+                None => break,
+            };
+        }
 
         let tok = self.now();
         let right_paren = match self.try_consume(TokenKind::RightParen) {
             Some(tok) => tok,
-            None => {
-                return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(tok));
+            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&tok)),
+        };
+
+        ParseAttempt::Ok(Expr::Tuple(self.factory.alloc(
+            self.factory.tuple_expr(left_paren, elements, right_paren, comma_tokens)
+        )))
+    }
+
+    // array_expr ::= "[" expr ( "," expr )* ","? "]"
+    // empty_collection_expr ::= "[" "]"
+    pub fn parse_array_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
+        let left_bracket = match self.try_consume(TokenKind::LeftSquareBracket) {
+            Some(tok) => tok,
+            None => return ParseAttempt::None,
+        };
+
+        if let Some(right_bracket) = self.try_consume(TokenKind::RightSquareBracket) {
+            return ParseAttempt::Ok(Expr::EmptyCollection(self.factory.alloc(
+                self.factory.empty_collection_expr(left_bracket, right_bracket)
+            )));
+        }
+
+        let mut elements: Vec<Expr> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
+        loop {
+            let tok = self.now();
+            match self.parse_expr() {
+                ParseAttempt::Ok(expr) => elements.push(expr),
+                ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
+                ParseAttempt::None => return ParseAttempt::Err(
+                    compiler_messages::Parser::expression_expected(&tok)
+                ),
+            };
+
+            // After the element, there may or may not be a comma. If there isn't a comma, then
+            // no more elements can be found.
+            if let Some(tok) = self.try_consume(TokenKind::Comma) {
+                comma_tokens.push(tok.id);
             }
+            else {
+                break;
+            }
+
+            // Trailing commas are allowed, so we may find a comma and still find the closing
+            // bracket afterward.
+            if self.check(TokenKind::RightSquareBracket) {
+                break;
+            }
+        }
+
+        let tok = self.now();
+        let right_bracket = match self.try_consume(TokenKind::RightSquareBracket) {
+            Some(tok) => tok,
+            None => return ParseAttempt::Err(
+                compiler_messages::Parser::right_square_bracket_expected(&tok)
+            ),
         };
 
-        ParseAttempt::Ok(Expr::Group(
-            Box::from(SyntaxFactory::group_expr(left_paren.clone(), expr, right_paren.clone()))
-        ))
+        ParseAttempt::Ok(Expr::Array(self.factory.alloc(
+            self.factory.array_expr(left_bracket, elements, right_bracket, comma_tokens)
+        )))
     }
 
     // identifier_expr ::= qualified_identifier
-    pub fn parse_identifier_expr(&mut self) -> ParseAttempt<Expr> {
+    pub fn parse_identifier_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         let id = match self.parse_qualified_identifier() {
             ParseAttempt::Ok(id) => id,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
@@ -838,15 +1174,15 @@ impl<'a> Parser<'a> {
         };
 
         ParseAttempt::Ok(Expr::Identifier(
-            Box::from(SyntaxFactory::identifier_expr(id))
+            self.factory.alloc(self.factory.identifier_expr(id))
         ))
     }
 
     // literal_expr ::= literal
-    pub fn parse_literal_expr(&mut self) -> ParseAttempt<Expr> {
+    pub fn parse_literal_expr(&mut self) -> ParseAttempt<Expr<'ast>> {
         match self.parse_literal() {
             ParseAttempt::Ok(expr) => return ParseAttempt::Ok(Expr::Literal(
-                Box::from(SyntaxFactory::literal_expr(expr))
+                self.factory.alloc(self.factory.literal_expr(expr))
             )),
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             _ => {}
@@ -860,17 +1196,17 @@ impl<'a> Parser<'a> {
 
     // region Fragments
     // identifier ::= IDENTIFIER
-    pub fn parse_simple_identifier(&mut self) -> ParseAttempt<SimpleIdentifier> {
+    pub fn parse_simple_identifier(&mut self) -> ParseAttempt<SimpleIdentifier<'ast>> {
         let id_tok = match self.try_consume(TokenKind::Identifier) {
             Some(id) => id,
             None => return ParseAttempt::None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::simple_identifier(id_tok))
+        ParseAttempt::Ok(self.factory.simple_identifier(id_tok))
     }
 
     // qualified_identifier ::= IDENTIFIER ( "::" IDENTIFIER )*
-    pub fn parse_qualified_identifier(&mut self) -> ParseAttempt<Identifier> {
+    pub fn parse_qualified_identifier(&mut self) -> ParseAttempt<Identifier<'ast>> {
         let simple_id = match self.parse_simple_identifier() {
             ParseAttempt::Ok(id) => id,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
@@ -885,12 +1221,12 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(id) => id,
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => {
-                    return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(tok));
+                    return ParseAttempt::Err(compiler_messages::Parser::identifier_expected(&tok));
                 }
             };
 
             identifier = Identifier::Qualified(
-                Box::from(SyntaxFactory::qualified_identifier(identifier, op, name))
+                self.factory.alloc(self.factory.qualified_identifier(identifier, op, name))
             );
         }
 
@@ -898,18 +1234,24 @@ impl<'a> Parser<'a> {
     }
 
     // literal ::= NUMBER | STRING | CHAR | REGEX | "true" | "false" | "null" | "undefined"
-    pub fn parse_literal(&mut self) -> ParseAttempt<Literal> {
+    pub fn parse_literal(&mut self) -> ParseAttempt<Literal<'ast>> {
         if let Some(tok) = self.try_consume(TokenKind::KwTrue) {
-            ParseAttempt::Ok(SyntaxFactory::literal(tok.clone()))
+            ParseAttempt::Ok(self.factory.literal(tok))
         }
         else if let Some(tok) = self.try_consume(TokenKind::KwFalse) {
-            ParseAttempt::Ok(SyntaxFactory::literal(tok.clone()))
+            ParseAttempt::Ok(self.factory.literal(tok))
         }
         else if let Some(tok) = self.try_consume(TokenKind::Number) {
-            ParseAttempt::Ok(SyntaxFactory::literal(tok.clone()))
+            ParseAttempt::Ok(self.factory.literal(tok))
         }
         else if let Some(tok) = self.try_consume(TokenKind::String) {
-            ParseAttempt::Ok(SyntaxFactory::literal(tok.clone()))
+            ParseAttempt::Ok(self.factory.literal(tok))
+        }
+        else if let Some(tok) = self.try_consume(TokenKind::KwNull) {
+            ParseAttempt::Ok(self.factory.literal(tok))
+        }
+        else if let Some(tok) = self.try_consume(TokenKind::KwUndefined) {
+            ParseAttempt::Ok(self.factory.literal(tok))
         }
         else {
             ParseAttempt::None
@@ -918,21 +1260,21 @@ impl<'a> Parser<'a> {
 
     pub fn parse_equals_value_clause(
         &mut self, allow_multiple: bool
-    ) -> ParseAttempt<EqualsValueClause> {
+    ) -> ParseAttempt<EqualsValueClause<'ast>> {
         let equal_tok = match self.try_consume(TokenKind::Equal) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
         let mut expressions: Vec<Expr> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         loop {
             let tok = self.now();
             let expr = match self.parse_expr() {
                 ParseAttempt::Ok(expr) => expr,
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(tok)
+                    compiler_messages::Parser::expression_expected(&tok)
                 ),
             };
 
@@ -943,17 +1285,17 @@ impl<'a> Parser<'a> {
             }
 
             if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                comma_tokens.push(tok);
+                comma_tokens.push(tok.id);
             }
             else {
                 break;
             }
         }
 
-        ParseAttempt::Ok(SyntaxFactory::equals_value_clause(equal_tok, expressions, comma_tokens))
+        ParseAttempt::Ok(self.factory.equals_value_clause(equal_tok, expressions, comma_tokens))
     }
 
-    pub fn parse_type_annotation(&mut self) -> ParseAttempt<TypeAnnotation> {
+    pub fn parse_type_annotation(&mut self) -> ParseAttempt<TypeAnnotation<'ast>> {
         let delimiter_tok = match self.try_consume(TokenKind::Colon) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
@@ -963,39 +1305,37 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(ty) => ty,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::type_expected(self.now())
+                compiler_messages::Parser::type_expected(&self.now())
             ),
 
         };
 
-        ParseAttempt::Ok(SyntaxFactory::type_annotation(delimiter_tok, ty))
+        ParseAttempt::Ok(self.factory.type_annotation(delimiter_tok, ty))
     }
 
     pub fn parse_operator(&mut self, kinds: &[TokenKind]) -> ParseAttempt<Operator> {
-        let op_tok = self.try_consume_many(kinds);
-        if op_tok.is_none() {
-            return ParseAttempt::None;
+        match self.try_consume_many(kinds) {
+            Some(op_tok) => ParseAttempt::Ok(self.factory.operator(op_tok)),
+            None => ParseAttempt::None,
         }
-
-        ParseAttempt::Ok(SyntaxFactory::operator(op_tok.unwrap()))
     }
 
     // param_list ::= "(" ( param ( "," param )* ","? )? ")"
-    pub fn parse_parameter_list(&mut self) -> ParseAttempt<ParameterList> {
+    pub fn parse_parameter_list(&mut self) -> ParseAttempt<ParameterList<'ast>> {
         let left_paren = match self.try_consume(TokenKind::LeftParen) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
         let mut params: Vec<Parameter> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         if self.check(TokenKind::RightParen) == false {
             loop {
                 let param = match self.parse_parameter() {
                     ParseAttempt::Ok(param) => param,
                     ParseAttempt::Err(err) => return ParseAttempt::Err(err),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::parameter_expected(self.now())
+                        compiler_messages::Parser::parameter_expected(&self.now())
                     ),
                 };
                 params.push(param);
@@ -1003,7 +1343,7 @@ impl<'a> Parser<'a> {
                 // After the parameter, there may or may not be a comma. If there isn't a comma
                 // then no more parameters can be found.
                 if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                    comma_tokens.push(tok);
+                    comma_tokens.push(tok.id);
                 }
                 else {
                     break;
@@ -1019,14 +1359,14 @@ impl<'a> Parser<'a> {
 
         let right_paren = match self.try_consume(TokenKind::RightParen) {
             Some(tok) => tok,
-            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(self.now())),
+            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&self.now())),
         };
 
-        ParseAttempt::Ok(SyntaxFactory::parameter_list(left_paren, params, right_paren, comma_tokens))
+        ParseAttempt::Ok(self.factory.parameter_list(left_paren, params, right_paren, comma_tokens))
     }
 
     // param ::= decl equals_value_clause?
-    pub fn parse_parameter(&mut self) -> ParseAttempt<Parameter> {
+    pub fn parse_parameter(&mut self) -> ParseAttempt<Parameter<'ast>> {
         let decl = match self.parse_regular_local_declarator() {
             ParseAttempt::Ok(decl) => decl,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
@@ -1039,18 +1379,18 @@ impl<'a> Parser<'a> {
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
         };
 
-        ParseAttempt::Ok(SyntaxFactory::parameter(decl, default_val))
+        ParseAttempt::Ok(self.factory.parameter(decl, default_val))
     }
 
     // arg_list ::= "(" ( arg ( "," arg )* ","? )? ")"
-    pub fn parse_argument_list(&mut self) -> ParseAttempt<ArgumentList> {
+    pub fn parse_argument_list(&mut self) -> ParseAttempt<ArgumentList<'ast>> {
         let left_paren = match self.try_consume(TokenKind::LeftParen) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
         let mut args: Vec<Argument> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         if self.check(TokenKind::RightParen) == false {
             loop {
                 let tok = self.now();
@@ -1058,7 +1398,7 @@ impl<'a> Parser<'a> {
                     ParseAttempt::Ok(arg) => arg,
                     ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::argument_expected(tok)
+                        compiler_messages::Parser::argument_expected(&tok)
                     ),
                 };
                 args.push(arg);
@@ -1066,7 +1406,7 @@ impl<'a> Parser<'a> {
                 // After the argument, there may or may not be a comma. If there isn't a comma, then
                 // no more arguments can be found.
                 if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                    comma_tokens.push(tok);
+                    comma_tokens.push(tok.id);
                 }
                 else {
                     break;
@@ -1083,24 +1423,24 @@ impl<'a> Parser<'a> {
         let tok = self.now();
         let right_paren = match self.try_consume(TokenKind::RightParen) {
             Some(tok) => tok,
-            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(tok))
+            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&tok))
         };
 
-        ParseAttempt::Ok(SyntaxFactory::argument_list(left_paren, args, right_paren, comma_tokens))
+        ParseAttempt::Ok(self.factory.argument_list(left_paren, args, right_paren, comma_tokens))
     }
 
     // arg ::= expr
-    pub fn parse_argument(&mut self) -> ParseAttempt<Argument> {
+    pub fn parse_argument(&mut self) -> ParseAttempt<Argument<'ast>> {
         let expr = match self.parse_expr() {
             ParseAttempt::Ok(expr) => expr,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => return ParseAttempt::None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::argument(expr))
+        ParseAttempt::Ok(self.factory.argument(expr))
     }
 
-    pub fn parse_local_declarator(&mut self) -> ParseAttempt<PartialLocalDecl> {
+    pub fn parse_local_declarator(&mut self) -> ParseAttempt<PartialLocalDecl<'ast>> {
         match self.parse_regular_local_declarator() {
             ParseAttempt::Ok(node) => return ParseAttempt::Ok(
                 PartialLocalDecl::Regular(node)
@@ -1109,12 +1449,84 @@ impl<'a> Parser<'a> {
             _ => {}
         };
 
-        // TODO: Destructure local declarator
+        match self.parse_destructured_local_declarator() {
+            ParseAttempt::Ok(node) => return ParseAttempt::Ok(
+                PartialLocalDecl::Destructured(node)
+            ),
+            ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+            _ => {}
+        };
 
         ParseAttempt::None
     }
 
-    pub fn parse_regular_local_declarator(&mut self) -> ParseAttempt<RegularLocalDecl> {
+    // destructured_local_decl ::= "[" local_declarator ( "," local_declarator )* ","? "]"
+    //                           | "{" local_declarator ( "," local_declarator )* ","? "}"
+    pub fn parse_destructured_local_declarator(
+        &mut self
+    ) -> ParseAttempt<DestructuredLocalDecl<'ast>> {
+        let opening_tok = match self.try_consume_many(&[
+            TokenKind::LeftSquareBracket, TokenKind::LeftCurlyBracket
+        ]) {
+            Some(tok) => tok,
+            None => return ParseAttempt::None,
+        };
+        let closing_kind = match opening_tok.kind() {
+            TokenKind::LeftSquareBracket => TokenKind::RightSquareBracket,
+            _ => TokenKind::RightCurlyBracket,
+        };
+
+        if self.check(closing_kind) {
+            return ParseAttempt::Err(
+                compiler_messages::Parser::empty_destructuring_pattern(&self.now())
+            );
+        }
+
+        let mut declarators: Vec<LocalDeclarator> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
+        loop {
+            let tok = self.now();
+            match self.parse_regular_local_declarator() {
+                ParseAttempt::Ok(decl) => declarators.push(decl.declarator),
+                ParseAttempt::Err(err) => return ParseAttempt::Err(err),
+                ParseAttempt::None => return ParseAttempt::Err(
+                    compiler_messages::Parser::variable_declarator_expected(&tok)
+                ),
+            };
+
+            // After the declarator, there may or may not be a comma. If there isn't a comma, then
+            // no more declarators can be found.
+            if let Some(tok) = self.try_consume(TokenKind::Comma) {
+                comma_tokens.push(tok.id);
+            }
+            else {
+                break;
+            }
+
+            // Trailing commas are allowed, so we may find a comma and still find the closing
+            // bracket afterward.
+            if self.check(closing_kind) {
+                break;
+            }
+        }
+
+        let tok = self.now();
+        let closing_tok = match self.try_consume(closing_kind) {
+            Some(tok) => tok,
+            None if closing_kind == TokenKind::RightSquareBracket => return ParseAttempt::Err(
+                compiler_messages::Parser::right_square_bracket_expected(&tok)
+            ),
+            None => return ParseAttempt::Err(
+                compiler_messages::Parser::right_curly_bracket_expected(&tok)
+            ),
+        };
+
+        ParseAttempt::Ok(self.factory.destructured_local_decl(
+            opening_tok, declarators, closing_tok, comma_tokens
+        ))
+    }
+
+    pub fn parse_regular_local_declarator(&mut self) -> ParseAttempt<RegularLocalDecl<'ast>> {
         let ownership_tok = self.parse_ownership_token();
 
         let ident = match self.parse_simple_identifier() {
@@ -1129,12 +1541,12 @@ impl<'a> Parser<'a> {
             ParseAttempt::None => None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::regular_local_decl(
-            SyntaxFactory::local_declarator(ownership_tok, ident, type_annot)
+        ParseAttempt::Ok(self.factory.regular_local_decl(
+            self.factory.local_declarator(ownership_tok, ident, type_annot)
         ))
     }
 
-    pub fn parse_field_init(&mut self) -> ParseAttempt<FieldInit> {
+    pub fn parse_field_init(&mut self) -> ParseAttempt<FieldInit<'ast>> {
         let identifier = match self.parse_simple_identifier() {
             ParseAttempt::Ok(id) => id,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
@@ -1146,21 +1558,21 @@ impl<'a> Parser<'a> {
             ParseAttempt::Ok(evc) => evc,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => return ParseAttempt::Err(
-                compiler_messages::Parser::field_must_be_initialized(tok)
+                compiler_messages::Parser::field_must_be_initialized(&tok)
             ),
         };
 
-        ParseAttempt::Ok(SyntaxFactory::field_init(identifier, initializer))
+        ParseAttempt::Ok(self.factory.field_init(identifier, initializer))
     }
 
-    pub fn parse_object_initializer(&mut self) -> ParseAttempt<ObjectInitializer> {
+    pub fn parse_object_initializer(&mut self) -> ParseAttempt<ObjectInitializer<'ast>> {
         let left_bracket = match self.try_consume(TokenKind::LeftCurlyBracket) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
         let mut field_inits: Vec<FieldInit> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         if self.check(TokenKind::RightCurlyBracket) == false {
             loop {
                 let tok = self.now();
@@ -1168,7 +1580,7 @@ impl<'a> Parser<'a> {
                     ParseAttempt::Ok(arg) => arg,
                     ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::field_initialization_expected(tok)
+                        compiler_messages::Parser::field_initialization_expected(&tok)
                     ),
                 };
                 field_inits.push(init);
@@ -1176,7 +1588,7 @@ impl<'a> Parser<'a> {
                 // After the argument, there may or may not be a comma. If there isn't a comma, then
                 // no more arguments can be found.
                 if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                    comma_tokens.push(tok);
+                    comma_tokens.push(tok.id);
                 }
                 else {
                     break;
@@ -1193,23 +1605,28 @@ impl<'a> Parser<'a> {
         let tok = self.now();
         let right_bracket = match self.try_consume(TokenKind::RightCurlyBracket) {
             Some(tok) => tok,
-            None => return ParseAttempt::Err(compiler_messages::Parser::right_curly_bracket_expected(tok))
+            None => return ParseAttempt::Err(compiler_messages::Parser::right_curly_bracket_expected(&tok))
         };
 
         ParseAttempt::Ok(
-            SyntaxFactory::object_initializer(left_bracket, field_inits, right_bracket, comma_tokens)
+            self.factory.object_initializer(left_bracket, field_inits, right_bracket, comma_tokens)
         )
     }
     // endregion Fragments
 
-    pub fn parse_type(&mut self) -> ParseAttempt<TypeNode> {
+    pub fn parse_type(&mut self) -> ParseAttempt<TypeNode<'ast>> {
+        self.nested(Self::parse_unnested_type)
+    }
+
+    /// Parses a type without counting it as a level of nesting, which `parse_type` does.
+    fn parse_unnested_type(&mut self) -> ParseAttempt<TypeNode<'ast>> {
         let ownership_tok = self.parse_ownership_token();
 
         match self.parse_sum_type() {
-            ParseAttempt::Ok(ty) => ParseAttempt::Ok(SyntaxFactory::type_node(
+            ParseAttempt::Ok(ty) => ParseAttempt::Ok(self.factory.type_node(
                 ownership_tok,
                 ty.ty,
-                ty.nullable_token,
+                ty.nullable_token.and_then(|id| self.token(id.index())),
             )),
             ParseAttempt::Err(msg) => ParseAttempt::Err(msg),
             ParseAttempt::None => ParseAttempt::None,
@@ -1217,7 +1634,7 @@ impl<'a> Parser<'a> {
     }
 
     // sum_type ::= product_type ( "|" product_type )*
-    pub fn parse_sum_type(&mut self) -> ParseAttempt<TypeNode> {
+    pub fn parse_sum_type(&mut self) -> ParseAttempt<TypeNode<'ast>> {
         let ty = match self.parse_product_type() {
             ParseAttempt::Ok(ty) => ty,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
@@ -1229,10 +1646,10 @@ impl<'a> Parser<'a> {
         }
 
         let mut member_types = vec![ty];
-        let mut or_tokens: Vec<Token> = Vec::new();
+        let mut or_tokens: Vec<TokenId> = Vec::new();
         loop {
             match self.try_consume(TokenKind::Pipe) {
-                Some(tok) => or_tokens.push(tok),
+                Some(tok) => or_tokens.push(tok.id),
                 None => break,
             };
 
@@ -1240,22 +1657,22 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(ty) => member_types.push(ty),
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::type_expected(self.now())
+                    compiler_messages::Parser::type_expected(&self.now())
                 ),
             };
         };
 
-        ParseAttempt::Ok(SyntaxFactory::type_node(
+        ParseAttempt::Ok(self.factory.type_node(
             None,
-            PartialType::Sum(Box::from(
-                SyntaxFactory::sum_type(member_types, or_tokens)
+            PartialType::Sum(self.factory.alloc(
+                self.factory.sum_type(member_types, or_tokens)
             )),
             None,
         ))
     }
 
     // product_type ::= raw_array_type ( "&" raw_array_type )*
-    pub fn parse_product_type(&mut self) -> ParseAttempt<TypeNode> {
+    pub fn parse_product_type(&mut self) -> ParseAttempt<TypeNode<'ast>> {
         let ty = match self.parse_raw_array_type() {
             ParseAttempt::Ok(ty) => ty,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
@@ -1267,10 +1684,10 @@ impl<'a> Parser<'a> {
         }
 
         let mut member_types = vec![ty];
-        let mut and_tokens: Vec<Token> = Vec::new();
+        let mut and_tokens: Vec<TokenId> = Vec::new();
         loop {
             match self.try_consume(TokenKind::Ampersand) {
-                Some(tok) => and_tokens.push(tok),
+                Some(tok) => and_tokens.push(tok.id),
                 None => break,
             };
 
@@ -1278,22 +1695,22 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(ty) => member_types.push(ty),
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::type_expected(self.now())
+                    compiler_messages::Parser::type_expected(&self.now())
                 ),
             };
         };
 
-        ParseAttempt::Ok(SyntaxFactory::type_node(
+        ParseAttempt::Ok(self.factory.type_node(
             None,
-            PartialType::Product(Box::from(
-                SyntaxFactory::product_type(member_types, and_tokens)
+            PartialType::Product(self.factory.alloc(
+                self.factory.product_type(member_types, and_tokens)
             )),
             None,
         ))
     }
 
     // raw_array_type ::= primary_type ( "[" expr "]" )*
-    pub fn parse_raw_array_type(&mut self) -> ParseAttempt<TypeNode> {
+    pub fn parse_raw_array_type(&mut self) -> ParseAttempt<TypeNode<'ast>> {
         let ty = match self.parse_primary_type() {
             ParseAttempt::Ok(ty) => ty,
             ParseAttempt::Err(err) => return ParseAttempt::Err(err),
@@ -1302,7 +1719,7 @@ impl<'a> Parser<'a> {
 
         let nullable_tok = self.try_consume(TokenKind::QuestionMark);
 
-        let mut ty= SyntaxFactory::type_node(None, ty, nullable_tok);
+        let mut ty= self.factory.type_node(None, ty, nullable_tok);
 
         loop {
             let left_sq_bracket = match self.try_consume(TokenKind::LeftSquareBracket) {
@@ -1314,23 +1731,23 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(expr) => expr,
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::expression_expected(self.now())
+                    compiler_messages::Parser::expression_expected(&self.now())
                 ),
             };
 
             let right_sq_bracket = match self.try_consume(TokenKind::RightSquareBracket) {
                 Some(tok) => tok,
                 None => return ParseAttempt::Err(
-                    compiler_messages::Parser::right_square_bracket_expected(self.now())
+                    compiler_messages::Parser::right_square_bracket_expected(&self.now())
                 ),
             };
 
             let nullable_tok = self.try_consume(TokenKind::QuestionMark);
 
-            ty = SyntaxFactory::type_node(
+            ty = self.factory.type_node(
                 None,
-                PartialType::RawArray(Box::from(
-                    SyntaxFactory::raw_array_type(ty, left_sq_bracket, len, right_sq_bracket)
+                PartialType::RawArray(self.factory.alloc(
+                    self.factory.raw_array_type(ty, left_sq_bracket, len, right_sq_bracket)
                 )),
                 nullable_tok,
             );
@@ -1338,14 +1755,14 @@ impl<'a> Parser<'a> {
     }
 
     // primary_type ::= identifier_type | function_type | tuple_array_type | literal_type
-    pub fn parse_primary_type(&mut self) -> ParseAttempt<PartialType> {
+    pub fn parse_primary_type(&mut self) -> ParseAttempt<PartialType<'ast>> {
         match self.parse_function_type() {
             ParseAttempt::Ok(ty) => return ParseAttempt::Ok(ty),
             ParseAttempt::Err(err) => return self.register_err_type(err),
             _ => {}
         };
         match self.parse_tuple_array_type() {
-            ParseAttempt::Ok(ty) => return ParseAttempt::Ok(PartialType::TupleArray(Box::from(ty))),
+            ParseAttempt::Ok(ty) => return ParseAttempt::Ok(PartialType::TupleArray(self.factory.alloc(ty))),
             ParseAttempt::Err(err) => return self.register_err_type(err),
             _ => {}
         };
@@ -1364,19 +1781,19 @@ impl<'a> Parser<'a> {
     }
 
     // identifier_type ::= identifier
-    pub fn parse_identifier_type(&mut self) -> ParseAttempt<IdentifierType> {
+    pub fn parse_identifier_type(&mut self) -> ParseAttempt<IdentifierType<'ast>> {
         let identifier = match self.parse_qualified_identifier() {
             ParseAttempt::Ok(id) => id,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => return ParseAttempt::None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::identifier_type(identifier))
+        ParseAttempt::Ok(self.factory.identifier_type(identifier))
     }
 
     // function_type ::= ( "s" | "S" | "sS" )? "!"? "(" type ( "," type )* ")" "->" type
     //                 | "(" type ")" ; group_type
-    pub fn parse_function_type(&mut self) -> ParseAttempt<PartialType> {
+    pub fn parse_function_type(&mut self) -> ParseAttempt<PartialType<'ast>> {
         // We may be parsing a function type or a group type. More on that later.
 
         // A function type may start with the ss token ('s' for Send, 'S' for Sync, or 'sS' for both).
@@ -1402,19 +1819,19 @@ impl<'a> Parser<'a> {
                 ParseAttempt::None
             }
             else {
-                ParseAttempt::Err(compiler_messages::Parser::parameter_type_list_expected(self.now()))
+                ParseAttempt::Err(compiler_messages::Parser::parameter_type_list_expected(&self.now()))
             },
         };
 
         let mut types: Vec<TypeNode> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         if self.check(TokenKind::RightParen) == false {
             loop {
                 let ty = match self.parse_type() {
                     ParseAttempt::Ok(ty) => ty,
                     ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::type_expected(self.now())
+                        compiler_messages::Parser::type_expected(&self.now())
                     ),
                 };
                 types.push(ty);
@@ -1422,7 +1839,7 @@ impl<'a> Parser<'a> {
                 // After the type, there may or may not be a comma. If there isn't a comma, then
                 // no more types can be found.
                 if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                    comma_tokens.push(tok);
+                    comma_tokens.push(tok.id);
                 }
                 else {
                     break;
@@ -1438,7 +1855,7 @@ impl<'a> Parser<'a> {
 
         let right_paren = match self.try_consume(TokenKind::RightParen) {
             Some(tok) => tok,
-            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(self.now()))
+            None => return ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&self.now()))
         };
 
         // Now, there's 3 possible scenarios: if we encounter '->', this is a function type. If we
@@ -1453,12 +1870,12 @@ impl<'a> Parser<'a> {
                 ParseAttempt::Ok(ty) => ty,
                 ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                 ParseAttempt::None => return ParseAttempt::Err(
-                    compiler_messages::Parser::type_expected(self.now())
+                    compiler_messages::Parser::type_expected(&self.now())
                 ),
             };
 
-            ParseAttempt::Ok(PartialType::Function(Box::from(
-                SyntaxFactory::function_type(
+            ParseAttempt::Ok(PartialType::Function(self.factory.alloc(
+                self.factory.function_type(
                     ss_tok,
                     except_tok,
                     left_paren,
@@ -1474,41 +1891,41 @@ impl<'a> Parser<'a> {
         else if ss_tok.is_none() && except_tok.is_none() {
             // The group type is empty, which isn't allowed.
             if types.len() == 0 {
-                ParseAttempt::Err(compiler_messages::Parser::type_expected(self.now()))
+                ParseAttempt::Err(compiler_messages::Parser::type_expected(&self.now()))
             }
             // The group type has exactly one type, which is allowed.
             else if types.len() == 1 {
-                ParseAttempt::Ok(PartialType::Group(Box::from(
-                    SyntaxFactory::group_type(left_paren, types.remove(0), right_paren)
+                ParseAttempt::Ok(PartialType::Group(self.factory.alloc(
+                    self.factory.group_type(left_paren, types.remove(0), right_paren)
                 )))
             }
             // The group type has many types separated by commas, which isn't allowed.
             else {
-                ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(self.now()))
+                ParseAttempt::Err(compiler_messages::Parser::right_paren_expected(&self.now()))
             }
         }
         // We are parsing an incorrect function type.
         else {
-            ParseAttempt::Err(compiler_messages::Parser::return_type_expected(self.now()))
+            ParseAttempt::Err(compiler_messages::Parser::return_type_expected(&self.now()))
         }
     }
 
     // tuple_array_type ::= "[" type ( "," type )* "]"
-    pub fn parse_tuple_array_type(&mut self) -> ParseAttempt<TupleArrayType> {
+    pub fn parse_tuple_array_type(&mut self) -> ParseAttempt<TupleArrayType<'ast>> {
         let left_sq_bracket = match self.try_consume(TokenKind::LeftSquareBracket) {
             Some(tok) => tok,
             None => return ParseAttempt::None,
         };
 
         let mut types: Vec<TypeNode> = Vec::new();
-        let mut comma_tokens: Vec<Token> = Vec::new();
+        let mut comma_tokens: Vec<TokenId> = Vec::new();
         if self.check(TokenKind::RightSquareBracket) == false {
             loop {
                 let ty = match self.parse_type() {
                     ParseAttempt::Ok(ty) => ty,
                     ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
                     ParseAttempt::None => return ParseAttempt::Err(
-                        compiler_messages::Parser::type_expected(self.now())
+                        compiler_messages::Parser::type_expected(&self.now())
                     ),
                 };
                 types.push(ty);
@@ -1516,7 +1933,7 @@ impl<'a> Parser<'a> {
                 // After the type, there may or may not be a comma. If there isn't a comma, then
                 // no more types can be found.
                 if let Some(tok) = self.try_consume(TokenKind::Comma) {
-                    comma_tokens.push(tok);
+                    comma_tokens.push(tok.id);
                 }
                 else {
                     break;
@@ -1533,28 +1950,28 @@ impl<'a> Parser<'a> {
         let right_sq_bracket = match self.try_consume(TokenKind::RightSquareBracket) {
             Some(tok) => tok,
             None => return ParseAttempt::Err(
-                compiler_messages::Parser::right_square_bracket_expected(self.now())
+                compiler_messages::Parser::right_square_bracket_expected(&self.now())
             )
         };
 
         ParseAttempt::Ok(
-            SyntaxFactory::tuple_array_type(left_sq_bracket, types, right_sq_bracket, comma_tokens)
+            self.factory.tuple_array_type(left_sq_bracket, types, right_sq_bracket, comma_tokens)
         )
     }
 
     // literal_type ::= literal
-    pub fn parse_literal_type(&mut self) -> ParseAttempt<LiteralType> {
+    pub fn parse_literal_type(&mut self) -> ParseAttempt<LiteralType<'ast>> {
         let lit = match self.parse_literal() {
             ParseAttempt::Ok(lit) => lit,
             ParseAttempt::Err(msg) => return ParseAttempt::Err(msg),
             ParseAttempt::None => return ParseAttempt::None,
         };
 
-        ParseAttempt::Ok(SyntaxFactory::literal_type(lit))
+        ParseAttempt::Ok(self.factory.literal_type(lit))
     }
     // endregion Types
 
-    fn parse_ownership_token(&mut self) -> Option<Token> {
+    fn parse_ownership_token(&mut self) -> Option<TokenRef<'src>> {
         if let Some(tok) = self.try_consume(TokenKind::KwFinal) {
             Some(tok)
         }
@@ -1583,54 +2000,89 @@ impl<'a> Parser<'a> {
         self.messages.add(msg);
     }
 
-    fn register_err_node(&mut self, err: CompilerMessage) -> ParseAttempt<SyntaxNode> {
+    fn register_err_node(&mut self, err: CompilerMessage) -> ParseAttempt<SyntaxNode<'ast>> {
         self.messages.add(err);
 
-        ParseAttempt::Ok(SyntaxNode::Error(SyntaxFactory::error_node()))
+        ParseAttempt::Ok(SyntaxNode::Error(self.factory.error_node()))
     }
 
-    fn register_err_stmt(&mut self, err: CompilerMessage) -> ParseAttempt<Stmt> {
+    fn register_err_stmt(&mut self, err: CompilerMessage) -> ParseAttempt<Stmt<'ast>> {
         self.messages.add(err);
 
-        ParseAttempt::Ok(Stmt::Error(SyntaxFactory::error_node()))
+        ParseAttempt::Ok(Stmt::Error(self.factory.error_node()))
     }
 
-    fn register_err_expr(&mut self, err: CompilerMessage) -> ParseAttempt<Expr> {
+    fn register_err_expr(&mut self, err: CompilerMessage) -> ParseAttempt<Expr<'ast>> {
         self.messages.add(err);
 
-        ParseAttempt::Ok(Expr::Error(SyntaxFactory::error_node()))
+        ParseAttempt::Ok(Expr::Error(self.factory.error_node()))
     }
 
-    fn register_err_type(&mut self, err: CompilerMessage) -> ParseAttempt<PartialType> {
+    fn register_err_type(&mut self, err: CompilerMessage) -> ParseAttempt<PartialType<'ast>> {
         self.messages.add(err);
 
-        ParseAttempt::Ok(PartialType::Error(SyntaxFactory::error_node()))
+        ParseAttempt::Ok(PartialType::Error(self.factory.error_node()))
     }
 }
 
-pub fn parse(tokens: Vec<Token>) -> ParserResult {
-    let mut parser = Parser::new(&tokens);
+/// Parses the tokens given, allocating the nodes in the arena given.
+pub fn parse<'src, 'ast>(tokens: Vec<Token<'src>>, arena: &'ast Arena) -> ParserResult<'src, 'ast> {
+    parse_source(tokens.into_iter(), arena)
+}
+
+/// Parses the tokens pulled from the source given, which are read only as the parser needs them.
+pub fn parse_source<'src, 'ast>(
+    source: impl TokenSource<'src>, arena: &'ast Arena
+) -> ParserResult<'src, 'ast> {
+    parse_source_with(source, Interner::new(arena))
+}
+
+/// Parses the tokens pulled from the source given, interning names into the interner given. This
+/// lets sources parsed one after another share their names.
+pub fn parse_source_with<'src, 'ast>(
+    source: impl TokenSource<'src>, interner: Interner<'ast>
+) -> ParserResult<'src, 'ast> {
+    let mut parser = Parser::with_interner(source, interner);
 
     let mut nodes: Vec<SyntaxNode> = vec![];
 
-    while let node = parser.parse_top_level_node() {
-        if let ParseAttempt::Ok(node) = node {
-            nodes.push(node);
+    loop {
+        let start = parser.cursor();
+        match parser.parse_top_level_node() {
+            ParseAttempt::Ok(node) => nodes.push(node),
+            ParseAttempt::Err(err) => parser.error(err),
+            ParseAttempt::None if parser.is_at_end() => break,
+            ParseAttempt::None => {
+                let err = compiler_messages::Parser::unexpected_token(&parser.now());
+                parser.error(err);
+            }
         }
-        else if let ParseAttempt::None = node {
-            break;
+
+        // A node that couldn't be read may not have consumed any token. Skipping one ensures the
+        // parser always moves forward.
+        if parser.cursor() == start {
+            parser.advance();
         }
     }
 
+    let node_count = parser.factory.node_count();
+    let messages = parser.messages;
+    let tokens = parser.tokens;
+
     ParserResult {
         nodes,
-        messages: parser.messages,
+        tokens,
+        node_count,
+        messages,
+        interner: parser.factory.interner,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::lexical::lexer::{tokenize, Lexer};
+    use crate::judith::compiler_messages::MessageCode;
+    use crate::SourceSpan;
     use super::*;
 
     #[test]
@@ -1639,7 +2091,8 @@ mod tests {
 
         println!("Testing 'Num'.");
         let lexer_res = tokenize("Num");
-        let mut parser = Parser::new(&lexer_res.tokens);
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
         let ParseAttempt::Ok(node) = parser.parse_type() else { panic!("Parse failed.") };
 
         assert!(matches!(&node.ty, PartialType::Identifier(_)));
@@ -1654,7 +2107,8 @@ mod tests {
 
         println!("Testing 'mut String?'.");
         let lexer_res = tokenize("mut String?");
-        let mut parser = Parser::new(&lexer_res.tokens);
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
         let ParseAttempt::Ok(node) = parser.parse_type() else { panic!("Parse failed.") };
 
         assert!(matches!(&node.ty, PartialType::Identifier(_)));
@@ -1674,11 +2128,12 @@ mod tests {
 
         println!("Testing 'let n: String = \"Kevin\"'.");
         let lexer_res = tokenize("let n: String = \"Kevin\"");
-        let mut parser = Parser::new(&lexer_res.tokens);
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
         let ParseAttempt::Ok(node) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
 
-        assert!(matches!(&node.decl, PartialLocalDecl::Regular(_)));
-        let PartialLocalDecl::Regular(decl) = &node.decl else { panic!("???") };
+        assert!(matches!(&node.declarators[0], PartialLocalDecl::Regular(_)));
+        let PartialLocalDecl::Regular(decl) = &node.declarators[0] else { panic!("???") };
 
         assert!(matches!(&decl.declarator.ownership_kind, OwnershipKind::None));
         assert_eq!(&decl.declarator.name.name, "n");
@@ -1687,11 +2142,12 @@ mod tests {
 
         println!("Testing 'let mut score = 42'.");
         let lexer_res = tokenize("let mut score = 42");
-        let mut parser = Parser::new(&lexer_res.tokens);
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
         let ParseAttempt::Ok(node) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
 
-        assert!(matches!(&node.decl, PartialLocalDecl::Regular(_)));
-        let PartialLocalDecl::Regular(decl) = &node.decl else { panic!("???") };
+        assert!(matches!(&node.declarators[0], PartialLocalDecl::Regular(_)));
+        let PartialLocalDecl::Regular(decl) = &node.declarators[0] else { panic!("???") };
 
         assert!(matches!(&decl.declarator.ownership_kind, OwnershipKind::Mutable));
         assert_eq!(&decl.declarator.name.name, "score");
@@ -1700,15 +2156,745 @@ mod tests {
 
         println!("Testing 'let sh res");
         let lexer_res = tokenize("let sh res");
-        let mut parser = Parser::new(&lexer_res.tokens);
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
         let ParseAttempt::Ok(node) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
 
-        assert!(matches!(&node.decl, PartialLocalDecl::Regular(_)));
-        let PartialLocalDecl::Regular(decl) = &node.decl else { panic!("???") };
+        assert!(matches!(&node.declarators[0], PartialLocalDecl::Regular(_)));
+        let PartialLocalDecl::Regular(decl) = &node.declarators[0] else { panic!("???") };
 
         assert!(matches!(&decl.declarator.ownership_kind, OwnershipKind::Shared));
         assert_eq!(&decl.declarator.name.name, "res");
         assert!(&decl.declarator.type_annotation.is_none());
         assert!(node.initializer.is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn valid_null_operators() {
+        println!("== Testing null operators ==");
+
+        println!("Testing 'a ?? b or c ?? d'.");
+        let lexer_res = tokenize("a ?? b or c ?? d");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        // "??" binds looser than "or" and is right-associative.
+        let Expr::Binary(node) = &node else { panic!("Expected a binary expression.") };
+        assert_eq!(node.operator.kind, OperatorKind::NullCoalescing);
+        assert!(matches!(&node.left, Expr::Identifier(_)));
+
+        let Expr::Binary(right) = &node.right else { panic!("Expected a binary expression.") };
+        assert_eq!(right.operator.kind, OperatorKind::NullCoalescing);
+        let Expr::Binary(or) = &right.left else { panic!("Expected a binary expression.") };
+        assert_eq!(or.operator.kind, OperatorKind::LogicalOr);
+
+        println!("Testing 'a?.b!.c()'.");
+        let lexer_res = tokenize("a?.b!.c()");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Call(call) = &node else { panic!("Expected a call expression.") };
+        let Expr::Access(c) = &call.callee else { panic!("Expected an access expression.") };
+        assert_eq!(c.operator.kind, OperatorKind::MemberAccess);
+        assert_eq!(c.member.name, "c");
+
+        let Some(Expr::RightUnary(assertion)) = &c.receiver else { panic!("Expected '!'.") };
+        assert_eq!(assertion.operator.kind, OperatorKind::NonNullAssertion);
+
+        let Expr::Access(b) = &assertion.expr else { panic!("Expected an access expression.") };
+        assert_eq!(b.operator.kind, OperatorKind::SafeMemberAccess);
+        assert_eq!(b.member.name, "b");
+
+        println!("Testing 'f()?.g(null)'.");
+        let lexer_res = tokenize("f()?.g(null)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Call(call) = &node else { panic!("Expected a call expression.") };
+        let Expr::Access(g) = &call.callee else { panic!("Expected an access expression.") };
+        assert_eq!(g.operator.kind, OperatorKind::SafeMemberAccess);
+        assert!(matches!(&g.receiver, Some(Expr::Call(_))));
+        assert!(matches!(&call.arguments.arguments[0].expr, Expr::Literal(_)));
+
+        println!("Testing '?.a'.");
+        let lexer_res = tokenize("?.a");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        assert!(matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Error(_))));
+    }
+
+    #[test]
+    fn valid_postfix_chains() {
+        println!("== Testing postfix chains ==");
+
+        println!("Testing 'a.b(c).d'.");
+        let lexer_res = tokenize("a.b(c).d");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Access(d) = &node else { panic!("Expected an access expression.") };
+        assert_eq!(d.member.name, "d");
+        assert_eq!(d.span, Some(SourceSpan { start: 0, end: 8, line: 1 }));
+
+        let Some(Expr::Call(call)) = &d.receiver else { panic!("Expected a call expression.") };
+        assert_eq!(call.span, Some(SourceSpan { start: 0, end: 6, line: 1 }));
+        let Expr::Access(b) = &call.callee else { panic!("Expected an access expression.") };
+        assert_eq!(b.span, Some(SourceSpan { start: 0, end: 3, line: 1 }));
+
+        println!("Testing 'f(x)(y)'.");
+        let lexer_res = tokenize("f(x)(y)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Call(outer) = &node else { panic!("Expected a call expression.") };
+        assert_eq!(outer.span, Some(SourceSpan { start: 0, end: 7, line: 1 }));
+        let Expr::Call(inner) = &outer.callee else { panic!("Expected a call expression.") };
+        assert_eq!(inner.span, Some(SourceSpan { start: 0, end: 4, line: 1 }));
+        assert!(matches!(&inner.callee, Expr::Identifier(_)));
+
+        println!("Testing 'list.get(0).name'.");
+        let lexer_res = tokenize("list.get(0).name");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Access(name) = &node else { panic!("Expected an access expression.") };
+        assert!(matches!(&name.receiver, Some(Expr::Call(_))));
+
+        println!("Testing 'grid[i][j + 1].cells::Item'.");
+        let lexer_res = tokenize("grid[i][j + 1].cells::Item");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Access(item) = &node else { panic!("Expected an access expression.") };
+        assert_eq!(item.operator.kind, OperatorKind::ScopeResolution);
+        assert_eq!(item.span, Some(SourceSpan { start: 0, end: 26, line: 1 }));
+
+        let Some(Expr::Access(cells)) = &item.receiver else { panic!("Expected an access.") };
+        let Expr::Index(outer) = &cells.receiver.as_ref().unwrap() else { panic!("Expected an index.") };
+        assert_eq!(outer.span, Some(SourceSpan { start: 0, end: 14, line: 1 }));
+        assert!(matches!(&outer.index, Expr::Binary(_)));
+
+        let Expr::Index(inner) = &outer.receiver else { panic!("Expected an index expression.") };
+        assert_eq!(inner.span, Some(SourceSpan { start: 0, end: 7, line: 1 }));
+
+        println!("Testing 'a' followed by '[b]' in the next line.");
+        let lexer_res = tokenize("a\n[b]");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::Identifier(_)));
+
+        println!("Testing 'let a = b' followed by '(c, d)' in the next line.");
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("let a = b\n(c, d)").tokens, &arena);
+        assert_eq!(parser_res.messages.count(), 0);
+        assert_eq!(parser_res.nodes.len(), 2);
+        let SyntaxNode::Stmt(Stmt::Expr(stmt)) = &parser_res.nodes[1] else {
+            panic!("Expected an expression statement.")
+        };
+        assert!(matches!(&stmt.expr, Expr::Tuple(_)));
+
+        println!("Testing 'a[]'.");
+        let lexer_res = tokenize("a[]");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        assert!(matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Error(_))));
+    }
+
+    #[test]
+    fn valid_lambdas() {
+        println!("== Testing lambdas ==");
+
+        println!("Testing '(a: Num, b) => a + b'.");
+        let lexer_res = tokenize("(a: Num, b) => a + b");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Lambda(lambda) = &node else { panic!("Expected a lambda expression.") };
+        assert_eq!(lambda.params.params.len(), 2);
+        assert!(lambda.params.params[0].declarator.declarator.type_annotation.is_some());
+        assert!(lambda.params.params[1].declarator.declarator.type_annotation.is_none());
+        assert!(lambda.func_token.is_none());
+        assert!(matches!(&lambda.body, Body::Arrow(body) if matches!(body.expr, Expr::Binary(_))));
+        assert_eq!(lambda.span, Some(SourceSpan { start: 0, end: 20, line: 1 }));
+
+        println!("Testing '() -> Num => 1'.");
+        let lexer_res = tokenize("() -> Num => 1");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Lambda(lambda) = &node else { panic!("Expected a lambda expression.") };
+        assert_eq!(lambda.params.params.len(), 0);
+        assert!(lambda.return_type.is_some());
+
+        println!("Testing 'func (x) ... end'.");
+        let lexer_res = tokenize("func (x)\n    let y = x * 2\n    y\nend");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Lambda(lambda) = &node else { panic!("Expected a lambda expression.") };
+        assert!(lambda.func_token.is_some());
+        let Body::Block(body) = &lambda.body else { panic!("Expected a block body.") };
+        assert_eq!(body.nodes.len(), 2);
+
+        println!("Testing 'map(list, func (x) => x)(0)'.");
+        let lexer_res = tokenize("map(list, func (x) => x)(0)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Call(call) = &node else { panic!("Expected a call expression.") };
+        let Expr::Call(map) = &call.callee else { panic!("Expected a call expression.") };
+        assert!(matches!(&map.arguments.arguments[1].expr, Expr::Lambda(_)));
+
+        println!("Testing '(a) + (b)'.");
+        let lexer_res = tokenize("(a) + (b)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::Binary(_)));
+
+        println!("Testing '(a, 2) => a'.");
+        let lexer_res = tokenize("(a, 2) => a");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        assert!(matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Error(_))));
+    }
+
+    #[test]
+    fn valid_collections() {
+        println!("== Testing collection literals ==");
+
+        println!("Testing '[1, 2, 3,]'.");
+        let lexer_res = tokenize("[1, 2, 3,]");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Array(array) = &node else { panic!("Expected an array expression.") };
+        assert_eq!(array.elements.len(), 3);
+        assert_eq!(array.comma_tokens.as_ref().unwrap().len(), 3);
+        assert_eq!(array.span, Some(SourceSpan { start: 0, end: 10, line: 1 }));
+
+        println!("Testing '[]'.");
+        let lexer_res = tokenize("[]");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::EmptyCollection(_)));
+
+        println!("Testing '(a, [b, c])'.");
+        let lexer_res = tokenize("(a, [b, c])");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Tuple(tuple) = &node else { panic!("Expected a tuple expression.") };
+        assert_eq!(tuple.elements.len(), 2);
+        assert!(matches!(&tuple.elements[1], Expr::Array(_)));
+
+        println!("Testing '(a,)' and '(a)'.");
+        let lexer_res = tokenize("(a,)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Tuple(tuple) = &node else { panic!("Expected a tuple expression.") };
+        assert_eq!(tuple.elements.len(), 1);
+
+        let lexer_res = tokenize("(a)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::Group(_)));
+
+        println!("Testing 'list[0]' and a literal on its own line.");
+        let lexer_res = tokenize("list[0]\n[1]");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::Index(_)));
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        assert!(matches!(&node, Expr::Array(_)));
+
+        println!("Testing '[1, 2' and '(1, 2'.");
+        let lexer_res = tokenize("[1, 2");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        assert!(matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Error(_))));
+
+        let lexer_res = tokenize("(1, 2");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        assert!(matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Error(_))));
+    }
+
+    #[test]
+    fn valid_new_operators() {
+        println!("== Testing modulo, bitwise, shift and compound assignment operators ==");
+
+        // Each case is written with every operator given the precedence the parser should give it.
+        let cases = vec![
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+            ("a & b << 2 + c", "(a & (b << (2 + c)))"),
+            ("a >> 1 << 2", "((a >> 1) << 2)"),
+            ("a + b % c * d", "(a + ((b % c) * d))"),
+            ("a | b == c ^ d", "((a | b) == (c ^ d))"),
+            ("a !~ b", "(a !~ b)"),
+        ];
+
+        fn to_string(expr: &Expr) -> String {
+            match expr {
+                Expr::Binary(bin) => format!(
+                    "({} {} {})",
+                    to_string(&bin.left),
+                    bin.operator.kind.lexeme(),
+                    to_string(&bin.right),
+                ),
+                Expr::Identifier(id) => match &id.identifier {
+                    Identifier::Simple(id) => id.name.to_string(),
+                    _ => panic!("Unexpected qualified identifier."),
+                },
+                Expr::Literal(lit) => lit.literal.source.to_string(),
+                _ => panic!("Unexpected expression."),
+            }
+        }
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let lexer_res = tokenize(input);
+            let arena = Arena::new();
+            let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+            let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+            assert_eq!(to_string(&node), expected);
+        }
+
+        println!("Testing 'a.b %= 2 + c'.");
+        let lexer_res = tokenize("a.b %= 2 + c");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Assignment(assignment) = &node else { panic!("Expected an assignment.") };
+        assert_eq!(assignment.operator.kind, OperatorKind::ModuloAssignment);
+        assert!(matches!(&assignment.left, Expr::Access(_)));
+        assert!(matches!(&assignment.right, Expr::Binary(_)));
+
+        println!("Testing 'take(in p, q)'.");
+        let lexer_res = tokenize("take(in p, q)");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Call(call) = &node else { panic!("Expected a call.") };
+        let Expr::LeftUnary(transfer) = &call.arguments.arguments[0].expr else {
+            panic!("Expected a transfer.")
+        };
+        assert_eq!(transfer.operator.kind, OperatorKind::OwnershipTransfer);
+        assert!(matches!(&call.arguments.arguments[1].expr, Expr::Identifier(_)));
+    }
+
+    #[test]
+    fn valid_precedence() {
+        println!("== Testing operator precedence ==");
+
+        /// Prints the expression given, with only the parentheses its operators require.
+        fn print(expr: &Expr) -> String {
+            match expr {
+                Expr::Binary(bin) => print_binary(&bin.left, &bin.operator, &bin.right),
+                Expr::Assignment(assign) => print_binary(&assign.left, &assign.operator, &assign.right),
+                Expr::Group(group) => print(&group.expr),
+                Expr::Identifier(id) => match &id.identifier {
+                    Identifier::Simple(id) => id.name.to_string(),
+                    _ => panic!("Unexpected qualified identifier."),
+                },
+                _ => panic!("Unexpected expression."),
+            }
+        }
+
+        fn print_binary(left: &Expr, op: &Operator, right: &Expr) -> String {
+            let operand = |expr: &Expr, is_right: bool| {
+                let mut inner = expr;
+                while let Expr::Group(group) = inner {
+                    inner = &group.expr;
+                }
+
+                let child_op = match inner {
+                    Expr::Binary(bin) => Some(&bin.operator.kind),
+                    Expr::Assignment(assign) => Some(&assign.operator.kind),
+                    _ => None,
+                };
+
+                match child_op {
+                    Some(child) if precedence::needs_parentheses(&op.kind, child, is_right) => {
+                        format!("({})", print(inner))
+                    }
+                    _ => print(inner),
+                }
+            };
+
+            let lexeme = op.kind.lexeme();
+            format!("{} {} {}", operand(left, false), lexeme, operand(right, true))
+        }
+
+        let cases = vec![
+            ("a = b ?? c or d and e == f | g ^ h & i << j + k * l", None),
+            ("a * (b + c) - (d - e)", None),
+            ("((a * b)) + c", Some("a * b + c")),
+            ("a ?? (b ?? c)", Some("a ?? b ?? c")),
+            ("(a ?? b) ?? c", None),
+            ("a += (b = c)", None),
+            ("(a < b) < c", Some("a < b < c")),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let lexer_res = tokenize(input);
+            let arena = Arena::new();
+            let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+            let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+            assert_eq!(print(&node), expected.unwrap_or(input));
+        }
+
+        println!("Testing 'a = b = c'.");
+        let lexer_res = tokenize("a = b = c");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+        let Expr::Assignment(assign) = &node else { panic!("Expected an assignment.") };
+        assert!(matches!(&assign.right, Expr::Identifier(_)));
+        assert!(parser.check(TokenKind::Equal), "Assignments can't be chained.");
+    }
+
+    #[test]
+    fn valid_destructuring() {
+        println!("== Testing destructuring declarations ==");
+
+        println!("Testing 'let [a, mut b,] = arr'.");
+        let lexer_res = tokenize("let [a, mut b,] = arr");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(stmt) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
+
+        let PartialLocalDecl::Destructured(decl) = &stmt.declarators[0] else { panic!("Expected a pattern.") };
+        assert_eq!(decl.destructuring_kind, DestructuringKind::ArrayPattern);
+        assert_eq!(decl.declarators.len(), 2);
+        assert_eq!(decl.declarators[0].ownership_kind, OwnershipKind::None);
+        assert_eq!(decl.declarators[1].ownership_kind, OwnershipKind::Mutable);
+        assert_eq!(decl.declarators[1].name.name, "b");
+        assert_eq!(decl.span, Some(SourceSpan { start: 4, end: 15, line: 1 }));
+        assert!(stmt.initializer.is_some());
+
+        println!("Testing 'let {{ x, final y: Num }} = point'.");
+        let lexer_res = tokenize("let { x, final y: Num } = point");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(stmt) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
+
+        let PartialLocalDecl::Destructured(decl) = &stmt.declarators[0] else { panic!("Expected a pattern.") };
+        assert_eq!(decl.destructuring_kind, DestructuringKind::ObjectPattern);
+        assert_eq!(decl.declarators.len(), 2);
+        assert!(decl.declarators[0].type_annotation.is_none());
+        assert!(decl.declarators[1].type_annotation.is_some());
+        assert_eq!(decl.declarators[1].ownership_kind, OwnershipKind::Final);
+    }
+
+    #[test]
+    fn invalid_destructuring() {
+        println!("== Testing invalid destructuring declarations ==");
+
+        let cases = vec![
+            ("let [] = arr", MessageCode::EmptyDestructuringPattern),
+            ("let {} = obj", MessageCode::EmptyDestructuringPattern),
+            ("let [a, 2] = arr", MessageCode::VariableDeclaratorExpected),
+            ("let [a, mut] = arr", MessageCode::VariableDeclaratorExpected),
+            ("let [a b] = arr", MessageCode::RightSquareBracketExpected),
+            ("let { a = obj", MessageCode::RightCurlyBracketExpected),
+            ("let [a, b]", MessageCode::DestructuringMustBeInitialized),
+        ];
+
+        for (input, expected) in cases {
+            println!("Testing '{}'.", input);
+
+            let lexer_res = tokenize(input);
+            let arena = Arena::new();
+            let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+            let ParseAttempt::Err(err) = parser.parse_local_decl_stmt() else {
+                panic!("Parse should have failed.")
+            };
+            assert_eq!(err.code, expected);
+        }
+    }
+
+    #[test]
+    fn valid_multiple_declarators() {
+        println!("== Testing local declarations with multiple declarators ==");
+
+        println!("Testing 'let a, mut b: Num = 1, 2'.");
+        let lexer_res = tokenize("let a, mut b: Num = 1, 2");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(stmt) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
+
+        assert_eq!(stmt.declarators.len(), 2);
+        assert_eq!(stmt.comma_tokens.as_ref().unwrap().len(), 1);
+        let PartialLocalDecl::Regular(a) = &stmt.declarators[0] else { panic!("???") };
+        let PartialLocalDecl::Regular(b) = &stmt.declarators[1] else { panic!("???") };
+        assert_eq!(a.declarator.ownership_kind, OwnershipKind::None);
+        assert!(a.declarator.type_annotation.is_none());
+        assert_eq!(b.declarator.ownership_kind, OwnershipKind::Mutable);
+        assert!(b.declarator.type_annotation.is_some());
+        assert_eq!(stmt.initializer.as_ref().unwrap().values.len(), 2);
+        assert_eq!(stmt.span, Some(SourceSpan { start: 0, end: 24, line: 1 }));
+
+        println!("Testing 'let x, [y, z] = pair'.");
+        let lexer_res = tokenize("let x, [y, z] = pair");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(stmt) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
+
+        assert!(matches!(&stmt.declarators[0], PartialLocalDecl::Regular(_)));
+        assert!(matches!(&stmt.declarators[1], PartialLocalDecl::Destructured(_)));
+        assert_eq!(stmt.initializer.as_ref().unwrap().values.len(), 1);
+
+        println!("Testing 'let a, b'.");
+        let lexer_res = tokenize("let a, b");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Ok(stmt) = parser.parse_local_decl_stmt() else { panic!("Parse failed.") };
+
+        assert_eq!(stmt.declarators.len(), 2);
+        assert!(stmt.initializer.is_none());
+        assert_eq!(stmt.span, Some(SourceSpan { start: 0, end: 8, line: 1 }));
+
+        println!("Testing 'let a, = 1'.");
+        let lexer_res = tokenize("let a, = 1");
+        let arena = Arena::new();
+        let mut parser = Parser::new(lexer_res.tokens.iter(), &arena);
+        let ParseAttempt::Err(err) = parser.parse_local_decl_stmt() else {
+            panic!("Parse should have failed.")
+        };
+        assert_eq!(err.code, MessageCode::VariableDeclaratorExpected);
+    }
+
+    #[test]
+    fn streaming_token_sources() {
+        println!("== Testing token sources ==");
+
+        let src = "func add (a: Num, b: Num) -> Num => a + b\nlet f = (x) => (x, [x]) ?? (y)\n";
+
+        println!("Testing a lexer against the tokens it produces.");
+        let lexer_res = tokenize(src);
+        let arena = Arena::new();
+        let expected = parse(lexer_res.tokens, &arena);
+
+        let mut lexer = Lexer::new(src);
+        let arena = Arena::new();
+        let parser_res = parse_source(&mut lexer, &arena);
+
+        assert_eq!(parser_res.messages.count(), 0);
+        assert_eq!(parser_res.node_count, expected.node_count);
+        assert_eq!(parser_res.tokens.len(), expected.tokens.len());
+        assert_eq!(lexer.trivia().len(), lexer_res.trivia.len());
+        assert_eq!(
+            serde_json::to_string(&parser_res.nodes).unwrap(),
+            serde_json::to_string(&expected.nodes).unwrap(),
+        );
+
+        println!("Testing tokens that aren't ended by an EOF token.");
+        let mut tokens = tokenize("a + b").tokens;
+        tokens.pop();
+        let arena = Arena::new();
+        let parser_res = parse(tokens, &arena);
+
+        assert_eq!(parser_res.nodes.len(), 1);
+        assert_eq!(parser_res.tokens.last().unwrap().kind(), TokenKind::EOF);
+        assert_eq!(parser_res.tokens.last().unwrap().base().start, 5);
+
+        println!("Testing no tokens at all.");
+        let arena = Arena::new();
+        let parser_res = parse(vec![], &arena);
+
+        assert_eq!(parser_res.nodes.len(), 0);
+        assert_eq!(parser_res.tokens.len(), 1);
+        assert_eq!(parser_res.tokens[0].kind(), TokenKind::EOF);
+
+        println!("Testing a lambda with a long parameter list, read from a lexer.");
+        let arena = Arena::new();
+        let mut parser = Parser::new(Lexer::new("(a: Num, b: (Num, Num), c, d) => a"), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Lambda(lambda) = &node else { panic!("Expected a lambda expression.") };
+        assert_eq!(lambda.params.params.len(), 4);
+    }
+
+    #[test]
+    fn lambda_lookahead() {
+        println!("== Testing how far lambdas are looked for ==");
+
+        println!("Testing a group nested 64 times.");
+        let src = format!("{}a{}", "(".repeat(64), ")".repeat(64));
+        let arena = Arena::new();
+        let parser_res = parse(tokenize(&src).tokens, &arena);
+
+        assert_eq!(parser_res.messages.count(), 0);
+        assert!(matches!(parser_res.nodes[0], SyntaxNode::Stmt(Stmt::Expr(stmt)) if matches!(
+            stmt.expr, Expr::Group(_)
+        )));
+
+        println!("Testing a long group, read from a lexer.");
+        let src = format!("({}a)", "a + ".repeat(200));
+        let arena = Arena::new();
+        let mut parser = Parser::new(Lexer::new(&src), &arena);
+
+        assert!(matches!(parser.parse_lambda_expr(), ParseAttempt::None));
+        assert!(parser.tokens.len() <= LAMBDA_LOOKAHEAD + 1);
+
+        println!("Testing a parameter list longer than the limit.");
+        let params: Vec<String> = (0..LAMBDA_LOOKAHEAD).map(|i| format!("a{}", i)).collect();
+        let params = params.join(", ");
+        let src = format!("func ({}) => a0", params);
+        let arena = Arena::new();
+        let mut parser = Parser::new(Lexer::new(&src), &arena);
+        let ParseAttempt::Ok(node) = parser.parse_expr() else { panic!("Parse failed.") };
+
+        let Expr::Lambda(lambda) = &node else { panic!("Expected a lambda expression.") };
+        assert_eq!(lambda.params.params.len(), LAMBDA_LOOKAHEAD);
+
+        let src = format!("({}) => a0", params);
+        let arena = Arena::new();
+        let mut parser = Parser::new(Lexer::new(&src), &arena);
+        assert!(!matches!(parser.parse_expr(), ParseAttempt::Ok(Expr::Lambda(_))));
+    }
+
+    #[test]
+    fn invalid_top_level_tokens() {
+        println!("== Testing tokens that don't start a node ==");
+
+        println!("Testing ') a'.");
+        let arena = Arena::new();
+        let parser_res = parse(tokenize(") a").tokens, &arena);
+
+        assert_eq!(parser_res.nodes.len(), 1);
+        assert_eq!(parser_res.messages.errors.len(), 1);
+        assert_eq!(parser_res.messages.errors[0].code, MessageCode::UnexpectedToken);
+
+        println!("Testing 'loop ?.a'.");
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("loop ?.a").tokens, &arena);
+
+        assert!(parser_res.messages.errors.len() > 0);
+    }
+
+    #[test]
+    fn shared_interner() {
+        println!("== Testing sources that share their names ==");
+
+        let arena = Arena::new();
+        let first = parse(tokenize("let name = 1").tokens, &arena);
+        let second = parse_source_with(Lexer::new("name"), first.interner);
+
+        let SyntaxNode::Stmt(Stmt::LocalDecl(decl)) = &first.nodes[0] else {
+            panic!("'let name = 1' should be a local declaration.");
+        };
+        let PartialLocalDecl::Regular(decl) = &decl.declarators[0] else {
+            panic!("'let name = 1' should have a regular declarator.");
+        };
+        let SyntaxNode::Stmt(Stmt::Expr(stmt)) = &second.nodes[0] else {
+            panic!("'name' should be an expression.");
+        };
+        let Expr::Identifier(IdentifierExpr { identifier: Identifier::Simple(id), .. }) = &stmt.expr
+        else {
+            panic!("'name' should be an identifier.");
+        };
+
+        assert_eq!(decl.declarator.name.name, id.name);
+        assert!(std::ptr::eq(decl.declarator.name.name.as_str(), id.name.as_str()));
+    }
+
+    #[test]
+    fn deep_nesting() {
+        println!("== Testing deeply nested code ==");
+
+        let cases = vec![
+            format!("{}a{}", "(".repeat(1000), ")".repeat(1000)),
+            format!("{}a{}", "[".repeat(1000), "]".repeat(1000)),
+            format!("{}a", "- ".repeat(1000)),
+            format!("{}a", "a ?? ".repeat(1000)),
+            format!("{}a{}", "if a then ".repeat(1000), " end".repeat(1000)),
+            format!("let a: {}Num{}", "(".repeat(1000), ")".repeat(1000)),
+            format!("{}a{}", "func f ()\n".repeat(1000), "\nend".repeat(1000)),
+            "(".repeat(1000),
+        ];
+
+        for src in cases {
+            println!("Testing '{}…'.", &src[..24]);
+            let arena = Arena::new();
+            let parser_res = parse(tokenize(&src).tokens, &arena);
+
+            assert!(
+                parser_res.messages.errors.iter().any(|msg| msg.code == MessageCode::NestingTooDeep)
+            );
+        }
+
+        println!("Testing nesting that closes right after the limit.");
+        let src = format!("{}a{}\nb", "(".repeat(MAX_DEPTH + 1), ")".repeat(MAX_DEPTH + 1));
+        let arena = Arena::new();
+        let parser_res = parse(tokenize(&src).tokens, &arena);
+
+        assert_eq!(parser_res.messages.errors.len(), 1);
+        assert_eq!(parser_res.nodes.len(), 2);
+
+        println!("Testing nesting right at the limit.");
+        let src = format!("{}a{}", "(".repeat(MAX_DEPTH - 1), ")".repeat(MAX_DEPTH - 1));
+        let arena = Arena::new();
+        let parser_res = parse(tokenize(&src).tokens, &arena);
+
+        assert_eq!(parser_res.messages.count(), 0);
+    }
+
+    /// Pieces of code that arbitrary sources are built from, so they get past the lexer and reach
+    /// deep into the parser more often than random characters would.
+    const FRAGMENTS: &[&str] = &[
+        "func", "let", "mut", "if", "elsif", "else", "then", "end", "do", "loop", "while", "and",
+        "or", "not", "return", "true", "false", "null", "__p_print", "a", "b", "Num", "s", "sS",
+        "(", ")", "[", "]", "{", "}", ",", ".", "?.", "::", ":", "=", "=>", "->", "+", "-", "*",
+        "/", "?", "!", "??", "==", "<", ">", "&", "|", "~", "1", "0x1f", "2.5e3", "-3", "\"a\"",
+        "``b``", "-- c", "--! d --", "--!", "#e", "\n", "\r", "\t", "é", "\\", "\"", "`", "0x",
+        "1e", ".5", "1.", "__",
+    ];
+
+    /// Lexes and parses the source given, both at once and by pulling tokens from the lexer.
+    fn parse_both_ways(src: &str) {
+        let arena = Arena::new();
+        let _ = parse(tokenize(src).tokens, &arena);
+        let _ = parse_source(Lexer::new(src), &arena);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn arbitrary_text_never_panics(src in "\\PC*") {
+            parse_both_ways(&src);
+        }
+
+        #[test]
+        fn arbitrary_code_never_panics(
+            fragments in proptest::collection::vec(proptest::sample::select(FRAGMENTS), 0..48)
+        ) {
+            parse_both_ways(&fragments.join(" "));
+            parse_both_ways(&fragments.concat());
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::judith::lexical::token::TokenKind;
+use crate::judith::syntax::nodes::OperatorKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a op b op c` is `(a op b) op c`.
+    Left,
+    /// `a op b op c` is `a op (b op c)`.
+    Right,
+    /// The operator can't be chained with operators of the same precedence.
+    None,
+}
+
+/// How a binary operator binds to its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinaryOperatorInfo {
+    /// The precedence of the operator. Operators with a higher precedence bind tighter. The lowest
+    /// precedence is 1.
+    pub precedence: u8,
+    pub associativity: Associativity,
+}
+
+/// Every binary operator, from the lowest to the highest precedence. Operators in the same row
+/// share their precedence and associativity.
+const BINARY_OPERATORS: &[(&[OperatorKind], Associativity)] = &[
+    (&[
+        OperatorKind::Assignment,
+        OperatorKind::AddAssignment,
+        OperatorKind::SubtractAssignment,
+        OperatorKind::MultiplyAssignment,
+        OperatorKind::DivideAssignment,
+        OperatorKind::ModuloAssignment,
+    ], Associativity::None),
+    (&[OperatorKind::NullCoalescing], Associativity::Right),
+    (&[OperatorKind::LogicalOr], Associativity::Left),
+    (&[OperatorKind::LogicalAnd], Associativity::Left),
+    (&[
+        OperatorKind::Equals,
+        OperatorKind::NotEquals,
+        OperatorKind::Like,
+        OperatorKind::NotLike,
+        OperatorKind::ReferenceEquals,
+        OperatorKind::ReferenceNotEquals,
+        OperatorKind::LessThan,
+        OperatorKind::LessThanOrEqualsTo,
+        OperatorKind::GreaterThan,
+        OperatorKind::GreaterThanOrEqualsTo,
+    ], Associativity::Left),
+    (&[OperatorKind::BitwiseOr], Associativity::Left),
+    (&[OperatorKind::BitwiseXor], Associativity::Left),
+    (&[OperatorKind::BitwiseAnd], Associativity::Left),
+    (&[OperatorKind::LeftShift, OperatorKind::RightShift], Associativity::Left),
+    (&[OperatorKind::Add, OperatorKind::Subtract], Associativity::Left),
+    (&[OperatorKind::Multiply, OperatorKind::Divide, OperatorKind::Modulo], Associativity::Left),
+];
+
+/// Returns the kind of operator represented by the token kind given, or `Invalid` if the token
+/// isn't an operator.
+pub fn operator_kind(kind: TokenKind) -> OperatorKind {
+    match kind {
+        TokenKind::Plus => OperatorKind::Add,
+        TokenKind::Minus => OperatorKind::Subtract,
+        TokenKind::Asterisk => OperatorKind::Multiply,
+        TokenKind::Slash => OperatorKind::Divide,
+        TokenKind::Percent => OperatorKind::Modulo,
+        TokenKind::Tilde => OperatorKind::BitwiseNot,
+        TokenKind::Ampersand => OperatorKind::BitwiseAnd,
+        TokenKind::Pipe => OperatorKind::BitwiseOr,
+        TokenKind::Caret => OperatorKind::BitwiseXor,
+        TokenKind::LessLess => OperatorKind::LeftShift,
+        TokenKind::GreaterGreater => OperatorKind::RightShift,
+        TokenKind::Equal => OperatorKind::Assignment,
+        TokenKind::PlusEqual => OperatorKind::AddAssignment,
+        TokenKind::MinusEqual => OperatorKind::SubtractAssignment,
+        TokenKind::AsteriskEqual => OperatorKind::MultiplyAssignment,
+        TokenKind::SlashEqual => OperatorKind::DivideAssignment,
+        TokenKind::PercentEqual => OperatorKind::ModuloAssignment,
+        TokenKind::EqualEqual => OperatorKind::Equals,
+        TokenKind::BangEqual => OperatorKind::NotEquals,
+        TokenKind::TildeTilde => OperatorKind::Like,
+        TokenKind::BangTilde => OperatorKind::NotLike,
+        TokenKind::EqualEqualEqual => OperatorKind::ReferenceEquals,
+        TokenKind::BangEqualEqual => OperatorKind::ReferenceNotEquals,
+        TokenKind::Less => OperatorKind::LessThan,
+        TokenKind::LessEqual => OperatorKind::LessThanOrEqualsTo,
+        TokenKind::Greater => OperatorKind::GreaterThan,
+        TokenKind::GreaterEqual => OperatorKind::GreaterThanOrEqualsTo,
+        TokenKind::KwNot => OperatorKind::LogicalNot,
+        TokenKind::KwIn => OperatorKind::OwnershipTransfer,
+        TokenKind::KwAnd => OperatorKind::LogicalAnd,
+        TokenKind::KwOr => OperatorKind::LogicalOr,
+        TokenKind::DoubleQuestionMark => OperatorKind::NullCoalescing,
+        TokenKind::Bang => OperatorKind::NonNullAssertion,
+        TokenKind::Dot => OperatorKind::MemberAccess,
+        TokenKind::QuestionDot => OperatorKind::SafeMemberAccess,
+        TokenKind::DoubleColon => OperatorKind::ScopeResolution,
+        _ => OperatorKind::Invalid,
+    }
+}
+
+/// Returns how the operator given binds to its operands, or `None` if it isn't a binary operator.
+pub fn binary_operator_info(kind: &OperatorKind) -> Option<BinaryOperatorInfo> {
+    BINARY_OPERATORS.iter()
+        .position(|(kinds, _)| kinds.contains(kind))
+        .map(|i| BinaryOperatorInfo {
+            precedence: i as u8 + 1,
+            associativity: BINARY_OPERATORS[i].1,
+        })
+}
+
+/// Returns whether the operator given produces an assignment expression rather than a binary one.
+pub fn is_assignment_operator(kind: &OperatorKind) -> bool {
+    matches!(
+        kind,
+        OperatorKind::Assignment
+            | OperatorKind::AddAssignment
+            | OperatorKind::SubtractAssignment
+            | OperatorKind::MultiplyAssignment
+            | OperatorKind::DivideAssignment
+            | OperatorKind::ModuloAssignment
+    )
+}
+
+/// Returns whether an operand of the binary operator `parent` whose outermost operator is `child`
+/// has to be wrapped in parentheses to keep its meaning. `is_right` tells whether the operand is
+/// the right one.
+pub fn needs_parentheses(parent: &OperatorKind, child: &OperatorKind, is_right: bool) -> bool {
+    let (Some(parent), Some(child)) = (binary_operator_info(parent), binary_operator_info(child))
+    else {
+        return false;
+    };
+
+    if child.precedence != parent.precedence {
+        return child.precedence < parent.precedence;
+    }
+
+    match parent.associativity {
+        Associativity::Left => is_right,
+        Associativity::Right => !is_right,
+        Associativity::None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operator_table() {
+        println!("== Testing the binary operator table ==");
+
+        let add = binary_operator_info(&OperatorKind::Add).unwrap();
+        let mul = binary_operator_info(&OperatorKind::Modulo).unwrap();
+        let coalesce = binary_operator_info(&OperatorKind::NullCoalescing).unwrap();
+        assert!(mul.precedence > add.precedence);
+        assert!(add.precedence > coalesce.precedence);
+        assert_eq!(coalesce.associativity, Associativity::Right);
+        assert_eq!(binary_operator_info(&OperatorKind::BitwiseNot), None);
+
+        // An operator can only appear once in the table.
+        let all: Vec<&OperatorKind> = BINARY_OPERATORS.iter().flat_map(|(kinds, _)| kinds.iter()).collect();
+        for (i, kind) in all.iter().enumerate() {
+            assert!(!all[i + 1..].contains(kind), "{:?} appears twice.", kind);
+        }
+        assert_eq!(operator_kind(TokenKind::LessLess), OperatorKind::LeftShift);
+
+        println!("Testing parentheses.");
+        // (a + b) * c
+        assert!(needs_parentheses(&OperatorKind::Multiply, &OperatorKind::Add, false));
+        // a * b + c
+        assert!(!needs_parentheses(&OperatorKind::Add, &OperatorKind::Multiply, false));
+        // a - (b - c), but (a - b) - c
+        assert!(needs_parentheses(&OperatorKind::Subtract, &OperatorKind::Add, true));
+        assert!(!needs_parentheses(&OperatorKind::Subtract, &OperatorKind::Subtract, false));
+        // a ?? b ?? c, but (a ?? b) ?? c
+        assert!(!needs_parentheses(&OperatorKind::NullCoalescing, &OperatorKind::NullCoalescing, true));
+        assert!(needs_parentheses(&OperatorKind::NullCoalescing, &OperatorKind::NullCoalescing, false));
+        // a = (b = c)
+        assert!(needs_parentheses(&OperatorKind::Assignment, &OperatorKind::Assignment, true));
+    }
+}
@@ -1,21 +1,65 @@
-use crate::judith::lexical::token::{Token, TokenKind};
+use std::cell::Cell;
+use crate::judith::lexical::symbol::Interner;
+use crate::judith::lexical::token::{Token, TokenId, TokenKind, TokenRef};
+use crate::judith::syntax::arena::Arena;
+use crate::judith::syntax::node_id::NodeId;
 use crate::judith::syntax::nodes::*;
+use crate::judith::syntax::precedence;
 use crate::SourceSpan;
 
-pub struct SyntaxFactory;
+/// Builds the nodes of a syntax tree in the arena given. Each node built is given the next id; as
+/// children are always built before their parents, nodes are numbered in postorder.
+pub struct SyntaxFactory<'ast> {
+    arena: &'ast Arena,
+    /// Interns the names of identifiers, in the same arena as the nodes.
+    pub interner: Interner<'ast>,
+    next_id: Cell<u32>,
+}
+
+impl<'ast> SyntaxFactory<'ast> {
+    pub fn new(arena: &'ast Arena) -> SyntaxFactory<'ast> {
+        SyntaxFactory::with_interner(Interner::new(arena))
+    }
+
+    /// Creates a factory that keeps interning names into the interner given, allocating nodes in
+    /// the arena that interner stores its strings in.
+    pub fn with_interner(interner: Interner<'ast>) -> SyntaxFactory<'ast> {
+        SyntaxFactory {
+            arena: interner.arena(),
+            interner,
+            next_id: Cell::new(0),
+        }
+    }
+
+    /// Moves the node given into the arena.
+    pub fn alloc<T: Copy>(&self, node: T) -> &'ast T {
+        self.arena.alloc(node)
+    }
+
+    /// Returns the amount of ids given so far.
+    pub fn node_count(&self) -> usize {
+        self.next_id.get() as usize
+    }
+
+    fn next_id(&self) -> NodeId {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        NodeId(id)
+    }
 
-impl SyntaxFactory {
     // region Items
     pub fn func_def(
-        func_tok: Token,
-        name: SimpleIdentifier,
-        params: ParameterList,
-        arrow_tok: Option<Token>,
-        return_type: Option<TypeNode>,
-        body: Body
-    ) -> FuncDef {
+        &self,
+        func_tok: TokenRef,
+        name: SimpleIdentifier<'ast>,
+        params: ParameterList<'ast>,
+        arrow_tok: Option<TokenRef>,
+        return_type: Option<TypeNode<'ast>>,
+        body: Body<'ast>
+    ) -> FuncDef<'ast> {
         let start = func_tok.base().start;
-        let end = body.span().unwrap().end;
+        let end = body.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = func_tok.base().line;
 
         FuncDef {
@@ -24,17 +68,19 @@ impl SyntaxFactory {
             params,
             return_type,
             body,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            func_token: Some(func_tok),
-            return_type_arrow_token: arrow_tok,
+            func_token: Some(func_tok.id),
+            return_type_arrow_token: arrow_tok.map(|tok| tok.id),
         }
     }
     // endregion Items
 
     // region Bodies
     pub fn block_body(
-        opening_token: Option<Token>, nodes: Vec<SyntaxNode>, closing_token: Option<Token>
-    ) -> BlockBody {
+        &self,
+        opening_token: Option<TokenRef>, nodes: Vec<SyntaxNode<'ast>>, closing_token: Option<TokenRef>
+    ) -> BlockBody<'ast> {
         let start: i64;
         let end: i64;
         let line: i64;
@@ -43,308 +89,477 @@ impl SyntaxFactory {
             start = tok.base().start;
             line = tok.base().line;
         } else if let Some(node) = nodes.last() {
-            start = node.span().unwrap().start;
-            line = node.span().unwrap().end;
+            start = node.span().unwrap_or_else(SourceSpan::no_location).start;
+            line = node.span().unwrap_or_else(SourceSpan::no_location).end;
         } else if let Some(tok) = &closing_token {
             start = tok.base().start;
             line = tok.base().line;
         }
         else {
-            panic!("Block body must contain, at least, one token.");
+            start = SourceSpan::no_location().start;
+            line = SourceSpan::no_location().line;
         };
 
         if let Some(tok) = &closing_token {
             end = tok.base().end;
         }
         else if let Some(node) = nodes.last() {
-            end = node.span().unwrap().end;
+            end = node.span().unwrap_or_else(SourceSpan::no_location).end;
         }
         else if let Some(tok) = &opening_token {
             end = tok.base().end;
         }
         else {
-            panic!("Block body must contain, at least, one token.");
+            end = SourceSpan::no_location().end;
         }
 
         BlockBody {
-            opening_token,
-            nodes,
-            closing_token,
+            opening_token: opening_token.map(|tok| tok.id),
+            nodes: self.arena.alloc_slice(&nodes),
+            closing_token: closing_token.map(|tok| tok.id),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn arrow_body(arrow_tok: Token, expr: Expr) -> ArrowBody {
+    pub fn arrow_body(&self, arrow_tok: TokenRef, expr: Expr<'ast>) -> ArrowBody<'ast> {
         let start = arrow_tok.base().start;
-        let end = expr.span().unwrap().end;
+        let end = expr.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = arrow_tok.base().line;
 
         ArrowBody {
-            arrow_token: Some(arrow_tok),
+            arrow_token: Some(arrow_tok.id),
             expr,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn expr_body(expr: Expr) -> ExprBody {
-        let start = expr.span().unwrap().start;
-        let end = expr.span().unwrap().end;
-        let line = expr.span().unwrap().line;
+    pub fn expr_body(&self, expr: Expr<'ast>) -> ExprBody<'ast> {
+        let start = expr.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = expr.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line = expr.span().unwrap_or_else(SourceSpan::no_location).line;
 
         ExprBody {
             expr,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
     // endregion
 
     // region Statements
-    pub fn expr_stmt(expr: Expr) -> ExprStmt {
-        let span = expr.span().unwrap().clone();
+    pub fn expr_stmt(&self, expr: Expr<'ast>) -> ExprStmt<'ast> {
+        let span = expr.span().unwrap_or_else(SourceSpan::no_location);
 
         ExprStmt {
             expr,
+            id: self.next_id(),
             span: Some(span),
         }
     }
 
     pub fn local_decl_stmt(
-        let_tok: Token,
-        decl: PartialLocalDecl,
-        init: Option<EqualsValueClause>
-    ) -> LocalDeclStmt {
+        &self,
+        let_tok: TokenRef,
+        declarators: Vec<PartialLocalDecl<'ast>>,
+        init: Option<EqualsValueClause<'ast>>,
+        comma_tokens: Vec<TokenId>,
+    ) -> LocalDeclStmt<'ast> {
         let start = let_tok.base().start;
         let end: i64;
         let line = let_tok.base().line;
 
         if let Some(init) = &init {
-            end = init.span.unwrap().end;
+            end = init.span.unwrap_or_else(SourceSpan::no_location).end;
+        }
+        else if let Some(decl) = declarators.last() {
+            end = decl.span().unwrap_or_else(SourceSpan::no_location).end;
         }
         else {
-            end = decl.span().unwrap().end;
+            end = let_tok.base().end;
         }
 
         LocalDeclStmt {
-            decl,
+            declarators: self.arena.alloc_slice(&declarators),
             initializer: init,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            let_token: Some(let_tok),
+            let_token: Some(let_tok.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
 
-    pub fn regular_local_decl(declarator: LocalDeclarator) -> RegularLocalDecl {
-        let span = declarator.span.unwrap().clone();
+    pub fn regular_local_decl(&self, declarator: LocalDeclarator<'ast>) -> RegularLocalDecl<'ast> {
+        let span = declarator.span.unwrap_or_else(SourceSpan::no_location);
 
         RegularLocalDecl {
             declarator,
+            id: self.next_id(),
             span: Some(span),
         }
     }
 
     pub fn destructured_local_decl(
-        opening_tok: Token, declarators: Vec<LocalDeclarator>, closing_tok: Token
-    ) -> DestructuredLocalDecl {
-        panic_when_invalid_pair(&opening_tok, &closing_tok);
-
+        &self,
+        opening_tok: TokenRef,
+        declarators: Vec<LocalDeclarator<'ast>>,
+        closing_tok: TokenRef,
+        comma_tokens: Vec<TokenId>
+    ) -> DestructuredLocalDecl<'ast> {
         let start = opening_tok.base().start;
         let end = closing_tok.base().end;
         let line = opening_tok.base().line;
 
         let destructuring_kind = match &opening_tok.kind() {
-            TokenKind::LeftSquareBracket => DestructuringKind::ArrayPattern,
             TokenKind::LeftCurlyBracket => DestructuringKind::ObjectPattern,
-            _ => panic!("Invalid destructuring token."),
+            _ => DestructuringKind::ArrayPattern,
         };
 
         DestructuredLocalDecl {
-            declarators,
+            declarators: self.arena.alloc_slice(&declarators),
             destructuring_kind,
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            opening_token: Some(opening_tok.id),
+            closing_token: Some(closing_tok.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
+        }
+    }
+
+    pub fn priv_print_stmt(&self, p_print_tok: TokenRef, expr: Expr<'ast>) -> PrivPrintStmt<'ast> {
+        let start = p_print_tok.base().start;
+        let end = expr.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line = p_print_tok.base().line;
+
+        PrivPrintStmt {
+            expr,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            opening_token: Some(opening_tok),
-            closing_token: Some(closing_tok),
+            p_print_token: Some(p_print_tok.id),
         }
     }
     // endregion Statements
 
     // region Expressions
-    pub fn if_expr(if_tok: Token, test: Expr, body: Body) -> IfExpr {
+    pub fn if_expr(&self, if_tok: TokenRef, test: Expr<'ast>, body: Body<'ast>) -> IfExpr<'ast> {
         let start = if_tok.base().start;
-        let end = body.span().unwrap().end;
+        let end = body.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = if_tok.base().line;
 
         IfExpr {
             test,
             consequent: body,
             alternate: None,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            if_token: Some(if_tok),
+            if_token: Some(if_tok.id),
             else_token: None,
         }
     }
 
     pub fn if_else_expr(
-        if_tok: Token, test: Expr, consequent: Body, else_tok: Option<Token>, alternate: Body
-    ) -> IfExpr {
+        &self,
+        if_tok: TokenRef, test: Expr<'ast>, consequent: Body<'ast>, else_tok: Option<TokenRef>, alternate: Body<'ast>
+    ) -> IfExpr<'ast> {
         let start = if_tok.base().start;
-        let end = alternate.span().unwrap().end;
+        let end = alternate.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = if_tok.base().line;
 
         IfExpr {
             test,
             consequent,
             alternate: Some(alternate),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            if_token: Some(if_tok),
-            else_token: else_tok,
+            if_token: Some(if_tok.id),
+            else_token: else_tok.map(|tok| tok.id),
         }
     }
 
-    pub fn loop_expr(loop_tok: Token, body: Body) -> LoopExpr {
+    pub fn loop_expr(&self, loop_tok: TokenRef, body: Body<'ast>) -> LoopExpr<'ast> {
         let start = loop_tok.base().start;
-        let end = body.span().unwrap().end;
+        let end = body.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = loop_tok.base().line;
 
         LoopExpr {
             body,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            loop_token: Some(loop_tok),
+            loop_token: Some(loop_tok.id),
         }
     }
 
-    pub fn while_expr(while_tok: Token, test: Expr, body: Body) -> WhileExpr {
+    pub fn while_expr(
+        &self, while_tok: TokenRef, test: Expr<'ast>, body: Body<'ast>
+    ) -> WhileExpr<'ast> {
         let start = while_tok.base().start;
-        let end = body.span().unwrap().end;
+        let end = body.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = while_tok.base().line;
 
         WhileExpr {
             test,
             body,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            while_token: Some(while_tok),
+            while_token: Some(while_tok.id),
         }
     }
 
     // region Cascading expressions
-    pub fn assignment_expr(left: Expr, op: Operator, right: Expr) -> AssignmentExpr {
-        let start = left.span().unwrap().start;
-        let end = right.span().unwrap().end;
-        let line = left.span().unwrap().line;
+    pub fn assignment_expr(
+        &self, left: Expr<'ast>, op: Operator, right: Expr<'ast>
+    ) -> AssignmentExpr<'ast> {
+        let start = left.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = right.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line = left.span().unwrap_or_else(SourceSpan::no_location).line;
 
         AssignmentExpr {
             left,
             operator: op,
             right,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn binary_expr(left: Expr, op: Operator, right: Expr) -> BinaryExpr {
-        let start = left.span().unwrap().start;
-        let end = right.span().unwrap().end;
-        let line = left.span().unwrap().line;
+    pub fn binary_expr(
+        &self, left: Expr<'ast>, op: Operator, right: Expr<'ast>
+    ) -> BinaryExpr<'ast> {
+        let start = left.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = right.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line = left.span().unwrap_or_else(SourceSpan::no_location).line;
 
         BinaryExpr {
             left,
             operator: op,
             right,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn left_unary_expr(op: Operator, expr: Expr) -> LeftUnaryExpr {
-        let start = op.span.unwrap().start;
-        let end = expr.span().unwrap().end;
-        let line = op.span.unwrap().line;
+    pub fn left_unary_expr(&self, op: Operator, expr: Expr<'ast>) -> LeftUnaryExpr<'ast> {
+        let start = op.span.unwrap_or_else(SourceSpan::no_location).start;
+        let end = expr.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line = op.span.unwrap_or_else(SourceSpan::no_location).line;
 
         LeftUnaryExpr {
             operator: op,
             expr,
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+        }
+    }
+
+    pub fn right_unary_expr(&self, expr: Expr<'ast>, op: Operator) -> RightUnaryExpr<'ast> {
+        let start = expr.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = op.span.unwrap_or_else(SourceSpan::no_location).end;
+        let line = expr.span().unwrap_or_else(SourceSpan::no_location).line;
+
+        RightUnaryExpr {
+            expr,
+            operator: op,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn group_expr(left_paren: Token, expr: Expr, right_paren: Token) -> GroupExpr {
+    pub fn group_expr(
+        &self, left_paren: TokenRef, expr: Expr<'ast>, right_paren: TokenRef
+    ) -> GroupExpr<'ast> {
         let start = left_paren.base().start;
         let end = right_paren.base().end;
         let line = left_paren.base().line;
 
         GroupExpr {
             expr,
-            left_paren_token: Some(left_paren),
-            right_paren_token: Some(right_paren),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn object_init_expr(provider: Option<Expr>, initializer: ObjectInitializer) -> ObjectInitExpr {
+    pub fn tuple_expr(
+        &self,
+        left_paren: TokenRef, elements: Vec<Expr<'ast>>, right_paren: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> TupleExpr<'ast> {
+        let start = left_paren.base().start;
+        let end = right_paren.base().end;
+        let line = left_paren.base().line;
+
+        TupleExpr {
+            elements: self.arena.alloc_slice(&elements),
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
+        }
+    }
+
+    pub fn array_expr(
+        &self,
+        left_bracket: TokenRef, elements: Vec<Expr<'ast>>, right_bracket: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> ArrayExpr<'ast> {
+        let start = left_bracket.base().start;
+        let end = right_bracket.base().end;
+        let line = left_bracket.base().line;
+
+        ArrayExpr {
+            elements: self.arena.alloc_slice(&elements),
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            left_square_bracket_token: Some(left_bracket.id),
+            right_square_bracket_token: Some(right_bracket.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
+        }
+    }
+
+    pub fn empty_collection_expr(
+        &self, left_bracket: TokenRef, right_bracket: TokenRef
+    ) -> EmptyCollectionExpr {
+        let start = left_bracket.base().start;
+        let end = right_bracket.base().end;
+        let line = left_bracket.base().line;
+
+        EmptyCollectionExpr {
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            left_square_bracket_token: Some(left_bracket.id),
+            right_square_bracket_token: Some(right_bracket.id),
+        }
+    }
+
+    pub fn object_init_expr(
+        &self, provider: Option<Expr<'ast>>, initializer: ObjectInitializer<'ast>
+    ) -> ObjectInitExpr<'ast> {
         let start: i64;
-        let end = initializer.span.unwrap().end;
+        let end = initializer.span.unwrap_or_else(SourceSpan::no_location).end;
         let line: i64;
 
         if let Some(provider) = &provider {
-            start = provider.span().unwrap().start;
-            line = provider.span().unwrap().line;
+            start = provider.span().unwrap_or_else(SourceSpan::no_location).start;
+            line = provider.span().unwrap_or_else(SourceSpan::no_location).line;
         }
         else {
-            start = initializer.span.unwrap().start;
-            line = initializer.span.unwrap().line;
+            start = initializer.span.unwrap_or_else(SourceSpan::no_location).start;
+            line = initializer.span.unwrap_or_else(SourceSpan::no_location).line;
         };
 
         ObjectInitExpr {
             provider,
             initializer,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn access_expr(receiver: Option<Expr>, op: Operator, member: SimpleIdentifier) -> AccessExpr {
+    pub fn access_expr(
+        &self, receiver: Option<Expr<'ast>>, op: Operator, member: SimpleIdentifier<'ast>
+    ) -> AccessExpr<'ast> {
         let start: i64;
-        let end = member.span.unwrap().end;
+        let end = member.span.unwrap_or_else(SourceSpan::no_location).end;
         let line: i64;
 
         if let Some(expr) = &receiver {
-            start = expr.span().unwrap().start;
-            line = expr.span().unwrap().line;
+            start = expr.span().unwrap_or_else(SourceSpan::no_location).start;
+            line = expr.span().unwrap_or_else(SourceSpan::no_location).line;
         }
         else {
-            start = op.span.unwrap().start;
-            line = op.span.unwrap().line;
+            start = op.span.unwrap_or_else(SourceSpan::no_location).start;
+            line = op.span.unwrap_or_else(SourceSpan::no_location).line;
         }
 
         AccessExpr {
             receiver,
             operator: op,
             member,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn call_expr(callee: Expr, arguments: ArgumentList) -> CallExpr {
-        let start = callee.span().unwrap().start;
-        let end = arguments.span.unwrap().end;
-        let line = callee.span().unwrap().line;
+    pub fn call_expr(&self, callee: Expr<'ast>, arguments: ArgumentList<'ast>) -> CallExpr<'ast> {
+        let start = callee.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = arguments.span.unwrap_or_else(SourceSpan::no_location).end;
+        let line = callee.span().unwrap_or_else(SourceSpan::no_location).line;
 
         CallExpr {
             callee,
             arguments,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn identifier_expr(id: Identifier) -> IdentifierExpr {
+    pub fn index_expr(
+        &self,
+        receiver: Expr<'ast>, left_sq_bracket: TokenRef, index: Expr<'ast>, right_sq_bracket: TokenRef
+    ) -> IndexExpr<'ast> {
+        let start = receiver.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = right_sq_bracket.base().end;
+        let line = receiver.span().unwrap_or_else(SourceSpan::no_location).line;
+
+        IndexExpr {
+            receiver,
+            index,
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            left_square_bracket_token: Some(left_sq_bracket.id),
+            right_square_bracket_token: Some(right_sq_bracket.id),
+        }
+    }
+
+    pub fn lambda_expr(
+        &self,
+        func_tok: Option<TokenRef>,
+        params: ParameterList<'ast>,
+        arrow_tok: Option<TokenRef>,
+        return_type: Option<TypeNode<'ast>>,
+        body: Body<'ast>
+    ) -> LambdaExpr<'ast> {
+        let start: i64;
+        let end = body.span().unwrap_or_else(SourceSpan::no_location).end;
+        let line: i64;
+
+        if let Some(tok) = &func_tok {
+            start = tok.base().start;
+            line = tok.base().line;
+        }
+        else {
+            start = params.span.unwrap_or_else(SourceSpan::no_location).start;
+            line = params.span.unwrap_or_else(SourceSpan::no_location).line;
+        }
+
+        LambdaExpr {
+            params,
+            return_type,
+            body,
+            id: self.next_id(),
+            span: Some(SourceSpan { start, end, line }),
+            func_token: func_tok.map(|tok| tok.id),
+            return_type_arrow_token: arrow_tok.map(|tok| tok.id),
+        }
+    }
+
+    pub fn identifier_expr(&self, id: Identifier<'ast>) -> IdentifierExpr<'ast> {
         let span = id.span().clone();
 
         IdentifierExpr {
             identifier: id,
+            id: self.next_id(),
             span,
         }
     }
 
-    pub fn literal_expr(literal: Literal) -> LiteralExpr {
+    pub fn literal_expr(&self, literal: Literal<'ast>) -> LiteralExpr<'ast> {
         let span = literal.span.clone();
 
         LiteralExpr {
             literal,
+            id: self.next_id(),
             span,
         }
     }
@@ -353,7 +568,7 @@ impl SyntaxFactory {
     // endregion Expressions
 
     // region Fragments
-    pub fn simple_identifier(tok: Token) -> SimpleIdentifier {
+    pub fn simple_identifier(&self, tok: TokenRef) -> SimpleIdentifier<'ast> {
         const ESCAPE_CHAR: char = '\\';
         let start = tok.base().start;
         let end = tok.base().end;
@@ -362,185 +577,192 @@ impl SyntaxFactory {
         let mut is_escaped = false;
         let name = if tok.base().lexeme.starts_with(ESCAPE_CHAR) {
             is_escaped = true;
-            tok.base().lexeme.chars().skip(1).collect::<String>()
+            self.interner.intern_identifier(&tok.base().lexeme[ESCAPE_CHAR.len_utf8()..])
         }
         else {
-            tok.base().lexeme.clone()
+            self.interner.intern_identifier(tok.base().lexeme)
         };
 
         SimpleIdentifier {
             is_meta_name: false,
             name,
             is_escaped,
-            raw_token: Some(tok),
+            raw_token: Some(tok.id),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
     pub fn qualified_identifier(
-        qualifier: Identifier, op: Operator, name: SimpleIdentifier
-    ) -> QualifiedIdentifier {
-        let start = qualifier.span().unwrap().start;
-        let end = name.span.unwrap().end;
-        let line = qualifier.span().unwrap().start;
+        &self,
+        qualifier: Identifier<'ast>, op: Operator, name: SimpleIdentifier<'ast>
+    ) -> QualifiedIdentifier<'ast> {
+        let start = qualifier.span().unwrap_or_else(SourceSpan::no_location).start;
+        let end = name.span.unwrap_or_else(SourceSpan::no_location).end;
+        let line = qualifier.span().unwrap_or_else(SourceSpan::no_location).start;
 
         QualifiedIdentifier {
             is_meta_name: false,
             qualifier,
             operator: op,
             name,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
-    pub fn literal(tok: Token) -> Literal {
+    pub fn literal(&self, tok: TokenRef) -> Literal<'ast> {
         let start = tok.base().start;
         let end = tok.base().end;
         let line = tok.base().line;
 
+        let delimiters = match tok.token {
+            Token::String(str) => Some(StringDelimiters {
+                string_kind: str.string_kind,
+                delimiter: str.delimiter,
+                count: str.delimiter_count as usize,
+            }),
+            Token::Regular(_) => None,
+        };
+
         Literal {
-            source: tok.base().lexeme.clone(),
-            raw_token: Some(tok),
+            kind: tok.kind(),
+            source: self.arena.alloc_str(tok.base().lexeme),
+            delimiters,
+            raw_token: Some(tok.id),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
     pub fn equals_value_clause(
-        equals_token: Token, values: Vec<Expr>, comma_tokens: Vec<Token>
-    ) -> EqualsValueClause {
-        if values.len() == 0 {
-            panic!("EqualsValueClause must have at least one value.");
-        }
-
+        &self,
+        equals_token: TokenRef, values: Vec<Expr<'ast>>, comma_tokens: Vec<TokenId>
+    ) -> EqualsValueClause<'ast> {
         let start = equals_token.base().start;
-        let end = values.last().unwrap().span().unwrap().end;
+        let end = match values.last() {
+            Some(value) => value.span().unwrap_or_else(SourceSpan::no_location).end,
+            None => equals_token.base().end,
+        };
         let line = equals_token.base().line;
 
         EqualsValueClause {
-            values,
+            values: self.arena.alloc_slice(&values),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            equals_token: Some(equals_token),
-            comma_tokens: Some(comma_tokens),
+            equals_token: Some(equals_token.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
 
-    pub fn type_annotation(colon: Token, ty: TypeNode) -> TypeAnnotation {
+    pub fn type_annotation(&self, colon: TokenRef, ty: TypeNode<'ast>) -> TypeAnnotation<'ast> {
         let start = colon.base().start;
-        let end = ty.span().unwrap().end;
+        let end = ty.span().unwrap_or_else(SourceSpan::no_location).end;
         let line = colon.base().line;
 
         TypeAnnotation {
             ty,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            colon_token: Some(colon),
+            colon_token: Some(colon.id),
         }
     }
 
-    pub fn operator(tok: Token) -> Operator {
+    pub fn operator(&self, tok: TokenRef) -> Operator {
         let start = tok.base().start;
         let end = tok.base().end;
         let line = tok.base().line;
 
-        let kind = match tok.kind() {
-            TokenKind::Plus => OperatorKind::Add,
-            TokenKind::Minus => OperatorKind::Subtract,
-            TokenKind::Asterisk => OperatorKind::Multiply,
-            TokenKind::Slash => OperatorKind::Divide,
-            TokenKind::Tilde => OperatorKind::BitwiseNot,
-            TokenKind::Equal => OperatorKind::Assignment,
-            TokenKind::EqualEqual => OperatorKind::Equals,
-            TokenKind::BangEqual => OperatorKind::NotEquals,
-            TokenKind::TildeTilde => OperatorKind::Like,
-            TokenKind::BangTilde => OperatorKind::NotLike,
-            TokenKind::EqualEqualEqual => OperatorKind::ReferenceEquals,
-            TokenKind::BangEqualEqual => OperatorKind::ReferenceNotEquals,
-            TokenKind::Less => OperatorKind::LessThan,
-            TokenKind::LessEqual => OperatorKind::LessThanOrEqualsTo,
-            TokenKind::Greater => OperatorKind::GreaterThan,
-            TokenKind::GreaterEqual => OperatorKind::GreaterThanOrEqualsTo,
-            TokenKind::KwAnd => OperatorKind::LogicalAnd,
-            TokenKind::KwOr => OperatorKind::LogicalOr,
-            TokenKind::Dot => OperatorKind::MemberAccess,
-            TokenKind::DoubleColon => OperatorKind::ScopeResolution,
-            _ => OperatorKind::Invalid,
-        };
+        let kind = precedence::operator_kind(tok.kind());
 
         Operator {
             kind,
-            raw_token: Some(tok),
+            raw_token: Some(tok.id),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
     pub fn parameter_list(
-        left_paren: Token, params: Vec<Parameter>, right_paren: Token, comma_tokens: Vec<Token>
-    ) -> ParameterList {
+        &self,
+        left_paren: TokenRef, params: Vec<Parameter<'ast>>, right_paren: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> ParameterList<'ast> {
         let start = left_paren.base().start;
         let end = right_paren.base().end;
         let line = left_paren.base().line;
 
         ParameterList {
-            params,
+            params: self.arena.alloc_slice(&params),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_paren_token: Some(left_paren),
-            right_paren_token: Some(right_paren),
-            comma_tokens: Some(comma_tokens),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
 
-    pub fn parameter(decl: RegularLocalDecl, default_val: Option<EqualsValueClause>) -> Parameter {
-        let start = decl.span.unwrap().start;
+    pub fn parameter(
+        &self, decl: RegularLocalDecl<'ast>, default_val: Option<EqualsValueClause<'ast>>
+    ) -> Parameter<'ast> {
+        let start = decl.span.unwrap_or_else(SourceSpan::no_location).start;
         let end = if let Some(evc) = &default_val {
-            evc.span.unwrap().end
+            evc.span.unwrap_or_else(SourceSpan::no_location).end
         }
         else {
-            decl.span.unwrap().end
+            decl.span.unwrap_or_else(SourceSpan::no_location).end
         };
-        let line = decl.span.unwrap().line;
+        let line = decl.span.unwrap_or_else(SourceSpan::no_location).line;
 
         Parameter {
             declarator: decl,
             default_val: default_val,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
     pub fn argument_list(
-        left_paren: Token, args: Vec<Argument>, right_paren: Token, comma_tokens: Vec<Token>
-    ) -> ArgumentList {
+        &self,
+        left_paren: TokenRef, args: Vec<Argument<'ast>>, right_paren: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> ArgumentList<'ast> {
         let start = left_paren.base().start;
         let end = right_paren.base().end;
         let line = left_paren.base().line;
 
         ArgumentList {
-            arguments: args,
+            arguments: self.arena.alloc_slice(&args),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_paren_token: Some(left_paren),
-            right_paren_token: Some(right_paren),
-            comma_tokens: Some(comma_tokens),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
 
-    pub fn argument(expr: Expr) -> Argument {
+    pub fn argument(&self, expr: Expr<'ast>) -> Argument<'ast> {
         let span = expr.span().clone();
         Argument {
             expr,
+            id: self.next_id(),
             span,
         }
     }
 
     pub fn local_declarator(
-        ownership_tok: Option<Token>, name: SimpleIdentifier, ty: Option<TypeAnnotation>
-    ) -> LocalDeclarator {
-        let start = name.span.unwrap().start;
+        &self,
+        ownership_tok: Option<TokenRef>,
+        name: SimpleIdentifier<'ast>,
+        ty: Option<TypeAnnotation<'ast>>,
+    ) -> LocalDeclarator<'ast> {
+        let start = name.span.unwrap_or_else(SourceSpan::no_location).start;
         let end: i64;
-        let line = name.span.unwrap().line;
+        let line = name.span.unwrap_or_else(SourceSpan::no_location).line;
 
         if let Some(ty) = &ty {
-            end = ty.span.unwrap().end;
+            end = ty.span.unwrap_or_else(SourceSpan::no_location).end;
         }
         else {
-            end = name.span.unwrap().end;
+            end = name.span.unwrap_or_else(SourceSpan::no_location).end;
         };
 
         let ownership_kind = get_ownership(&ownership_tok);
@@ -549,49 +771,52 @@ impl SyntaxFactory {
             ownership_kind,
             name,
             type_annotation: ty,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            ownership_token: ownership_tok,
+            ownership_token: ownership_tok.map(|tok| tok.id),
         }
     }
 
-    pub fn field_init(field_name: SimpleIdentifier, initializer: EqualsValueClause) -> FieldInit {
-        let start = field_name.span.unwrap().start;
-        let end = initializer.span.unwrap().end;
-        let line = field_name.span.unwrap().line;
+    pub fn field_init(
+        &self, field_name: SimpleIdentifier<'ast>, initializer: EqualsValueClause<'ast>
+    ) -> FieldInit<'ast> {
+        let start = field_name.span.unwrap_or_else(SourceSpan::no_location).start;
+        let end = initializer.span.unwrap_or_else(SourceSpan::no_location).end;
+        let line = field_name.span.unwrap_or_else(SourceSpan::no_location).line;
 
         FieldInit {
             field_name,
             initializer,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
         }
     }
 
     pub fn object_initializer(
-        left_bracket: Token, field_inits: Vec<FieldInit>, right_bracket: Token, comma_tokens: Vec<Token>
-    ) -> ObjectInitializer {
+        &self,
+        left_bracket: TokenRef, field_inits: Vec<FieldInit<'ast>>, right_bracket: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> ObjectInitializer<'ast> {
         let start = left_bracket.base().start;
         let end = right_bracket.base().end;
         let line = left_bracket.base().line;
 
         ObjectInitializer {
-            field_inits,
+            field_inits: self.arena.alloc_slice(&field_inits),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_bracket_token: Some(left_bracket),
-            right_bracket_token: Some(right_bracket),
-            comma_tokens: Some(comma_tokens),
+            left_bracket_token: Some(left_bracket.id),
+            right_bracket_token: Some(right_bracket.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
     // endregion Fragments
 
     //region Type nodes
     pub fn type_node(
-        ownership_tok: Option<Token>, ty: PartialType, nullable_tok: Option<Token>
-    ) -> TypeNode {
-        let is_nullable = match &nullable_tok {
-            Some(tok) if tok.kind() == TokenKind::QuestionMark => true,
-            None => false,
-            _ => panic!("'nullable_tok' can only be None or '?'."),
-        };
+        &self,
+        ownership_tok: Option<TokenRef>, ty: PartialType<'ast>, nullable_tok: Option<TokenRef>
+    ) -> TypeNode<'ast> {
+        let is_nullable = nullable_tok.is_some_and(|tok| tok.kind() == TokenKind::QuestionMark);
 
         let ownership_kind = get_ownership(&ownership_tok);
 
@@ -599,45 +824,50 @@ impl SyntaxFactory {
             is_nullable,
             ownership_kind,
             ty,
-            nullable_token: nullable_tok,
-            ownership_token: ownership_tok,
+            nullable_token: nullable_tok.map(|tok| tok.id),
+            ownership_token: ownership_tok.map(|tok| tok.id),
         }
     }
 
-    pub fn identifier_type(id: Identifier) -> IdentifierType {
-        let span = id.span().unwrap().clone();
+    pub fn identifier_type(&self, id: Identifier<'ast>) -> IdentifierType<'ast> {
+        let span = id.span().unwrap_or_else(SourceSpan::no_location);
 
         IdentifierType {
             name: id,
+            id: self.next_id(),
             span: Some(span),
         }
     }
 
-    pub fn group_type(left_paren: Token, ty: TypeNode, right_paren: Token) -> GroupType {
+    pub fn group_type(
+        &self, left_paren: TokenRef, ty: TypeNode<'ast>, right_paren: TokenRef
+    ) -> GroupType<'ast> {
         let start = left_paren.base().start;
         let end = right_paren.base().end;
         let line = left_paren.base().line;
 
         GroupType {
             ty,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_paren_token: Some(left_paren),
-            right_paren_token: Some(right_paren),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
         }
     }
 
     pub fn function_type(
-        ss: Option<Token>,
-        except: Option<Token>,
-        left_paren: Token,
-        param_types: Vec<TypeNode>,
-        right_paren: Token,
-        param_comma_tokens: Vec<Token>,
-        return_annotation: Token,
-        return_type: TypeNode
-    ) -> FunctionType {
+        &self,
+        ss: Option<TokenRef>,
+        except: Option<TokenRef>,
+        left_paren: TokenRef,
+        param_types: Vec<TypeNode<'ast>>,
+        right_paren: TokenRef,
+        param_comma_tokens: Vec<TokenId>,
+        return_annotation: TokenRef,
+        return_type: TypeNode<'ast>
+    ) -> FunctionType<'ast> {
         let start: i64;
-        let end = return_type.span().unwrap().end;
+        let end = return_type.span().unwrap_or_else(SourceSpan::no_location).end;
         let line: i64;
 
         if let Some(tok) = &ss {
@@ -661,151 +891,130 @@ impl SyntaxFactory {
             Some(tok) if tok.base().lexeme == "s" => { is_send = true; is_sync = false; },
             Some(tok) if tok.base().lexeme == "S" => { is_send = false; is_sync = true; },
             Some(tok) if tok.base().lexeme == "sS" => { is_send = true; is_sync = true; },
-            None => { is_send = false; is_sync = false; },
-            _ => panic!("ss token can only contain None, 's', 'sS' or 'S'."),
+            _ => { is_send = false; is_sync = false; },
         }
 
-        match &except {
-            Some(tok) if tok.kind() == TokenKind::Bang => has_exception = true,
-            None => has_exception = false,
-            _ => panic!("except token can only contain None or '!'."),
-        }
+        has_exception = except.is_some_and(|tok| tok.kind() == TokenKind::Bang);
 
         FunctionType {
-            param_types,
+            param_types: self.arena.alloc_slice(&param_types),
             return_type,
             is_send,
             is_sync,
             has_exception,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            ss_token: ss,
-            exception_mark_token: except,
-            left_paren_token: Some(left_paren),
-            right_paren_token: Some(right_paren),
-            param_comma_tokens: Some(param_comma_tokens),
-            return_annotation_token: Some(return_annotation),
+            ss_token: ss.map(|tok| tok.id),
+            exception_mark_token: except.map(|tok| tok.id),
+            left_paren_token: Some(left_paren.id),
+            right_paren_token: Some(right_paren.id),
+            param_comma_tokens: Some(self.arena.alloc_slice(&param_comma_tokens)),
+            return_annotation_token: Some(return_annotation.id),
         }
     }
 
     pub fn tuple_array_type(
-        left_bracket: Token, member_types: Vec<TypeNode>, right_bracket: Token, comma_tokens: Vec<Token>
-    ) -> TupleArrayType {
+        &self,
+        left_bracket: TokenRef, member_types: Vec<TypeNode<'ast>>, right_bracket: TokenRef, comma_tokens: Vec<TokenId>
+    ) -> TupleArrayType<'ast> {
         let start = left_bracket.base().start;
         let end = right_bracket.base().end;
         let line = left_bracket.base().line;
 
         TupleArrayType {
-            member_types,
+            member_types: self.arena.alloc_slice(&member_types),
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_square_bracket_token: Some(left_bracket),
-            right_square_bracket_token: Some(right_bracket),
-            comma_tokens: Some(comma_tokens),
+            left_square_bracket_token: Some(left_bracket.id),
+            right_square_bracket_token: Some(right_bracket.id),
+            comma_tokens: Some(self.arena.alloc_slice(&comma_tokens)),
         }
     }
 
     pub fn raw_array_type(
-        member_type: TypeNode, left_bracket: Token, len: Expr, right_bracket: Token
-    ) -> RawArrayType {
-        let start = member_type.span().unwrap().start;
+        &self,
+        member_type: TypeNode<'ast>, left_bracket: TokenRef, len: Expr<'ast>, right_bracket: TokenRef
+    ) -> RawArrayType<'ast> {
+        let start = member_type.span().unwrap_or_else(SourceSpan::no_location).start;
         let end = right_bracket.base().end;
-        let line = member_type.span().unwrap().start;
+        let line = member_type.span().unwrap_or_else(SourceSpan::no_location).start;
 
         RawArrayType {
             member_type,
             length: len,
+            id: self.next_id(),
             span: Some(SourceSpan { start, end, line }),
-            left_square_bracket_token: Some(left_bracket),
-            right_square_bracket_token: Some(right_bracket),
+            left_square_bracket_token: Some(left_bracket.id),
+            right_square_bracket_token: Some(right_bracket.id),
         }
     }
 
-    pub fn literal_type(lit: Literal) -> LiteralType {
-        let span = lit.span.unwrap().clone();
+    pub fn literal_type(&self, lit: Literal<'ast>) -> LiteralType<'ast> {
+        let span = lit.span.unwrap_or_else(SourceSpan::no_location);
 
         LiteralType {
             literal: lit,
+            id: self.next_id(),
             span: Some(span),
         }
     }
 
-    pub fn sum_type(member_types: Vec<TypeNode>, or_tokens: Vec<Token>) -> SumType {
-        if member_types.len() == 0 {
-            panic!("A sum type must contain, at least, one type.");
+    pub fn sum_type(
+        &self, member_types: Vec<TypeNode<'ast>>, or_tokens: Vec<TokenId>
+    ) -> SumType<'ast> {
+        let span = match (member_types.first(), member_types.last()) {
+            (Some(first), Some(last)) => {
+                let first = first.span().unwrap_or_else(SourceSpan::no_location);
+                let last = last.span().unwrap_or_else(SourceSpan::no_location);
+                Some(SourceSpan { start: first.start, end: last.end, line: first.line })
+            }
+            _ => None,
         };
 
-        let start = member_types.first().unwrap().span().unwrap().start;
-        let end = member_types.last().unwrap().span().unwrap().end;
-        let line = member_types.first().unwrap().span().unwrap().line;
-
         SumType {
-            member_types,
-            span: Some(SourceSpan { start, end, line }),
-            or_tokens: Some(or_tokens),
+            member_types: self.arena.alloc_slice(&member_types),
+            id: self.next_id(),
+            span,
+            or_tokens: Some(self.arena.alloc_slice(&or_tokens)),
         }
     }
 
-    pub fn product_type(member_types: Vec<TypeNode>, and_tokens: Vec<Token>) -> ProductType {
-        if member_types.len() == 0 {
-            panic!("A product type must contain, at least, one type.");
+    pub fn product_type(
+        &self, member_types: Vec<TypeNode<'ast>>, and_tokens: Vec<TokenId>
+    ) -> ProductType<'ast> {
+        let span = match (member_types.first(), member_types.last()) {
+            (Some(first), Some(last)) => {
+                let first = first.span().unwrap_or_else(SourceSpan::no_location);
+                let last = last.span().unwrap_or_else(SourceSpan::no_location);
+                Some(SourceSpan { start: first.start, end: last.end, line: first.line })
+            }
+            _ => None,
         };
 
-        let start = member_types.first().unwrap().span().unwrap().start;
-        let end = member_types.last().unwrap().span().unwrap().end;
-        let line = member_types.first().unwrap().span().unwrap().line;
-
         ProductType {
-            member_types,
-            span: Some(SourceSpan { start, end, line }),
-            and_tokens: Some(and_tokens),
+            member_types: self.arena.alloc_slice(&member_types),
+            id: self.next_id(),
+            span,
+            and_tokens: Some(self.arena.alloc_slice(&and_tokens)),
         }
     }
     //endregion Type nodes
 
-    pub fn error_node() -> ErrorNode {
+    pub fn error_node(&self) -> ErrorNode {
         ErrorNode {
+            id: self.next_id(),
             span: Some(SourceSpan { start: -1, end: -1, line: -1 }), // TODO
         }
     }
 }
 
-fn get_ownership(tok: &Option<Token>) -> OwnershipKind {
+fn get_ownership(tok: &Option<TokenRef>) -> OwnershipKind {
     match &tok {
         Some(tok) if tok.kind() == TokenKind::KwFinal => OwnershipKind::Final,
         Some(tok) if tok.kind() == TokenKind::KwMut => OwnershipKind::Mutable,
         Some(tok) if tok.kind() == TokenKind::KwIn => OwnershipKind::In,
         Some(tok) if tok.kind() == TokenKind::KwSh => OwnershipKind::Shared,
         Some(tok) if tok.kind() == TokenKind::KwRef => OwnershipKind::Reference,
-        None => OwnershipKind::None,
-        _ => panic!("Ownership token can only be None, 'final', 'mut', 'in', 'shared' or 'ref'."),
+        _ => OwnershipKind::None,
     }
 }
-
-/// Checks the token kinds given and panics if they don't form a pair. Valid pairs:
-/// * `(` and `)`.
-/// * `[` and `]`.
-/// * `{` and `}`.
-/// * `<` and `>` only when they are `LeftAngleBracket` and `RightAngleBracket`.
-fn panic_when_invalid_pair(left: &Token, right: &Token) {
-    if left.kind() == TokenKind::LeftParen {
-        if right.kind() == TokenKind::RightParen {
-            return;
-        }
-    }
-    if left.kind() == TokenKind::LeftSquareBracket {
-        if right.kind() == TokenKind::RightSquareBracket {
-            return;
-        }
-    }
-    if left.kind() == TokenKind::LeftCurlyBracket {
-        if right.kind() == TokenKind::RightCurlyBracket {
-            return;
-        }
-    }
-    if left.kind() == TokenKind::LeftAngleBracket {
-        if right.kind() == TokenKind::RightAngleBracket {
-            return;
-        }
-    }
-
-    panic!("Invalid token pair.");
-}
\ No newline at end of file
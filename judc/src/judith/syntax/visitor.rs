@@ -0,0 +1,820 @@
+use crate::judith::syntax::nodes::*;
+
+/// Walks a syntax tree. Each `visit_*` method is called on a node of its type and, by default,
+/// calls the `walk_*` function of the same name, which visits the children of that node. To act
+/// on some kind of node, override its method and call the `walk_*` function from it to keep
+/// walking into its children (or don't, to skip them).
+pub trait Visitor<'a> {
+    fn visit_node(&mut self, node: &'a SyntaxNode<'a>) {
+        walk_node(self, node);
+    }
+
+    // region Items
+    fn visit_item(&mut self, item: &'a Item<'a>) {
+        walk_item(self, item);
+    }
+
+    fn visit_func_def(&mut self, def: &'a FuncDef<'a>) {
+        walk_func_def(self, def);
+    }
+    // endregion Items
+
+    // region Bodies
+    fn visit_body(&mut self, body: &'a Body<'a>) {
+        walk_body(self, body);
+    }
+
+    fn visit_block_body(&mut self, body: &'a BlockBody<'a>) {
+        walk_block_body(self, body);
+    }
+
+    fn visit_arrow_body(&mut self, body: &'a ArrowBody<'a>) {
+        walk_arrow_body(self, body);
+    }
+
+    fn visit_expr_body(&mut self, body: &'a ExprBody<'a>) {
+        walk_expr_body(self, body);
+    }
+    // endregion Bodies
+
+    // region Statements
+    fn visit_stmt(&mut self, stmt: &'a Stmt<'a>) {
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr_stmt(&mut self, stmt: &'a ExprStmt<'a>) {
+        walk_expr_stmt(self, stmt);
+    }
+
+    fn visit_local_decl_stmt(&mut self, stmt: &'a LocalDeclStmt<'a>) {
+        walk_local_decl_stmt(self, stmt);
+    }
+
+    fn visit_priv_print_stmt(&mut self, stmt: &'a PrivPrintStmt<'a>) {
+        walk_priv_print_stmt(self, stmt);
+    }
+
+    fn visit_partial_local_decl(&mut self, decl: &'a PartialLocalDecl<'a>) {
+        walk_partial_local_decl(self, decl);
+    }
+
+    fn visit_regular_local_decl(&mut self, decl: &'a RegularLocalDecl<'a>) {
+        walk_regular_local_decl(self, decl);
+    }
+
+    fn visit_destructured_local_decl(&mut self, decl: &'a DestructuredLocalDecl<'a>) {
+        walk_destructured_local_decl(self, decl);
+    }
+    // endregion Statements
+
+    // region Expressions
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_if_expr(&mut self, expr: &'a IfExpr<'a>) {
+        walk_if_expr(self, expr);
+    }
+
+    fn visit_loop_expr(&mut self, expr: &'a LoopExpr<'a>) {
+        walk_loop_expr(self, expr);
+    }
+
+    fn visit_while_expr(&mut self, expr: &'a WhileExpr<'a>) {
+        walk_while_expr(self, expr);
+    }
+
+    fn visit_assignment_expr(&mut self, expr: &'a AssignmentExpr<'a>) {
+        walk_assignment_expr(self, expr);
+    }
+
+    fn visit_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) {
+        walk_binary_expr(self, expr);
+    }
+
+    fn visit_left_unary_expr(&mut self, expr: &'a LeftUnaryExpr<'a>) {
+        walk_left_unary_expr(self, expr);
+    }
+
+    fn visit_right_unary_expr(&mut self, expr: &'a RightUnaryExpr<'a>) {
+        walk_right_unary_expr(self, expr);
+    }
+
+    fn visit_group_expr(&mut self, expr: &'a GroupExpr<'a>) {
+        walk_group_expr(self, expr);
+    }
+
+    fn visit_tuple_expr(&mut self, expr: &'a TupleExpr<'a>) {
+        walk_tuple_expr(self, expr);
+    }
+
+    fn visit_array_expr(&mut self, expr: &'a ArrayExpr<'a>) {
+        walk_array_expr(self, expr);
+    }
+
+    fn visit_empty_collection_expr(&mut self, _expr: &'a EmptyCollectionExpr) {}
+
+    fn visit_object_init_expr(&mut self, expr: &'a ObjectInitExpr<'a>) {
+        walk_object_init_expr(self, expr);
+    }
+
+    fn visit_access_expr(&mut self, expr: &'a AccessExpr<'a>) {
+        walk_access_expr(self, expr);
+    }
+
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        walk_call_expr(self, expr);
+    }
+
+    fn visit_index_expr(&mut self, expr: &'a IndexExpr<'a>) {
+        walk_index_expr(self, expr);
+    }
+
+    fn visit_lambda_expr(&mut self, expr: &'a LambdaExpr<'a>) {
+        walk_lambda_expr(self, expr);
+    }
+
+    fn visit_identifier_expr(&mut self, expr: &'a IdentifierExpr<'a>) {
+        walk_identifier_expr(self, expr);
+    }
+
+    fn visit_literal_expr(&mut self, expr: &'a LiteralExpr<'a>) {
+        walk_literal_expr(self, expr);
+    }
+
+    /// Visits an error node, in any position (node, statement, expression or type).
+    fn visit_error_node(&mut self, _node: &'a ErrorNode) {}
+    // endregion Expressions
+
+    // region Fragments
+    fn visit_identifier(&mut self, identifier: &'a Identifier<'a>) {
+        walk_identifier(self, identifier);
+    }
+
+    fn visit_simple_identifier(&mut self, _identifier: &'a SimpleIdentifier<'a>) {}
+
+    fn visit_qualified_identifier(&mut self, identifier: &'a QualifiedIdentifier<'a>) {
+        walk_qualified_identifier(self, identifier);
+    }
+
+    fn visit_literal(&mut self, _literal: &'a Literal<'a>) {}
+
+    fn visit_operator(&mut self, _operator: &'a Operator) {}
+
+    fn visit_equals_value_clause(&mut self, clause: &'a EqualsValueClause<'a>) {
+        walk_equals_value_clause(self, clause);
+    }
+
+    fn visit_type_annotation(&mut self, annotation: &'a TypeAnnotation<'a>) {
+        walk_type_annotation(self, annotation);
+    }
+
+    fn visit_parameter_list(&mut self, params: &'a ParameterList<'a>) {
+        walk_parameter_list(self, params);
+    }
+
+    fn visit_parameter(&mut self, param: &'a Parameter<'a>) {
+        walk_parameter(self, param);
+    }
+
+    fn visit_argument_list(&mut self, args: &'a ArgumentList<'a>) {
+        walk_argument_list(self, args);
+    }
+
+    fn visit_argument(&mut self, arg: &'a Argument<'a>) {
+        walk_argument(self, arg);
+    }
+
+    fn visit_local_declarator(&mut self, declarator: &'a LocalDeclarator<'a>) {
+        walk_local_declarator(self, declarator);
+    }
+
+    fn visit_field_init(&mut self, field_init: &'a FieldInit<'a>) {
+        walk_field_init(self, field_init);
+    }
+
+    fn visit_object_initializer(&mut self, initializer: &'a ObjectInitializer<'a>) {
+        walk_object_initializer(self, initializer);
+    }
+    // endregion Fragments
+
+    // region Type nodes
+    fn visit_type_node(&mut self, ty: &'a TypeNode<'a>) {
+        walk_type_node(self, ty);
+    }
+
+    fn visit_partial_type(&mut self, ty: &'a PartialType<'a>) {
+        walk_partial_type(self, ty);
+    }
+
+    fn visit_identifier_type(&mut self, ty: &'a IdentifierType<'a>) {
+        walk_identifier_type(self, ty);
+    }
+
+    fn visit_group_type(&mut self, ty: &'a GroupType<'a>) {
+        walk_group_type(self, ty);
+    }
+
+    fn visit_function_type(&mut self, ty: &'a FunctionType<'a>) {
+        walk_function_type(self, ty);
+    }
+
+    fn visit_tuple_array_type(&mut self, ty: &'a TupleArrayType<'a>) {
+        walk_tuple_array_type(self, ty);
+    }
+
+    fn visit_raw_array_type(&mut self, ty: &'a RawArrayType<'a>) {
+        walk_raw_array_type(self, ty);
+    }
+
+    fn visit_literal_type(&mut self, ty: &'a LiteralType<'a>) {
+        walk_literal_type(self, ty);
+    }
+
+    fn visit_sum_type(&mut self, ty: &'a SumType<'a>) {
+        walk_sum_type(self, ty);
+    }
+
+    fn visit_product_type(&mut self, ty: &'a ProductType<'a>) {
+        walk_product_type(self, ty);
+    }
+    // endregion Type nodes
+}
+
+pub fn walk_node<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, node: &'a SyntaxNode<'a>) {
+    match node {
+        SyntaxNode::Item(item) => visitor.visit_item(item),
+        SyntaxNode::Stmt(stmt) => visitor.visit_stmt(stmt),
+        SyntaxNode::Expr(expr) => visitor.visit_expr(expr),
+        SyntaxNode::Error(err) => visitor.visit_error_node(err),
+    }
+}
+
+// region Items
+pub fn walk_item<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, item: &'a Item<'a>) {
+    match item {
+        Item::FuncDef(def) => visitor.visit_func_def(def),
+    }
+}
+
+pub fn walk_func_def<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, def: &'a FuncDef<'a>) {
+    visitor.visit_simple_identifier(&def.name);
+    visitor.visit_parameter_list(&def.params);
+    if let Some(return_type) = &def.return_type {
+        visitor.visit_type_node(return_type);
+    }
+    visitor.visit_body(&def.body);
+}
+// endregion Items
+
+// region Bodies
+pub fn walk_body<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, body: &'a Body<'a>) {
+    match body {
+        Body::Block(body) => visitor.visit_block_body(body),
+        Body::Arrow(body) => visitor.visit_arrow_body(body),
+        Body::Expr(body) => visitor.visit_expr_body(body),
+    }
+}
+
+pub fn walk_block_body<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, body: &'a BlockBody<'a>) {
+    for node in body.nodes {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_arrow_body<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, body: &'a ArrowBody<'a>) {
+    visitor.visit_expr(&body.expr);
+}
+
+pub fn walk_expr_body<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, body: &'a ExprBody<'a>) {
+    visitor.visit_expr(&body.expr);
+}
+// endregion Bodies
+
+// region Statements
+pub fn walk_stmt<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, stmt: &'a Stmt<'a>) {
+    match stmt {
+        Stmt::Expr(stmt) => visitor.visit_expr_stmt(stmt),
+        Stmt::LocalDecl(stmt) => visitor.visit_local_decl_stmt(stmt),
+        Stmt::PrivPrint(stmt) => visitor.visit_priv_print_stmt(stmt),
+        Stmt::Error(err) => visitor.visit_error_node(err),
+    }
+}
+
+pub fn walk_expr_stmt<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, stmt: &'a ExprStmt<'a>) {
+    visitor.visit_expr(&stmt.expr);
+}
+
+pub fn walk_local_decl_stmt<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, stmt: &'a LocalDeclStmt<'a>
+) {
+    for decl in stmt.declarators {
+        visitor.visit_partial_local_decl(decl);
+    }
+    if let Some(initializer) = &stmt.initializer {
+        visitor.visit_equals_value_clause(initializer);
+    }
+}
+
+pub fn walk_priv_print_stmt<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, stmt: &'a PrivPrintStmt<'a>
+) {
+    visitor.visit_expr(&stmt.expr);
+}
+
+pub fn walk_partial_local_decl<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, decl: &'a PartialLocalDecl<'a>
+) {
+    match decl {
+        PartialLocalDecl::Regular(decl) => visitor.visit_regular_local_decl(decl),
+        PartialLocalDecl::Destructured(decl) => visitor.visit_destructured_local_decl(decl),
+    }
+}
+
+pub fn walk_regular_local_decl<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, decl: &'a RegularLocalDecl<'a>
+) {
+    visitor.visit_local_declarator(&decl.declarator);
+}
+
+pub fn walk_destructured_local_decl<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, decl: &'a DestructuredLocalDecl<'a>
+) {
+    for declarator in decl.declarators {
+        visitor.visit_local_declarator(declarator);
+    }
+}
+// endregion Statements
+
+// region Expressions
+pub fn walk_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a Expr<'a>) {
+    match expr {
+        Expr::If(expr) => visitor.visit_if_expr(expr),
+        Expr::Loop(expr) => visitor.visit_loop_expr(expr),
+        Expr::While(expr) => visitor.visit_while_expr(expr),
+        Expr::Assignment(expr) => visitor.visit_assignment_expr(expr),
+        Expr::Binary(expr) => visitor.visit_binary_expr(expr),
+        Expr::LeftUnary(expr) => visitor.visit_left_unary_expr(expr),
+        Expr::RightUnary(expr) => visitor.visit_right_unary_expr(expr),
+        Expr::Group(expr) => visitor.visit_group_expr(expr),
+        Expr::Tuple(expr) => visitor.visit_tuple_expr(expr),
+        Expr::Array(expr) => visitor.visit_array_expr(expr),
+        Expr::EmptyCollection(expr) => visitor.visit_empty_collection_expr(expr),
+        Expr::ObjectInit(expr) => visitor.visit_object_init_expr(expr),
+        Expr::Access(expr) => visitor.visit_access_expr(expr),
+        Expr::Call(expr) => visitor.visit_call_expr(expr),
+        Expr::Index(expr) => visitor.visit_index_expr(expr),
+        Expr::Lambda(expr) => visitor.visit_lambda_expr(expr),
+        Expr::Identifier(expr) => visitor.visit_identifier_expr(expr),
+        Expr::Literal(expr) => visitor.visit_literal_expr(expr),
+        Expr::Error(err) => visitor.visit_error_node(err),
+    }
+}
+
+pub fn walk_if_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a IfExpr<'a>) {
+    visitor.visit_expr(&expr.test);
+    visitor.visit_body(&expr.consequent);
+    if let Some(alternate) = &expr.alternate {
+        visitor.visit_body(alternate);
+    }
+}
+
+pub fn walk_loop_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a LoopExpr<'a>) {
+    visitor.visit_body(&expr.body);
+}
+
+pub fn walk_while_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a WhileExpr<'a>) {
+    visitor.visit_expr(&expr.test);
+    visitor.visit_body(&expr.body);
+}
+
+pub fn walk_assignment_expr<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, expr: &'a AssignmentExpr<'a>
+) {
+    visitor.visit_expr(&expr.left);
+    visitor.visit_operator(&expr.operator);
+    visitor.visit_expr(&expr.right);
+}
+
+pub fn walk_binary_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a BinaryExpr<'a>) {
+    visitor.visit_expr(&expr.left);
+    visitor.visit_operator(&expr.operator);
+    visitor.visit_expr(&expr.right);
+}
+
+pub fn walk_left_unary_expr<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, expr: &'a LeftUnaryExpr<'a>
+) {
+    visitor.visit_operator(&expr.operator);
+    visitor.visit_expr(&expr.expr);
+}
+
+pub fn walk_right_unary_expr<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, expr: &'a RightUnaryExpr<'a>
+) {
+    visitor.visit_expr(&expr.expr);
+    visitor.visit_operator(&expr.operator);
+}
+
+pub fn walk_group_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a GroupExpr<'a>) {
+    visitor.visit_expr(&expr.expr);
+}
+
+pub fn walk_tuple_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a TupleExpr<'a>) {
+    for element in expr.elements {
+        visitor.visit_expr(element);
+    }
+}
+
+pub fn walk_array_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a ArrayExpr<'a>) {
+    for element in expr.elements {
+        visitor.visit_expr(element);
+    }
+}
+
+pub fn walk_object_init_expr<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, expr: &'a ObjectInitExpr<'a>
+) {
+    if let Some(provider) = &expr.provider {
+        visitor.visit_expr(provider);
+    }
+    visitor.visit_object_initializer(&expr.initializer);
+}
+
+pub fn walk_access_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a AccessExpr<'a>) {
+    if let Some(receiver) = &expr.receiver {
+        visitor.visit_expr(receiver);
+    }
+    visitor.visit_operator(&expr.operator);
+    visitor.visit_simple_identifier(&expr.member);
+}
+
+pub fn walk_call_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a CallExpr<'a>) {
+    visitor.visit_expr(&expr.callee);
+    visitor.visit_argument_list(&expr.arguments);
+}
+
+pub fn walk_index_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a IndexExpr<'a>) {
+    visitor.visit_expr(&expr.receiver);
+    visitor.visit_expr(&expr.index);
+}
+
+pub fn walk_lambda_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a LambdaExpr<'a>) {
+    visitor.visit_parameter_list(&expr.params);
+    if let Some(return_type) = &expr.return_type {
+        visitor.visit_type_node(return_type);
+    }
+    visitor.visit_body(&expr.body);
+}
+
+pub fn walk_identifier_expr<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, expr: &'a IdentifierExpr<'a>
+) {
+    visitor.visit_identifier(&expr.identifier);
+}
+
+pub fn walk_literal_expr<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &'a LiteralExpr<'a>) {
+    visitor.visit_literal(&expr.literal);
+}
+// endregion Expressions
+
+// region Fragments
+pub fn walk_identifier<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, identifier: &'a Identifier<'a>
+) {
+    match identifier {
+        Identifier::Simple(identifier) => visitor.visit_simple_identifier(identifier),
+        Identifier::Qualified(identifier) => visitor.visit_qualified_identifier(identifier),
+    }
+}
+
+pub fn walk_qualified_identifier<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, identifier: &'a QualifiedIdentifier<'a>
+) {
+    visitor.visit_identifier(&identifier.qualifier);
+    visitor.visit_operator(&identifier.operator);
+    visitor.visit_simple_identifier(&identifier.name);
+}
+
+pub fn walk_equals_value_clause<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, clause: &'a EqualsValueClause<'a>
+) {
+    for value in clause.values {
+        visitor.visit_expr(value);
+    }
+}
+
+pub fn walk_type_annotation<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, annotation: &'a TypeAnnotation<'a>
+) {
+    visitor.visit_type_node(&annotation.ty);
+}
+
+pub fn walk_parameter_list<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, params: &'a ParameterList<'a>
+) {
+    for param in params.params {
+        visitor.visit_parameter(param);
+    }
+}
+
+pub fn walk_parameter<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, param: &'a Parameter<'a>) {
+    visitor.visit_regular_local_decl(&param.declarator);
+    if let Some(default_val) = &param.default_val {
+        visitor.visit_equals_value_clause(default_val);
+    }
+}
+
+pub fn walk_argument_list<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, args: &'a ArgumentList<'a>
+) {
+    for arg in args.arguments {
+        visitor.visit_argument(arg);
+    }
+}
+
+pub fn walk_argument<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, arg: &'a Argument<'a>) {
+    visitor.visit_expr(&arg.expr);
+}
+
+pub fn walk_local_declarator<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, declarator: &'a LocalDeclarator<'a>
+) {
+    visitor.visit_simple_identifier(&declarator.name);
+    if let Some(annotation) = &declarator.type_annotation {
+        visitor.visit_type_annotation(annotation);
+    }
+}
+
+pub fn walk_field_init<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, field_init: &'a FieldInit<'a>
+) {
+    visitor.visit_simple_identifier(&field_init.field_name);
+    visitor.visit_equals_value_clause(&field_init.initializer);
+}
+
+pub fn walk_object_initializer<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, initializer: &'a ObjectInitializer<'a>
+) {
+    for field_init in initializer.field_inits {
+        visitor.visit_field_init(field_init);
+    }
+}
+// endregion Fragments
+
+// region Type nodes
+pub fn walk_type_node<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a TypeNode<'a>) {
+    visitor.visit_partial_type(&ty.ty);
+}
+
+pub fn walk_partial_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a PartialType<'a>) {
+    match ty {
+        PartialType::Identifier(ty) => visitor.visit_identifier_type(ty),
+        PartialType::Group(ty) => visitor.visit_group_type(ty),
+        PartialType::Function(ty) => visitor.visit_function_type(ty),
+        PartialType::TupleArray(ty) => visitor.visit_tuple_array_type(ty),
+        PartialType::RawArray(ty) => visitor.visit_raw_array_type(ty),
+        PartialType::Literal(ty) => visitor.visit_literal_type(ty),
+        PartialType::Sum(ty) => visitor.visit_sum_type(ty),
+        PartialType::Product(ty) => visitor.visit_product_type(ty),
+        PartialType::Error(err) => visitor.visit_error_node(err),
+    }
+}
+
+pub fn walk_identifier_type<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, ty: &'a IdentifierType<'a>
+) {
+    visitor.visit_identifier(&ty.name);
+}
+
+pub fn walk_group_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a GroupType<'a>) {
+    visitor.visit_type_node(&ty.ty);
+}
+
+pub fn walk_function_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a FunctionType<'a>) {
+    for param_type in ty.param_types {
+        visitor.visit_type_node(param_type);
+    }
+    visitor.visit_type_node(&ty.return_type);
+}
+
+pub fn walk_tuple_array_type<'a, V: Visitor<'a> + ?Sized>(
+    visitor: &mut V, ty: &'a TupleArrayType<'a>
+) {
+    for member_type in ty.member_types {
+        visitor.visit_type_node(member_type);
+    }
+}
+
+pub fn walk_raw_array_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a RawArrayType<'a>) {
+    visitor.visit_type_node(&ty.member_type);
+    visitor.visit_expr(&ty.length);
+}
+
+pub fn walk_literal_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a LiteralType<'a>) {
+    visitor.visit_literal(&ty.literal);
+}
+
+pub fn walk_sum_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a SumType<'a>) {
+    for member_type in ty.member_types {
+        visitor.visit_type_node(member_type);
+    }
+}
+
+pub fn walk_product_type<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, ty: &'a ProductType<'a>) {
+    for member_type in ty.member_types {
+        visitor.visit_type_node(member_type);
+    }
+}
+// endregion Type nodes
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use serde_json::Value;
+    use crate::judith::lexical::lexer::tokenize;
+    use crate::judith::syntax::arena::Arena;
+    use crate::judith::syntax::parser::parse;
+    use super::*;
+
+    /// Counts each node visited, by type.
+    #[derive(Default)]
+    struct NodeCounter {
+        counts: HashMap<&'static str, usize>,
+    }
+
+    impl NodeCounter {
+        fn add(&mut self, kind: &'static str) {
+            *self.counts.entry(kind).or_default() += 1;
+        }
+
+        fn total(&self) -> usize {
+            self.counts.values().sum()
+        }
+    }
+
+    macro_rules! count {
+        ($($visit:ident($ty:ident) $(=> $walk:ident)?;)*) => {
+            $(
+                fn $visit(&mut self, _node: &'a $ty) {
+                    self.add(stringify!($ty));
+                    $($walk(self, _node);)?
+                }
+            )*
+        };
+    }
+
+    impl<'a> Visitor<'a> for NodeCounter {
+        count! {
+            visit_func_def(FuncDef) => walk_func_def;
+            visit_block_body(BlockBody) => walk_block_body;
+            visit_arrow_body(ArrowBody) => walk_arrow_body;
+            visit_expr_body(ExprBody) => walk_expr_body;
+            visit_expr_stmt(ExprStmt) => walk_expr_stmt;
+            visit_local_decl_stmt(LocalDeclStmt) => walk_local_decl_stmt;
+            visit_priv_print_stmt(PrivPrintStmt) => walk_priv_print_stmt;
+            visit_regular_local_decl(RegularLocalDecl) => walk_regular_local_decl;
+            visit_destructured_local_decl(DestructuredLocalDecl) => walk_destructured_local_decl;
+            visit_if_expr(IfExpr) => walk_if_expr;
+            visit_loop_expr(LoopExpr) => walk_loop_expr;
+            visit_while_expr(WhileExpr) => walk_while_expr;
+            visit_assignment_expr(AssignmentExpr) => walk_assignment_expr;
+            visit_binary_expr(BinaryExpr) => walk_binary_expr;
+            visit_left_unary_expr(LeftUnaryExpr) => walk_left_unary_expr;
+            visit_right_unary_expr(RightUnaryExpr) => walk_right_unary_expr;
+            visit_group_expr(GroupExpr) => walk_group_expr;
+            visit_tuple_expr(TupleExpr) => walk_tuple_expr;
+            visit_array_expr(ArrayExpr) => walk_array_expr;
+            visit_empty_collection_expr(EmptyCollectionExpr);
+            visit_object_init_expr(ObjectInitExpr) => walk_object_init_expr;
+            visit_access_expr(AccessExpr) => walk_access_expr;
+            visit_call_expr(CallExpr) => walk_call_expr;
+            visit_index_expr(IndexExpr) => walk_index_expr;
+            visit_lambda_expr(LambdaExpr) => walk_lambda_expr;
+            visit_identifier_expr(IdentifierExpr) => walk_identifier_expr;
+            visit_literal_expr(LiteralExpr) => walk_literal_expr;
+            visit_error_node(ErrorNode);
+            visit_simple_identifier(SimpleIdentifier);
+            visit_qualified_identifier(QualifiedIdentifier) => walk_qualified_identifier;
+            visit_literal(Literal);
+            visit_operator(Operator);
+            visit_equals_value_clause(EqualsValueClause) => walk_equals_value_clause;
+            visit_type_annotation(TypeAnnotation) => walk_type_annotation;
+            visit_parameter_list(ParameterList) => walk_parameter_list;
+            visit_parameter(Parameter) => walk_parameter;
+            visit_argument_list(ArgumentList) => walk_argument_list;
+            visit_argument(Argument) => walk_argument;
+            visit_local_declarator(LocalDeclarator) => walk_local_declarator;
+            visit_field_init(FieldInit) => walk_field_init;
+            visit_object_initializer(ObjectInitializer) => walk_object_initializer;
+            visit_identifier_type(IdentifierType) => walk_identifier_type;
+            visit_group_type(GroupType) => walk_group_type;
+            visit_function_type(FunctionType) => walk_function_type;
+            visit_tuple_array_type(TupleArrayType) => walk_tuple_array_type;
+            visit_raw_array_type(RawArrayType) => walk_raw_array_type;
+            visit_literal_type(LiteralType) => walk_literal_type;
+            visit_sum_type(SumType) => walk_sum_type;
+            visit_product_type(ProductType) => walk_product_type;
+        }
+    }
+
+    /// Counts the nodes in the JSON form of a syntax tree: every object with a span, except for
+    /// trivia (the only part of tokens that has a span).
+    fn count_json_nodes(value: &Value) -> usize {
+        match value {
+            Value::Object(map) => {
+                let is_node = map.contains_key("span") && !map.contains_key("lexeme");
+                map.values().map(count_json_nodes).sum::<usize>() + is_node as usize
+            }
+            Value::Array(values) => values.iter().map(count_json_nodes).sum(),
+            _ => 0,
+        }
+    }
+
+    fn count_nodes(nodes: &[SyntaxNode]) -> NodeCounter {
+        let mut counter = NodeCounter::default();
+        for node in nodes {
+            counter.visit_node(node);
+        }
+
+        counter
+    }
+
+    #[test]
+    fn visit_resources() {
+        println!("== Testing that every node in the example resources is visited ==");
+
+        let mut kinds: HashMap<&'static str, usize> = HashMap::new();
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/resources");
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            println!("Testing '{}'.", path.display());
+
+            let src = fs::read_to_string(&path).unwrap();
+            let arena = Arena::new();
+            let parser_res = parse(tokenize(&src).tokens, &arena);
+            assert_eq!(parser_res.messages.count(), 0);
+
+            let counter = count_nodes(&parser_res.nodes);
+            let json = serde_json::to_value(&parser_res.nodes).unwrap();
+            println!("{} nodes visited.", counter.total());
+            assert_eq!(counter.total(), count_json_nodes(&json));
+
+            for (kind, count) in counter.counts {
+                *kinds.entry(kind).or_default() += count;
+            }
+        }
+
+        // Between them, the resources contain every kind of node, except for error nodes.
+        assert_eq!(kinds.len(), 48);
+        assert!(!kinds.contains_key("ErrorNode"));
+    }
+
+    #[test]
+    fn visit_counts() {
+        println!("== Testing the nodes visited in small programs ==");
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("let a: Num = b + 1").tokens, &arena);
+        let counter = count_nodes(&parser_res.nodes);
+        assert_eq!(counter.counts["LocalDeclStmt"], 1);
+        assert_eq!(counter.counts["SimpleIdentifier"], 3);
+        assert_eq!(counter.counts["Operator"], 1);
+        assert_eq!(counter.counts["Literal"], 1);
+        assert_eq!(counter.total(), 14);
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("let a = )").tokens, &arena);
+        let counter = count_nodes(&parser_res.nodes);
+        assert!(parser_res.messages.count() > 0);
+        assert_eq!(counter.counts["ErrorNode"], 1);
+    }
+
+    #[test]
+    fn skip_children() {
+        println!("== Testing visitors that don't walk into some nodes ==");
+
+        /// Collects the names used outside of lambdas.
+        #[derive(Default)]
+        struct NameCollector<'a> {
+            names: Vec<&'a str>,
+        }
+
+        impl<'a> Visitor<'a> for NameCollector<'a> {
+            fn visit_lambda_expr(&mut self, _expr: &'a LambdaExpr) {}
+
+            fn visit_simple_identifier(&mut self, identifier: &'a SimpleIdentifier<'a>) {
+                self.names.push(&identifier.name);
+            }
+        }
+
+        let arena = Arena::new();
+        let parser_res = parse(tokenize("f(a, func (b) => c, d.e)").tokens, &arena);
+        let mut collector = NameCollector::default();
+        for node in &parser_res.nodes {
+            collector.visit_node(node);
+        }
+        assert_eq!(collector.names, vec!["f", "a", "d", "e"]);
+    }
+}
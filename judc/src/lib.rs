@@ -2,7 +2,8 @@ use serde::Serialize;
 
 pub mod judith;
 
-#[derive(Debug, Clone, Copy, Serialize)]
+/// A part of a source file. `start` and `end` are byte offsets into the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct SourceSpan {
     pub start: i64,
     pub end: i64,
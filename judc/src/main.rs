@@ -0,0 +1,142 @@
+use std::{env, fs};
+use std::io;
+use std::process::ExitCode;
+use judc::judith::analysis::analyze;
+use judc::judith::analysis::lints::{lint, LintConfig, LintLevel};
+use judc::judith::jasm::assembler::assemble;
+use judc::judith::jasm::disassembler::disassemble;
+use judc::judith::jasm::optimizer::optimize;
+use judc::judith::jasm::reader::read_assembly;
+use judc::judith::jasm::verifier::load_assembly;
+use judc::judith::lexical::lexer::tokenize;
+use judc::judith::repl;
+use judc::judith::syntax::arena::Arena;
+use judc::judith::syntax::parser::parse;
+
+const USAGE: &str = "\
+Usage: judc <command>
+
+Commands:
+  repl                      Starts an interactive session.
+  check <file.jud> [opts]   Checks a Judith file and reports the issues found.
+  disasm <file.jdll>        Prints the contents of an assembly as JASM text.
+  asm <file.jasm> <out>     Assembles JASM text into a .jdll file.
+  verify <file.jdll>        Checks that an assembly is well-formed.
+  opt <file.jdll> <out>     Optimizes the bytecode of an assembly.
+
+Options for 'check':
+  -A <lint>                 Allows the lint given.
+  -W <lint>                 Makes the lint given report warnings.
+  -D <lint>                 Makes the lint given report errors.
+  --lint-config <file>      Reads lint levels from the file given. Options set in the command
+                            line take precedence over it.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let res = match args[..] {
+        ["repl"] => {
+            println!("Judith REPL. Type :help for help.");
+            repl::run(io::stdin().lock(), io::stdout());
+            Ok(())
+        }
+        ["check", path, ref options @ ..] => run_check(path, options),
+        ["disasm", path] => run_disasm(path),
+        ["asm", path, out_path] => run_asm(path, out_path),
+        ["verify", path] => run_verify(path),
+        ["opt", path, out_path] => run_opt(path, out_path),
+        _ => Err(String::from(USAGE)),
+    };
+
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_check(path: &str, options: &[&str]) -> Result<(), String> {
+    let mut config = LintConfig::new();
+    let mut levels = Vec::new();
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let Some(value) = options.next() else {
+            return Err(format!("Missing value for '{}'.\n\n{}", option, USAGE));
+        };
+
+        match *option {
+            "-A" => levels.push((value, LintLevel::Allow)),
+            "-W" => levels.push((value, LintLevel::Warn)),
+            "-D" => levels.push((value, LintLevel::Deny)),
+            "--lint-config" => {
+                let src = fs::read_to_string(value)
+                    .map_err(|err| format!("Couldn't read '{}': {}", value, err))?;
+                config.read_config(&src).map_err(|err| format!("{}: {}", value, err))?;
+            }
+            _ => return Err(format!("Unknown option '{}'.\n\n{}", option, USAGE)),
+        }
+    }
+    for (name, level) in levels {
+        config.set_level(name, level)?;
+    }
+
+    let src = fs::read_to_string(path).map_err(|err| format!("Couldn't read '{}': {}", path, err))?;
+    let lexer_res = tokenize(&src);
+    let mut messages = config.read_comments(&lexer_res);
+    messages.add_all(lexer_res.messages);
+
+    let arena = Arena::new();
+    let parser_res = parse(lexer_res.tokens, &arena);
+    messages.add_all(parser_res.messages);
+
+    // Semantic checks would only report noise about code that couldn't be read.
+    if messages.errors.is_empty() {
+        messages.add_all(analyze(&parser_res.nodes).messages);
+        messages.add_all(lint(&parser_res.nodes, &config));
+    }
+
+    messages.dump_all();
+    match messages.errors.len() {
+        0 => Ok(()),
+        count => Err(format!("{}: {} error(s) found.", path, count)),
+    }
+}
+
+fn run_disasm(path: &str) -> Result<(), String> {
+    let bytes = fs::read(path).map_err(|err| format!("Couldn't read '{}': {}", path, err))?;
+    let assembly = read_assembly(&bytes).map_err(|err| format!("{}: {}", path, err))?;
+
+    print!("{}", disassemble(&assembly));
+    Ok(())
+}
+
+fn run_asm(path: &str, out_path: &str) -> Result<(), String> {
+    let src = fs::read_to_string(path).map_err(|err| format!("Couldn't read '{}': {}", path, err))?;
+    let assembly = assemble(&src).map_err(|err| format!("{}: {}", path, err))?;
+
+    fs::write(out_path, assembly.to_bytes())
+        .map_err(|err| format!("Couldn't write '{}': {}", out_path, err))
+}
+
+fn run_verify(path: &str) -> Result<(), String> {
+    let bytes = fs::read(path).map_err(|err| format!("Couldn't read '{}': {}", path, err))?;
+    load_assembly(&bytes).map_err(|err| format!("{}: {}", path, err))?;
+
+    println!("{}: OK", path);
+    Ok(())
+}
+
+fn run_opt(path: &str, out_path: &str) -> Result<(), String> {
+    let bytes = fs::read(path).map_err(|err| format!("Couldn't read '{}': {}", path, err))?;
+    let mut assembly = load_assembly(&bytes).map_err(|err| format!("{}: {}", path, err))?;
+
+    let res = optimize(&mut assembly);
+    res.messages.dump_all();
+
+    fs::write(out_path, assembly.to_bytes())
+        .map_err(|err| format!("Couldn't write '{}': {}", out_path, err))
+}